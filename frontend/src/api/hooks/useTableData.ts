@@ -66,7 +66,9 @@ export default function useTableData(): UseTableDataResult {
       data[3] = filteredUnauditedBurns
       data[2] = filteredUnauditedMints
       setSortedData(sortData(data.flat()))
-      setTotalUnauditedDeposits(sumBy(data[5], (dep) => dep.deposit.amount))
+      setTotalUnauditedDeposits(
+        sumBy(data[5], (dep) => Number(dep.deposit.amount))
+      )
       setTotalUnauditedBurns(
         sumBy(filteredUnauditedBurns, (burn) => burn.burn.amount)
       )
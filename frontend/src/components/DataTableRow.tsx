@@ -192,7 +192,7 @@ export default function DataTableRow({ rowItem }: { rowItem: TTableData }) {
               <DetailsSection
                 header="Wrap"
                 time={rowItem.deposit.executionDate}
-                amount={rowItem.deposit.amount}
+                amount={Number(rowItem.deposit.amount)}
                 title={`${getSymbolFromContactAddress(
                   rowItem.deposit.tokenAddr
                 )} received by custodian multisig`}
@@ -243,7 +243,7 @@ export default function DataTableRow({ rowItem }: { rowItem: TTableData }) {
               <DetailsSection
                 header="Unwrap"
                 time={rowItem.withdrawal.executionDate}
-                amount={rowItem.withdrawal.amount}
+                amount={Number(rowItem.withdrawal.amount)}
                 title={`${getSymbolFromContactAddress(
                   rowItem.withdrawal.tokenAddr
                 )} sent from custodian multisig`}
@@ -366,7 +366,7 @@ export default function DataTableRow({ rowItem }: { rowItem: TTableData }) {
         <TableRow
           type={`Wrapped ${ercSymbol}`}
           icon={<EUSDWrapIcon pxSize={24} />}
-          amount={rowItem.deposit.amount}
+          amount={Number(rowItem.deposit.amount)}
           amountIcon={getIconFromContactAddress(rowItem.deposit.tokenAddr)}
           timestamp={rowItem.deposit.executionDate}
           link={<EthLink hash={rowItem.deposit.ethTxHash} />}
@@ -376,7 +376,7 @@ export default function DataTableRow({ rowItem }: { rowItem: TTableData }) {
                 header="Wrap"
                 time={rowItem.deposit.executionDate}
                 link={<EthLink hash={rowItem.deposit.ethTxHash} />}
-                amount={rowItem.deposit.amount}
+                amount={Number(rowItem.deposit.amount)}
                 title={`${ercSymbol} received by custodian multisig`}
                 linkTitle="Eth Tx Hash"
               />
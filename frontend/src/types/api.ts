@@ -21,8 +21,11 @@ export type TAuditedMintResponse = {
     eth_block_number: number
     safe_addr: string //SqlEthAddr
     token_addr: string //SqlEthAddr
-    amount: number
+    amount: string //SqlU128
     expected_mc_mint_tx_nonce_hex: string
+    chain_id: number
+    verification_status: string
+    truncation_remainder: string //SqlU128
   } //pub struct GnosisSafeDeposit
 }
 
@@ -46,8 +49,11 @@ export type TAuditedBurnResponse = {
     eth_block_number: number
     safe_addr: string //SqlEthAddr
     token_addr: string //SqlEthAddr
-    amount: number
+    amount: string //SqlU128
     mc_tx_out_public_key_hex: string
+    chain_id: number
+    verification_status: string
+    truncation_remainder: string //SqlU128
   } //pub struct GnosisSafeWithdrawal
 }
 
@@ -83,6 +89,7 @@ export type TGnosisSafeConfigResponse = {
 
 export type TAuditedSafeResponse = {
   safe_addr: string
+  chain_id: number
   api_url: string
   tokens: TAuditedTokenResponse[]
   token_decimals_max: number
@@ -3,6 +3,7 @@ export type TAuditedBurn = {
     id?: number
     burnTxOutId: number
     gnosisSafeWithdrawalId: number
+    amount: number
   }
   burn: TBurn
   withdrawal: TWithdrawal
@@ -26,8 +27,13 @@ export type TWithdrawal = {
   safeAddr: string
   tokenAddr: string
   toAddr: string
-  amount: number
+  // decimal string, since it may exceed 64 bits
+  amount: string
   mcTxOutPublicKeyHex: string
+  chainId: number
+  verificationStatus: string
+  // sub-precision part of the Ethereum value dropped from amount (decimal string)
+  truncationRemainder: string
 }
 
 export type TUnauditedBurn = {
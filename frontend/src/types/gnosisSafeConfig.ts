@@ -4,6 +4,7 @@ export type TGnosisSafeConfig = {
 
 export type TAuditedSafe = {
   safeAddr: string
+  chainId: number
   apiUrl: string
   etherscanUrl: string
   tokens: TAuditedToken[]
@@ -3,6 +3,7 @@ export type TAuditedMint = {
     id?: number
     mintTxId: number
     gnosisSafeDepositId: number
+    amount: number
   }
   mint: TMint
   deposit: TDeposit
@@ -19,6 +20,7 @@ export type TMint = {
   tombstoneBlock: number
   protobuf: number[]
   mintConfigId?: number
+  decodedNonce?: string
 }
 
 export type TDeposit = {
@@ -29,8 +31,13 @@ export type TDeposit = {
   safeAddr: string
   tokenAddr: string
   fromAddr: string
-  amount: number
+  // decimal string, since it may exceed 64 bits
+  amount: string
   expectedMcMintTxNonceHex: string
+  chainId: number
+  verificationStatus: string
+  // sub-precision part of the Ethereum value dropped from amount (decimal string)
+  truncationRemainder: string
 }
 
 export type TUnauditedSafeDeposit = {
@@ -0,0 +1 @@
+DROP TABLE gnosis_safe_sync_states;
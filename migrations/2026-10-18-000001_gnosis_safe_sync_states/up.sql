@@ -0,0 +1,12 @@
+-- Per-safe sync cursor, used to avoid re-fetching the entire transaction history from the Gnosis API on every poll.
+CREATE TABLE gnosis_safe_sync_states (
+    -- The safe address this sync state is for.
+    safe_addr VARCHAR(42) NOT NULL PRIMARY KEY,
+    -- Whether we have managed to fetch and process every page of the safe's transaction history at least once.
+    -- Until this is set, every poll fetches all pages.
+    full_sync_completed BOOLEAN NOT NULL,
+    -- The hash of the newest transaction seen during the last successful poll (the high-water mark).
+    last_eth_tx_hash VARCHAR(66),
+    -- When the last successful poll completed.
+    last_synced_at TIMESTAMP
+);
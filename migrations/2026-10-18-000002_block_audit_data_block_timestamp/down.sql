@@ -0,0 +1 @@
+ALTER TABLE block_audit_data DROP COLUMN block_timestamp;
@@ -0,0 +1,2 @@
+-- The timestamp of the block, when available (it is obtained from the watcher db).
+ALTER TABLE block_audit_data ADD COLUMN block_timestamp TIMESTAMP;
@@ -0,0 +1,10 @@
+-- The configuration each safe was last audited with, used to detect configuration changes that require re-auditing
+-- the transactions we already have stored.
+CREATE TABLE gnosis_safe_audit_configs (
+    -- The safe address this configuration is for.
+    safe_addr VARCHAR(42) NOT NULL PRIMARY KEY,
+    -- The JSON-serialized AuditedSafeConfig.
+    config_json TEXT NOT NULL,
+    -- When the configuration was last stored.
+    updated_at TIMESTAMP NOT NULL
+);
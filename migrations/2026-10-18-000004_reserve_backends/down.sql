@@ -0,0 +1,4 @@
+DROP TABLE audited_reserve_burns;
+DROP TABLE audited_reserve_mints;
+DROP TABLE reserve_withdrawals;
+DROP TABLE reserve_deposits;
@@ -0,0 +1,65 @@
+-- Deposits into a reserve held by a custodian other than a Gnosis safe (e.g. a bank account whose statements are
+-- imported). Each row is reported by a reserve backend, identified by its configured name.
+CREATE TABLE reserve_deposits (
+    id INTEGER PRIMARY KEY,
+    -- The name of the reserve backend that reported this deposit.
+    source VARCHAR(255) NOT NULL,
+    -- The reference the custodian uses for this deposit (e.g. a bank transaction id).
+    external_ref VARCHAR(255) NOT NULL,
+    -- The MobileCoin token id this deposit backs.
+    token_id BIGINT NOT NULL,
+    -- The amount deposited, in the MobileCoin token's precision.
+    amount BIGINT NOT NULL,
+    -- This is the expected nonce of the matching MintTx we want to see on the MobileCoin blockchain.
+    expected_mc_mint_tx_nonce_hex VARCHAR(128) NOT NULL,
+    -- When the custodian recorded the deposit, if known.
+    occurred_at TIMESTAMP,
+    -- Constraints
+    UNIQUE (source, external_ref)
+);
+CREATE INDEX idx__reserve_deposits__expected_mc_mint_tx_nonce_hex ON reserve_deposits(expected_mc_mint_tx_nonce_hex);
+
+-- Withdrawals from a reserve held by a custodian other than a Gnosis safe.
+CREATE TABLE reserve_withdrawals (
+    id INTEGER PRIMARY KEY,
+    -- The name of the reserve backend that reported this withdrawal.
+    source VARCHAR(255) NOT NULL,
+    -- The reference the custodian uses for this withdrawal (e.g. a bank transaction id).
+    external_ref VARCHAR(255) NOT NULL,
+    -- The MobileCoin token id this withdrawal is for.
+    token_id BIGINT NOT NULL,
+    -- The amount withdrawn, in the MobileCoin token's precision.
+    amount BIGINT NOT NULL,
+    -- The public key of the burn TxOut this withdrawal redeems.
+    mc_tx_out_public_key_hex VARCHAR(64) NOT NULL,
+    -- When the custodian recorded the withdrawal, if known.
+    occurred_at TIMESTAMP,
+    -- Constraints
+    UNIQUE (source, external_ref)
+);
+CREATE INDEX idx__reserve_withdrawals__mc_tx_out_public_key_hex ON reserve_withdrawals(mc_tx_out_public_key_hex);
+
+-- Mapping between MintTxs and ReserveDeposits that match each other, the reserve backend counterpart of audited_mints.
+CREATE TABLE audited_reserve_mints (
+    id INTEGER PRIMARY KEY,
+    mint_tx_id INTEGER NOT NULL,
+    reserve_deposit_id INTEGER NOT NULL,
+    -- Constraints
+    FOREIGN KEY (mint_tx_id) REFERENCES mint_txs(id),
+    FOREIGN KEY (reserve_deposit_id) REFERENCES reserve_deposits(id)
+);
+CREATE INDEX idx__audited_reserve_mints__mint_tx_id ON audited_reserve_mints(mint_tx_id);
+CREATE INDEX idx__audited_reserve_mints__reserve_deposit_id ON audited_reserve_mints(reserve_deposit_id);
+
+-- Mapping between BurnTxOuts and ReserveWithdrawals that match each other, the reserve backend counterpart of
+-- audited_burns.
+CREATE TABLE audited_reserve_burns (
+    id INTEGER PRIMARY KEY,
+    burn_tx_out_id INTEGER NOT NULL,
+    reserve_withdrawal_id INTEGER NOT NULL,
+    -- Constraints
+    FOREIGN KEY (burn_tx_out_id) REFERENCES burn_tx_outs(id),
+    FOREIGN KEY (reserve_withdrawal_id) REFERENCES reserve_withdrawals(id)
+);
+CREATE INDEX idx__audited_reserve_burns__burn_tx_out_id ON audited_reserve_burns(burn_tx_out_id);
+CREATE INDEX idx__audited_reserve_burns__reserve_withdrawal_id ON audited_reserve_burns(reserve_withdrawal_id);
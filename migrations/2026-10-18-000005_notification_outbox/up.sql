@@ -0,0 +1,18 @@
+-- Anomalies waiting to be (or that were) delivered to the configured webhooks. A row is added whenever an anomaly
+-- counter gets incremented, so that notifications survive restarts and are retried until delivered.
+CREATE TABLE notification_outbox (
+    id INTEGER PRIMARY KEY,
+    -- The JSON-encoded anomaly event.
+    payload TEXT NOT NULL,
+    -- When the anomaly was detected.
+    created_at TIMESTAMP NOT NULL,
+    -- Number of failed delivery attempts so far.
+    num_attempts INTEGER NOT NULL DEFAULT 0,
+    -- When the next delivery attempt should happen.
+    next_attempt_at TIMESTAMP NOT NULL,
+    -- When the notification was delivered to all webhooks, NULL while pending.
+    delivered_at TIMESTAMP,
+    -- The error of the last failed delivery attempt.
+    last_error TEXT
+);
+CREATE INDEX idx__notification_outbox__delivered_at__next_attempt_at ON notification_outbox(delivered_at, next_attempt_at);
@@ -0,0 +1,25 @@
+-- A log of every anomaly detected while auditing. A row is added whenever an anomaly counter gets incremented, so that
+-- each increment can be traced back to the objects that caused it.
+CREATE TABLE audit_events (
+    id INTEGER PRIMARY KEY,
+    -- The kind of anomaly (e.g. mismatching_mint_and_deposit).
+    kind VARCHAR NOT NULL,
+    -- How severe the anomaly is (warning, error or critical).
+    severity VARCHAR NOT NULL,
+    -- Description of what went wrong.
+    message TEXT NOT NULL,
+    -- The MobileCoin block index, if known.
+    block_index BIGINT,
+    -- The Ethereum block number, if known.
+    eth_block_number BIGINT,
+    -- Ids of the rows involved. These are not foreign keys since the rows may get removed when rewinding.
+    mint_tx_id INTEGER,
+    burn_tx_out_id INTEGER,
+    gnosis_safe_deposit_id INTEGER,
+    gnosis_safe_withdrawal_id INTEGER,
+    reserve_deposit_id INTEGER,
+    reserve_withdrawal_id INTEGER,
+    -- When the anomaly was detected.
+    created_at TIMESTAMP NOT NULL
+);
+CREATE INDEX idx__audit_events__kind ON audit_events(kind);
@@ -0,0 +1,2 @@
+DROP TABLE audit_event_acknowledgements;
+DROP TABLE manual_matches;
@@ -0,0 +1,37 @@
+-- Matches an operator forced between a MintTx and a GnosisSafeDeposit, or a BurnTxOut and a GnosisSafeWithdrawal, that
+-- failed to match automatically. The match itself is stored in audited_mints/audited_burns, this table keeps who made it
+-- and why. Exactly one of the two pairs of ids is set. The ids are not foreign keys so that the history survives
+-- rewinding and re-auditing.
+CREATE TABLE manual_matches (
+    id INTEGER PRIMARY KEY,
+    mint_tx_id INTEGER,
+    gnosis_safe_deposit_id INTEGER,
+    burn_tx_out_id INTEGER,
+    gnosis_safe_withdrawal_id INTEGER,
+    -- The operator that made the match.
+    actor VARCHAR NOT NULL,
+    -- Why the match was made.
+    justification TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    -- Constraints
+    CHECK (
+        (mint_tx_id IS NOT NULL AND gnosis_safe_deposit_id IS NOT NULL AND burn_tx_out_id IS NULL AND gnosis_safe_withdrawal_id IS NULL) OR
+        (mint_tx_id IS NULL AND gnosis_safe_deposit_id IS NULL AND burn_tx_out_id IS NOT NULL AND gnosis_safe_withdrawal_id IS NOT NULL)
+    )
+);
+
+-- Acknowledgements of audit events by operators. An audit event is open until it is acknowledged.
+CREATE TABLE audit_event_acknowledgements (
+    id INTEGER PRIMARY KEY,
+    audit_event_id INTEGER NOT NULL UNIQUE,
+    -- The operator that investigated the event.
+    actor VARCHAR NOT NULL,
+    -- The outcome of the investigation.
+    note TEXT NOT NULL,
+    -- The manual match that resolved the event, if any.
+    manual_match_id INTEGER,
+    created_at TIMESTAMP NOT NULL,
+    -- Constraints
+    FOREIGN KEY (audit_event_id) REFERENCES audit_events(id),
+    FOREIGN KEY (manual_match_id) REFERENCES manual_matches(id)
+);
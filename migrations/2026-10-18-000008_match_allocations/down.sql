@@ -0,0 +1,2 @@
+ALTER TABLE audited_burns DROP COLUMN amount;
+ALTER TABLE audited_mints DROP COLUMN amount;
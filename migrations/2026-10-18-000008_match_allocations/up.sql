@@ -0,0 +1,8 @@
+-- Amount allocated by each match. A deposit can be split across several mints and several withdrawals can be
+-- batched into one burn, so an object is fully matched once the allocations of all its matches add up to its amount.
+ALTER TABLE audited_mints ADD COLUMN amount BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE audited_burns ADD COLUMN amount BIGINT NOT NULL DEFAULT 0;
+
+-- Existing matches are 1:1, so they allocate the entire amount.
+UPDATE audited_mints SET amount = (SELECT mint_txs.amount FROM mint_txs WHERE mint_txs.id = audited_mints.mint_tx_id);
+UPDATE audited_burns SET amount = (SELECT burn_tx_outs.amount FROM burn_tx_outs WHERE burn_tx_outs.id = audited_burns.burn_tx_out_id);
@@ -0,0 +1,2 @@
+ALTER TABLE counters DROP COLUMN num_mint_txs_with_unknown_nonce;
+ALTER TABLE mint_txs DROP COLUMN decoded_nonce;
@@ -0,0 +1,6 @@
+-- Human readable decoded nonce of each MintTx, NULL when the nonce is not of a known type. Existing rows are populated
+-- on startup since the nonce is decoded by the auditor.
+ALTER TABLE mint_txs ADD COLUMN decoded_nonce TEXT;
+
+-- Number of MintTxs with a nonce of an unknown type.
+ALTER TABLE counters ADD COLUMN num_mint_txs_with_unknown_nonce BIGINT NOT NULL DEFAULT 0;
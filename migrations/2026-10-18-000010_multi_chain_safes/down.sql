@@ -0,0 +1,25 @@
+-- Only the Ethereum mainnet state can be kept.
+CREATE TABLE gnosis_safe_audit_configs_old (
+    safe_addr VARCHAR(42) NOT NULL PRIMARY KEY,
+    config_json TEXT NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+INSERT INTO gnosis_safe_audit_configs_old
+    SELECT safe_addr, config_json, updated_at FROM gnosis_safe_audit_configs WHERE chain_id = 1;
+DROP TABLE gnosis_safe_audit_configs;
+ALTER TABLE gnosis_safe_audit_configs_old RENAME TO gnosis_safe_audit_configs;
+
+CREATE TABLE gnosis_safe_sync_states_old (
+    safe_addr VARCHAR(42) NOT NULL PRIMARY KEY,
+    full_sync_completed BOOLEAN NOT NULL,
+    last_eth_tx_hash VARCHAR(66),
+    last_synced_at TIMESTAMP
+);
+INSERT INTO gnosis_safe_sync_states_old
+    SELECT safe_addr, full_sync_completed, last_eth_tx_hash, last_synced_at FROM gnosis_safe_sync_states WHERE chain_id = 1;
+DROP TABLE gnosis_safe_sync_states;
+ALTER TABLE gnosis_safe_sync_states_old RENAME TO gnosis_safe_sync_states;
+
+ALTER TABLE gnosis_safe_withdrawals DROP COLUMN chain_id;
+ALTER TABLE gnosis_safe_deposits DROP COLUMN chain_id;
+ALTER TABLE gnosis_safe_txs DROP COLUMN chain_id;
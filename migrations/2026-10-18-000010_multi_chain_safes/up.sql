@@ -0,0 +1,44 @@
+-- The id of the EVM chain each Gnosis transaction, deposit and withdrawal happened on. Everything stored so far came
+-- from Ethereum mainnet. Transaction hashes commit to the chain id (EIP-155), so they remain unique across chains.
+ALTER TABLE gnosis_safe_txs ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE gnosis_safe_deposits ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE gnosis_safe_withdrawals ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 1;
+
+-- Safe addresses are not unique across chains (the same Safe is commonly deployed to the same address on several
+-- chains), so per-safe state is keyed by the chain id and the safe address.
+CREATE TABLE gnosis_safe_sync_states_new (
+    -- The chain the safe lives on.
+    chain_id BIGINT NOT NULL,
+    -- The safe address this sync state is for.
+    safe_addr VARCHAR(42) NOT NULL,
+    -- Whether we have managed to fetch and process every page of the safe's transaction history at least once.
+    -- Until this is set, every poll fetches all pages.
+    full_sync_completed BOOLEAN NOT NULL,
+    -- The hash of the newest transaction seen during the last successful poll (the high-water mark).
+    last_eth_tx_hash VARCHAR(66),
+    -- When the last successful poll completed.
+    last_synced_at TIMESTAMP,
+    -- Constraints
+    PRIMARY KEY (chain_id, safe_addr)
+);
+INSERT INTO gnosis_safe_sync_states_new
+    SELECT 1, safe_addr, full_sync_completed, last_eth_tx_hash, last_synced_at FROM gnosis_safe_sync_states;
+DROP TABLE gnosis_safe_sync_states;
+ALTER TABLE gnosis_safe_sync_states_new RENAME TO gnosis_safe_sync_states;
+
+CREATE TABLE gnosis_safe_audit_configs_new (
+    -- The chain the safe lives on.
+    chain_id BIGINT NOT NULL,
+    -- The safe address this configuration is for.
+    safe_addr VARCHAR(42) NOT NULL,
+    -- The JSON-serialized AuditedSafeConfig.
+    config_json TEXT NOT NULL,
+    -- When the configuration was last stored.
+    updated_at TIMESTAMP NOT NULL,
+    -- Constraints
+    PRIMARY KEY (chain_id, safe_addr)
+);
+INSERT INTO gnosis_safe_audit_configs_new
+    SELECT 1, safe_addr, config_json, updated_at FROM gnosis_safe_audit_configs;
+DROP TABLE gnosis_safe_audit_configs;
+ALTER TABLE gnosis_safe_audit_configs_new RENAME TO gnosis_safe_audit_configs;
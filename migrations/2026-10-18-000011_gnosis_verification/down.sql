@@ -0,0 +1,3 @@
+ALTER TABLE counters DROP COLUMN num_gnosis_source_disagreements;
+ALTER TABLE gnosis_safe_withdrawals DROP COLUMN verification_status;
+ALTER TABLE gnosis_safe_deposits DROP COLUMN verification_status;
@@ -0,0 +1,7 @@
+-- Outcome of cross-checking each deposit and withdrawal against the safe's verification source: one of 'unverified',
+-- 'verified' or 'disagreement'. Rows stay 'unverified' when the safe has no verification source configured.
+ALTER TABLE gnosis_safe_deposits ADD COLUMN verification_status VARCHAR(16) NOT NULL DEFAULT 'unverified';
+ALTER TABLE gnosis_safe_withdrawals ADD COLUMN verification_status VARCHAR(16) NOT NULL DEFAULT 'unverified';
+
+-- Number of deposits and withdrawals the verification source disagreed with.
+ALTER TABLE counters ADD COLUMN num_gnosis_source_disagreements BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+ALTER TABLE counters DROP COLUMN num_mint_config_tx_governor_violations;
+DROP TABLE signer_set_changes;
@@ -0,0 +1,17 @@
+-- History of the minter signer sets of each token. A row is added whenever a MintConfigTx replaces the signer sets of
+-- its token with a different collection of signer sets than the previous MintConfigTx of that token had.
+CREATE TABLE signer_set_changes (
+    id INTEGER PRIMARY KEY,
+    token_id BIGINT NOT NULL,
+    -- The block the MintConfigTx that made the change appeared in.
+    block_index BIGINT NOT NULL,
+    mint_config_tx_id INTEGER NOT NULL UNIQUE,
+    -- The JSON-serialized signer sets of the MintConfigTx's mint configs, in order.
+    signer_sets_json TEXT NOT NULL,
+    -- Constraints
+    FOREIGN KEY (mint_config_tx_id) REFERENCES mint_config_txs(id)
+);
+CREATE INDEX idx__signer_set_changes__token_id ON signer_set_changes(token_id);
+
+-- Number of MintConfigTxs that were not signed by the configured governors of their token.
+ALTER TABLE counters ADD COLUMN num_mint_config_tx_governor_violations BIGINT NOT NULL DEFAULT 0;
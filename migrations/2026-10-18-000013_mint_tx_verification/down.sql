@@ -0,0 +1,5 @@
+ALTER TABLE counters DROP COLUMN num_mint_txs_past_tombstone_block;
+ALTER TABLE counters DROP COLUMN num_mint_txs_exceeding_mint_limit;
+ALTER TABLE counters DROP COLUMN num_mint_txs_exceeding_total_mint_limit;
+ALTER TABLE counters DROP COLUMN num_mint_txs_with_invalid_signature;
+ALTER TABLE counters DROP COLUMN num_mint_txs_without_active_mint_config;
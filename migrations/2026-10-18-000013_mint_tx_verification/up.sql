@@ -0,0 +1,9 @@
+-- Breakdown of MintTxs that did not match any active mint config, by the reason they did not match. The total is still
+-- counted in num_mint_txs_without_matching_mint_config.
+ALTER TABLE counters ADD COLUMN num_mint_txs_without_active_mint_config BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE counters ADD COLUMN num_mint_txs_with_invalid_signature BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE counters ADD COLUMN num_mint_txs_exceeding_total_mint_limit BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE counters ADD COLUMN num_mint_txs_exceeding_mint_limit BIGINT NOT NULL DEFAULT 0;
+
+-- Number of MintTxs that appeared in a block at or after their tombstone block.
+ALTER TABLE counters ADD COLUMN num_mint_txs_past_tombstone_block BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,17 @@
+ALTER TABLE counters DROP COLUMN num_oversized_reserve_amounts;
+
+-- Values that do not fit in a signed 64 bit integer are clamped by the cast.
+ALTER TABLE gnosis_safe_withdrawals ADD COLUMN amount_int BIGINT NOT NULL DEFAULT 0;
+UPDATE gnosis_safe_withdrawals SET amount_int = CAST(amount AS INTEGER);
+ALTER TABLE gnosis_safe_withdrawals DROP COLUMN amount;
+ALTER TABLE gnosis_safe_withdrawals RENAME COLUMN amount_int TO amount;
+
+ALTER TABLE gnosis_safe_deposits ADD COLUMN amount_int BIGINT NOT NULL DEFAULT 0;
+UPDATE gnosis_safe_deposits SET amount_int = CAST(amount AS INTEGER);
+ALTER TABLE gnosis_safe_deposits DROP COLUMN amount;
+ALTER TABLE gnosis_safe_deposits RENAME COLUMN amount_int TO amount;
+
+ALTER TABLE block_balance ADD COLUMN balance_int BIGINT NOT NULL DEFAULT 0;
+UPDATE block_balance SET balance_int = CAST(balance AS INTEGER);
+ALTER TABLE block_balance DROP COLUMN balance;
+ALTER TABLE block_balance RENAME COLUMN balance_int TO balance;
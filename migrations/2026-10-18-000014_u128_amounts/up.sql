@@ -0,0 +1,43 @@
+-- Token balances and Gnosis deposit/withdrawal amounts are not bounded to 64 bits: a token with more decimals than
+-- the MobileCoin side can be truncated into a value that does not fit, and a balance is a sum of many mints. SQLite
+-- integers are signed 64 bit, so these are stored as decimal strings instead.
+--
+-- Existing values were stored as the bit pattern of a u64, so negative values are converted back by adding 2^64.
+-- SQLite cannot represent that sum as an integer, so the conversion is done on two base 10^10 halves:
+-- value + 2^64 = (value + 2^63) + 922337203 * 10^10 + 6854775808.
+
+ALTER TABLE block_balance ADD COLUMN balance_text TEXT NOT NULL DEFAULT '0';
+UPDATE block_balance SET balance_text = CASE
+    WHEN balance >= 0 THEN CAST(balance AS TEXT)
+    ELSE printf('%d%010d',
+        (balance + 9223372036854775807 + 1) / 10000000000 + 922337203
+            + ((balance + 9223372036854775807 + 1) % 10000000000 + 6854775808) / 10000000000,
+        ((balance + 9223372036854775807 + 1) % 10000000000 + 6854775808) % 10000000000)
+END;
+ALTER TABLE block_balance DROP COLUMN balance;
+ALTER TABLE block_balance RENAME COLUMN balance_text TO balance;
+
+ALTER TABLE gnosis_safe_deposits ADD COLUMN amount_text TEXT NOT NULL DEFAULT '0';
+UPDATE gnosis_safe_deposits SET amount_text = CASE
+    WHEN amount >= 0 THEN CAST(amount AS TEXT)
+    ELSE printf('%d%010d',
+        (amount + 9223372036854775807 + 1) / 10000000000 + 922337203
+            + ((amount + 9223372036854775807 + 1) % 10000000000 + 6854775808) / 10000000000,
+        ((amount + 9223372036854775807 + 1) % 10000000000 + 6854775808) % 10000000000)
+END;
+ALTER TABLE gnosis_safe_deposits DROP COLUMN amount;
+ALTER TABLE gnosis_safe_deposits RENAME COLUMN amount_text TO amount;
+
+ALTER TABLE gnosis_safe_withdrawals ADD COLUMN amount_text TEXT NOT NULL DEFAULT '0';
+UPDATE gnosis_safe_withdrawals SET amount_text = CASE
+    WHEN amount >= 0 THEN CAST(amount AS TEXT)
+    ELSE printf('%d%010d',
+        (amount + 9223372036854775807 + 1) / 10000000000 + 922337203
+            + ((amount + 9223372036854775807 + 1) % 10000000000 + 6854775808) / 10000000000,
+        ((amount + 9223372036854775807 + 1) % 10000000000 + 6854775808) % 10000000000)
+END;
+ALTER TABLE gnosis_safe_withdrawals DROP COLUMN amount;
+ALTER TABLE gnosis_safe_withdrawals RENAME COLUMN amount_text TO amount;
+
+-- Number of Gnosis deposits and withdrawals whose amount does not fit in a MobileCoin (u64) amount.
+ALTER TABLE counters ADD COLUMN num_oversized_reserve_amounts BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+ALTER TABLE counters DROP COLUMN num_deposits_with_truncation_remainder;
+ALTER TABLE gnosis_safe_withdrawals DROP COLUMN truncation_remainder;
+ALTER TABLE gnosis_safe_deposits DROP COLUMN truncation_remainder;
@@ -0,0 +1,8 @@
+-- The sub-precision part of the raw Ethereum value that was dropped when truncating a deposit or withdrawal to the
+-- MobileCoin token precision, in raw token units. Rows stored before this migration keep a remainder of 0 until the
+-- operator runs the `reaudit` command, which recomputes it from the stored Gnosis transactions.
+ALTER TABLE gnosis_safe_deposits ADD COLUMN truncation_remainder TEXT NOT NULL DEFAULT '0';
+ALTER TABLE gnosis_safe_withdrawals ADD COLUMN truncation_remainder TEXT NOT NULL DEFAULT '0';
+
+-- Number of Gnosis deposits whose value had sub-precision digits dropped when truncating it.
+ALTER TABLE counters ADD COLUMN num_deposits_with_truncation_remainder BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,50 @@
+-- Matches whose rows do not currently exist (e.g. after rewinding) cannot be converted back to ids and are dropped.
+CREATE TABLE manual_matches_old (
+    id INTEGER PRIMARY KEY,
+    mint_tx_id INTEGER,
+    gnosis_safe_deposit_id INTEGER,
+    burn_tx_out_id INTEGER,
+    gnosis_safe_withdrawal_id INTEGER,
+    actor VARCHAR NOT NULL,
+    justification TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    CHECK (
+        (mint_tx_id IS NOT NULL AND gnosis_safe_deposit_id IS NOT NULL AND burn_tx_out_id IS NULL AND gnosis_safe_withdrawal_id IS NULL) OR
+        (mint_tx_id IS NULL AND gnosis_safe_deposit_id IS NULL AND burn_tx_out_id IS NOT NULL AND gnosis_safe_withdrawal_id IS NOT NULL)
+    )
+);
+INSERT INTO manual_matches_old
+    SELECT manual_matches.id, mint_txs.id, gnosis_safe_deposits.id, NULL, NULL, manual_matches.actor,
+        manual_matches.justification, manual_matches.created_at
+    FROM manual_matches
+    JOIN mint_txs ON mint_txs.nonce_hex = manual_matches.mint_tx_nonce_hex
+    JOIN gnosis_safe_deposits ON gnosis_safe_deposits.chain_id = manual_matches.chain_id
+        AND gnosis_safe_deposits.eth_tx_hash = manual_matches.eth_tx_hash;
+INSERT INTO manual_matches_old
+    SELECT manual_matches.id, NULL, NULL, burn_tx_outs.id, gnosis_safe_withdrawals.id, manual_matches.actor,
+        manual_matches.justification, manual_matches.created_at
+    FROM manual_matches
+    JOIN burn_tx_outs ON burn_tx_outs.public_key_hex = manual_matches.burn_tx_out_public_key_hex
+    JOIN gnosis_safe_withdrawals ON gnosis_safe_withdrawals.chain_id = manual_matches.chain_id
+        AND gnosis_safe_withdrawals.eth_tx_hash = manual_matches.eth_tx_hash;
+
+CREATE TABLE audit_event_acknowledgements_old (
+    id INTEGER PRIMARY KEY,
+    audit_event_id INTEGER NOT NULL UNIQUE,
+    actor VARCHAR NOT NULL,
+    note TEXT NOT NULL,
+    manual_match_id INTEGER,
+    created_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (audit_event_id) REFERENCES audit_events(id),
+    FOREIGN KEY (manual_match_id) REFERENCES manual_matches_old(id)
+);
+INSERT INTO audit_event_acknowledgements_old
+    SELECT id, audit_event_id, actor, note,
+        CASE WHEN manual_match_id IN (SELECT id FROM manual_matches_old) THEN manual_match_id END,
+        created_at
+    FROM audit_event_acknowledgements;
+
+DROP TABLE audit_event_acknowledgements;
+DROP TABLE manual_matches;
+ALTER TABLE manual_matches_old RENAME TO manual_matches;
+ALTER TABLE audit_event_acknowledgements_old RENAME TO audit_event_acknowledgements;
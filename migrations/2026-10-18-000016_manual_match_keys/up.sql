@@ -0,0 +1,69 @@
+-- Manual matches used to reference the matched rows by id. Re-auditing Gnosis transactions and rewinding the ledger
+-- delete and re-create those rows under new ids, so matches are now keyed by identifiers that survive that: the nonce
+-- of the MintTx or the public key of the BurnTxOut, and the chain id and transaction hash of the GnosisSafeDeposit or
+-- GnosisSafeWithdrawal. The links in audited_mints/audited_burns are re-applied from this table whenever the rows
+-- get re-created.
+--
+-- audit_event_acknowledgements references this table, so it is re-created as well to keep its foreign key valid.
+-- Matches whose rows no longer exist cannot be keyed, and are dropped along with the acknowledgements' reference to
+-- them.
+CREATE TABLE manual_matches_new (
+    id INTEGER PRIMARY KEY,
+    -- The nonce of the matched MintTx, for mint matches.
+    mint_tx_nonce_hex VARCHAR(128),
+    -- The public key of the matched BurnTxOut, for burn matches.
+    burn_tx_out_public_key_hex VARCHAR(64),
+    -- The chain and Ethereum transaction of the matched GnosisSafeDeposit (mint matches) or GnosisSafeWithdrawal (burn
+    -- matches).
+    chain_id BIGINT NOT NULL,
+    eth_tx_hash VARCHAR(66) NOT NULL,
+    -- The operator that made the match.
+    actor VARCHAR NOT NULL,
+    -- Why the match was made.
+    justification TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    -- Constraints
+    CHECK ((mint_tx_nonce_hex IS NULL) != (burn_tx_out_public_key_hex IS NULL))
+);
+CREATE INDEX idx__manual_matches__mint_tx_nonce_hex ON manual_matches_new(mint_tx_nonce_hex);
+CREATE INDEX idx__manual_matches__burn_tx_out_public_key_hex ON manual_matches_new(burn_tx_out_public_key_hex);
+
+INSERT INTO manual_matches_new
+    SELECT manual_matches.id, mint_txs.nonce_hex, NULL, gnosis_safe_deposits.chain_id,
+        gnosis_safe_deposits.eth_tx_hash, manual_matches.actor, manual_matches.justification, manual_matches.created_at
+    FROM manual_matches
+    JOIN mint_txs ON mint_txs.id = manual_matches.mint_tx_id
+    JOIN gnosis_safe_deposits ON gnosis_safe_deposits.id = manual_matches.gnosis_safe_deposit_id;
+INSERT INTO manual_matches_new
+    SELECT manual_matches.id, NULL, burn_tx_outs.public_key_hex, gnosis_safe_withdrawals.chain_id,
+        gnosis_safe_withdrawals.eth_tx_hash, manual_matches.actor, manual_matches.justification,
+        manual_matches.created_at
+    FROM manual_matches
+    JOIN burn_tx_outs ON burn_tx_outs.id = manual_matches.burn_tx_out_id
+    JOIN gnosis_safe_withdrawals ON gnosis_safe_withdrawals.id = manual_matches.gnosis_safe_withdrawal_id;
+
+CREATE TABLE audit_event_acknowledgements_new (
+    id INTEGER PRIMARY KEY,
+    audit_event_id INTEGER NOT NULL UNIQUE,
+    -- The operator that investigated the event.
+    actor VARCHAR NOT NULL,
+    -- The outcome of the investigation.
+    note TEXT NOT NULL,
+    -- The manual match that resolved the event, if any.
+    manual_match_id INTEGER,
+    created_at TIMESTAMP NOT NULL,
+    -- Constraints
+    FOREIGN KEY (audit_event_id) REFERENCES audit_events(id),
+    FOREIGN KEY (manual_match_id) REFERENCES manual_matches_new(id)
+);
+INSERT INTO audit_event_acknowledgements_new
+    SELECT id, audit_event_id, actor, note,
+        CASE WHEN manual_match_id IN (SELECT id FROM manual_matches_new) THEN manual_match_id END,
+        created_at
+    FROM audit_event_acknowledgements;
+
+DROP TABLE audit_event_acknowledgements;
+DROP TABLE manual_matches;
+-- Renaming also updates the foreign key of audit_event_acknowledgements_new.
+ALTER TABLE manual_matches_new RENAME TO manual_matches;
+ALTER TABLE audit_event_acknowledgements_new RENAME TO audit_event_acknowledgements;
@@ -0,0 +1,4 @@
+DROP INDEX idx__audit_events__eth_tx_hash;
+ALTER TABLE audit_events DROP COLUMN eth_tx_hash;
+ALTER TABLE audit_events DROP COLUMN burn_tx_out_public_key_hex;
+ALTER TABLE audit_events DROP COLUMN mint_tx_nonce_hex;
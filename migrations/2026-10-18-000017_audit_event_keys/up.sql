@@ -0,0 +1,21 @@
+-- Rewinding and re-auditing delete the MintTxs, BurnTxOuts, deposits and withdrawals audit events reference, and SQLite
+-- reuses the freed ids for the rows that replace them. Those ids are now cleared when the rows get deleted, and the
+-- objects involved are identified by the following columns instead.
+-- The hex-encoded nonce of the MintTx involved.
+ALTER TABLE audit_events ADD COLUMN mint_tx_nonce_hex VARCHAR(128);
+-- The hex-encoded public key of the BurnTxOut involved.
+ALTER TABLE audit_events ADD COLUMN burn_tx_out_public_key_hex VARCHAR(64);
+-- The hash of the Ethereum transaction involved.
+ALTER TABLE audit_events ADD COLUMN eth_tx_hash VARCHAR(66);
+CREATE INDEX idx__audit_events__eth_tx_hash ON audit_events(eth_tx_hash);
+
+UPDATE audit_events SET mint_tx_nonce_hex = (
+    SELECT nonce_hex FROM mint_txs WHERE mint_txs.id = audit_events.mint_tx_id
+);
+UPDATE audit_events SET burn_tx_out_public_key_hex = (
+    SELECT public_key_hex FROM burn_tx_outs WHERE burn_tx_outs.id = audit_events.burn_tx_out_id
+);
+UPDATE audit_events SET eth_tx_hash = COALESCE(
+    (SELECT eth_tx_hash FROM gnosis_safe_deposits WHERE gnosis_safe_deposits.id = audit_events.gnosis_safe_deposit_id),
+    (SELECT eth_tx_hash FROM gnosis_safe_withdrawals WHERE gnosis_safe_withdrawals.id = audit_events.gnosis_safe_withdrawal_id)
+);
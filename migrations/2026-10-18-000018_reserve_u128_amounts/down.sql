@@ -0,0 +1,10 @@
+-- Amounts that do not fit in a signed 64 bit integer are clamped.
+ALTER TABLE reserve_withdrawals ADD COLUMN amount_int BIGINT NOT NULL DEFAULT 0;
+UPDATE reserve_withdrawals SET amount_int = CAST(amount AS INTEGER);
+ALTER TABLE reserve_withdrawals DROP COLUMN amount;
+ALTER TABLE reserve_withdrawals RENAME COLUMN amount_int TO amount;
+
+ALTER TABLE reserve_deposits ADD COLUMN amount_int BIGINT NOT NULL DEFAULT 0;
+UPDATE reserve_deposits SET amount_int = CAST(amount AS INTEGER);
+ALTER TABLE reserve_deposits DROP COLUMN amount;
+ALTER TABLE reserve_deposits RENAME COLUMN amount_int TO amount;
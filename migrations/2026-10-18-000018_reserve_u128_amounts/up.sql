@@ -0,0 +1,16 @@
+-- Amounts reported by reserve backends come from custodians and are not bounded to 64 bits any more than Gnosis amounts
+-- are, so they are stored as decimal strings as well. Statement imports used to reject amounts that do not fit in a
+-- signed 64 bit integer, so existing values are all non-negative.
+--
+-- The amounts of MintTxs, BurnTxOuts, the matches between them and reserve movements, and mint limits stay 64 bit:
+-- they are MobileCoin amounts, which the ledger bounds to a u64.
+
+ALTER TABLE reserve_deposits ADD COLUMN amount_text TEXT NOT NULL DEFAULT '0';
+UPDATE reserve_deposits SET amount_text = CAST(amount AS TEXT);
+ALTER TABLE reserve_deposits DROP COLUMN amount;
+ALTER TABLE reserve_deposits RENAME COLUMN amount_text TO amount;
+
+ALTER TABLE reserve_withdrawals ADD COLUMN amount_text TEXT NOT NULL DEFAULT '0';
+UPDATE reserve_withdrawals SET amount_text = CAST(amount AS TEXT);
+ALTER TABLE reserve_withdrawals DROP COLUMN amount;
+ALTER TABLE reserve_withdrawals RENAME COLUMN amount_text TO amount;
@@ -0,0 +1,2 @@
+DROP VIEW burn_tx_out_matches;
+DROP VIEW mint_tx_matches;
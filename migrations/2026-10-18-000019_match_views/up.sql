@@ -0,0 +1,17 @@
+-- Gnosis safe matches are stored in `audited_mints`/`audited_burns`, and allocate part of a MintTx/BurnTxOut amount,
+-- while the matches of the other reserve backends are stored in `audited_reserve_mints`/`audited_reserve_burns`, and
+-- always cover the whole amount. These views combine both, so that whether a MintTx/BurnTxOut is matched is answered in
+-- a single place regardless of the backend that matched it.
+CREATE VIEW mint_tx_matches AS
+    SELECT mint_tx_id, amount FROM audited_mints
+    UNION ALL
+    SELECT audited_reserve_mints.mint_tx_id, mint_txs.amount
+    FROM audited_reserve_mints
+    INNER JOIN mint_txs ON mint_txs.id = audited_reserve_mints.mint_tx_id;
+
+CREATE VIEW burn_tx_out_matches AS
+    SELECT burn_tx_out_id, amount FROM audited_burns
+    UNION ALL
+    SELECT audited_reserve_burns.burn_tx_out_id, burn_tx_outs.amount
+    FROM audited_reserve_burns
+    INNER JOIN burn_tx_outs ON burn_tx_outs.id = audited_reserve_burns.burn_tx_out_id;
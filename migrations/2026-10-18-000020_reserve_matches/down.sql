@@ -0,0 +1,88 @@
+CREATE TABLE audited_mints (
+    id INTEGER PRIMARY KEY,
+    mint_tx_id INTEGER NOT NULL,
+    gnosis_safe_deposit_id INTEGER NOT NULL,
+    amount BIGINT NOT NULL DEFAULT 0,
+    -- Constraints
+    FOREIGN KEY (mint_tx_id) REFERENCES mint_txs(id),
+    FOREIGN KEY (gnosis_safe_deposit_id) REFERENCES gnosis_safe_deposits(id)
+);
+CREATE INDEX idx__audited_mints__mint_tx_id ON audited_mints(mint_tx_id);
+CREATE INDEX idx__audited_mints__gnosis_safe_deposit_id ON audited_mints(gnosis_safe_deposit_id);
+
+CREATE TABLE audited_burns (
+    id INTEGER PRIMARY KEY,
+    burn_tx_out_id INTEGER NOT NULL,
+    gnosis_safe_withdrawal_id INTEGER NOT NULL,
+    amount BIGINT NOT NULL DEFAULT 0,
+    -- Constraints
+    FOREIGN KEY (burn_tx_out_id) REFERENCES burn_tx_outs(id),
+    FOREIGN KEY (gnosis_safe_withdrawal_id) REFERENCES gnosis_safe_withdrawals(id)
+);
+CREATE INDEX idx__audited_burns__burn_tx_out_id ON audited_burns(burn_tx_out_id);
+CREATE INDEX idx__audited_burns__gnosis_safe_withdrawal_id ON audited_burns(gnosis_safe_withdrawal_id);
+
+CREATE TABLE audited_reserve_mints (
+    id INTEGER PRIMARY KEY,
+    mint_tx_id INTEGER NOT NULL,
+    reserve_deposit_id INTEGER NOT NULL,
+    -- Constraints
+    FOREIGN KEY (mint_tx_id) REFERENCES mint_txs(id),
+    FOREIGN KEY (reserve_deposit_id) REFERENCES reserve_deposits(id)
+);
+CREATE INDEX idx__audited_reserve_mints__mint_tx_id ON audited_reserve_mints(mint_tx_id);
+CREATE INDEX idx__audited_reserve_mints__reserve_deposit_id ON audited_reserve_mints(reserve_deposit_id);
+
+CREATE TABLE audited_reserve_burns (
+    id INTEGER PRIMARY KEY,
+    burn_tx_out_id INTEGER NOT NULL,
+    reserve_withdrawal_id INTEGER NOT NULL,
+    -- Constraints
+    FOREIGN KEY (burn_tx_out_id) REFERENCES burn_tx_outs(id),
+    FOREIGN KEY (reserve_withdrawal_id) REFERENCES reserve_withdrawals(id)
+);
+CREATE INDEX idx__audited_reserve_burns__burn_tx_out_id ON audited_reserve_burns(burn_tx_out_id);
+CREATE INDEX idx__audited_reserve_burns__reserve_withdrawal_id ON audited_reserve_burns(reserve_withdrawal_id);
+
+INSERT INTO audited_mints (mint_tx_id, gnosis_safe_deposit_id, amount)
+    SELECT reserve_matches.mint_tx_id, gnosis_safe_deposits.id, reserve_matches.amount
+    FROM reserve_matches
+    INNER JOIN gnosis_safe_deposits ON gnosis_safe_deposits.eth_tx_hash = reserve_matches.reference
+    WHERE reserve_matches.backend = 'gnosis' AND reserve_matches.mint_tx_id IS NOT NULL
+    ORDER BY reserve_matches.id;
+INSERT INTO audited_burns (burn_tx_out_id, gnosis_safe_withdrawal_id, amount)
+    SELECT reserve_matches.burn_tx_out_id, gnosis_safe_withdrawals.id, reserve_matches.amount
+    FROM reserve_matches
+    INNER JOIN gnosis_safe_withdrawals ON gnosis_safe_withdrawals.eth_tx_hash = reserve_matches.reference
+    WHERE reserve_matches.backend = 'gnosis' AND reserve_matches.burn_tx_out_id IS NOT NULL
+    ORDER BY reserve_matches.id;
+INSERT INTO audited_reserve_mints (mint_tx_id, reserve_deposit_id)
+    SELECT reserve_matches.mint_tx_id, reserve_deposits.id
+    FROM reserve_matches
+    INNER JOIN reserve_deposits
+        ON reserve_deposits.source = reserve_matches.backend AND reserve_deposits.external_ref = reserve_matches.reference
+    WHERE reserve_matches.mint_tx_id IS NOT NULL
+    ORDER BY reserve_matches.id;
+INSERT INTO audited_reserve_burns (burn_tx_out_id, reserve_withdrawal_id)
+    SELECT reserve_matches.burn_tx_out_id, reserve_withdrawals.id
+    FROM reserve_matches
+    INNER JOIN reserve_withdrawals
+        ON reserve_withdrawals.source = reserve_matches.backend AND reserve_withdrawals.external_ref = reserve_matches.reference
+    WHERE reserve_matches.burn_tx_out_id IS NOT NULL
+    ORDER BY reserve_matches.id;
+
+CREATE VIEW mint_tx_matches AS
+    SELECT mint_tx_id, amount FROM audited_mints
+    UNION ALL
+    SELECT audited_reserve_mints.mint_tx_id, mint_txs.amount
+    FROM audited_reserve_mints
+    INNER JOIN mint_txs ON mint_txs.id = audited_reserve_mints.mint_tx_id;
+
+CREATE VIEW burn_tx_out_matches AS
+    SELECT burn_tx_out_id, amount FROM audited_burns
+    UNION ALL
+    SELECT audited_reserve_burns.burn_tx_out_id, burn_tx_outs.amount
+    FROM audited_reserve_burns
+    INNER JOIN burn_tx_outs ON burn_tx_outs.id = audited_reserve_burns.burn_tx_out_id;
+
+DROP TABLE reserve_matches;
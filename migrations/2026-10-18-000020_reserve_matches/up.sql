@@ -0,0 +1,58 @@
+-- Matches between MintTxs/BurnTxOuts and the deposits/withdrawals of every reserve backend, Gnosis safes included. This
+-- replaces audited_mints/audited_burns, which only held Gnosis safe matches, audited_reserve_mints/audited_reserve_burns,
+-- which held the matches of the other backends, and the views combining them. The deposit or withdrawal is identified by
+-- the name of the backend holding it and the reference that backend uses for it, so that matches do not depend on the
+-- way each backend stores its data.
+CREATE TABLE reserve_matches (
+    id INTEGER PRIMARY KEY,
+    -- The name of the reserve backend holding the matched deposit or withdrawal (`gnosis` for Gnosis safes).
+    backend VARCHAR(255) NOT NULL,
+    -- The reference of the matched deposit or withdrawal within its backend: the Ethereum transaction hash for Gnosis
+    -- safes, the custodian reference (`external_ref`) for the other backends.
+    reference VARCHAR(255) NOT NULL,
+    -- The matched MintTx, when matching a deposit.
+    mint_tx_id INTEGER,
+    -- The matched BurnTxOut, when matching a withdrawal.
+    burn_tx_out_id INTEGER,
+    -- Amount of the MintTx/BurnTxOut allocated by this match. A Gnosis deposit can be split across several mints and
+    -- several Gnosis withdrawals can be batched into one burn, the matches of the other backends always cover the whole
+    -- amount.
+    amount BIGINT NOT NULL,
+    -- Constraints
+    FOREIGN KEY (mint_tx_id) REFERENCES mint_txs(id),
+    FOREIGN KEY (burn_tx_out_id) REFERENCES burn_tx_outs(id),
+    CHECK ((mint_tx_id IS NULL) != (burn_tx_out_id IS NULL))
+);
+CREATE INDEX idx__reserve_matches__backend__reference ON reserve_matches(backend, reference);
+CREATE INDEX idx__reserve_matches__mint_tx_id ON reserve_matches(mint_tx_id);
+CREATE INDEX idx__reserve_matches__burn_tx_out_id ON reserve_matches(burn_tx_out_id);
+
+INSERT INTO reserve_matches (backend, reference, mint_tx_id, burn_tx_out_id, amount)
+    SELECT 'gnosis', gnosis_safe_deposits.eth_tx_hash, audited_mints.mint_tx_id, NULL, audited_mints.amount
+    FROM audited_mints
+    INNER JOIN gnosis_safe_deposits ON gnosis_safe_deposits.id = audited_mints.gnosis_safe_deposit_id
+    ORDER BY audited_mints.id;
+INSERT INTO reserve_matches (backend, reference, mint_tx_id, burn_tx_out_id, amount)
+    SELECT 'gnosis', gnosis_safe_withdrawals.eth_tx_hash, NULL, audited_burns.burn_tx_out_id, audited_burns.amount
+    FROM audited_burns
+    INNER JOIN gnosis_safe_withdrawals ON gnosis_safe_withdrawals.id = audited_burns.gnosis_safe_withdrawal_id
+    ORDER BY audited_burns.id;
+INSERT INTO reserve_matches (backend, reference, mint_tx_id, burn_tx_out_id, amount)
+    SELECT reserve_deposits.source, reserve_deposits.external_ref, audited_reserve_mints.mint_tx_id, NULL, mint_txs.amount
+    FROM audited_reserve_mints
+    INNER JOIN reserve_deposits ON reserve_deposits.id = audited_reserve_mints.reserve_deposit_id
+    INNER JOIN mint_txs ON mint_txs.id = audited_reserve_mints.mint_tx_id
+    ORDER BY audited_reserve_mints.id;
+INSERT INTO reserve_matches (backend, reference, mint_tx_id, burn_tx_out_id, amount)
+    SELECT reserve_withdrawals.source, reserve_withdrawals.external_ref, NULL, audited_reserve_burns.burn_tx_out_id, burn_tx_outs.amount
+    FROM audited_reserve_burns
+    INNER JOIN reserve_withdrawals ON reserve_withdrawals.id = audited_reserve_burns.reserve_withdrawal_id
+    INNER JOIN burn_tx_outs ON burn_tx_outs.id = audited_reserve_burns.burn_tx_out_id
+    ORDER BY audited_reserve_burns.id;
+
+DROP VIEW burn_tx_out_matches;
+DROP VIEW mint_tx_matches;
+DROP TABLE audited_reserve_burns;
+DROP TABLE audited_reserve_mints;
+DROP TABLE audited_burns;
+DROP TABLE audited_mints;
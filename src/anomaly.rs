@@ -0,0 +1,292 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Anomalies detected while auditing.
+
+use crate::{
+    db::{
+        BurnTxOut, GnosisSafeDeposit, GnosisSafeWithdrawal, MintConfigTx, MintTx, ReserveDeposit,
+        ReserveWithdrawal,
+    },
+    gnosis::{EthTxHash, EthTxValue},
+};
+use displaydoc::Display;
+use serde::{Deserialize, Serialize};
+
+/// The kind of an anomaly. Each kind has a matching counter in
+/// [crate::db::Counters].
+#[derive(Clone, Copy, Debug, Deserialize, Display, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    /// A burn exceeded the token balance
+    BurnExceedingBalance,
+
+    /// A mint did not match an active mint configuration
+    MintTxWithoutMatchingMintConfig,
+
+    /// A mint and a deposit with the same nonce did not match
+    MismatchingMintAndDeposit,
+
+    /// A burn and a withdrawal with the same public key did not match
+    MismatchingBurnAndWithdrawal,
+
+    /// A deposit of an unaudited Ethereum token
+    UnknownEthereumTokenDeposit,
+
+    /// A withdrawal of an unaudited Ethereum token
+    UnknownEthereumTokenWithdrawal,
+
+    /// A mint associated with an unaudited safe
+    MintToUnknownSafe,
+
+    /// A burn associated with an unaudited safe
+    BurnFromUnknownSafe,
+
+    /// An Ethereum transfer without a valid structure
+    InvalidEthereumTransaction,
+
+    /// An Ethereum MultiSig transaction without a valid structure
+    InvalidMultiSigTransaction,
+
+    /// An unexpected error matching a deposit to a mint
+    UnexpectedErrorMatchingDepositToMint,
+
+    /// An unexpected error matching a mint to a deposit
+    UnexpectedErrorMatchingMintToDeposit,
+
+    /// An unexpected error matching a withdrawal to a burn
+    UnexpectedErrorMatchingWithdrawalToBurn,
+
+    /// An unexpected error matching a burn to a withdrawal
+    UnexpectedErrorMatchingBurnToWithdrawal,
+
+    /// A mint with a nonce of an unknown type
+    MintTxWithUnknownNonce,
+
+    /// A deposit or withdrawal the verification source disagrees with
+    GnosisSourceDisagreement,
+
+    /// A mint configuration not signed by the token's governors
+    MintConfigTxGovernorViolation,
+
+    /// A mint that appeared at or after its tombstone block
+    MintTxPastTombstoneBlock,
+
+    /// A deposit or withdrawal whose amount does not fit in a MobileCoin amount
+    OversizedReserveAmount,
+
+    /// A deposit whose value had sub-precision digits dropped when truncating
+    /// it to the MobileCoin token precision
+    DepositTruncationRemainder,
+}
+
+impl AnomalyKind {
+    /// The severity of this kind of anomaly.
+    pub fn severity(&self) -> AnomalySeverity {
+        match self {
+            Self::BurnExceedingBalance
+            | Self::MintTxWithoutMatchingMintConfig
+            | Self::MismatchingMintAndDeposit
+            | Self::MismatchingBurnAndWithdrawal
+            | Self::MintToUnknownSafe
+            | Self::BurnFromUnknownSafe
+            | Self::GnosisSourceDisagreement
+            | Self::MintConfigTxGovernorViolation
+            | Self::MintTxPastTombstoneBlock
+            | Self::OversizedReserveAmount => AnomalySeverity::Critical,
+
+            Self::UnexpectedErrorMatchingDepositToMint
+            | Self::UnexpectedErrorMatchingMintToDeposit
+            | Self::UnexpectedErrorMatchingWithdrawalToBurn
+            | Self::UnexpectedErrorMatchingBurnToWithdrawal
+            | Self::MintTxWithUnknownNonce => AnomalySeverity::Error,
+
+            Self::UnknownEthereumTokenDeposit
+            | Self::UnknownEthereumTokenWithdrawal
+            | Self::InvalidEthereumTransaction
+            | Self::InvalidMultiSigTransaction
+            | Self::DepositTruncationRemainder => AnomalySeverity::Warning,
+        }
+    }
+}
+
+/// How severe an anomaly is.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Display, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalySeverity {
+    /// warning
+    Warning,
+
+    /// error
+    Error,
+
+    /// critical
+    Critical,
+}
+
+/// An anomaly detected while auditing, along with the details of the objects
+/// involved.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AnomalyEvent {
+    /// The kind of anomaly.
+    pub kind: AnomalyKind,
+
+    /// Description of what went wrong.
+    pub message: String,
+
+    /// The MobileCoin block index, if known.
+    pub block_index: Option<u64>,
+
+    /// The Ethereum block number, if known.
+    pub eth_block_number: Option<u64>,
+
+    /// The MobileCoin token id, if known.
+    pub token_id: Option<u64>,
+
+    /// Id of the MintTx involved.
+    pub mint_tx_id: Option<i32>,
+
+    /// Id of the BurnTxOut involved.
+    pub burn_tx_out_id: Option<i32>,
+
+    /// Id of the GnosisSafeDeposit involved.
+    pub gnosis_safe_deposit_id: Option<i32>,
+
+    /// Id of the GnosisSafeWithdrawal involved.
+    pub gnosis_safe_withdrawal_id: Option<i32>,
+
+    /// Id of the ReserveDeposit involved.
+    pub reserve_deposit_id: Option<i32>,
+
+    /// Id of the ReserveWithdrawal involved.
+    pub reserve_withdrawal_id: Option<i32>,
+
+    /// The hex-encoded nonce of the MintTx involved.
+    pub mint_tx_nonce_hex: Option<String>,
+
+    /// The hex-encoded public key of the BurnTxOut involved.
+    pub burn_tx_out_public_key_hex: Option<String>,
+
+    /// The amount minted or burned on the MobileCoin blockchain. The ledger
+    /// bounds these to u64.
+    pub mc_amount: Option<u64>,
+
+    /// The hash of the Ethereum transaction involved.
+    pub eth_tx_hash: Option<EthTxHash>,
+
+    /// The raw value of the Ethereum transaction involved.
+    pub eth_tx_value: Option<EthTxValue>,
+
+    /// The name of the reserve backend that reported the deposit or withdrawal
+    /// involved.
+    pub reserve_source: Option<String>,
+
+    /// The custodian reference of the deposit or withdrawal involved.
+    pub reserve_external_ref: Option<String>,
+
+    /// The amount deposited or withdrawn, in the MobileCoin token's precision.
+    /// This is not bounded to u64 since reserve amounts might not fit in one.
+    pub reserve_amount: Option<u128>,
+}
+
+impl AnomalyEvent {
+    /// Create a new [AnomalyEvent] without any object details.
+    pub fn new(kind: AnomalyKind, message: impl ToString) -> Self {
+        Self {
+            kind,
+            message: message.to_string(),
+            block_index: None,
+            eth_block_number: None,
+            token_id: None,
+            mint_tx_id: None,
+            burn_tx_out_id: None,
+            gnosis_safe_deposit_id: None,
+            gnosis_safe_withdrawal_id: None,
+            reserve_deposit_id: None,
+            reserve_withdrawal_id: None,
+            mint_tx_nonce_hex: None,
+            burn_tx_out_public_key_hex: None,
+            mc_amount: None,
+            eth_tx_hash: None,
+            eth_tx_value: None,
+            reserve_source: None,
+            reserve_external_ref: None,
+            reserve_amount: None,
+        }
+    }
+
+    /// Add the details of a [MintTx].
+    pub fn with_mint_tx(mut self, mint_tx: &MintTx) -> Self {
+        self.block_index = Some(mint_tx.block_index());
+        self.mint_tx_id = mint_tx.id();
+        self.token_id = Some(*mint_tx.token_id());
+        self.mint_tx_nonce_hex = Some(mint_tx.nonce_hex().to_string());
+        self.mc_amount = Some(mint_tx.amount());
+        self
+    }
+
+    /// Add the details of a [MintConfigTx].
+    pub fn with_mint_config_tx(mut self, mint_config_tx: &MintConfigTx) -> Self {
+        self.block_index = Some(mint_config_tx.block_index());
+        self.token_id = Some(*mint_config_tx.token_id());
+        self
+    }
+
+    /// Add the details of a [BurnTxOut].
+    pub fn with_burn_tx_out(mut self, burn_tx_out: &BurnTxOut) -> Self {
+        self.block_index = Some(burn_tx_out.block_index());
+        self.burn_tx_out_id = burn_tx_out.id();
+        self.token_id = Some(*burn_tx_out.token_id());
+        self.burn_tx_out_public_key_hex = Some(burn_tx_out.public_key_hex().to_string());
+        self.mc_amount = Some(burn_tx_out.amount());
+        self
+    }
+
+    /// Add the details of a [GnosisSafeDeposit].
+    pub fn with_gnosis_deposit(mut self, deposit: &GnosisSafeDeposit) -> Self {
+        self.gnosis_safe_deposit_id = deposit.id();
+        self.eth_block_number = Some(deposit.eth_block_number());
+        self.eth_tx_hash = Some(*deposit.eth_tx_hash());
+        self.eth_tx_value = Some(*deposit.eth_tx_value());
+        self.reserve_amount = Some(deposit.amount());
+        self
+    }
+
+    /// Add the details of a [GnosisSafeWithdrawal].
+    pub fn with_gnosis_withdrawal(mut self, withdrawal: &GnosisSafeWithdrawal) -> Self {
+        self.gnosis_safe_withdrawal_id = withdrawal.id();
+        self.eth_block_number = Some(withdrawal.eth_block_number());
+        self.eth_tx_hash = Some(*withdrawal.eth_tx_hash());
+        self.eth_tx_value = Some(*withdrawal.eth_tx_value());
+        self.reserve_amount = Some(withdrawal.amount());
+        self
+    }
+
+    /// Add the details of a [ReserveDeposit].
+    pub fn with_reserve_deposit(mut self, deposit: &ReserveDeposit) -> Self {
+        self.reserve_deposit_id = deposit.id();
+        self.token_id = Some(*deposit.token_id());
+        self.reserve_source = Some(deposit.source().to_string());
+        self.reserve_external_ref = Some(deposit.external_ref().to_string());
+        self.reserve_amount = Some(deposit.amount());
+        self
+    }
+
+    /// Add the details of a [ReserveWithdrawal].
+    pub fn with_reserve_withdrawal(mut self, withdrawal: &ReserveWithdrawal) -> Self {
+        self.reserve_withdrawal_id = withdrawal.id();
+        self.token_id = Some(*withdrawal.token_id());
+        self.reserve_source = Some(withdrawal.source().to_string());
+        self.reserve_external_ref = Some(withdrawal.external_ref().to_string());
+        self.reserve_amount = Some(withdrawal.amount());
+        self
+    }
+
+    /// Add the hash and block number of the Ethereum transaction involved.
+    pub fn with_eth_tx(mut self, eth_tx_hash: &EthTxHash, eth_block_number: u64) -> Self {
+        self.eth_tx_hash = Some(*eth_tx_hash);
+        self.eth_block_number = Some(eth_block_number);
+        self
+    }
+}
@@ -5,15 +5,26 @@
 use chrono::{TimeZone, Utc};
 use clap::{Parser, Subcommand};
 use grpcio::{EnvBuilder, ServerBuilder};
-use mc_common::logger::{log, o, Logger};
+use mc_common::{
+    logger::{log, o, Logger},
+    HashMap,
+};
 use mc_ledger_db::{Ledger, LedgerDB};
 use mc_reserve_auditor::{
     db::{
-        transaction, AuditedBurn, AuditedMint, BlockAuditData, BlockBalance, Conn,
-        ReserveAuditorDb, SyncBlockData,
+        transaction, AuditEvent, AuditEventAcknowledgement, BlockAuditData, BlockBalance,
+        ManualMatch, ReserveAuditorDb,
+    },
+    gnosis::{GnosisSafeConfig, GnosisSync},
+    governance::{GovernanceConfig, MintGovernanceAuditor},
+    http_api::{start_http_server, OperatorToken, OperatorTokens},
+    mint_limits::MintLimitsReport,
+    notifier::{NotifierThread, WebhookNotifier},
+    reserve::{
+        CsvStatementBackend, GnosisReserveBackend, ReserveBackend, ReserveBackends, ReserveConfig,
+        ReserveSyncThread,
     },
-    gnosis::{GnosisSafeConfig, GnosisSyncThread},
-    http_api::start_http_server,
+    unmatched::{UnmatchedAlertConfig, UnmatchedChecker, UnmatchedCheckerThread},
     Error, ReserveAuditorService,
 };
 use mc_reserve_auditor_api::ReserveAuditorUri;
@@ -23,6 +34,7 @@ use mc_util_uri::AdminUri;
 use mc_watcher::watcher_db::WatcherDB;
 use serde_json::json;
 use std::{cmp::Ordering, path::PathBuf, sync::Arc, thread::sleep, time::Duration};
+use url::Url;
 
 /// Maximum number of concurrent connections in the database pool.
 const DB_POOL_SIZE: u32 = 10;
@@ -63,6 +75,42 @@ pub enum Command {
         /// When provided, the configured gnosis safe(s) will be audited.
         #[clap(long, env = "MC_GNOSIS_SAFE_CONFIG", parse(try_from_str = parse_gnosis_safe_config))]
         gnosis_safe_config: Option<GnosisSafeConfig>,
+
+        /// Reserve backends configuration file (json/toml).
+        /// When provided, the configured reserves (e.g. CSV statements) will be
+        /// audited alongside the Gnosis safe(s).
+        #[clap(long, env = "MC_RESERVE_CONFIG", parse(try_from_str = parse_reserve_config))]
+        reserve_config: Option<ReserveConfig>,
+
+        /// Mint governance configuration file (json/toml).
+        /// When provided, the mint configurations of the configured tokens will
+        /// be verified against their governors.
+        #[clap(long, env = "MC_GOVERNANCE_CONFIG", parse(try_from_str = parse_governance_config))]
+        governance_config: Option<GovernanceConfig>,
+
+        /// Unmatched alerts configuration file (json/toml), holding the grace
+        /// periods after which unmatched items are alerted on.
+        #[clap(long, env = "MC_UNMATCHED_ALERTS_CONFIG", parse(try_from_str = parse_unmatched_alerts_config))]
+        unmatched_alerts_config: Option<UnmatchedAlertConfig>,
+
+        /// How often to check for mints, burns, deposits and withdrawals that
+        /// stayed unmatched for longer than their grace period (in seconds).
+        #[clap(long, default_value = "60", parse(try_from_str = parse_duration_in_seconds), env = "MC_UNMATCHED_CHECK_INTERVAL")]
+        unmatched_check_interval: Duration,
+
+        /// Webhook URL(s) to POST anomaly notifications to. May be repeated,
+        /// or given as a comma separated list.
+        #[clap(
+            long = "webhook-url",
+            env = "MC_WEBHOOK_URLS",
+            use_value_delimiter = true
+        )]
+        webhook_urls: Vec<Url>,
+
+        /// How many seconds to wait between attempts to deliver pending
+        /// anomaly notifications.
+        #[clap(long, default_value = "5", parse(try_from_str = parse_duration_in_seconds), env = "MC_WEBHOOK_POLL_INTERVAL")]
+        webhook_poll_interval: Duration,
     },
 
     /// Get the audit data for a specific block, optionally in JSON format
@@ -97,6 +145,139 @@ pub enum Command {
         /// When provided, the configured gnosis safe(s) will be audited.
         #[clap(long, env = "MC_GNOSIS_SAFE_CONFIG", parse(try_from_str = parse_gnosis_safe_config))]
         gnosis_safe_config: Option<GnosisSafeConfig>,
+
+        /// Operator bearer token(s) for the write API, in `<actor>:<token>`
+        /// format. May be repeated, or given as a comma separated list. The
+        /// write API is disabled when none are given.
+        #[clap(
+            long = "operator-token",
+            env = "MC_OPERATOR_TOKENS",
+            use_value_delimiter = true
+        )]
+        operator_tokens: Vec<OperatorToken>,
+    },
+
+    /// Re-audit the Gnosis transactions stored in the reserve auditor database.
+    /// All deposits, withdrawals and audit records are rebuilt from the stored
+    /// raw transaction data, without contacting the Gnosis API.
+    Reaudit {
+        /// Path to reserve auditor db.
+        #[clap(long, parse(from_os_str), env = "MC_RESERVE_AUDITOR_DB")]
+        reserve_auditor_db: PathBuf,
+
+        /// Gnosis safe configuration file (json/toml).
+        #[clap(long, env = "MC_GNOSIS_SAFE_CONFIG", parse(try_from_str = parse_gnosis_safe_config))]
+        gnosis_safe_config: GnosisSafeConfig,
+    },
+
+    /// Rewind the reserve auditor database so that the given block becomes the
+    /// last synced block. Data derived from later blocks is deleted, while
+    /// Gnosis data is kept. The next ledger scan resumes from the following
+    /// block.
+    Rewind {
+        /// Path to reserve auditor db.
+        #[clap(long, parse(from_os_str), env = "MC_RESERVE_AUDITOR_DB")]
+        reserve_auditor_db: PathBuf,
+
+        /// Block index to rewind to. This block and all blocks before it are
+        /// kept.
+        #[clap(long)]
+        to_block: u64,
+    },
+
+    /// List audit events (anomalies detected while auditing), optionally in
+    /// JSON format (serialized `AuditEvent`s).
+    ListAuditEvents {
+        /// Path to reserve auditor db.
+        #[clap(long, parse(from_os_str), env = "MC_RESERVE_AUDITOR_DB")]
+        reserve_auditor_db: PathBuf,
+
+        /// Number of events to skip.
+        #[clap(long)]
+        offset: Option<u64>,
+
+        /// Maximum number of events to list.
+        #[clap(long)]
+        limit: Option<u64>,
+
+        /// Only list events that were not acknowledged yet.
+        #[clap(long)]
+        open_only: bool,
+
+        /// Output JSON (serialized `AuditEvent`s).
+        #[clap(long, env = "MC_JSON")]
+        json: bool,
+    },
+
+    /// Acknowledge an audit event after investigating it.
+    AcknowledgeAuditEvent {
+        /// Path to reserve auditor db.
+        #[clap(long, parse(from_os_str), env = "MC_RESERVE_AUDITOR_DB")]
+        reserve_auditor_db: PathBuf,
+
+        /// Id of the audit event to acknowledge.
+        #[clap(long)]
+        audit_event_id: i32,
+
+        /// The operator acknowledging the event.
+        #[clap(long, env = "MC_ACTOR")]
+        actor: String,
+
+        /// The outcome of the investigation.
+        #[clap(long)]
+        note: String,
+
+        /// Id of the manual match that resolved the event, if any.
+        #[clap(long)]
+        manual_match_id: Option<i32>,
+    },
+
+    /// Force a match between a MintTx and a Gnosis safe deposit that did not
+    /// match automatically.
+    ForceMatchMint {
+        /// Path to reserve auditor db.
+        #[clap(long, parse(from_os_str), env = "MC_RESERVE_AUDITOR_DB")]
+        reserve_auditor_db: PathBuf,
+
+        /// Id of the MintTx.
+        #[clap(long)]
+        mint_tx_id: i32,
+
+        /// Id of the Gnosis safe deposit.
+        #[clap(long)]
+        gnosis_safe_deposit_id: i32,
+
+        /// The operator making the match.
+        #[clap(long, env = "MC_ACTOR")]
+        actor: String,
+
+        /// Why the match is being made.
+        #[clap(long)]
+        justification: String,
+    },
+
+    /// Force a match between a BurnTxOut and a Gnosis safe withdrawal that did
+    /// not match automatically.
+    ForceMatchBurn {
+        /// Path to reserve auditor db.
+        #[clap(long, parse(from_os_str), env = "MC_RESERVE_AUDITOR_DB")]
+        reserve_auditor_db: PathBuf,
+
+        /// Id of the BurnTxOut.
+        #[clap(long)]
+        burn_tx_out_id: i32,
+
+        /// Id of the Gnosis safe withdrawal.
+        #[clap(long)]
+        gnosis_safe_withdrawal_id: i32,
+
+        /// The operator making the match.
+        #[clap(long, env = "MC_ACTOR")]
+        actor: String,
+
+        /// Why the match is being made.
+        #[clap(long)]
+        justification: String,
     },
 }
 
@@ -127,6 +308,12 @@ async fn main() {
             listen_uri,
             admin_listen_uri,
             gnosis_safe_config,
+            reserve_config,
+            governance_config,
+            unmatched_alerts_config,
+            unmatched_check_interval,
+            webhook_urls,
+            webhook_poll_interval,
         } => cmd_scan_ledger(
             ledger_db,
             watcher_db,
@@ -135,6 +322,12 @@ async fn main() {
             listen_uri,
             admin_listen_uri,
             gnosis_safe_config,
+            reserve_config,
+            governance_config,
+            unmatched_alerts_config,
+            unmatched_check_interval,
+            webhook_urls,
+            webhook_poll_interval,
             logger,
         ),
 
@@ -151,16 +344,105 @@ async fn main() {
             gnosis_safe_config,
             port,
             host,
+            operator_tokens,
         } => {
             cmd_start_http_server(
                 reserve_auditor_db,
                 gnosis_safe_config.expect("Failure to read gnosis safe config file."),
                 port,
                 host,
+                OperatorTokens::new(operator_tokens),
                 logger,
             )
             .await;
         }
+
+        Command::Reaudit {
+            reserve_auditor_db,
+            gnosis_safe_config,
+        } => {
+            cmd_reaudit(reserve_auditor_db, gnosis_safe_config, logger);
+        }
+
+        Command::Rewind {
+            reserve_auditor_db,
+            to_block,
+        } => {
+            cmd_rewind(reserve_auditor_db, to_block, logger);
+        }
+
+        Command::ListAuditEvents {
+            reserve_auditor_db,
+            offset,
+            limit,
+            open_only,
+            json,
+        } => {
+            cmd_list_audit_events(reserve_auditor_db, offset, limit, open_only, json, logger);
+        }
+
+        Command::AcknowledgeAuditEvent {
+            reserve_auditor_db,
+            audit_event_id,
+            actor,
+            note,
+            manual_match_id,
+        } => {
+            let conn = open_reserve_auditor_db(reserve_auditor_db, logger.clone())
+                .get_conn()
+                .expect("Could not get db connection");
+            AuditEventAcknowledgement::acknowledge(
+                audit_event_id,
+                &actor,
+                &note,
+                manual_match_id,
+                &conn,
+            )
+            .expect("Failed acknowledging audit event");
+            log::info!(logger, "Acknowledged audit event {}", audit_event_id);
+        }
+
+        Command::ForceMatchMint {
+            reserve_auditor_db,
+            mint_tx_id,
+            gnosis_safe_deposit_id,
+            actor,
+            justification,
+        } => {
+            let conn = open_reserve_auditor_db(reserve_auditor_db, logger.clone())
+                .get_conn()
+                .expect("Could not get db connection");
+            let manual_match = ManualMatch::force_match_mint(
+                mint_tx_id,
+                gnosis_safe_deposit_id,
+                &actor,
+                &justification,
+                &conn,
+            )
+            .expect("Failed matching mint");
+            log::info!(logger, "Created manual match {:?}", manual_match.id());
+        }
+
+        Command::ForceMatchBurn {
+            reserve_auditor_db,
+            burn_tx_out_id,
+            gnosis_safe_withdrawal_id,
+            actor,
+            justification,
+        } => {
+            let conn = open_reserve_auditor_db(reserve_auditor_db, logger.clone())
+                .get_conn()
+                .expect("Could not get db connection");
+            let manual_match = ManualMatch::force_match_burn(
+                burn_tx_out_id,
+                gnosis_safe_withdrawal_id,
+                &actor,
+                &justification,
+                &conn,
+            )
+            .expect("Failed matching burn");
+            log::info!(logger, "Created manual match {:?}", manual_match.id());
+        }
     }
 }
 
@@ -174,23 +456,23 @@ fn cmd_scan_ledger(
     listen_uri: Option<ReserveAuditorUri>,
     admin_listen_uri: Option<AdminUri>,
     gnosis_safe_config: Option<GnosisSafeConfig>,
+    reserve_config: Option<ReserveConfig>,
+    governance_config: Option<GovernanceConfig>,
+    unmatched_alerts_config: Option<UnmatchedAlertConfig>,
+    unmatched_check_interval: Duration,
+    webhook_urls: Vec<Url>,
+    webhook_poll_interval: Duration,
     logger: Logger,
 ) {
+    let reserve_config = reserve_config.unwrap_or_default();
+    let governance_config = governance_config.unwrap_or_default();
     let ledger_db = LedgerDB::open(&ledger_db_path).expect("Could not open ledger DB");
 
     let watcher_db = watcher_db_path.map(|watcher_db_path| {
         WatcherDB::open_ro(&watcher_db_path, logger.clone()).expect("Could not open watcher DB")
     });
 
-    let reserve_auditor_db = ReserveAuditorDb::new_from_path(
-        &reserve_auditor_db_path
-            .into_os_string()
-            .into_string()
-            .unwrap(),
-        DB_POOL_SIZE,
-        logger.clone(),
-    )
-    .expect("Could not open reserve auditor DB");
+    let reserve_auditor_db = open_reserve_auditor_db(reserve_auditor_db_path, logger.clone());
 
     let _api_server = listen_uri.map(|listen_uri| {
         // Create RPC services.
@@ -236,26 +518,74 @@ fn cmd_scan_ledger(
         .expect("Failed starting admin grpc server")
     });
 
-    let _gnosis_safe_fetcher_threads = gnosis_safe_config.as_ref().map(|gnosis_safe_config| {
-        gnosis_safe_config
-            .safes
-            .iter()
-            .map(|safe_config| {
-                GnosisSyncThread::start(
-                    safe_config,
-                    reserve_auditor_db.clone(),
-                    poll_interval,
-                    logger.clone(),
-                )
-                .expect("Failed starting gnosis safe fetcher thread")
-            })
-            .collect::<Vec<_>>()
+    if let Some(gnosis_safe_config) = gnosis_safe_config.as_ref() {
+        GnosisSync::apply_config_changes(gnosis_safe_config, &reserve_auditor_db, &logger)
+            .expect("Failed applying Gnosis safe config changes");
+    }
+
+    let mut backends: Vec<Arc<dyn ReserveBackend>> = Vec::new();
+    if let Some(gnosis_safe_config) = gnosis_safe_config {
+        backends.push(Arc::new(
+            GnosisReserveBackend::new(
+                gnosis_safe_config,
+                reserve_auditor_db.clone(),
+                logger.clone(),
+            )
+            .expect("Failed creating Gnosis reserve backend"),
+        ));
+    }
+    for csv_statement_config in reserve_config.csv_statements {
+        backends.push(Arc::new(CsvStatementBackend::new(
+            csv_statement_config,
+            reserve_auditor_db.clone(),
+            logger.clone(),
+        )));
+    }
+    let reserve_backends =
+        ReserveBackends::new(backends).expect("Invalid reserve backends configuration");
+
+    let mint_governance =
+        MintGovernanceAuditor::new(&governance_config.mint_governors, logger.clone())
+            .expect("Invalid mint governors configuration");
+
+    let _reserve_sync_threads = reserve_backends
+        .backends()
+        .iter()
+        .map(|backend| {
+            ReserveSyncThread::start(backend.clone(), poll_interval, logger.clone())
+                .expect("Failed starting reserve sync thread")
+        })
+        .collect::<Vec<_>>();
+
+    let _unmatched_checker_thread = UnmatchedCheckerThread::start(
+        UnmatchedChecker::new(unmatched_alerts_config.unwrap_or_default(), logger.clone()),
+        reserve_auditor_db.clone(),
+        unmatched_check_interval,
+        logger.clone(),
+    )
+    .expect("Failed starting unmatched checker thread");
+
+    let _notifier_thread = (!webhook_urls.is_empty()).then(|| {
+        log::info!(
+            logger,
+            "Delivering anomaly notifications to {:?}",
+            webhook_urls
+        );
+        NotifierThread::start(
+            WebhookNotifier::new(webhook_urls, logger.clone())
+                .expect("Failed creating webhook notifier"),
+            reserve_auditor_db.clone(),
+            webhook_poll_interval,
+            logger.clone(),
+        )
+        .expect("Failed starting notifier thread")
     });
 
     loop {
         sync_loop(
             &reserve_auditor_db,
-            gnosis_safe_config.as_ref(),
+            &reserve_backends,
+            &mint_governance,
             &ledger_db,
             &watcher_db,
             &logger,
@@ -272,15 +602,7 @@ fn cmd_get_block_audit_data(
     json: bool,
     logger: Logger,
 ) {
-    let reserve_auditor_db = ReserveAuditorDb::new_from_path(
-        &reserve_auditor_db_path
-            .into_os_string()
-            .into_string()
-            .unwrap(),
-        DB_POOL_SIZE,
-        logger,
-    )
-    .expect("Could not open reserve auditor DB");
+    let reserve_auditor_db = open_reserve_auditor_db(reserve_auditor_db_path, logger);
 
     let conn = reserve_auditor_db
         .get_conn()
@@ -298,7 +620,11 @@ fn cmd_get_block_audit_data(
         if json {
             let obj = json!({
                 "block_audit_data": audit_data,
-                "balances": balance_map,
+                // Balances may exceed 64 bits, so they are encoded as decimal strings.
+                "balances": balance_map
+                    .iter()
+                    .map(|(token_id, balance)| (token_id, balance.to_string()))
+                    .collect::<HashMap<_, _>>(),
             });
             println!(
                 "{}",
@@ -317,31 +643,114 @@ fn cmd_get_block_audit_data(
     .expect("db transaction failed");
 }
 
-async fn cmd_start_http_server(
+/// Implementation of the Reaudit CLI command.
+fn cmd_reaudit(
     reserve_auditor_db_path: PathBuf,
     gnosis_safe_config: GnosisSafeConfig,
-    port: u16,
-    host: String,
     logger: Logger,
 ) {
-    let reserve_auditor_db = ReserveAuditorDb::new_from_path(
+    let reserve_auditor_db = open_reserve_auditor_db(reserve_auditor_db_path, logger.clone());
+
+    let num_processed = GnosisSync::reaudit_all(&gnosis_safe_config, &reserve_auditor_db, &logger)
+        .expect("Failed re-auditing Gnosis transactions");
+
+    log::info!(
+        logger,
+        "Re-audited {} stored Gnosis transactions",
+        num_processed
+    );
+}
+
+/// Implementation of the Rewind CLI command.
+fn cmd_rewind(reserve_auditor_db_path: PathBuf, to_block: u64, logger: Logger) {
+    let reserve_auditor_db = open_reserve_auditor_db(reserve_auditor_db_path, logger.clone());
+
+    let num_blocks_synced = reserve_auditor_db
+        .rewind_to_block(to_block)
+        .expect("Failed rewinding reserve auditor DB");
+
+    log::info!(
+        logger,
+        "Rewound reserve auditor DB to block {}, {} blocks are now synced",
+        to_block,
+        num_blocks_synced
+    );
+}
+
+/// Implementation of the ListAuditEvents CLI command.
+fn cmd_list_audit_events(
+    reserve_auditor_db_path: PathBuf,
+    offset: Option<u64>,
+    limit: Option<u64>,
+    open_only: bool,
+    json: bool,
+    logger: Logger,
+) {
+    let conn = open_reserve_auditor_db(reserve_auditor_db_path, logger)
+        .get_conn()
+        .expect("Could not get db connection");
+
+    let audit_events =
+        AuditEvent::list(offset, limit, open_only, &conn).expect("Failed listing audit events");
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string(&audit_events).expect("failed serializing json")
+        );
+    } else {
+        for audit_event in audit_events {
+            println!(
+                "#{} {} [{}] {}: {}",
+                audit_event.id().unwrap_or_default(),
+                audit_event.created_at(),
+                audit_event.severity(),
+                audit_event.kind(),
+                audit_event.message(),
+            );
+        }
+    }
+}
+
+/// Open the reserve auditor database at a given path.
+fn open_reserve_auditor_db(reserve_auditor_db_path: PathBuf, logger: Logger) -> ReserveAuditorDb {
+    ReserveAuditorDb::new_from_path(
         &reserve_auditor_db_path
             .into_os_string()
             .into_string()
             .unwrap(),
         DB_POOL_SIZE,
-        logger.clone(),
+        logger,
     )
-    .expect("Could not open reserve auditor DB");
+    .expect("Could not open reserve auditor DB")
+}
 
-    start_http_server(reserve_auditor_db, gnosis_safe_config, port, host).await;
+async fn cmd_start_http_server(
+    reserve_auditor_db_path: PathBuf,
+    gnosis_safe_config: GnosisSafeConfig,
+    port: u16,
+    host: String,
+    operator_tokens: OperatorTokens,
+    logger: Logger,
+) {
+    let reserve_auditor_db = open_reserve_auditor_db(reserve_auditor_db_path, logger.clone());
+
+    start_http_server(
+        reserve_auditor_db,
+        gnosis_safe_config,
+        port,
+        host,
+        operator_tokens,
+    )
+    .await;
 }
 
 /// Synchronizes the reserve auditor database with the ledger database.
 /// Will run until all blocks in the ledger database have been synced.
 fn sync_loop(
     reserve_auditor_db: &ReserveAuditorDb,
-    gnosis_safe_config: Option<&GnosisSafeConfig>,
+    reserve_backends: &ReserveBackends,
+    mint_governance: &MintGovernanceAuditor,
     ledger_db: &LedgerDB,
     watcher_db: &Option<WatcherDB>,
     logger: &Logger,
@@ -356,7 +765,15 @@ fn sync_loop(
             .unwrap_or(0);
         match num_blocks_synced.cmp(&num_blocks_in_ledger) {
             Ordering::Equal => {
-                // Nothing more to sync.
+                // Nothing more to sync. Refresh the mint limit gauges, since the blocks we
+                // synced may have minted or replaced mint configurations. Failing to do so
+                // should not stop the scanner.
+                match MintLimitsReport::compute(&conn) {
+                    Ok(report) => report.update_prometheus(),
+                    Err(err) => {
+                        log::error!(logger, "Failed computing mint limits report: {}", err);
+                    }
+                }
                 break;
             }
             Ordering::Greater => {
@@ -386,78 +803,22 @@ fn sync_loop(
                         block_timestamp,
                     )?;
 
-                    // If we were configured to audit Gnosis safes, attempt to do that with
-                    // information we found in the block.
-                    if let Some(config) = gnosis_safe_config {
-                        audit_block_data(&sync_block_data, config, &conn, logger)?;
-                    }
+                    // Verify the mint configurations we found in the block were signed by the
+                    // governors of their token.
+                    mint_governance.audit_block(block_data.block().index, &conn)?;
+
+                    // Audit the mints and burns we found in the block against the reserve
+                    // backend of each token.
+                    reserve_backends.audit_block_data(&sync_block_data, &conn, logger)?;
 
                     Ok(())
                 })?;
-            }
-        };
-    }
-
-    Ok(())
-}
 
-/// Perform gnosis auditing of any data found in the block.
-fn audit_block_data(
-    sync_block_data: &SyncBlockData,
-    config: &GnosisSafeConfig,
-    conn: &Conn,
-    logger: &Logger,
-) -> Result<(), Error> {
-    // Audit mints.
-    for mint_tx in &sync_block_data.mint_txs {
-        match AuditedMint::try_match_mint_with_deposit(mint_tx, config, conn) {
-            Ok(deposit) => {
-                log::info!(
-                    logger,
-                    "MintTx nonce={} matched Gnosis deposit eth_tx_hash={}",
-                    mint_tx.nonce_hex(),
-                    deposit.eth_tx_hash(),
-                )
-            }
-            Err(Error::NotFound) => {
-                log::debug!(logger, "MintTx with nonce={} does not currently have matching Gnosis deposit, this could be fine if the safe data is not fully synced.", mint_tx.nonce_hex());
-            }
-            Err(err) => {
-                log::error!(
-                    logger,
-                    "MintTx nonce={} failed matching Gnosis deposit: {}",
-                    mint_tx.nonce_hex(),
-                    err
-                );
+                reserve_auditor_db.notify_block_synced();
             }
         };
     }
 
-    // Audit burns.
-    for burn_tx_out in &sync_block_data.burn_tx_outs {
-        match AuditedBurn::try_match_burn_with_withdrawal(burn_tx_out, config, conn) {
-            Ok(withdrawal) => {
-                log::info!(
-                    logger,
-                    "BurnTxOut pub_key={} matched Gnosis withdrawal eth_tx_hash={}",
-                    burn_tx_out.public_key_hex(),
-                    withdrawal.eth_tx_hash(),
-                )
-            }
-            Err(Error::NotFound) => {
-                log::debug!(logger, "BurnTxOut with pub_key={} does not currently have matching Gnosis withdrawal, this could be fine if the safe data is not fully synced.", burn_tx_out.public_key_hex());
-            }
-            Err(err) => {
-                log::error!(
-                    logger,
-                    "BurnTxOut pub_key={} failed matching Gnosis withdrawal: {}",
-                    burn_tx_out.public_key_hex(),
-                    err
-                );
-            }
-        }
-    }
-
     Ok(())
 }
 
@@ -465,3 +826,18 @@ fn audit_block_data(
 fn parse_gnosis_safe_config(path: &str) -> Result<GnosisSafeConfig, Error> {
     Ok(GnosisSafeConfig::load_from_path(path)?)
 }
+
+/// Load a reserve backends config file.
+fn parse_reserve_config(path: &str) -> Result<ReserveConfig, Error> {
+    ReserveConfig::load_from_path(path)
+}
+
+/// Load a mint governance config file.
+fn parse_governance_config(path: &str) -> Result<GovernanceConfig, Error> {
+    GovernanceConfig::load_from_path(path)
+}
+
+/// Load an unmatched alerts config file.
+fn parse_unmatched_alerts_config(path: &str) -> Result<UnmatchedAlertConfig, Error> {
+    UnmatchedAlertConfig::load_from_path(path)
+}
@@ -0,0 +1,43 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Convert to/from mc_reserve_auditor_api::AuditEvent.
+
+use crate::db::AuditEvent;
+
+/// Convert AuditEvent --> mc_reserve_auditor_api::AuditEvent
+impl From<&AuditEvent> for mc_reserve_auditor_api::AuditEvent {
+    fn from(src: &AuditEvent) -> Self {
+        let mut dst = mc_reserve_auditor_api::AuditEvent::new();
+        dst.set_id(src.id().unwrap_or_default() as u32);
+        dst.set_kind(src.kind().to_string());
+        dst.set_severity(src.severity().to_string());
+        dst.set_message(src.message().to_string());
+        dst.set_block_index(src.block_index().unwrap_or_default());
+        dst.set_eth_block_number(src.eth_block_number().unwrap_or_default());
+        dst.set_mint_tx_id(src.mint_tx_id().unwrap_or_default() as u32);
+        dst.set_burn_tx_out_id(src.burn_tx_out_id().unwrap_or_default() as u32);
+        dst.set_gnosis_safe_deposit_id(src.gnosis_safe_deposit_id().unwrap_or_default() as u32);
+        dst.set_gnosis_safe_withdrawal_id(
+            src.gnosis_safe_withdrawal_id().unwrap_or_default() as u32
+        );
+        dst.set_reserve_deposit_id(src.reserve_deposit_id().unwrap_or_default() as u32);
+        dst.set_reserve_withdrawal_id(src.reserve_withdrawal_id().unwrap_or_default() as u32);
+        dst.set_created_at(
+            src.created_at()
+                .timestamp_nanos_opt()
+                .expect("Invalid created_at stored in database") as u64,
+        );
+        dst.set_mint_tx_nonce_hex(src.mint_tx_nonce_hex().unwrap_or_default().to_string());
+        dst.set_burn_tx_out_public_key_hex(
+            src.burn_tx_out_public_key_hex()
+                .unwrap_or_default()
+                .to_string(),
+        );
+        dst.set_eth_tx_hash(
+            src.eth_tx_hash()
+                .map(|eth_tx_hash| eth_tx_hash.to_string())
+                .unwrap_or_default(),
+        );
+        dst
+    }
+}
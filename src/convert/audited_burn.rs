@@ -0,0 +1,57 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Convert to/from mc_reserve_auditor_api::AuditedBurn.
+
+use crate::db::AuditedBurn as DbAuditedBurn;
+use mc_reserve_auditor_api::AuditedBurn as ProtoAuditedBurn;
+
+/// Convert DbAuditedBurn --> ProtoAuditedBurn
+impl From<&DbAuditedBurn> for ProtoAuditedBurn {
+    fn from(src: &DbAuditedBurn) -> Self {
+        let mut dst = Self::new();
+        dst.set_id(src.id.unwrap_or_default() as u32);
+        dst.set_burn_tx_out_id(src.burn_tx_out_id as u32);
+        dst.set_gnosis_safe_withdrawal_id(src.gnosis_safe_withdrawal_id as u32);
+        dst.set_amount(src.amount as u64);
+        dst
+    }
+}
+
+/// Convert ProtoAuditedBurn --> DbAuditedBurn
+impl From<&ProtoAuditedBurn> for DbAuditedBurn {
+    fn from(src: &ProtoAuditedBurn) -> Self {
+        Self {
+            id: match src.get_id() {
+                0 => None,
+                id => Some(id as i32),
+            },
+            burn_tx_out_id: src.get_burn_tx_out_id() as i32,
+            gnosis_safe_withdrawal_id: src.get_gnosis_safe_withdrawal_id() as i32,
+            amount: src.get_amount() as i64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // DbAuditedBurn --> ProtoAuditedBurn --> DbAuditedBurn should be the identity
+    // function.
+    fn test_convert_audited_burn() {
+        let source = DbAuditedBurn {
+            id: Some(10),
+            burn_tx_out_id: 20,
+            gnosis_safe_withdrawal_id: 30,
+            amount: 40,
+        };
+
+        // Converting should be the identity function.
+        {
+            let external = ProtoAuditedBurn::from(&source);
+            let recovered = DbAuditedBurn::from(&external);
+            assert_eq!(source, recovered);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Convert to/from mc_reserve_auditor_api::AuditedMint.
+
+use crate::db::AuditedMint as DbAuditedMint;
+use mc_reserve_auditor_api::AuditedMint as ProtoAuditedMint;
+
+/// Convert DbAuditedMint --> ProtoAuditedMint
+impl From<&DbAuditedMint> for ProtoAuditedMint {
+    fn from(src: &DbAuditedMint) -> Self {
+        let mut dst = Self::new();
+        dst.set_id(src.id.unwrap_or_default() as u32);
+        dst.set_mint_tx_id(src.mint_tx_id as u32);
+        dst.set_gnosis_safe_deposit_id(src.gnosis_safe_deposit_id as u32);
+        dst.set_amount(src.amount as u64);
+        dst
+    }
+}
+
+/// Convert ProtoAuditedMint --> DbAuditedMint
+impl From<&ProtoAuditedMint> for DbAuditedMint {
+    fn from(src: &ProtoAuditedMint) -> Self {
+        Self {
+            id: match src.get_id() {
+                0 => None,
+                id => Some(id as i32),
+            },
+            mint_tx_id: src.get_mint_tx_id() as i32,
+            gnosis_safe_deposit_id: src.get_gnosis_safe_deposit_id() as i32,
+            amount: src.get_amount() as i64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // DbAuditedMint --> ProtoAuditedMint --> DbAuditedMint should be the identity
+    // function.
+    fn test_convert_audited_mint() {
+        let source = DbAuditedMint {
+            id: Some(10),
+            mint_tx_id: 20,
+            gnosis_safe_deposit_id: 30,
+            amount: 40,
+        };
+
+        // Converting should be the identity function.
+        {
+            let external = ProtoAuditedMint::from(&source);
+            let recovered = DbAuditedMint::from(&external);
+            assert_eq!(source, recovered);
+        }
+    }
+}
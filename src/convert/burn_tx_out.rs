@@ -0,0 +1,63 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Convert to mc_reserve_auditor_api::BurnTxOut.
+
+use crate::db::BurnTxOut as DbBurnTxOut;
+use mc_reserve_auditor_api::BurnTxOut as ProtoBurnTxOut;
+
+/// Convert DbBurnTxOut --> ProtoBurnTxOut
+impl From<&DbBurnTxOut> for ProtoBurnTxOut {
+    fn from(src: &DbBurnTxOut) -> Self {
+        let mut dst = Self::new();
+        dst.set_id(src.id().unwrap_or_default() as u32);
+        dst.set_block_index(src.block_index());
+        dst.set_token_id(*src.token_id());
+        dst.set_amount(src.amount());
+        dst.set_public_key_hex(src.public_key_hex().to_string());
+        dst.set_protobuf(src.protobuf().iter().map(|byte| *byte as u32).collect());
+        if let Some(block_timestamp) = src.block_timestamp() {
+            dst.set_block_timestamp(
+                block_timestamp
+                    .timestamp_nanos_opt()
+                    .expect("Invalid block_timestamp stored in database") as u64,
+            );
+        }
+        dst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::create_burn_tx_out;
+    use mc_transaction_core::TokenId;
+
+    #[test]
+    fn test_convert_burn_tx_out() {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let source = create_burn_tx_out(TokenId::from(3), 100, &mut rng);
+        let external = ProtoBurnTxOut::from(&source);
+
+        assert_eq!(external.get_id(), 0);
+        assert_eq!(external.get_block_index(), source.block_index());
+        assert_eq!(external.get_token_id(), 3);
+        assert_eq!(external.get_amount(), 100);
+        assert_eq!(external.get_public_key_hex(), source.public_key_hex());
+        assert_eq!(
+            external
+                .get_protobuf()
+                .iter()
+                .map(|byte| *byte as u8)
+                .collect::<Vec<_>>(),
+            source.protobuf()
+        );
+        assert_eq!(
+            external.get_block_timestamp(),
+            source
+                .block_timestamp()
+                .unwrap()
+                .timestamp_nanos_opt()
+                .unwrap() as u64
+        );
+    }
+}
@@ -35,6 +35,24 @@ impl From<&Counters> for mc_reserve_auditor_api::Counters {
         dst.set_num_unexpected_errors_matching_burns_to_withdrawals(
             src.num_unexpected_errors_matching_burns_to_withdrawals(),
         );
+        dst.set_num_mint_txs_with_unknown_nonce(src.num_mint_txs_with_unknown_nonce());
+        dst.set_num_gnosis_source_disagreements(src.num_gnosis_source_disagreements());
+        dst.set_num_mint_config_tx_governor_violations(
+            src.num_mint_config_tx_governor_violations(),
+        );
+        dst.set_num_mint_txs_without_active_mint_config(
+            src.num_mint_txs_without_active_mint_config(),
+        );
+        dst.set_num_mint_txs_with_invalid_signature(src.num_mint_txs_with_invalid_signature());
+        dst.set_num_mint_txs_exceeding_total_mint_limit(
+            src.num_mint_txs_exceeding_total_mint_limit(),
+        );
+        dst.set_num_mint_txs_exceeding_mint_limit(src.num_mint_txs_exceeding_mint_limit());
+        dst.set_num_mint_txs_past_tombstone_block(src.num_mint_txs_past_tombstone_block());
+        dst.set_num_oversized_reserve_amounts(src.num_oversized_reserve_amounts());
+        dst.set_num_deposits_with_truncation_remainder(
+            src.num_deposits_with_truncation_remainder(),
+        );
         dst
     }
 }
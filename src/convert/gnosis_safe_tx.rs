@@ -11,6 +11,7 @@ impl From<&DbGnosisSafeTx> for ProtoGnosisSafeTx {
         let mut dst = Self::new();
         dst.set_raw_tx_json(src.raw_tx_json.clone());
         dst.set_eth_tx_hash(src.eth_tx_hash.clone());
+        dst.set_chain_id(src.chain_id as u64);
         dst
     }
 }
@@ -21,6 +22,7 @@ impl From<&ProtoGnosisSafeTx> for DbGnosisSafeTx {
         Self {
             eth_tx_hash: src.get_eth_tx_hash().to_string(),
             raw_tx_json: src.get_raw_tx_json().to_string(),
+            chain_id: src.get_chain_id() as i64,
         }
     }
 }
@@ -37,6 +39,7 @@ mod tests {
             eth_tx_hash: "0x0e781edb7739aa88ad2ffb6a69aab46ff9e32dbd0f0c87e4006a176838b075d2"
                 .to_string(),
             raw_tx_json: "{\"test\": 10}".to_string(),
+            chain_id: 10,
         };
 
         // Converting should be the identity function.
@@ -22,10 +22,13 @@ impl TryFrom<&DbGnosisSafeWithdrawal> for ProtoGnosisSafeWithdrawal {
         dst.set_eth_tx_hash(src.eth_tx_hash().to_string());
         dst.set_eth_tx_value(src.eth_tx_value().to_string());
         dst.set_eth_block_number(src.eth_block_number());
+        dst.set_chain_id(src.chain_id());
+        dst.set_verification_status(src.verification_status().to_string());
         dst.set_safe_addr(src.safe_addr().to_string());
         dst.set_token_addr(src.token_addr().to_string());
         dst.set_to_addr(src.to_addr().to_string());
-        dst.set_amount(src.amount());
+        dst.set_amount(src.amount().to_string());
+        dst.set_truncation_remainder(src.truncation_remainder().to_string());
         dst.set_mc_tx_out_pub_key((&src.mc_tx_out_public_key()?).into());
         dst.set_execution_date(
             src.execution_date()
@@ -48,6 +51,7 @@ impl TryFrom<&ProtoGnosisSafeWithdrawal> for DbGnosisSafeWithdrawal {
                 0 => None,
                 id => Some(id),
             },
+            src.get_chain_id(),
             EthTxHash::from_str(src.get_eth_tx_hash())?,
             execution_date,
             EthTxValue::from_str(src.get_eth_tx_value())?,
@@ -55,7 +59,18 @@ impl TryFrom<&ProtoGnosisSafeWithdrawal> for DbGnosisSafeWithdrawal {
             EthAddr::from_str(src.get_safe_addr())?,
             EthAddr::from_str(src.get_token_addr())?,
             EthAddr::from_str(src.get_to_addr())?,
-            src.get_amount(),
+            u128::from_str(src.get_amount()).map_err(|err| {
+                Error::Other(format!(
+                    "Invalid withdrawal amount {}: {err}",
+                    src.get_amount()
+                ))
+            })?,
+            u128::from_str(src.get_truncation_remainder()).map_err(|err| {
+                Error::Other(format!(
+                    "Invalid withdrawal truncation remainder {}: {err}",
+                    src.get_truncation_remainder()
+                ))
+            })?,
             src.get_mc_tx_out_pub_key().get_data().encode_hex(),
         ))
     }
@@ -74,6 +89,7 @@ mod tests {
     fn test_convert_gnosis_safe_withdrawal() {
         let source = DbGnosisSafeWithdrawal::new(
             Some(10),
+            10,
             EthTxHash::from_str(
                 "0x0e781edb7739aa88ad2ffb6a69aab46ff9e32dbd0f0c87e4006a176838b075d2",
             )
@@ -84,7 +100,8 @@ mod tests {
             EthAddr::from_str(SAFE_ADDR).unwrap(),
             EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
             EthAddr::from_str(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR).unwrap(),
-            333,
+            u128::from(u64::MAX) + 333,
+            999_999_999_999,
             "1111111111111111111111111111111111111111111111111111111111111111".to_string(),
         );
 
@@ -0,0 +1,47 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Convert to mc_reserve_auditor_api::MintConfig.
+
+use crate::db::MintConfig as DbMintConfig;
+use mc_reserve_auditor_api::MintConfig as ProtoMintConfig;
+
+/// Convert DbMintConfig --> ProtoMintConfig
+impl From<&DbMintConfig> for ProtoMintConfig {
+    fn from(src: &DbMintConfig) -> Self {
+        let mut dst = Self::new();
+        dst.set_id(src.id().unwrap_or_default() as u32);
+        dst.set_mint_config_tx_id(src.mint_config_tx_id() as u32);
+        dst.set_mint_limit(src.mint_limit());
+        dst.set_protobuf(src.protobuf().iter().map(|byte| *byte as u32).collect());
+        dst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mc_transaction_core::TokenId;
+    use mc_transaction_core_test_utils::create_mint_config_tx_and_signers;
+
+    #[test]
+    fn test_convert_mint_config() {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let (core_mint_config_tx, _signers) =
+            create_mint_config_tx_and_signers(TokenId::from(1), &mut rng);
+
+        let source = DbMintConfig::from_core_mint_config(4, &core_mint_config_tx.prefix.configs[0]);
+        let external = ProtoMintConfig::from(&source);
+
+        assert_eq!(external.get_id(), 0);
+        assert_eq!(external.get_mint_config_tx_id(), 4);
+        assert_eq!(external.get_mint_limit(), source.mint_limit());
+        assert_eq!(
+            external
+                .get_protobuf()
+                .iter()
+                .map(|byte| *byte as u8)
+                .collect::<Vec<_>>(),
+            source.protobuf()
+        );
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Convert to mc_reserve_auditor_api::MintConfigTx.
+
+use crate::db::MintConfigTx as DbMintConfigTx;
+use mc_reserve_auditor_api::MintConfigTx as ProtoMintConfigTx;
+
+/// Convert DbMintConfigTx --> ProtoMintConfigTx
+impl From<&DbMintConfigTx> for ProtoMintConfigTx {
+    fn from(src: &DbMintConfigTx) -> Self {
+        let mut dst = Self::new();
+        dst.set_id(src.id().unwrap_or_default() as u32);
+        dst.set_block_index(src.block_index());
+        dst.set_token_id(*src.token_id());
+        dst.set_nonce_hex(src.nonce_hex().to_string());
+        dst.set_total_mint_limit(src.total_mint_limit());
+        dst.set_tombstone_block(src.tombstone_block());
+        dst.set_protobuf(src.protobuf().iter().map(|byte| *byte as u32).collect());
+        if let Some(block_timestamp) = src.block_timestamp() {
+            dst.set_block_timestamp(
+                block_timestamp
+                    .timestamp_nanos_opt()
+                    .expect("Invalid block_timestamp stored in database") as u64,
+            );
+        }
+        dst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use mc_transaction_core::TokenId;
+    use mc_transaction_core_test_utils::create_mint_config_tx_and_signers;
+
+    #[test]
+    fn test_convert_mint_config_tx() {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let (core_mint_config_tx, _signers) =
+            create_mint_config_tx_and_signers(TokenId::from(1), &mut rng);
+        let block_timestamp = Utc::now();
+
+        let source = DbMintConfigTx::from_core_mint_config_tx(
+            7,
+            Some(block_timestamp),
+            &core_mint_config_tx,
+        );
+        let external = ProtoMintConfigTx::from(&source);
+
+        assert_eq!(external.get_id(), 0);
+        assert_eq!(external.get_block_index(), 7);
+        assert_eq!(external.get_token_id(), 1);
+        assert_eq!(external.get_nonce_hex(), source.nonce_hex());
+        assert_eq!(external.get_total_mint_limit(), source.total_mint_limit());
+        assert_eq!(external.get_tombstone_block(), source.tombstone_block());
+        assert_eq!(
+            external
+                .get_protobuf()
+                .iter()
+                .map(|byte| *byte as u8)
+                .collect::<Vec<_>>(),
+            source.protobuf()
+        );
+        assert_eq!(
+            external.get_block_timestamp(),
+            block_timestamp.timestamp_nanos_opt().unwrap() as u64
+        );
+    }
+}
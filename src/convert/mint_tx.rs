@@ -0,0 +1,80 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Convert to mc_reserve_auditor_api::MintTx.
+
+use crate::db::MintTx as DbMintTx;
+use mc_reserve_auditor_api::MintTx as ProtoMintTx;
+
+/// Convert DbMintTx --> ProtoMintTx
+impl From<&DbMintTx> for ProtoMintTx {
+    fn from(src: &DbMintTx) -> Self {
+        let mut dst = Self::new();
+        dst.set_id(src.id().unwrap_or_default() as u32);
+        dst.set_block_index(src.block_index());
+        dst.set_token_id(*src.token_id());
+        dst.set_amount(src.amount());
+        dst.set_nonce_hex(src.nonce_hex().to_string());
+        dst.set_recipient_b58_addr(src.recipient_b58_addr().to_string());
+        dst.set_tombstone_block(src.tombstone_block());
+        dst.set_protobuf(src.protobuf().iter().map(|byte| *byte as u32).collect());
+        dst.set_mint_config_id(src.mint_config_id().unwrap_or_default() as u32);
+        dst.set_decoded_nonce(src.decoded_nonce().unwrap_or_default().to_string());
+        if let Some(block_timestamp) = src.block_timestamp() {
+            dst.set_block_timestamp(
+                block_timestamp
+                    .timestamp_nanos_opt()
+                    .expect("Invalid block_timestamp stored in database") as u64,
+            );
+        }
+        dst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use mc_transaction_core::TokenId;
+    use mc_transaction_core_test_utils::{create_mint_config_tx_and_signers, create_mint_tx};
+
+    #[test]
+    fn test_convert_mint_tx() {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let token_id = TokenId::from(1);
+        let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let core_mint_tx = create_mint_tx(token_id, &signers, 100, &mut rng);
+        let block_timestamp = Utc::now();
+
+        let source =
+            DbMintTx::from_core_mint_tx(5, Some(block_timestamp), Some(3), &core_mint_tx).unwrap();
+        let external = ProtoMintTx::from(&source);
+
+        assert_eq!(external.get_id(), 0);
+        assert_eq!(external.get_block_index(), 5);
+        assert_eq!(external.get_token_id(), 1);
+        assert_eq!(external.get_amount(), 100);
+        assert_eq!(external.get_nonce_hex(), source.nonce_hex());
+        assert_eq!(
+            external.get_recipient_b58_addr(),
+            source.recipient_b58_addr()
+        );
+        assert_eq!(external.get_tombstone_block(), source.tombstone_block());
+        assert_eq!(
+            external
+                .get_protobuf()
+                .iter()
+                .map(|byte| *byte as u8)
+                .collect::<Vec<_>>(),
+            source.protobuf()
+        );
+        assert_eq!(external.get_mint_config_id(), 3);
+        assert_eq!(
+            external.get_decoded_nonce(),
+            source.decoded_nonce().unwrap_or_default()
+        );
+        assert_eq!(
+            external.get_block_timestamp(),
+            block_timestamp.timestamp_nanos_opt().unwrap() as u64
+        );
+    }
+}
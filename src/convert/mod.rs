@@ -2,7 +2,15 @@
 
 //! Convertion trait implementations betweens Prost and Protobuf.
 
+mod audit_event;
+mod audited_burn;
+mod audited_mint;
+mod burn_tx_out;
 mod counters;
 mod gnosis_safe_deposit;
 mod gnosis_safe_tx;
 mod gnosis_safe_withdrawal;
+mod mint_config;
+mod mint_config_tx;
+mod mint_tx;
+mod reconciliation;
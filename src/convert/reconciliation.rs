@@ -0,0 +1,51 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Convert to mc_reserve_auditor_api reconciliation types.
+
+use crate::reconciliation::{ReconciliationReport, TokenReconciliation};
+use mc_reserve_auditor_api::{
+    GetReconciliationReportResponse, TokenReconciliation as ProtoTokenReconciliation,
+};
+
+/// Convert TokenReconciliation --> ProtoTokenReconciliation
+impl From<&TokenReconciliation> for ProtoTokenReconciliation {
+    fn from(src: &TokenReconciliation) -> Self {
+        let mut dst = Self::new();
+        dst.set_token_id(*src.token_id);
+        dst.set_supply(src.supply.to_string());
+        dst.set_gnosis_safe_holdings(src.gnosis_safe_holdings.to_string());
+        dst.set_gnosis_safe_raw_holdings(src.gnosis_safe_raw_holdings.to_string());
+        dst.set_gnosis_safe_dust(src.gnosis_safe_dust.to_string());
+        dst.set_other_reserve_holdings(src.other_reserve_holdings.to_string());
+        dst.set_difference(src.difference.to_string());
+        dst.set_num_unmatched_mints(src.num_unmatched_mints);
+        dst.set_num_unmatched_burns(src.num_unmatched_burns);
+        dst.set_num_unmatched_deposits(src.num_unmatched_deposits);
+        dst.set_num_unmatched_withdrawals(src.num_unmatched_withdrawals);
+        dst
+    }
+}
+
+/// Convert ReconciliationReport --> GetReconciliationReportResponse
+impl From<&ReconciliationReport> for GetReconciliationReportResponse {
+    fn from(src: &ReconciliationReport) -> Self {
+        let mut dst = Self::new();
+        dst.set_block_index(src.block_index.unwrap_or_default());
+        if let Some(block_timestamp) = src.block_timestamp {
+            dst.set_block_timestamp(
+                block_timestamp
+                    .timestamp_nanos_opt()
+                    .expect("Invalid block_timestamp stored in database") as u64,
+            );
+        }
+        dst.set_tokens(src.tokens.iter().map(Into::into).collect());
+        if let Some(as_of) = src.as_of {
+            dst.set_as_of(
+                as_of
+                    .timestamp_nanos_opt()
+                    .expect("Invalid reconciliation time") as u64,
+            );
+        }
+        dst
+    }
+}
@@ -53,6 +53,57 @@ lazy_static::lazy_static! {
     /// Number of unexpected errors attempting to match burns to withdrawals.
     pub static ref NUM_UNEXPECTED_ERRORS_MATCHING_BURNS_TO_WITHDRAWALS: IntGauge = OP_COUNTERS.gauge("num_unexpected_errors_matching_burns_to_withdrawals");
 
+    /// Number of mint transactions with a nonce of an unknown type.
+    pub static ref NUM_MINT_TXS_WITH_UNKNOWN_NONCE: IntGauge = OP_COUNTERS.gauge("num_mint_txs_with_unknown_nonce");
+
+    /// Number of deposits and withdrawals the verification source disagreed with.
+    pub static ref NUM_GNOSIS_SOURCE_DISAGREEMENTS: IntGauge = OP_COUNTERS.gauge("num_gnosis_source_disagreements");
+
+    /// Number of mint configuration transactions not signed by the governors of their token.
+    pub static ref NUM_MINT_CONFIG_TX_GOVERNOR_VIOLATIONS: IntGauge = OP_COUNTERS.gauge("num_mint_config_tx_governor_violations");
+
+    /// Number of mint transactions whose token had no active mint configuration.
+    pub static ref NUM_MINT_TXS_WITHOUT_ACTIVE_MINT_CONFIG: IntGauge = OP_COUNTERS.gauge("num_mint_txs_without_active_mint_config");
+
+    /// Number of mint transactions not signed by any active signer set.
+    pub static ref NUM_MINT_TXS_WITH_INVALID_SIGNATURE: IntGauge = OP_COUNTERS.gauge("num_mint_txs_with_invalid_signature");
+
+    /// Number of mint transactions exceeding the total mint limit of the active mint configuration.
+    pub static ref NUM_MINT_TXS_EXCEEDING_TOTAL_MINT_LIMIT: IntGauge = OP_COUNTERS.gauge("num_mint_txs_exceeding_total_mint_limit");
+
+    /// Number of mint transactions exceeding the mint limit of the mint configurations that signed them.
+    pub static ref NUM_MINT_TXS_EXCEEDING_MINT_LIMIT: IntGauge = OP_COUNTERS.gauge("num_mint_txs_exceeding_mint_limit");
+
+    /// Number of mint transactions that appeared in a block at or after their tombstone block.
+    pub static ref NUM_MINT_TXS_PAST_TOMBSTONE_BLOCK: IntGauge = OP_COUNTERS.gauge("num_mint_txs_past_tombstone_block");
+
+    /// Number of reserve deposits and withdrawals whose amount does not fit in a MobileCoin amount.
+    pub static ref NUM_OVERSIZED_RESERVE_AMOUNTS: IntGauge = OP_COUNTERS.gauge("num_oversized_reserve_amounts");
+
+    /// Number of Gnosis deposits whose value had sub-precision digits dropped when truncating it.
+    pub static ref NUM_DEPOSITS_WITH_TRUNCATION_REMAINDER: IntGauge = OP_COUNTERS.gauge("num_deposits_with_truncation_remainder");
+
+    /// Number of mints that stayed unmatched for longer than their grace period.
+    pub static ref NUM_OVERDUE_UNMATCHED_MINTS: IntGauge = OP_COUNTERS.gauge("num_overdue_unmatched_mints");
+
+    /// Number of burns that stayed unmatched for longer than their grace period.
+    pub static ref NUM_OVERDUE_UNMATCHED_BURNS: IntGauge = OP_COUNTERS.gauge("num_overdue_unmatched_burns");
+
+    /// Number of deposits that stayed unmatched for longer than their grace period.
+    pub static ref NUM_OVERDUE_UNMATCHED_DEPOSITS: IntGauge = OP_COUNTERS.gauge("num_overdue_unmatched_deposits");
+
+    /// Number of withdrawals that stayed unmatched for longer than their grace period.
+    pub static ref NUM_OVERDUE_UNMATCHED_WITHDRAWALS: IntGauge = OP_COUNTERS.gauge("num_overdue_unmatched_withdrawals");
+
+    /// Number of critical audit events that were not acknowledged yet.
+    pub static ref NUM_OPEN_CRITICAL_AUDIT_EVENTS: IntGauge = OP_COUNTERS.gauge("num_open_critical_audit_events");
+
+    /// Number of error audit events that were not acknowledged yet.
+    pub static ref NUM_OPEN_ERROR_AUDIT_EVENTS: IntGauge = OP_COUNTERS.gauge("num_open_error_audit_events");
+
+    /// Number of warning audit events that were not acknowledged yet.
+    pub static ref NUM_OPEN_WARNING_AUDIT_EVENTS: IntGauge = OP_COUNTERS.gauge("num_open_warning_audit_events");
+
     /// Number of times we failed to fetch gnosis transactions.
     pub static ref NUM_FAILED_GNOSIS_GET_ALL_TRANSACTION_DATA: IntCounter = OP_COUNTERS.counter("num_failed_gnosis_get_all_transaction_data");
 }
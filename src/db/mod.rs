@@ -15,19 +15,26 @@ pub mod schema;
 pub use self::{
     conn::{Conn, ConnectionOptions},
     models::{
-        AuditedBurn, AuditedMint, BlockAuditData, BlockBalance, BurnTxOut, Counters,
-        GnosisSafeDeposit, GnosisSafeTx, GnosisSafeWithdrawal, MintConfig, MintConfigTx, MintTx,
+        AuditEvent, AuditEventAcknowledgement, AuditedBurn, AuditedMint, AuditedReserveBurn,
+        AuditedReserveMint, BlockAuditData, BlockBalance, BurnTxOut, Counters,
+        GnosisSafeAuditConfig, GnosisSafeDeposit, GnosisSafeSyncState, GnosisSafeTx,
+        GnosisSafeWithdrawal, ManualMatch, MintConfig, MintConfigTx, MintTx, OutboxNotification,
+        ReserveDeposit, ReserveMatch, ReserveWithdrawal, SignerSetChange,
     },
     transaction::{transaction, TransactionRetriableError},
 };
 
-use crate::Error;
+use crate::{
+    anomaly::{AnomalyEvent, AnomalyKind},
+    Error,
+};
 use chrono::{DateTime, Utc};
 use diesel::{
     r2d2::{ConnectionManager, Pool},
     SqliteConnection,
 };
 use diesel_migrations::embed_migrations;
+use displaydoc::Display;
 use mc_blockchain_types::{Block, BlockContents, BlockIndex};
 use mc_common::{
     logger::{log, Logger},
@@ -35,7 +42,10 @@ use mc_common::{
 };
 use mc_transaction_core::TokenId;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use std::time::Duration;
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
 
 embed_migrations!("migrations/");
 
@@ -52,7 +62,7 @@ pub struct SyncBlockData {
     pub block_audit: BlockAuditData,
 
     /// Balance map after the block has been processed.
-    pub balance_map: HashMap<TokenId, u64>,
+    pub balance_map: HashMap<TokenId, u128>,
 
     /// Mint transactions in the block.
     pub mint_txs: Vec<MintTx>,
@@ -61,17 +71,74 @@ pub struct SyncBlockData {
     pub burn_tx_outs: Vec<BurnTxOut>,
 }
 
+impl SyncBlockData {
+    /// Load the data that was stored when a given block was synced. Returns
+    /// `None` if the block has not been synced yet.
+    pub fn get(conn: &Conn, block_index: BlockIndex) -> Result<Option<Self>, Error> {
+        let block_audit = match BlockAuditData::get(conn, block_index) {
+            Ok(block_audit) => block_audit,
+            Err(Error::NotFound) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        Ok(Some(Self {
+            block_audit,
+            balance_map: BlockBalance::get_balances_for_block(conn, block_index)?,
+            mint_txs: MintTx::get_mint_txs_by_block_index(block_index, conn)?,
+            burn_tx_outs: BurnTxOut::get_burn_txs_by_block(block_index, conn)?,
+        }))
+    }
+}
+
+/// The reason a MintTx did not match any active mint configuration.
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+pub enum MintConfigMismatch {
+    /// The token has no active mint configuration
+    NoActiveMintConfig,
+
+    /// No signer set of the active mint configuration signed the MintTx
+    InvalidSignature,
+
+    /// The MintTx exceeds the total mint limit of the active mint configuration
+    TotalMintLimitExceeded,
+
+    /// The MintTx exceeds the mint limit of the mint configurations that signed it
+    MintLimitExceeded,
+}
+
+impl MintConfigMismatch {
+    /// Increase the counter of this kind of mismatch.
+    pub fn inc_counter(&self, conn: &Conn) -> Result<(), Error> {
+        match self {
+            Self::NoActiveMintConfig => Counters::inc_num_mint_txs_without_active_mint_config(conn),
+            Self::InvalidSignature => Counters::inc_num_mint_txs_with_invalid_signature(conn),
+            Self::TotalMintLimitExceeded => {
+                Counters::inc_num_mint_txs_exceeding_total_mint_limit(conn)
+            }
+            Self::MintLimitExceeded => Counters::inc_num_mint_txs_exceeding_mint_limit(conn),
+        }
+    }
+}
+
 /// Reserve Auditor Database.
 #[derive(Clone)]
 pub struct ReserveAuditorDb {
     pool: Pool<ConnectionManager<SqliteConnection>>,
     logger: Logger,
+
+    /// Number of blocks synced notifications, used to wake up anyone waiting
+    /// for a block to get synced.
+    block_synced_notifier: Arc<(Mutex<u64>, Condvar)>,
 }
 
 impl ReserveAuditorDb {
     /// Instantiate a new database using an existing connection pool.
     pub fn new(pool: Pool<ConnectionManager<SqliteConnection>>, logger: Logger) -> Self {
-        Self { pool, logger }
+        Self {
+            pool,
+            logger,
+            block_synced_notifier: Default::default(),
+        }
     }
 
     /// Instantiate a new database from a path that points at a database file.
@@ -96,6 +163,17 @@ impl ReserveAuditorDb {
         // Ensure we have a row in the counters table (this makes all the atomic updates
         // in [Counters] work as expected).
         Counters::ensure_exists(&conn)?;
+        AuditEvent::update_prometheus(&conn)?;
+
+        // Decode the nonces of MintTxs stored by earlier versions.
+        let num_decoded_nonces = MintTx::backfill_decoded_nonces(&conn)?;
+        if num_decoded_nonces > 0 {
+            log::info!(
+                logger,
+                "Decoded the nonce of {} MintTxs",
+                num_decoded_nonces
+            );
+        }
 
         Ok(Self::new(pool, logger))
     }
@@ -105,6 +183,39 @@ impl ReserveAuditorDb {
         Ok(self.pool.get()?)
     }
 
+    /// Notify anyone waiting in [Self::wait_for_sync_block_data] that a block
+    /// has been synced. This should be called after the transaction that
+    /// synced the block has been committed.
+    pub fn notify_block_synced(&self) {
+        let (lock, condvar) = &*self.block_synced_notifier;
+        *lock.lock().expect("mutex poisoned") += 1;
+        condvar.notify_all();
+    }
+
+    /// Get the [SyncBlockData] of a given block. If the block has not been
+    /// synced yet, wait up to `timeout` for it to get synced.
+    pub fn wait_for_sync_block_data(
+        &self,
+        block_index: BlockIndex,
+        timeout: Duration,
+    ) -> Result<Option<SyncBlockData>, Error> {
+        let (lock, condvar) = &*self.block_synced_notifier;
+        let num_notifications = *lock.lock().expect("mutex poisoned");
+
+        if let Some(sync_block_data) = SyncBlockData::get(&self.get_conn()?, block_index)? {
+            return Ok(Some(sync_block_data));
+        }
+
+        // Nothing yet, wait for a notification (or the timeout) before checking again.
+        let _guard = condvar
+            .wait_timeout_while(lock.lock().expect("mutex poisoned"), timeout, |n| {
+                *n == num_notifications
+            })
+            .expect("mutex poisoned");
+
+        SyncBlockData::get(&self.get_conn()?, block_index)
+    }
+
     /// Sync reserve audit data of a single block.
     pub fn sync_block(
         &self,
@@ -113,10 +224,15 @@ impl ReserveAuditorDb {
         block_timestamp: Option<DateTime<Utc>>,
     ) -> Result<SyncBlockData, Error> {
         let conn = self.get_conn()?;
-        self.sync_block_with_conn(&conn, block, block_contents, block_timestamp)
+        let sync_block_data =
+            self.sync_block_with_conn(&conn, block, block_contents, block_timestamp)?;
+        self.notify_block_synced();
+        Ok(sync_block_data)
     }
 
     /// Sync reserve audit data of a single block using a pre-existing connection.
+    /// Callers are expected to call [Self::notify_block_synced] once the
+    /// changes have been committed.
     pub fn sync_block_with_conn(
         &self,
         conn: &Conn,
@@ -135,14 +251,23 @@ impl ReserveAuditorDb {
                 return Err(Error::UnexpectedBlockIndex(block_index, next_block_index));
             }
 
-            // Store mint config txs.
+            // Store mint config txs, and keep track of signer set changes.
             for validated_mint_config_tx in &block_contents.validated_mint_config_txs {
-                MintConfigTx::insert_from_core_mint_config_tx(
+                let mint_config_tx = MintConfigTx::insert_from_core_mint_config_tx(
                     block_index,
                     block_timestamp,
                     &validated_mint_config_tx.mint_config_tx,
                     conn,
                 )?;
+                if SignerSetChange::record_if_changed(&mint_config_tx, conn)?.is_some() {
+                    log::info!(
+                        self.logger,
+                        "Signer sets of token {} changed by MintConfigTx {} in block {}",
+                        mint_config_tx.token_id(),
+                        mint_config_tx.nonce_hex(),
+                        block_index
+                    );
+                }
             }
 
             // Get balance map for the previous block
@@ -165,7 +290,7 @@ impl ReserveAuditorDb {
                     .entry(TokenId::from(mint_tx.prefix.token_id))
                     .or_default();
 
-                *mint_balance += mint_tx.prefix.amount;
+                *mint_balance += u128::from(mint_tx.prefix.amount);
                 log::info!(
                     self.logger,
                     "Block {}: Minted {} of token id {}, balance is now {}",
@@ -175,29 +300,87 @@ impl ReserveAuditorDb {
                     mint_balance,
                 );
 
-                // Try and match the mint tx to an active mint config.
+                // Try and match the mint tx to an active mint config. This verifies the
+                // signature and limits independently of consensus.
                 let mint_config = Self::lookup_mint_config(block_index, mint_tx, conn)?;
 
+                // Store the mint tx.
+                let inserted_mint_tx = MintTx::insert_from_core_mint_tx(
+                    block_index,
+                    block_timestamp,
+                    mint_config.as_ref().ok().and_then(|config| config.id()),
+                    mint_tx,
+                    conn,
+                )?;
+
                 // Alert and count if we did not find a matching mint config.
-                if mint_config.is_none() {
+                if let Err(mismatch) = mint_config {
                     log::crit!(
                         self.logger,
-                        "Block {}: Found mint tx {} that did not match any active mint config",
+                        "Block {}: Found mint tx {} that did not match any active mint config: {}",
                         block_index,
                         mint_tx,
+                        mismatch,
                     );
 
-                    Counters::inc_num_mint_txs_without_matching_mint_config(conn)?;
+                    // The breakdown by reason needs to add up to the total, so it is only
+                    // increased along with it.
+                    if Counters::record_anomaly(
+                        &AnomalyEvent::new(
+                            AnomalyKind::MintTxWithoutMatchingMintConfig,
+                            format!("MintTx did not match any active mint config: {mismatch}"),
+                        )
+                        .with_mint_tx(&inserted_mint_tx),
+                        conn,
+                    )? {
+                        mismatch.inc_counter(conn)?;
+                    }
                 }
 
-                // Store the mint tx.
-                mint_txs.push(MintTx::insert_from_core_mint_tx(
-                    block_index,
-                    block_timestamp,
-                    mint_config.and_then(|config| config.id()),
-                    mint_tx,
-                    conn,
-                )?);
+                // Alert and count if the mint tx should have expired before this block.
+                if block_index >= mint_tx.prefix.tombstone_block {
+                    log::crit!(
+                        self.logger,
+                        "Block {}: Found mint tx {} with tombstone block {}",
+                        block_index,
+                        mint_tx,
+                        mint_tx.prefix.tombstone_block,
+                    );
+
+                    Counters::record_anomaly(
+                        &AnomalyEvent::new(
+                            AnomalyKind::MintTxPastTombstoneBlock,
+                            format!(
+                                "MintTx appeared at or after its tombstone block {}",
+                                mint_tx.prefix.tombstone_block
+                            ),
+                        )
+                        .with_mint_tx(&inserted_mint_tx),
+                        conn,
+                    )?;
+                }
+
+                // Count mints whose nonce we do not know how to decode, since they cannot be
+                // matched with any deposit.
+                if inserted_mint_tx.decoded_nonce().is_none() {
+                    log::crit!(
+                        self.logger,
+                        "Block {}: Found mint tx with nonce {} of an unknown type",
+                        block_index,
+                        inserted_mint_tx.nonce_hex(),
+                    );
+
+                    Counters::record_anomaly(
+                        &AnomalyEvent::new(
+                            AnomalyKind::MintTxWithUnknownNonce,
+                            "MintTx nonce is not of a known type",
+                        )
+                        .with_mint_tx(&inserted_mint_tx),
+                        conn,
+                    )?;
+                }
+
+                mint_txs.push(inserted_mint_tx);
             }
 
             // Process burns.
@@ -213,7 +396,7 @@ impl ReserveAuditorDb {
 
             for burn_tx_out in burn_tx_outs.iter_mut() {
                 // Balance accounting.
-                let (amount, token_id) = (burn_tx_out.amount(), burn_tx_out.token_id());
+                let (amount, token_id) = (u128::from(burn_tx_out.amount()), burn_tx_out.token_id());
                 let burn_balance = balance_map.entry(token_id).or_default();
 
                 if amount > *burn_balance {
@@ -225,8 +408,15 @@ impl ReserveAuditorDb {
                         token_id,
                         burn_balance
                     );
+                    Counters::record_anomaly(
+                        &AnomalyEvent::new(
+                            AnomalyKind::BurnExceedingBalance,
+                            format!("Burned {amount} but only had {burn_balance}"),
+                        )
+                        .with_burn_tx_out(burn_tx_out),
+                        conn,
+                    )?;
                     *burn_balance = 0;
-                    Counters::inc_num_burns_exceeding_balance(conn)?;
                 } else {
                     *burn_balance -= amount;
                     log::info!(
@@ -245,7 +435,7 @@ impl ReserveAuditorDb {
 
             Counters::inc_num_blocks_synced(conn)?;
 
-            let block_audit = BlockAuditData::new(block_index);
+            let block_audit = BlockAuditData::new(block_index, block_timestamp);
             log::trace!(self.logger, "Storing block audit data: {:?}", block_audit);
             block_audit.set(conn)?;
 
@@ -268,12 +458,58 @@ impl ReserveAuditorDb {
         })
     }
 
-    /// Lookup a mint config that can accommodate a given mint tx.
+    /// Rewind the database so that `block_index` becomes the last synced
+    /// block. All data derived from later blocks (mint config txs, mint txs,
+    /// burns, balances and block audit data) is deleted, together with the
+    /// audit records linking it to Gnosis deposits and withdrawals. Gnosis
+    /// data is left in place so that re-synced blocks get audited against it
+    /// again. Audit events and manual matches involving the deleted MintTxs
+    /// and BurnTxOuts are kept, and identify them by nonce and public key.
+    /// Returns the number of blocks synced after rewinding.
+    pub fn rewind_to_block(&self, block_index: BlockIndex) -> Result<u64, Error> {
+        let conn = self.get_conn()?;
+
+        // SQLite3 does not like concurrent writes. Take an exclusive lock so that a
+        // running ledger scan cannot sync blocks while we are rewinding.
+        conn.exclusive_transaction(|| -> Result<u64, Error> {
+            let num_reserve_matches = ReserveMatch::delete_after_block(block_index, &conn)?;
+            // SQLite reuses the ids of deleted rows, so audit events must not keep
+            // referencing them.
+            AuditEvent::detach_after_block(block_index, &conn)?;
+            let num_mint_txs = MintTx::delete_after_block(block_index, &conn)?;
+            SignerSetChange::delete_after_block(block_index, &conn)?;
+            let num_mint_config_txs = MintConfigTx::delete_after_block(block_index, &conn)?;
+            let num_burn_tx_outs = BurnTxOut::delete_after_block(block_index, &conn)?;
+            BlockBalance::delete_after_block(&conn, block_index)?;
+            BlockAuditData::delete_after_block(&conn, block_index)?;
+
+            let num_blocks_synced = BlockAuditData::last_synced_block_index(&conn)?
+                .map(|last_synced_block_index| last_synced_block_index + 1)
+                .unwrap_or(0);
+            Counters::set_num_blocks_synced(num_blocks_synced, &conn)?;
+
+            log::info!(
+                self.logger,
+                "Rewound to block {}: deleted {} mint config txs, {} mint txs, {} burn tx outs and {} reserve matches. Number of blocks synced is now {}",
+                block_index,
+                num_mint_config_txs,
+                num_mint_txs,
+                num_burn_tx_outs,
+                num_reserve_matches,
+                num_blocks_synced,
+            );
+
+            Ok(num_blocks_synced)
+        })
+    }
+
+    /// Lookup a mint config that can accommodate a given mint tx, or the
+    /// reason there is none.
     pub fn lookup_mint_config(
         block_index: BlockIndex,
         mint_tx: &mc_transaction_core::mint::MintTx,
         conn: &Conn,
-    ) -> Result<Option<MintConfig>, Error> {
+    ) -> Result<Result<MintConfig, MintConfigMismatch>, Error> {
         let sql_mint_config_tx = match MintConfigTx::most_recent_for_token(
             block_index,
             TokenId::from(mint_tx.prefix.token_id),
@@ -281,21 +517,10 @@ impl ReserveAuditorDb {
         )? {
             Some(tx) => tx,
             None => {
-                return Ok(None);
+                return Ok(Err(MintConfigMismatch::NoActiveMintConfig));
             }
         };
 
-        // Get the total that was minted using this mint configuration and see if we
-        // will not exceed its total mint limit.
-        let total_minted = sql_mint_config_tx.get_total_minted_before_block(block_index, conn)?;
-        if let Some(new_total_minted) = total_minted.checked_add(mint_tx.prefix.amount) {
-            if new_total_minted > sql_mint_config_tx.total_mint_limit() {
-                return Ok(None);
-            }
-        } else {
-            return Ok(None);
-        }
-
         // SQLite auto-increment ids start at 1, so calling unwrap_or_default() on the
         // id field will result on no rows returned if no id is available.
         let sql_mint_configs = MintConfig::get_by_mint_config_tx_id(
@@ -303,45 +528,66 @@ impl ReserveAuditorDb {
             conn,
         )?;
 
+        // Find the mint configs whose signer set has signed the mint tx.
         let message = mint_tx.prefix.hash();
-
+        let mut signing_mint_configs = Vec::new();
         for sql_mint_config in sql_mint_configs {
-            // See if the mint tx was signed by this mint config.
             let mint_config = sql_mint_config.decode()?;
             if mint_config
                 .signer_set
                 .verify(&message, &mint_tx.signature)
-                .is_err()
+                .is_ok()
             {
-                continue;
+                signing_mint_configs.push(sql_mint_config);
             }
+        }
+        if signing_mint_configs.is_empty() {
+            return Ok(Err(MintConfigMismatch::InvalidSignature));
+        }
 
+        // Get the total that was minted using this mint configuration and see if we
+        // will not exceed its total mint limit.
+        let total_minted = sql_mint_config_tx.get_total_minted_before_block(block_index, conn)?;
+        if total_minted + u128::from(mint_tx.prefix.amount)
+            > u128::from(sql_mint_config_tx.total_mint_limit())
+        {
+            return Ok(Err(MintConfigMismatch::TotalMintLimitExceeded));
+        }
+
+        for sql_mint_config in signing_mint_configs {
             // See how much was minted already with this mint config.
             let total_minted = sql_mint_config.get_total_minted_before_block(block_index, conn)?;
 
             // This mint config has signed the mint tx. Is it allowed to mint the given
-            // amount of tokens?
-            // If we overflow (checked_add returns None) then we will keep looking for an
-            // active mint configuration that is able to accommodate the MintTx.
-            if let Some(new_total_minted) = total_minted.checked_add(mint_tx.prefix.amount) {
-                if new_total_minted <= sql_mint_config.mint_limit() {
-                    return Ok(Some(sql_mint_config));
-                }
+            // amount of tokens? If not, we will keep looking for an active mint
+            // configuration that is able to accommodate the MintTx.
+            if total_minted + u128::from(mint_tx.prefix.amount)
+                <= u128::from(sql_mint_config.mint_limit())
+            {
+                return Ok(Ok(sql_mint_config));
             }
         }
 
-        // Couldn't find a mint config that can accommodate the mint tx.
-        Ok(None)
+        // All the mint configs that signed the mint tx are exhausted.
+        Ok(Err(MintConfigMismatch::MintLimitExceeded))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::test_utils::{append_and_sync, TestDbContext};
+    use crate::{
+        db::test_utils::{
+            append_and_sync, create_burn_tx_out, create_gnosis_safe_deposit, insert_gnosis_deposit,
+            test_gnosis_config, TestDbContext,
+        },
+        gnosis::EthTxHash,
+        MintTxNonce,
+    };
     use mc_account_keys::{burn_address, AccountKey};
     use mc_common::logger::{test_with_logger, Logger};
-    use mc_crypto_keys::RistrettoPrivate;
+    use mc_crypto_keys::{RistrettoPrivate, Signer};
+    use mc_crypto_multisig::MultiSig;
     use mc_ledger_db::{
         test_utils::{create_ledger, initialize_ledger},
         Ledger,
@@ -380,15 +626,20 @@ mod tests {
         // The blocks we currently have in the ledger contain no burning or minting.
         for block_index in 0..initial_num_blocks {
             let block_data = ledger_db.get_block_data(block_index).unwrap();
+            let block_timestamp = Utc::now();
 
             let sync_block_data = reserve_auditor_db
-                .sync_block(block_data.block(), block_data.contents(), Some(Utc::now()))
+                .sync_block(
+                    block_data.block(),
+                    block_data.contents(),
+                    Some(block_timestamp),
+                )
                 .unwrap();
 
             assert_eq!(
                 sync_block_data,
                 SyncBlockData {
-                    block_audit: BlockAuditData::new(block_index),
+                    block_audit: BlockAuditData::new(block_index, Some(block_timestamp)),
                     balance_map: Default::default(),
                     mint_txs: vec![],
                     burn_tx_outs: vec![],
@@ -422,7 +673,7 @@ mod tests {
         assert_eq!(
             sync_block_data,
             SyncBlockData {
-                block_audit: BlockAuditData::new(block_index),
+                block_audit: BlockAuditData::new(block_index, None),
                 balance_map: Default::default(),
                 mint_txs: vec![],
                 burn_tx_outs: vec![],
@@ -453,7 +704,7 @@ mod tests {
         assert_eq!(
             sync_block_data,
             SyncBlockData {
-                block_audit: BlockAuditData::new(block_index),
+                block_audit: BlockAuditData::new(block_index, None),
                 balance_map: HashMap::from_iter([(token_id1, 101), (token_id2, 2)]),
                 mint_txs: MintTx::get_mint_txs_by_block_index(block_index, &conn).unwrap(),
                 burn_tx_outs: vec![],
@@ -508,7 +759,7 @@ mod tests {
         assert_eq!(
             sync_block_data,
             SyncBlockData {
-                block_audit: BlockAuditData::new(block_index),
+                block_audit: BlockAuditData::new(block_index, None),
                 balance_map: HashMap::from_iter([(token_id1, 41), (token_id2, 2)]),
                 mint_txs: MintTx::get_mint_txs_by_block_index(block_index, &conn).unwrap(),
                 burn_tx_outs: vec![
@@ -567,7 +818,7 @@ mod tests {
         assert_eq!(
             sync_block_data,
             SyncBlockData {
-                block_audit: BlockAuditData::new(block_index),
+                block_audit: BlockAuditData::new(block_index, None),
                 balance_map: HashMap::from_iter([
                     (token_id1, 141),
                     (token_id2, 1002),
@@ -624,6 +875,390 @@ mod tests {
         ));
     }
 
+    // SyncBlockData::get and wait_for_sync_block_data should return what sync_block
+    // returned.
+    #[test_with_logger]
+    fn test_get_sync_block_data(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let token_id1 = TokenId::from(1);
+
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let mut ledger_db = create_ledger();
+        let account_key = AccountKey::random(&mut rng);
+        initialize_ledger(BLOCK_VERSION, &mut ledger_db, 1, &account_key, &mut rng);
+
+        let block_data = ledger_db.get_block_data(0).unwrap();
+        let sync_block_data0 = reserve_auditor_db
+            .sync_block(block_data.block(), block_data.contents(), Some(Utc::now()))
+            .unwrap();
+
+        // Sync a block with a mint config tx, followed by a block with mints.
+        let (mint_config_tx1, signers1) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        let block_contents = BlockContents {
+            validated_mint_config_txs: vec![to_validated(&mint_config_tx1)],
+            ..Default::default()
+        };
+        let (sync_block_data1, _) = append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        let block_contents = BlockContents {
+            mint_txs: vec![
+                create_mint_tx(token_id1, &signers1, 1, &mut rng),
+                create_mint_tx(token_id1, &signers1, 2, &mut rng),
+            ],
+            outputs: vec![create_test_tx_out(BLOCK_VERSION, &mut rng)],
+            ..Default::default()
+        };
+        let (sync_block_data2, _) = append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        assert_eq!(
+            SyncBlockData::get(&conn, 0).unwrap(),
+            Some(sync_block_data0)
+        );
+        assert_eq!(
+            SyncBlockData::get(&conn, 1).unwrap(),
+            Some(sync_block_data1)
+        );
+        assert_eq!(
+            reserve_auditor_db
+                .wait_for_sync_block_data(2, Duration::from_secs(10))
+                .unwrap(),
+            Some(sync_block_data2)
+        );
+
+        // Block 3 was not synced.
+        assert_eq!(SyncBlockData::get(&conn, 3).unwrap(), None);
+        assert_eq!(
+            reserve_auditor_db
+                .wait_for_sync_block_data(3, Duration::from_millis(10))
+                .unwrap(),
+            None
+        );
+    }
+
+    // Rewinding deletes everything derived from later blocks, keeps the Gnosis data and
+    // allows re-syncing.
+    #[test_with_logger]
+    fn test_rewind_to_block(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let config = &test_gnosis_config().safes[0];
+        let token_id = config.tokens[0].token_id;
+
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let mut ledger_db = create_ledger();
+        let account_key = AccountKey::random(&mut rng);
+        initialize_ledger(BLOCK_VERSION, &mut ledger_db, 1, &account_key, &mut rng);
+
+        let block_data = ledger_db.get_block_data(0).unwrap();
+        reserve_auditor_db
+            .sync_block(block_data.block(), block_data.contents(), None)
+            .unwrap();
+
+        // Block 1 contains a mint config tx, block 2 contains a mint.
+        let (mint_config_tx1, signers1) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let block_contents = BlockContents {
+            validated_mint_config_txs: vec![to_validated(&mint_config_tx1)],
+            ..Default::default()
+        };
+        append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        let block_contents = BlockContents {
+            mint_txs: vec![create_mint_tx(token_id, &signers1, 1000, &mut rng)],
+            outputs: vec![create_test_tx_out(BLOCK_VERSION, &mut rng)],
+            ..Default::default()
+        };
+        let (sync_block_data2, _) = append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        // Block 3 contains another mint config tx, a mint that matches a Gnosis
+        // deposit and a burn.
+        let mut deposit = create_gnosis_safe_deposit(100, &mut rng);
+        insert_gnosis_deposit(&mut deposit, &conn);
+
+        let (mint_config_tx2, _signers2) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let mut mint_tx =
+            create_mint_tx(token_id, &signers1, deposit.mc_amount().unwrap(), &mut rng);
+        mint_tx.prefix.nonce = hex::decode(deposit.expected_mc_mint_tx_nonce_hex()).unwrap();
+        let burn_tx_out = create_burn_tx_out(token_id, 50, &mut rng);
+        let block_contents = BlockContents {
+            validated_mint_config_txs: vec![to_validated(&mint_config_tx2)],
+            mint_txs: vec![mint_tx],
+            outputs: vec![burn_tx_out.decode().unwrap()],
+            ..Default::default()
+        };
+        append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        AuditedMint::try_match_deposit_with_mint(&deposit, config, &conn).unwrap();
+        assert_eq!(AuditedMint::get_by_block_index(3, &conn).unwrap().len(), 1);
+        assert_eq!(BurnTxOut::get_burn_txs_by_block(3, &conn).unwrap().len(), 1);
+        assert_eq!(Counters::get(&conn).unwrap().num_blocks_synced(), 4);
+
+        // Rewind to block 2.
+        assert_eq!(reserve_auditor_db.rewind_to_block(2).unwrap(), 3);
+
+        assert_eq!(Counters::get(&conn).unwrap().num_blocks_synced(), 3);
+        assert_eq!(
+            BlockAuditData::last_synced_block_index(&conn).unwrap(),
+            Some(2)
+        );
+        assert_eq!(SyncBlockData::get(&conn, 3).unwrap(), None);
+        assert_eq!(
+            SyncBlockData::get(&conn, 2).unwrap(),
+            Some(sync_block_data2)
+        );
+        assert!(BlockBalance::get_balances_for_block(&conn, 3)
+            .unwrap()
+            .is_empty());
+        assert!(MintConfigTx::get_by_block_index(3, &conn)
+            .unwrap()
+            .is_empty());
+        assert_eq!(MintConfigTx::get_by_block_index(1, &conn).unwrap().len(), 1);
+        assert!(AuditedMint::get_by_block_index(3, &conn)
+            .unwrap()
+            .is_empty());
+        assert!(BurnTxOut::get_burn_txs_by_block(3, &conn)
+            .unwrap()
+            .is_empty());
+
+        // The Gnosis deposit is still there, and is unaudited again.
+        assert_eq!(
+            GnosisSafeDeposit::find_unaudited_deposits(&conn).unwrap(),
+            vec![deposit.clone()]
+        );
+
+        // Block 3 can now be re-synced and re-audited.
+        let block_data = ledger_db.get_block_data(3).unwrap();
+        let sync_block_data3 = reserve_auditor_db
+            .sync_block(block_data.block(), block_data.contents(), None)
+            .unwrap();
+        assert_eq!(sync_block_data3.mint_txs.len(), 1);
+        assert_eq!(sync_block_data3.burn_tx_outs.len(), 1);
+        assert_eq!(MintConfigTx::get_by_block_index(3, &conn).unwrap().len(), 1);
+
+        AuditedMint::try_match_deposit_with_mint(&deposit, config, &conn).unwrap();
+        assert!(GnosisSafeDeposit::find_unaudited_deposits(&conn)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test_with_logger]
+    fn test_rewind_to_block_keeps_audit_events_and_manual_matches(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let config = &test_gnosis_config().safes[0];
+        let token_id = config.tokens[0].token_id;
+
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let mut ledger_db = create_ledger();
+        let account_key = AccountKey::random(&mut rng);
+        initialize_ledger(BLOCK_VERSION, &mut ledger_db, 1, &account_key, &mut rng);
+
+        let block_data = ledger_db.get_block_data(0).unwrap();
+        reserve_auditor_db
+            .sync_block(block_data.block(), block_data.contents(), None)
+            .unwrap();
+
+        // Block 1 contains a mint config tx, block 2 contains a mint whose nonce does
+        // not reference the deposit it was made for, and a burn.
+        let (mint_config_tx1, signers1) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let block_contents = BlockContents {
+            validated_mint_config_txs: vec![to_validated(&mint_config_tx1)],
+            ..Default::default()
+        };
+        append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        let mut deposit = create_gnosis_safe_deposit(100, &mut rng);
+        insert_gnosis_deposit(&mut deposit, &conn);
+
+        let burn_tx_out = create_burn_tx_out(token_id, 50, &mut rng);
+        let block_contents = BlockContents {
+            mint_txs: vec![create_mint_tx(
+                token_id,
+                &signers1,
+                deposit.mc_amount().unwrap(),
+                &mut rng,
+            )],
+            outputs: vec![burn_tx_out.decode().unwrap()],
+            ..Default::default()
+        };
+        let (sync_block_data2, _) = append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+        let mint_tx = sync_block_data2.mint_txs[0].clone();
+        let burn_tx_out = sync_block_data2.burn_tx_outs[0].clone();
+
+        // The mismatch gets reported, and an operator resolves it with a manual
+        // match.
+        let mint_event = AuditEvent::insert(
+            &AnomalyEvent::new(AnomalyKind::MintTxWithUnknownNonce, "unknown nonce")
+                .with_mint_tx(&mint_tx),
+            &conn,
+        )
+        .unwrap();
+        AuditEvent::insert(
+            &AnomalyEvent::new(AnomalyKind::MismatchingBurnAndWithdrawal, "mismatch")
+                .with_burn_tx_out(&burn_tx_out),
+            &conn,
+        )
+        .unwrap();
+        let manual_match = ManualMatch::force_match_mint(
+            mint_tx.id().unwrap(),
+            deposit.id().unwrap(),
+            "alice",
+            "Nonce was mistyped",
+            &conn,
+        )
+        .unwrap();
+        AuditEventAcknowledgement::acknowledge(
+            mint_event.id().unwrap(),
+            "alice",
+            "Matched manually",
+            manual_match.id(),
+            &conn,
+        )
+        .unwrap();
+
+        // Rewinding deletes the mint and the burn. The events no longer reference
+        // their ids, which SQLite hands out again, but still identify them.
+        assert_eq!(reserve_auditor_db.rewind_to_block(1).unwrap(), 2);
+
+        let events = AuditEvent::list(None, None, false, &conn).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].mint_tx_id(), None);
+        assert_eq!(events[0].mint_tx_nonce_hex(), Some(mint_tx.nonce_hex()));
+        assert_eq!(events[1].burn_tx_out_id(), None);
+        assert_eq!(
+            events[1].burn_tx_out_public_key_hex(),
+            Some(burn_tx_out.public_key_hex())
+        );
+
+        // The manual match and the acknowledgement are kept, but the deposit is
+        // unaudited until the mint is re-synced.
+        assert_eq!(
+            ManualMatch::list(&conn).unwrap(),
+            vec![manual_match.clone()]
+        );
+        assert_eq!(
+            AuditEventAcknowledgement::get_by_audit_event_id(mint_event.id().unwrap(), &conn)
+                .unwrap()
+                .unwrap()
+                .manual_match_id(),
+            manual_match.id()
+        );
+        assert_eq!(
+            GnosisSafeDeposit::find_unaudited_deposits(&conn).unwrap(),
+            vec![deposit.clone()]
+        );
+
+        // Re-syncing the block and re-applying the manual match audits it again.
+        let block_data = ledger_db.get_block_data(2).unwrap();
+        let sync_block_data2 = reserve_auditor_db
+            .sync_block(block_data.block(), block_data.contents(), None)
+            .unwrap();
+        assert_eq!(
+            ManualMatch::reapply_for_mint_tx(&sync_block_data2.mint_txs[0], &conn).unwrap(),
+            vec![manual_match]
+        );
+        assert!(GnosisSafeDeposit::find_unaudited_deposits(&conn)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            AuditEvent::list(None, None, false, &conn).unwrap()[0].mint_tx_id(),
+            None
+        );
+    }
+
+    #[test_with_logger]
+    fn test_resync_after_rewind_keeps_mint_config_mismatch_breakdown(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let token_id = TokenId::from(1);
+
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let mut ledger_db = create_ledger();
+        let account_key = AccountKey::random(&mut rng);
+        initialize_ledger(BLOCK_VERSION, &mut ledger_db, 1, &account_key, &mut rng);
+
+        let block_data = ledger_db.get_block_data(0).unwrap();
+        reserve_auditor_db
+            .sync_block(block_data.block(), block_data.contents(), None)
+            .unwrap();
+
+        // Block 1 contains a mint of a token that has no mint config.
+        let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let block_contents = BlockContents {
+            mint_txs: vec![create_mint_tx(token_id, &signers, 100, &mut rng)],
+            ..Default::default()
+        };
+        append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        let assert_counters = || {
+            let counters = Counters::get(&conn).unwrap();
+            assert_eq!(counters.num_mint_txs_without_matching_mint_config(), 1);
+            assert_eq!(counters.num_mint_txs_without_active_mint_config(), 1);
+        };
+        assert_counters();
+
+        // Re-syncing the block after rewinding does not count the mint again, in
+        // the total or in the breakdown.
+        reserve_auditor_db.rewind_to_block(0).unwrap();
+        let block_data = ledger_db.get_block_data(1).unwrap();
+        reserve_auditor_db
+            .sync_block(block_data.block(), block_data.contents(), None)
+            .unwrap();
+        assert_counters();
+    }
+
     // Attempting to sync the same block twice should fail.
     #[test_with_logger]
     fn test_sync_block_refuses_same_block(logger: Logger) {
@@ -771,7 +1406,7 @@ mod tests {
         assert_eq!(
             sync_block_data,
             SyncBlockData {
-                block_audit: BlockAuditData::new(block_index),
+                block_audit: BlockAuditData::new(block_index, None),
                 balance_map: HashMap::from_iter([(token_id1, 101), (token_id2, 2)]),
                 mint_txs: MintTx::get_mint_txs_by_block_index(block_index, &conn).unwrap(),
                 burn_tx_outs: vec![],
@@ -832,7 +1467,7 @@ mod tests {
         assert_eq!(
             sync_block_data,
             SyncBlockData {
-                block_audit: BlockAuditData::new(block_index),
+                block_audit: BlockAuditData::new(block_index, None),
                 balance_map: HashMap::from_iter([(token_id1, 0), (token_id2, 0)]),
                 mint_txs: MintTx::get_mint_txs_by_block_index(block_index, &conn).unwrap(),
                 burn_tx_outs: vec![
@@ -976,6 +1611,7 @@ mod tests {
                 assert_eq!(counters.num_blocks_synced(), block.index + 1);
                 assert_eq!(counters.num_burns_exceeding_balance(), 0);
                 assert_eq!(counters.num_mint_txs_without_matching_mint_config(), 1);
+                assert_eq!(counters.num_mint_txs_with_invalid_signature(), 1);
 
                 // Chosen arbitrarily, we just need to return an error to ensure the transaction
                 // gets rolled back.
@@ -1029,6 +1665,7 @@ mod tests {
                 assert_eq!(counters.num_blocks_synced(), block.index + 1);
                 assert_eq!(counters.num_burns_exceeding_balance(), 0);
                 assert_eq!(counters.num_mint_txs_without_matching_mint_config(), 1);
+                assert_eq!(counters.num_mint_txs_with_invalid_signature(), 1);
 
                 // Chosen arbitrarily, we just need to return an error to ensure the transaction
                 // gets rolled back.
@@ -1171,5 +1808,153 @@ mod tests {
         assert_eq!(counters.num_blocks_synced(), block.index + 1);
         assert_eq!(counters.num_burns_exceeding_balance(), 0);
         assert_eq!(counters.num_mint_txs_without_matching_mint_config(), 1);
+        assert_eq!(counters.num_mint_txs_exceeding_total_mint_limit(), 1);
+    }
+
+    // MintTxs store their decoded nonce, and those with a nonce of an unknown type get
+    // counted.
+    #[test_with_logger]
+    fn test_sync_block_counts_mint_txs_with_unknown_nonce(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let token_id = TokenId::from(1);
+
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let mut ledger_db = create_ledger();
+        let account_key = AccountKey::random(&mut rng);
+        initialize_ledger(BLOCK_VERSION, &mut ledger_db, 1, &account_key, &mut rng);
+
+        let block_data = ledger_db.get_block_data(0).unwrap();
+        reserve_auditor_db
+            .sync_block(block_data.block(), block_data.contents(), None)
+            .unwrap();
+
+        let (mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let block_contents = BlockContents {
+            validated_mint_config_txs: vec![to_validated(&mint_config_tx)],
+            ..Default::default()
+        };
+        append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        let evm_nonce = MintTxNonce::EvmDeposit(10, EthTxHash::from_random(&mut rng));
+        let mut mint_tx1 = create_mint_tx(token_id, &signers, 100, &mut rng);
+        mint_tx1.prefix.nonce = evm_nonce.to_bytes().to_vec();
+        let mut mint_tx2 = create_mint_tx(token_id, &signers, 200, &mut rng);
+        mint_tx2.prefix.nonce = vec![0xff; mint_tx2.prefix.nonce.len()];
+
+        let block_contents = BlockContents {
+            mint_txs: vec![mint_tx1, mint_tx2],
+            outputs: vec![create_test_tx_out(BLOCK_VERSION, &mut rng)],
+            ..Default::default()
+        };
+        let (sync_block_data, _) = append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sync_block_data.mint_txs[0].decoded_nonce(),
+            Some(evm_nonce.to_string().as_str())
+        );
+        assert_eq!(sync_block_data.mint_txs[0].nonce().unwrap(), evm_nonce);
+        assert_eq!(sync_block_data.mint_txs[1].decoded_nonce(), None);
+        assert!(matches!(
+            sync_block_data.mint_txs[1].nonce(),
+            Err(Error::UnknownMintTxNonce(_))
+        ));
+
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_mint_txs_with_unknown_nonce(),
+            1
+        );
+    }
+
+    // MintTxs exceeding the mint limit of the configs that signed them, and MintTxs
+    // past their tombstone block, get counted.
+    #[test_with_logger]
+    fn test_sync_block_counts_mint_limit_and_tombstone_violations(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let token_id = TokenId::from(1);
+
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let mut ledger_db = create_ledger();
+        let account_key = AccountKey::random(&mut rng);
+        initialize_ledger(BLOCK_VERSION, &mut ledger_db, 1, &account_key, &mut rng);
+
+        let block_data = ledger_db.get_block_data(0).unwrap();
+        reserve_auditor_db
+            .sync_block(block_data.block(), block_data.contents(), None)
+            .unwrap();
+
+        // Sync a MintConfigTx whose mint configs can each only mint a single token,
+        // while the total mint limit is plenty.
+        let (mut mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        mint_config_tx.prefix.total_mint_limit = 100;
+        for config in mint_config_tx.prefix.configs.iter_mut() {
+            config.mint_limit = 1;
+        }
+        let block_contents = BlockContents {
+            validated_mint_config_txs: vec![to_validated(&mint_config_tx)],
+            ..Default::default()
+        };
+        append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        // A MintTx of 2 exceeds the mint limit of every config that signed it.
+        let mint_tx1 = create_mint_tx(token_id, &signers, 2, &mut rng);
+
+        // A MintTx whose tombstone block is the block it appears in.
+        let mut mint_tx2 = create_mint_tx(token_id, &signers, 1, &mut rng);
+        mint_tx2.prefix.tombstone_block = ledger_db.num_blocks().unwrap();
+        let message = mint_tx2.prefix.hash();
+        mint_tx2.signature = MultiSig::new(
+            signers
+                .iter()
+                .map(|signer| signer.try_sign(message.as_ref()).unwrap())
+                .collect(),
+        );
+
+        let block_contents = BlockContents {
+            mint_txs: vec![mint_tx1, mint_tx2],
+            outputs: vec![create_test_tx_out(BLOCK_VERSION, &mut rng)],
+            ..Default::default()
+        };
+        let (sync_block_data, _) = append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        // The first MintTx did not match, the second one did.
+        assert_eq!(sync_block_data.mint_txs[0].mint_config_id(), None);
+        assert!(sync_block_data.mint_txs[1].mint_config_id().is_some());
+
+        let counters = Counters::get(&conn).unwrap();
+        assert_eq!(counters.num_mint_txs_without_matching_mint_config(), 1);
+        assert_eq!(counters.num_mint_txs_exceeding_mint_limit(), 1);
+        assert_eq!(counters.num_mint_txs_with_invalid_signature(), 0);
+        assert_eq!(counters.num_mint_txs_exceeding_total_mint_limit(), 0);
+        assert_eq!(counters.num_mint_txs_past_tombstone_block(), 1);
     }
 }
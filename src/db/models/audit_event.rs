@@ -0,0 +1,452 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    anomaly::{AnomalyEvent, AnomalySeverity},
+    counters,
+    db::{
+        last_insert_rowid,
+        models::SqlEthTxHash,
+        schema::{audit_event_acknowledgements, audit_events, burn_tx_outs, mint_txs},
+        Conn,
+    },
+    error::Error,
+    gnosis::EthTxHash,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::{
+    dsl::{exists, not},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `audit_events` table.
+/// This table stores a record of every anomaly detected while auditing, so
+/// that each anomaly counter increment can be traced back to the objects that
+/// caused it.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+pub struct AuditEvent {
+    /// Auto incrementing primary key.
+    id: Option<i32>,
+
+    /// The kind of anomaly (see [crate::anomaly::AnomalyKind]).
+    kind: String,
+
+    /// How severe the anomaly is (see [crate::anomaly::AnomalySeverity]).
+    severity: String,
+
+    /// Description of what went wrong.
+    message: String,
+
+    /// The MobileCoin block index, if known.
+    block_index: Option<i64>,
+
+    /// The Ethereum block number, if known.
+    eth_block_number: Option<i64>,
+
+    /// Id of the MintTx involved.
+    mint_tx_id: Option<i32>,
+
+    /// Id of the BurnTxOut involved.
+    burn_tx_out_id: Option<i32>,
+
+    /// Id of the GnosisSafeDeposit involved.
+    gnosis_safe_deposit_id: Option<i32>,
+
+    /// Id of the GnosisSafeWithdrawal involved.
+    gnosis_safe_withdrawal_id: Option<i32>,
+
+    /// Id of the ReserveDeposit involved.
+    reserve_deposit_id: Option<i32>,
+
+    /// Id of the ReserveWithdrawal involved.
+    reserve_withdrawal_id: Option<i32>,
+
+    /// When the anomaly was detected.
+    created_at: NaiveDateTime,
+
+    /// The hex-encoded nonce of the MintTx involved.
+    mint_tx_nonce_hex: Option<String>,
+
+    /// The hex-encoded public key of the BurnTxOut involved.
+    burn_tx_out_public_key_hex: Option<String>,
+
+    /// The hash of the Ethereum transaction involved.
+    eth_tx_hash: Option<SqlEthTxHash>,
+}
+
+impl AuditEvent {
+    /// Get id.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// Get the kind of anomaly.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Get the severity of the anomaly.
+    pub fn severity(&self) -> &str {
+        &self.severity
+    }
+
+    /// Get the description of what went wrong.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the MobileCoin block index, if known.
+    pub fn block_index(&self) -> Option<u64> {
+        self.block_index.map(|block_index| block_index as u64)
+    }
+
+    /// Get the Ethereum block number, if known.
+    pub fn eth_block_number(&self) -> Option<u64> {
+        self.eth_block_number
+            .map(|eth_block_number| eth_block_number as u64)
+    }
+
+    /// Get the id of the MintTx involved. This is cleared if the MintTx gets
+    /// deleted by rewinding.
+    pub fn mint_tx_id(&self) -> Option<i32> {
+        self.mint_tx_id
+    }
+
+    /// Get the id of the BurnTxOut involved. This is cleared if the BurnTxOut
+    /// gets deleted by rewinding.
+    pub fn burn_tx_out_id(&self) -> Option<i32> {
+        self.burn_tx_out_id
+    }
+
+    /// Get the id of the GnosisSafeDeposit involved. This is cleared if the
+    /// deposit gets deleted by re-auditing.
+    pub fn gnosis_safe_deposit_id(&self) -> Option<i32> {
+        self.gnosis_safe_deposit_id
+    }
+
+    /// Get the id of the GnosisSafeWithdrawal involved. This is cleared if the
+    /// withdrawal gets deleted by re-auditing.
+    pub fn gnosis_safe_withdrawal_id(&self) -> Option<i32> {
+        self.gnosis_safe_withdrawal_id
+    }
+
+    /// Get the id of the ReserveDeposit involved.
+    pub fn reserve_deposit_id(&self) -> Option<i32> {
+        self.reserve_deposit_id
+    }
+
+    /// Get the id of the ReserveWithdrawal involved.
+    pub fn reserve_withdrawal_id(&self) -> Option<i32> {
+        self.reserve_withdrawal_id
+    }
+
+    /// Get the time the anomaly was detected.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.created_at, Utc)
+    }
+
+    /// Get the hex-encoded nonce of the MintTx involved.
+    pub fn mint_tx_nonce_hex(&self) -> Option<&str> {
+        self.mint_tx_nonce_hex.as_deref()
+    }
+
+    /// Get the hex-encoded public key of the BurnTxOut involved.
+    pub fn burn_tx_out_public_key_hex(&self) -> Option<&str> {
+        self.burn_tx_out_public_key_hex.as_deref()
+    }
+
+    /// Get the hash of the Ethereum transaction involved.
+    pub fn eth_tx_hash(&self) -> Option<&EthTxHash> {
+        self.eth_tx_hash.as_deref()
+    }
+
+    /// Record an anomaly.
+    pub fn insert(event: &AnomalyEvent, conn: &Conn) -> Result<Self, Error> {
+        let mut obj = Self {
+            id: None,
+            kind: enum_name(&event.kind)?,
+            severity: enum_name(&event.kind.severity())?,
+            message: event.message.clone(),
+            block_index: event.block_index.map(|block_index| block_index as i64),
+            eth_block_number: event
+                .eth_block_number
+                .map(|eth_block_number| eth_block_number as i64),
+            mint_tx_id: event.mint_tx_id,
+            burn_tx_out_id: event.burn_tx_out_id,
+            gnosis_safe_deposit_id: event.gnosis_safe_deposit_id,
+            gnosis_safe_withdrawal_id: event.gnosis_safe_withdrawal_id,
+            reserve_deposit_id: event.reserve_deposit_id,
+            reserve_withdrawal_id: event.reserve_withdrawal_id,
+            created_at: Utc::now().naive_utc(),
+            mint_tx_nonce_hex: event.mint_tx_nonce_hex.clone(),
+            burn_tx_out_public_key_hex: event.burn_tx_out_public_key_hex.clone(),
+            eth_tx_hash: event.eth_tx_hash.map(SqlEthTxHash::from),
+        };
+
+        diesel::insert_into(audit_events::table)
+            .values(&obj)
+            .execute(conn)?;
+
+        obj.id = Some(diesel::select(last_insert_rowid).get_result::<i32>(conn)?);
+
+        Ok(obj)
+    }
+
+    /// Look for an event recording the same anomaly as `event`, i.e. one of
+    /// the same kind that involves the same MintTx, BurnTxOut and Ethereum
+    /// transaction. Only anomalies involving at least one of those can be
+    /// told apart from other anomalies of the same kind. If one is found, it
+    /// is pointed at the rows involved in `event`, which replace the ones
+    /// deleted by re-auditing or rewinding, and returned.
+    pub fn update_duplicate(event: &AnomalyEvent, conn: &Conn) -> Result<Option<Self>, Error> {
+        if event.mint_tx_nonce_hex.is_none()
+            && event.burn_tx_out_public_key_hex.is_none()
+            && event.eth_tx_hash.is_none()
+        {
+            return Ok(None);
+        }
+
+        let mut query = audit_events::table
+            .filter(audit_events::kind.eq(enum_name(&event.kind)?))
+            .into_boxed();
+        query = match &event.mint_tx_nonce_hex {
+            Some(nonce_hex) => query.filter(audit_events::mint_tx_nonce_hex.eq(nonce_hex.clone())),
+            None => query.filter(audit_events::mint_tx_nonce_hex.is_null()),
+        };
+        query = match &event.burn_tx_out_public_key_hex {
+            Some(public_key_hex) => {
+                query.filter(audit_events::burn_tx_out_public_key_hex.eq(public_key_hex.clone()))
+            }
+            None => query.filter(audit_events::burn_tx_out_public_key_hex.is_null()),
+        };
+        query = match event.eth_tx_hash {
+            Some(eth_tx_hash) => {
+                query.filter(audit_events::eth_tx_hash.eq(SqlEthTxHash::from(eth_tx_hash)))
+            }
+            None => query.filter(audit_events::eth_tx_hash.is_null()),
+        };
+
+        let mut obj = match query
+            .order_by(audit_events::id)
+            .first::<Self>(conn)
+            .optional()?
+        {
+            Some(obj) => obj,
+            None => return Ok(None),
+        };
+
+        obj.mint_tx_id = event.mint_tx_id.or(obj.mint_tx_id);
+        obj.burn_tx_out_id = event.burn_tx_out_id.or(obj.burn_tx_out_id);
+        obj.gnosis_safe_deposit_id = event.gnosis_safe_deposit_id.or(obj.gnosis_safe_deposit_id);
+        obj.gnosis_safe_withdrawal_id = event
+            .gnosis_safe_withdrawal_id
+            .or(obj.gnosis_safe_withdrawal_id);
+        diesel::update(audit_events::table.filter(audit_events::id.eq(obj.id)))
+            .set((
+                audit_events::mint_tx_id.eq(obj.mint_tx_id),
+                audit_events::burn_tx_out_id.eq(obj.burn_tx_out_id),
+                audit_events::gnosis_safe_deposit_id.eq(obj.gnosis_safe_deposit_id),
+                audit_events::gnosis_safe_withdrawal_id.eq(obj.gnosis_safe_withdrawal_id),
+            ))
+            .execute(conn)?;
+
+        Ok(Some(obj))
+    }
+
+    /// Clear the references to MintTxs and BurnTxOuts that are about to be
+    /// deleted because they appeared after a given block. The events keep
+    /// identifying them by nonce and public key.
+    pub fn detach_after_block(block_index: u64, conn: &Conn) -> Result<(), Error> {
+        diesel::update(
+            audit_events::table.filter(
+                audit_events::mint_tx_id.eq_any(
+                    mint_txs::table
+                        .select(mint_txs::id)
+                        .filter(mint_txs::block_index.gt(block_index as i64)),
+                ),
+            ),
+        )
+        .set(audit_events::mint_tx_id.eq(None::<i32>))
+        .execute(conn)?;
+
+        diesel::update(
+            audit_events::table.filter(
+                audit_events::burn_tx_out_id.eq_any(
+                    burn_tx_outs::table
+                        .select(burn_tx_outs::id)
+                        .filter(burn_tx_outs::block_index.gt(block_index as i64)),
+                ),
+            ),
+        )
+        .set(audit_events::burn_tx_out_id.eq(None::<i32>))
+        .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// Get a paginated list of [AuditEvent]s, ordered by id, optionally only
+    /// those that were not acknowledged yet.
+    pub fn list(
+        offset: Option<u64>,
+        limit: Option<u64>,
+        open_only: bool,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        let mut query = audit_events::table.into_boxed();
+
+        if open_only {
+            query = query.filter(not(exists(
+                audit_event_acknowledgements::table.filter(
+                    audit_event_acknowledgements::audit_event_id
+                        .nullable()
+                        .eq(audit_events::id),
+                ),
+            )));
+        }
+
+        if let Some(o) = offset {
+            query = query.offset(o as i64);
+        }
+
+        if let Some(l) = limit {
+            query = query.limit(l as i64);
+        }
+
+        Ok(query.order_by(audit_events::id).load(conn)?)
+    }
+
+    /// Get the number of [AuditEvent]s of a given severity that were not
+    /// acknowledged yet.
+    pub fn num_open(severity: AnomalySeverity, conn: &Conn) -> Result<u64, Error> {
+        let num_open: i64 = audit_events::table
+            .filter(audit_events::severity.eq(enum_name(&severity)?))
+            .filter(not(exists(
+                audit_event_acknowledgements::table.filter(
+                    audit_event_acknowledgements::audit_event_id
+                        .nullable()
+                        .eq(audit_events::id),
+                ),
+            )))
+            .count()
+            .get_result(conn)?;
+        Ok(num_open as u64)
+    }
+
+    /// Update the prometheus gauges of open events.
+    pub fn update_prometheus(conn: &Conn) -> Result<(), Error> {
+        counters::NUM_OPEN_CRITICAL_AUDIT_EVENTS
+            .set(Self::num_open(AnomalySeverity::Critical, conn)? as i64);
+        counters::NUM_OPEN_ERROR_AUDIT_EVENTS
+            .set(Self::num_open(AnomalySeverity::Error, conn)? as i64);
+        counters::NUM_OPEN_WARNING_AUDIT_EVENTS
+            .set(Self::num_open(AnomalySeverity::Warning, conn)? as i64);
+        Ok(())
+    }
+}
+
+/// The serialized (snake_case) name of a unit enum variant.
+fn enum_name(value: &impl Serialize) -> Result<String, Error> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => Ok(name),
+        _ => Err(Error::Other("Failed serializing enum name".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anomaly::AnomalyKind, db::test_utils::TestDbContext};
+    use mc_common::logger::{test_with_logger, Logger};
+    use std::str::FromStr;
+
+    #[test_with_logger]
+    fn insert_and_list(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let mut event1 = AnomalyEvent::new(AnomalyKind::MismatchingMintAndDeposit, "mismatch");
+        event1.block_index = Some(5);
+        event1.mint_tx_id = Some(3);
+        let event2 = AnomalyEvent::new(AnomalyKind::InvalidMultiSigTransaction, "bad tx");
+        let event3 = AnomalyEvent::new(AnomalyKind::UnknownEthereumTokenDeposit, "token");
+
+        let audit_event1 = AuditEvent::insert(&event1, &conn).unwrap();
+        let audit_event2 = AuditEvent::insert(&event2, &conn).unwrap();
+        let audit_event3 = AuditEvent::insert(&event3, &conn).unwrap();
+
+        assert_eq!(audit_event1.kind(), "mismatching_mint_and_deposit");
+        assert_eq!(audit_event1.severity(), "critical");
+        assert_eq!(audit_event1.message(), "mismatch");
+        assert_eq!(audit_event1.block_index(), Some(5));
+        assert_eq!(audit_event1.mint_tx_id(), Some(3));
+        assert_eq!(audit_event2.severity(), "warning");
+
+        assert_eq!(
+            AuditEvent::list(None, None, false, &conn).unwrap(),
+            vec![
+                audit_event1.clone(),
+                audit_event2.clone(),
+                audit_event3.clone()
+            ]
+        );
+        assert_eq!(
+            AuditEvent::list(Some(1), Some(1), false, &conn).unwrap(),
+            vec![audit_event2]
+        );
+        assert_eq!(
+            AuditEvent::list(Some(2), None, false, &conn).unwrap(),
+            vec![audit_event3]
+        );
+    }
+
+    #[test_with_logger]
+    fn update_duplicate(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let eth_tx_hash = EthTxHash::from_str(
+            "0x4f3124c61c48aa7c7892f8fe426e0c0d8afae100fc0a9aa8e290e530a7632849",
+        )
+        .unwrap();
+
+        let mut event = AnomalyEvent::new(AnomalyKind::MismatchingMintAndDeposit, "mismatch");
+        event.mint_tx_id = Some(3);
+        event.mint_tx_nonce_hex = Some("0102".to_string());
+        event.gnosis_safe_deposit_id = Some(4);
+        event.eth_tx_hash = Some(eth_tx_hash);
+        assert_eq!(AuditEvent::update_duplicate(&event, &conn).unwrap(), None);
+        let audit_event = AuditEvent::insert(&event, &conn).unwrap();
+
+        // The same anomaly detected again after re-auditing, with a re-created deposit.
+        event.gnosis_safe_deposit_id = Some(7);
+        let updated = AuditEvent::update_duplicate(&event, &conn)
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.id(), audit_event.id());
+        assert_eq!(updated.gnosis_safe_deposit_id(), Some(7));
+        assert_eq!(
+            AuditEvent::list(None, None, false, &conn).unwrap(),
+            vec![updated]
+        );
+
+        // Anomalies of another kind or involving other objects are not duplicates.
+        let mut other_kind = event.clone();
+        other_kind.kind = AnomalyKind::MismatchingBurnAndWithdrawal;
+        assert_eq!(
+            AuditEvent::update_duplicate(&other_kind, &conn).unwrap(),
+            None
+        );
+        let mut other_mint = event.clone();
+        other_mint.mint_tx_nonce_hex = Some("0304".to_string());
+        assert_eq!(
+            AuditEvent::update_duplicate(&other_mint, &conn).unwrap(),
+            None
+        );
+
+        // Anomalies that involve none of the identifiers are never duplicates.
+        let event = AnomalyEvent::new(AnomalyKind::BurnExceedingBalance, "too much");
+        AuditEvent::insert(&event, &conn).unwrap();
+        assert_eq!(AuditEvent::update_duplicate(&event, &conn).unwrap(), None);
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    db::{
+        last_insert_rowid,
+        schema::{audit_event_acknowledgements, audit_events},
+        AuditEvent, Conn, ManualMatch,
+    },
+    error::Error,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `audit_event_acknowledgements` table.
+/// This table stores the outcome of operators investigating [AuditEvent]s.
+/// An [AuditEvent] is open until it gets acknowledged.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+pub struct AuditEventAcknowledgement {
+    /// Auto incrementing primary key.
+    id: Option<i32>,
+
+    /// Id of the acknowledged AuditEvent.
+    audit_event_id: i32,
+
+    /// The operator that investigated the event.
+    actor: String,
+
+    /// The outcome of the investigation.
+    note: String,
+
+    /// Id of the ManualMatch that resolved the event, if any.
+    manual_match_id: Option<i32>,
+
+    /// When the event was acknowledged.
+    created_at: NaiveDateTime,
+}
+
+impl AuditEventAcknowledgement {
+    /// Get id.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// Get the id of the acknowledged AuditEvent.
+    pub fn audit_event_id(&self) -> i32 {
+        self.audit_event_id
+    }
+
+    /// Get the operator that investigated the event.
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+
+    /// Get the outcome of the investigation.
+    pub fn note(&self) -> &str {
+        &self.note
+    }
+
+    /// Get the id of the ManualMatch that resolved the event, if any.
+    pub fn manual_match_id(&self) -> Option<i32> {
+        self.manual_match_id
+    }
+
+    /// Get the time the event was acknowledged.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.created_at, Utc)
+    }
+
+    /// Acknowledge an [AuditEvent]. Returns [Error::NotFound] if the event (or
+    /// the manual match) does not exist, and [Error::AlreadyExists] if the
+    /// event was already acknowledged.
+    pub fn acknowledge(
+        audit_event_id: i32,
+        actor: &str,
+        note: &str,
+        manual_match_id: Option<i32>,
+        conn: &Conn,
+    ) -> Result<Self, Error> {
+        if actor.trim().is_empty() {
+            return Err(Error::Other("An actor is required".into()));
+        }
+        if note.trim().is_empty() {
+            return Err(Error::Other("A note is required".into()));
+        }
+
+        // Ensure the referenced objects exist.
+        audit_events::table
+            .select(audit_events::id)
+            .filter(audit_events::id.eq(audit_event_id))
+            .first::<Option<i32>>(conn)?;
+        if let Some(manual_match_id) = manual_match_id {
+            ManualMatch::get(manual_match_id, conn)?;
+        }
+
+        let mut obj = Self {
+            id: None,
+            audit_event_id,
+            actor: actor.to_string(),
+            note: note.to_string(),
+            manual_match_id,
+            created_at: Utc::now().naive_utc(),
+        };
+
+        diesel::insert_into(audit_event_acknowledgements::table)
+            .values(&obj)
+            .execute(conn)?;
+
+        obj.id = Some(diesel::select(last_insert_rowid).get_result::<i32>(conn)?);
+
+        AuditEvent::update_prometheus(conn)?;
+
+        Ok(obj)
+    }
+
+    /// Get the acknowledgement of an [AuditEvent], if it was acknowledged.
+    pub fn get_by_audit_event_id(audit_event_id: i32, conn: &Conn) -> Result<Option<Self>, Error> {
+        Ok(audit_event_acknowledgements::table
+            .filter(audit_event_acknowledgements::audit_event_id.eq(audit_event_id))
+            .first(conn)
+            .optional()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        anomaly::{AnomalyEvent, AnomalyKind, AnomalySeverity},
+        db::test_utils::TestDbContext,
+    };
+    use mc_common::logger::{test_with_logger, Logger};
+
+    #[test_with_logger]
+    fn acknowledge_closes_events(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let event1 = AuditEvent::insert(
+            &AnomalyEvent::new(AnomalyKind::MismatchingMintAndDeposit, "mismatch"),
+            &conn,
+        )
+        .unwrap();
+        let event2 = AuditEvent::insert(
+            &AnomalyEvent::new(AnomalyKind::InvalidMultiSigTransaction, "bad tx"),
+            &conn,
+        )
+        .unwrap();
+        assert_eq!(
+            AuditEvent::num_open(AnomalySeverity::Critical, &conn).unwrap(),
+            1
+        );
+        assert_eq!(
+            AuditEvent::num_open(AnomalySeverity::Warning, &conn).unwrap(),
+            1
+        );
+
+        // Unknown events and manual matches are rejected.
+        assert!(matches!(
+            AuditEventAcknowledgement::acknowledge(1234, "alice", "ok", None, &conn),
+            Err(Error::NotFound)
+        ));
+        assert!(matches!(
+            AuditEventAcknowledgement::acknowledge(
+                event1.id().unwrap(),
+                "alice",
+                "ok",
+                Some(1234),
+                &conn
+            ),
+            Err(Error::NotFound)
+        ));
+
+        let ack = AuditEventAcknowledgement::acknowledge(
+            event1.id().unwrap(),
+            "alice",
+            "Deposit was refunded",
+            None,
+            &conn,
+        )
+        .unwrap();
+        assert_eq!(ack.actor(), "alice");
+        assert_eq!(
+            AuditEventAcknowledgement::get_by_audit_event_id(event1.id().unwrap(), &conn).unwrap(),
+            Some(ack)
+        );
+        assert_eq!(
+            AuditEventAcknowledgement::get_by_audit_event_id(event2.id().unwrap(), &conn).unwrap(),
+            None
+        );
+
+        // The acknowledged event drops out of the open events but stays in history.
+        assert_eq!(
+            AuditEvent::num_open(AnomalySeverity::Critical, &conn).unwrap(),
+            0
+        );
+        assert_eq!(
+            AuditEvent::list(None, None, true, &conn).unwrap(),
+            vec![event2]
+        );
+        assert_eq!(AuditEvent::list(None, None, false, &conn).unwrap().len(), 2);
+
+        // An event can only be acknowledged once.
+        assert!(matches!(
+            AuditEventAcknowledgement::acknowledge(event1.id().unwrap(), "bob", "ok", None, &conn),
+            Err(Error::AlreadyExists(_))
+        ));
+    }
+}
@@ -1,22 +1,24 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
 use crate::{
+    anomaly::{AnomalyEvent, AnomalyKind},
     db::{
-        schema::{audited_burns, burn_tx_outs, gnosis_safe_withdrawals},
-        transaction, BurnTxOut, Conn, Counters, GnosisSafeWithdrawal,
+        schema::{burn_tx_outs, gnosis_safe_withdrawals, reserve_matches},
+        transaction, BurnTxOut, Conn, Counters, GnosisSafeWithdrawal, ReserveMatch,
     },
     gnosis::{AuditedSafeConfig, GnosisSafeConfig},
+    reserve::GnosisReserveBackend,
     Error,
 };
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
-/// Diesel model for the `audited_burns` table.
-/// This stores audit data linking BurnTxOuts with matching
-/// GnosisSafeWithdrawals.
-#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+/// Audit data linking a BurnTxOut with a matching GnosisSafeWithdrawal.
+/// Matches are stored in the `reserve_matches` table under the Gnosis reserve
+/// backend, with the withdrawal referenced by its Ethereum transaction hash.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct AuditedBurn {
-    /// Id (required to keep Diesel happy).
+    /// Id of the match in the `reserve_matches` table.
     pub id: Option<i32>,
 
     /// Id pointing to the BurnTxOut table.
@@ -24,14 +26,19 @@ pub struct AuditedBurn {
 
     /// Id pointing to the GnosisSafeWithdrawal table.
     pub gnosis_safe_withdrawal_id: i32,
+
+    /// Amount of the burn allocated to the GnosisSafeWithdrawal by this match.
+    pub amount: i64,
 }
 
 impl AuditedBurn {
     /// Attempt to find a matching [BurnTxOut] for a given
     /// [GnosisSafeWithdrawal], and if successful return the [BurnTxOut] and
-    /// record the match in the database. Note that each [BurnTxOut] can be
-    /// matched to at most one [GnosisSafeWithdrawal], so calling this
-    /// repeatedly over the same withdrawal will fail.
+    /// record the match in the database. Several withdrawals can be batched
+    /// into a single [BurnTxOut], in which case the burn may already be
+    /// partially allocated to other withdrawals. Once the entire withdrawal
+    /// amount has been allocated, calling this again over the same withdrawal
+    /// will fail.
     pub fn try_match_withdrawal_with_burn(
         withdrawal: &GnosisSafeWithdrawal,
         config: &AuditedSafeConfig,
@@ -41,54 +48,72 @@ impl AuditedBurn {
         // function.
         let result = || -> Result<BurnTxOut, Error> {
             // We only operate on objects that were saved to the database.
-            let withdrawal_id = withdrawal.id().ok_or(Error::ObjectNotSaved)?;
+            withdrawal.id().ok_or(Error::ObjectNotSaved)?;
 
             // The withdrawal safe needs to match the audited safe configuration.
             // This shouldn't happen and indicates misuse of this function.
-            if withdrawal.safe_addr() != &config.safe_addr {
+            if withdrawal.chain_id() != config.chain_id
+                || withdrawal.safe_addr() != &config.safe_addr
+            {
                 return Err(Error::Other(format!(
-                    "Gnosis safe withdrawal addr {} does not match audited safe addr {}",
+                    "Gnosis safe withdrawal addr {} on chain {} does not match audited safe addr {} on chain {}",
                     withdrawal.safe_addr(),
-                    config.safe_addr
+                    withdrawal.chain_id(),
+                    config.safe_addr,
+                    config.chain_id,
                 )));
             }
 
             transaction(conn, |conn| {
-                // Currently we only support 1:1 mapping between deposits and mints, so ensure
-                // that there isn't already a match for this deposit.
-                let existing_match: Option<(String, String)> = audited_burns::table
+                // Ensure that some of the withdrawal amount is still left to be allocated to
+                // burns.
+                let allocated_amount = Self::allocated_to_withdrawal(withdrawal, conn)?;
+                let eth_tx_hash = withdrawal.eth_tx_hash().to_string();
+                let existing_match: Option<String> = reserve_matches::table
                     .inner_join(burn_tx_outs::table)
-                    .inner_join(gnosis_safe_withdrawals::table)
-                    .select((
-                        burn_tx_outs::public_key_hex,
-                        gnosis_safe_withdrawals::eth_tx_hash,
-                    ))
-                    .filter(audited_burns::gnosis_safe_withdrawal_id.eq(withdrawal_id))
+                    .select(burn_tx_outs::public_key_hex)
+                    .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+                    .filter(reserve_matches::reference.eq(eth_tx_hash.as_str()))
                     .first(conn)
                     .optional()?;
-                if let Some((public_key_hex, eth_tx_hash)) = existing_match {
-                    return Err(Error::AlreadyExists(format!(
-                    "GnosisSafeWithdrawal eth_tx_hash={eth_tx_hash} already matched with BurnTxOut pub_key={public_key_hex}",
-                )));
+                if let Some(public_key_hex) = existing_match {
+                    if allocated_amount >= withdrawal.amount() {
+                        return Err(Error::AlreadyExists(format!(
+                            "GnosisSafeWithdrawal eth_tx_hash={eth_tx_hash} already matched with BurnTxOut pub_key={public_key_hex}",
+                        )));
+                    }
                 }
+                let amount = withdrawal.amount() - allocated_amount;
 
                 // See if we can find a BurnTxOut that matches the txout public key and has not
-                // been associated with a deposit.
+                // been fully associated with withdrawals.
                 let burn_tx_out = BurnTxOut::find_unaudited_burn_tx_out_by_public_key(
                     withdrawal.mc_tx_out_public_key_hex(),
                     conn,
                 )?
                 .ok_or(Error::NotFound)?;
+                let burn_tx_out_id = burn_tx_out
+                    .id()
+                    .expect("got a BurnTxOut without id but database auto-populates that field");
 
                 // Check that the burn and withdrawal details match.
-                Self::verify_burn_tx_out_matches_withdrawal(&burn_tx_out, withdrawal, config)?;
+                let unallocated_amount =
+                    Self::unallocated_burn_tx_out_amount(&burn_tx_out, burn_tx_out_id, conn)?
+                        .ok_or(Error::NotFound)?;
+                Self::verify_burn_tx_out_matches_withdrawal(
+                    &burn_tx_out,
+                    withdrawal,
+                    config,
+                    amount,
+                    unallocated_amount,
+                )?;
 
-                // Associate the withdrawal with the burn.
+                // Associate the withdrawal with the burn. The amount fits in a u64 since it
+                // does not exceed what is left of the burn.
                 Self::associate_withdrawal_with_burn(
-                    withdrawal_id,
-                    burn_tx_out.id().expect(
-                        "got a BurnTxOut without id but database auto-populates that field",
-                    ),
+                    withdrawal,
+                    burn_tx_out_id,
+                    amount as u64,
                     conn,
                 )?;
 
@@ -98,112 +123,156 @@ impl AuditedBurn {
 
         // Count certain errors. This needs to happen outside of the transaction because
         // errors result in the transaction getting rolled back.
-        match result {
+        match &result {
             Ok(_) => {}
 
-            Err(Error::WithdrawalAndBurnMismatch(_)) => {
-                Counters::inc_num_mismatching_burns_and_withdrawals(conn)?;
+            Err(err @ Error::WithdrawalAndBurnMismatch(_)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::MismatchingBurnAndWithdrawal, err)
+                        .with_gnosis_withdrawal(withdrawal),
+                    conn,
+                )?;
             }
 
-            Err(Error::EthereumTokenNotAudited(_, _, _)) => {
-                Counters::inc_num_unknown_ethereum_token_withdrawals(conn)?;
+            Err(err @ Error::EthereumTokenNotAudited(_, _, _)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnknownEthereumTokenWithdrawal, err)
+                        .with_gnosis_withdrawal(withdrawal),
+                    conn,
+                )?;
             }
 
-            Err(_) => {
-                Counters::inc_num_unexpected_errors_matching_withdrawals_to_burns(conn)?;
+            // The other side has not been synced yet, or was already matched. Neither is
+            // an anomaly.
+            Err(Error::NotFound) | Err(Error::AlreadyExists(_)) => {}
+
+            Err(err) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnexpectedErrorMatchingWithdrawalToBurn, err)
+                        .with_gnosis_withdrawal(withdrawal),
+                    conn,
+                )?;
             }
         }
 
         result
     }
 
-    /// Attempt to find a matching [GnosisSafeWithdrawal] for a given
-    /// [BurnTxOut], and if successful return the [GnosisSafeWithdrawal] and
-    /// record the match in the database. Note that each
-    /// [GnosisSafeWithdrawal] can be matched to at most one [BurnTxOut], so
-    /// calling this repeatedly over the same tx out will fail.
+    /// Attempt to find the [GnosisSafeWithdrawal]s matching a given
+    /// [BurnTxOut], and if successful return them and record the matches in
+    /// the database. A burn is either matched by a single withdrawal or by
+    /// several withdrawals batched together, all referencing its public key.
+    /// Once the entire burn amount has been allocated, calling this again over
+    /// the same tx out will fail.
     pub fn try_match_burn_with_withdrawal(
         burn_tx_out: &BurnTxOut,
         config: &GnosisSafeConfig,
         conn: &Conn,
-    ) -> Result<GnosisSafeWithdrawal, Error> {
+    ) -> Result<Vec<GnosisSafeWithdrawal>, Error> {
         // Wrapped in a closure to allow using the ? operator without returning from the
         // function.
-        let result = || -> Result<GnosisSafeWithdrawal, Error> {
+        let result = || -> Result<Vec<GnosisSafeWithdrawal>, Error> {
             // We only operate on objects that were saved to the database.
             let burn_tx_out_id = burn_tx_out.id().ok_or(Error::ObjectNotSaved)?;
 
-            transaction(conn, |conn| -> Result<GnosisSafeWithdrawal, Error> {
-                // Currently we only support 1:1 mapping between deposits and mints, so ensure
-                // that there isn't already a match for this mint.
-                let existing_match: Option<(String, String)> = audited_burns::table
-                    .inner_join(burn_tx_outs::table)
-                    .inner_join(gnosis_safe_withdrawals::table)
-                    .select((
-                        burn_tx_outs::public_key_hex,
-                        gnosis_safe_withdrawals::eth_tx_hash,
-                    ))
-                    .filter(audited_burns::burn_tx_out_id.eq(burn_tx_out_id))
-                    .first(conn)
-                    .optional()?;
-                if let Some((public_key_hex, eth_tx_hash)) = existing_match {
-                    return Err(Error::AlreadyExists(format!(
-                        "BurnTxOut pub_key={public_key_hex} already matched with GnosisSafeDeposit eth_tx_hash={eth_tx_hash}",
-                    )));
-                }
-
-                // See if we can find a GnosisSafeWithdrawal that matches the nonce and has not
-                // been associated with a mint.
-                let withdrawal = GnosisSafeWithdrawal::find_unaudited_withdrawal_by_public_key(
-                    burn_tx_out.public_key_hex(),
-                    conn,
-                )?
-                .ok_or(Error::NotFound)?;
-
-                // See if the deposit we found is for a safe we are auditing.
-                let audited_safe_config = config
-                    .get_audited_safe_config_by_safe_addr(withdrawal.safe_addr())
-                    .ok_or_else(|| Error::GnosisSafeNotAudited(withdrawal.safe_addr().clone()))?;
-
-                // See if they match.
-                Self::verify_burn_tx_out_matches_withdrawal(
+            transaction(conn, |conn| -> Result<Vec<GnosisSafeWithdrawal>, Error> {
+                // Ensure that some of the burn amount is still left to be allocated to
+                // withdrawals.
+                let mut unallocated_amount = match Self::unallocated_burn_tx_out_amount(
                     burn_tx_out,
-                    &withdrawal,
-                    &audited_safe_config,
-                )?;
-
-                // Associate the mint with the deposit.
-                Self::associate_withdrawal_with_burn(
-                    withdrawal.id().expect(
-                        "got a GnosisSafeWithdrawal without id but database auto-populates that field",
-                    ),
                     burn_tx_out_id,
                     conn,
+                )? {
+                    Some(amount) => amount,
+                    None => {
+                        return Err(Error::AlreadyExists(format!(
+                            "BurnTxOut pub_key={} is already fully matched with withdrawals",
+                            burn_tx_out.public_key_hex(),
+                        )));
+                    }
+                };
+
+                // See if we can find GnosisSafeWithdrawals that reference the public key and
+                // have not been fully associated with burns.
+                let withdrawals = GnosisSafeWithdrawal::find_unaudited_withdrawals_by_public_key(
+                    burn_tx_out.public_key_hex(),
+                    conn,
                 )?;
+                if withdrawals.is_empty() {
+                    return Err(Error::NotFound);
+                }
+
+                for withdrawal in &withdrawals {
+                    let amount = withdrawal
+                        .amount()
+                        .saturating_sub(Self::allocated_to_withdrawal(withdrawal, conn)?);
+
+                    // See if the withdrawal we found is for a safe we are auditing.
+                    let audited_safe_config = config
+                        .get_audited_safe_config(withdrawal.chain_id(), withdrawal.safe_addr())
+                        .ok_or_else(|| {
+                            Error::GnosisSafeNotAudited(withdrawal.safe_addr().clone())
+                        })?;
+
+                    // See if they match.
+                    Self::verify_burn_tx_out_matches_withdrawal(
+                        burn_tx_out,
+                        withdrawal,
+                        &audited_safe_config,
+                        amount,
+                        unallocated_amount,
+                    )?;
+
+                    // Associate the burn with the withdrawal. The amount fits in a u64 since it
+                    // does not exceed what is left of the burn.
+                    let amount = amount as u64;
+                    Self::associate_withdrawal_with_burn(withdrawal, burn_tx_out_id, amount, conn)?;
+                    unallocated_amount -= amount;
+                }
 
-                Ok(withdrawal)
+                Ok(withdrawals)
             })
         }();
 
         // Count certain errors. This needs to happen outside of the transaction because
         // errors result in the transaction getting rolled back.
-        match result {
+        match &result {
             Ok(_) => {}
 
-            Err(Error::GnosisSafeNotAudited(_)) => {
-                Counters::inc_num_burns_from_unknown_safe(conn)?;
+            Err(err @ Error::GnosisSafeNotAudited(_)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::BurnFromUnknownSafe, err)
+                        .with_burn_tx_out(burn_tx_out),
+                    conn,
+                )?;
             }
 
-            Err(Error::WithdrawalAndBurnMismatch(_)) => {
-                Counters::inc_num_mismatching_burns_and_withdrawals(conn)?;
+            Err(err @ Error::WithdrawalAndBurnMismatch(_)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::MismatchingBurnAndWithdrawal, err)
+                        .with_burn_tx_out(burn_tx_out),
+                    conn,
+                )?;
             }
 
-            Err(Error::EthereumTokenNotAudited(_, _, _)) => {
-                Counters::inc_num_unknown_ethereum_token_withdrawals(conn)?;
+            Err(err @ Error::EthereumTokenNotAudited(_, _, _)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnknownEthereumTokenWithdrawal, err)
+                        .with_burn_tx_out(burn_tx_out),
+                    conn,
+                )?;
             }
 
-            Err(_) => {
-                Counters::inc_num_unexpected_errors_matching_burns_to_withdrawals(conn)?;
+            // The other side has not been synced yet, or was already matched. Neither is
+            // an anomaly.
+            Err(Error::NotFound) | Err(Error::AlreadyExists(_)) => {}
+
+            Err(err) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnexpectedErrorMatchingBurnToWithdrawal, err)
+                        .with_burn_tx_out(burn_tx_out),
+                    conn,
+                )?;
             }
         }
 
@@ -211,19 +280,25 @@ impl AuditedBurn {
     }
 
     /// Verify that the details of a BurnTxOut match the details of a
-    /// GnosisSafeWithdrawal (amount/public key/token).
+    /// GnosisSafeWithdrawal (amount/public key/token). `amount` is the amount
+    /// the match allocates and `unallocated_amount` is what is left of the
+    /// burn once its other matches are accounted for.
     fn verify_burn_tx_out_matches_withdrawal(
         burn_tx_out: &BurnTxOut,
         withdrawal: &GnosisSafeWithdrawal,
         config: &AuditedSafeConfig,
+        amount: u128,
+        unallocated_amount: u64,
     ) -> Result<(), Error> {
         // The withdrawal safe needs to match the audited safe configuration.
         // This shouldn't happen and indicates misuse of this function.
-        if withdrawal.safe_addr() != &config.safe_addr {
+        if withdrawal.chain_id() != config.chain_id || withdrawal.safe_addr() != &config.safe_addr {
             return Err(Error::Other(format!(
-                "Gnosis safe withdrawal addr {} does not match audited safe addr {}",
+                "Gnosis safe withdrawal addr {} on chain {} does not match audited safe addr {} on chain {}",
                 withdrawal.safe_addr(),
-                config.safe_addr
+                withdrawal.chain_id(),
+                config.safe_addr,
+                config.chain_id,
             )));
         }
 
@@ -256,12 +331,14 @@ impl AuditedBurn {
             )));
         }
 
-        // Check to see if the amount matches the withdrawal.
-        if burn_tx_out.amount() != withdrawal.amount() {
+        // Check to see if the withdrawal fits in what is left of the burn. Several
+        // withdrawals can be batched into a single burn, so the amounts only need to
+        // match once all of them are accounted for.
+        if amount > u128::from(unallocated_amount) {
             return Err(Error::WithdrawalAndBurnMismatch(format!(
-                "BurnTxOut amount={} does not match GnosisSafewithdrawal amount={} (pubkey={})",
-                burn_tx_out.amount(),
-                withdrawal.amount(),
+                "GnosisSafeWithdrawal amount={} exceeds unallocated BurnTxOut amount={} (pubkey={})",
+                amount,
+                unallocated_amount,
                 withdrawal.mc_tx_out_public_key_hex(),
             )));
         }
@@ -271,32 +348,118 @@ impl AuditedBurn {
 
     // This is pub(crate) since its used in tests.
     pub(crate) fn associate_withdrawal_with_burn(
-        gnosis_safe_withdrawal_id: i32,
+        withdrawal: &GnosisSafeWithdrawal,
         burn_tx_out_id: i32,
+        amount: u64,
         conn: &Conn,
     ) -> Result<(), Error> {
-        let audited_burn = Self {
-            id: None,
-            gnosis_safe_withdrawal_id,
+        ReserveMatch::insert_for_burn_tx_out(
+            GnosisReserveBackend::NAME,
+            &withdrawal.eth_tx_hash().to_string(),
             burn_tx_out_id,
-        };
-        let _ = diesel::insert_into(audited_burns::table)
-            .values(&audited_burn)
-            .execute(conn)?;
+            amount,
+            conn,
+        )?;
 
         Ok(())
     }
 
+    /// Get the total amount allocated to burns by the matches of a given
+    /// [GnosisSafeWithdrawal]. The withdrawal is fully covered once this
+    /// reaches its amount.
+    pub fn allocated_to_withdrawal(
+        withdrawal: &GnosisSafeWithdrawal,
+        conn: &Conn,
+    ) -> Result<u128, Error> {
+        Ok(Self::withdrawal_matches(withdrawal, conn)?
+            .iter()
+            .map(|reserve_match| u128::from(reserve_match.amount()))
+            .sum())
+    }
+
+    /// Get the amount of a [GnosisSafeWithdrawal] that is not allocated to
+    /// burns yet, or None if the withdrawal is already fully matched.
+    pub(crate) fn unallocated_withdrawal_amount(
+        withdrawal: &GnosisSafeWithdrawal,
+        conn: &Conn,
+    ) -> Result<Option<u128>, Error> {
+        let matches = Self::withdrawal_matches(withdrawal, conn)?;
+        let allocated_amount: u128 = matches
+            .iter()
+            .map(|reserve_match| u128::from(reserve_match.amount()))
+            .sum();
+
+        Ok(
+            (matches.is_empty() || allocated_amount < withdrawal.amount())
+                .then(|| withdrawal.amount() - allocated_amount),
+        )
+    }
+
+    fn withdrawal_matches(
+        withdrawal: &GnosisSafeWithdrawal,
+        conn: &Conn,
+    ) -> Result<Vec<ReserveMatch>, Error> {
+        ReserveMatch::list_for_withdrawal(
+            GnosisReserveBackend::NAME,
+            &withdrawal.eth_tx_hash().to_string(),
+            conn,
+        )
+    }
+
+    /// Get the amount of a [BurnTxOut] that is not allocated to withdrawals
+    /// yet, or None if the burn is already fully matched by any reserve
+    /// backend.
+    fn unallocated_burn_tx_out_amount(
+        burn_tx_out: &BurnTxOut,
+        burn_tx_out_id: i32,
+        conn: &Conn,
+    ) -> Result<Option<u64>, Error> {
+        Ok(match BurnTxOut::matched_amount(burn_tx_out_id, conn)? {
+            Some(matched_amount) if matched_amount >= burn_tx_out.amount() => None,
+            matched_amount => Some(burn_tx_out.amount() - matched_amount.unwrap_or_default()),
+        })
+    }
+
+    /// Get all audited burns whose [BurnTxOut] appeared in a given block.
+    pub fn get_by_block_index(block_index: u64, conn: &Conn) -> Result<Vec<Self>, Error> {
+        let rows: Vec<(ReserveMatch, BurnTxOut, GnosisSafeWithdrawal)> = reserve_matches::table
+            .inner_join(burn_tx_outs::table)
+            .inner_join(
+                gnosis_safe_withdrawals::table
+                    .on(gnosis_safe_withdrawals::eth_tx_hash.eq(reserve_matches::reference)),
+            )
+            .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+            .filter(burn_tx_outs::block_index.eq(block_index as i64))
+            .order_by(reserve_matches::id)
+            .select((
+                reserve_matches::all_columns,
+                burn_tx_outs::all_columns,
+                gnosis_safe_withdrawals::all_columns,
+            ))
+            .load(conn)?;
+
+        Ok(rows
+            .iter()
+            .map(|(reserve_match, burn_tx_out, withdrawal)| {
+                Self::from_reserve_match(reserve_match, burn_tx_out, withdrawal)
+            })
+            .collect())
+    }
+
     /// Get paginated list of audited burns
     pub fn list_with_burn_and_withdrawal(
         offset: Option<u64>,
         limit: Option<u64>,
         conn: &Conn,
     ) -> Result<Vec<(AuditedBurn, BurnTxOut, GnosisSafeWithdrawal)>, Error> {
-        let mut query = audited_burns::table
-            .into_boxed()
+        let mut query = reserve_matches::table
             .inner_join(burn_tx_outs::table)
-            .inner_join(gnosis_safe_withdrawals::table);
+            .inner_join(
+                gnosis_safe_withdrawals::table
+                    .on(gnosis_safe_withdrawals::eth_tx_hash.eq(reserve_matches::reference)),
+            )
+            .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+            .into_boxed();
 
         if let Some(o) = offset {
             query = query.offset(o as i64);
@@ -306,14 +469,42 @@ impl AuditedBurn {
             query = query.limit(l as i64);
         }
 
-        Ok(query
-            .order_by(audited_burns::id)
+        let rows: Vec<(ReserveMatch, BurnTxOut, GnosisSafeWithdrawal)> = query
+            .order_by(reserve_matches::id)
             .select((
-                audited_burns::all_columns,
+                reserve_matches::all_columns,
                 burn_tx_outs::all_columns,
                 gnosis_safe_withdrawals::all_columns,
             ))
-            .load(conn)?)
+            .load(conn)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(reserve_match, burn_tx_out, withdrawal)| {
+                (
+                    Self::from_reserve_match(&reserve_match, &burn_tx_out, &withdrawal),
+                    burn_tx_out,
+                    withdrawal,
+                )
+            })
+            .collect())
+    }
+
+    fn from_reserve_match(
+        reserve_match: &ReserveMatch,
+        burn_tx_out: &BurnTxOut,
+        withdrawal: &GnosisSafeWithdrawal,
+    ) -> Self {
+        Self {
+            id: reserve_match.id(),
+            burn_tx_out_id: burn_tx_out
+                .id()
+                .expect("got a BurnTxOut without id but database auto-populates that field"),
+            gnosis_safe_withdrawal_id: withdrawal.id().expect(
+                "got a GnosisSafeWithdrawal without id but database auto-populates that field",
+            ),
+            amount: reserve_match.amount() as i64,
+        }
     }
 }
 
@@ -327,6 +518,7 @@ mod tests {
             test_gnosis_config, TestDbContext, ETH_TOKEN_CONTRACT_ADDR,
             GNOSIS_SAFE_WITHDRAWAL_TO_ADDR, SAFE_ADDR,
         },
+        db::{AuditEvent, OutboxNotification},
         gnosis::{EthAddr, EthTxHash, EthTxValue},
     };
     use chrono::Utc;
@@ -335,9 +527,9 @@ mod tests {
     use mc_util_from_random::FromRandom;
     use std::str::FromStr;
 
-    fn assert_audited_burns_table_is_empty(conn: &Conn) {
-        let num_rows: i64 = audited_burns::table
-            .select(diesel::dsl::count(audited_burns::id))
+    fn assert_reserve_matches_table_is_empty(conn: &Conn) {
+        let num_rows: i64 = reserve_matches::table
+            .select(diesel::dsl::count(reserve_matches::id))
             .first(conn)
             .unwrap();
         assert_eq!(num_rows, 0);
@@ -373,7 +565,7 @@ mod tests {
             AuditedBurn::try_match_withdrawal_with_burn(&withdrawal1, config, &conn),
             Err(Error::NotFound)
         ));
-        assert_audited_burns_table_is_empty(&conn);
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Insert the first BurnTx to the database, we should get a match now.
         burn_tx_out1.insert(&conn).unwrap();
@@ -426,6 +618,7 @@ mod tests {
         // Create gnosis withdrawal and make the amount msimatch.
         let mut withdrawal = GnosisSafeWithdrawal::new(
             None,
+            1,
             EthTxHash::from_random(&mut rng),
             Utc::now(),
             EthTxValue((burn_tx_out.amount() as u128 + 1) * 1000000000000_u128),
@@ -433,7 +626,8 @@ mod tests {
             EthAddr::from_str(SAFE_ADDR).unwrap(),
             EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
             EthAddr::from_str(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR).unwrap(),
-            burn_tx_out.amount() + 1,
+            u128::from(burn_tx_out.amount()) + 1,
+            0,
             burn_tx_out.public_key_hex().to_string(),
         );
         insert_gnosis_withdrawal(&mut withdrawal, &conn);
@@ -445,8 +639,8 @@ mod tests {
             Err(Error::WithdrawalAndBurnMismatch(_))
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Mismatch counter was incremented
         assert_eq!(
@@ -457,6 +651,39 @@ mod tests {
         );
     }
 
+    #[test_with_logger]
+    fn test_try_match_withdrawal_with_burn_not_found_is_not_an_anomaly(logger: Logger) {
+        let config = &test_gnosis_config().safes[0];
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id = config.tokens[0].token_id;
+
+        // The withdrawal gets synced before its burn.
+        let burn_tx_out = create_burn_tx_out(token_id, 100, &mut rng);
+        let mut withdrawal = create_gnosis_safe_withdrawal_from_burn_tx_out(&burn_tx_out, &mut rng);
+        insert_gnosis_withdrawal(&mut withdrawal, &conn);
+        assert!(matches!(
+            AuditedBurn::try_match_withdrawal_with_burn(&withdrawal, config, &conn),
+            Err(Error::NotFound)
+        ));
+
+        // This is the normal case, so nothing gets recorded or notified.
+        assert!(AuditEvent::list(None, None, false, &conn)
+            .unwrap()
+            .is_empty());
+        assert!(OutboxNotification::find_due(Utc::now(), 100, &conn)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_unexpected_errors_matching_withdrawals_to_burns(),
+            0
+        );
+    }
+
     #[test_with_logger]
     fn test_try_match_withdrawal_with_burn_unsaved_object(logger: Logger) {
         let config = &test_gnosis_config().safes[0];
@@ -472,8 +699,8 @@ mod tests {
             Err(Error::ObjectNotSaved)
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -502,8 +729,8 @@ mod tests {
             Err(Error::Other(_))
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -533,8 +760,8 @@ mod tests {
             Err(Error::WithdrawalAndBurnMismatch(_))
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Mismatch counter was incremented
         assert_eq!(
@@ -566,8 +793,8 @@ mod tests {
             Err(Error::EthereumTokenNotAudited(_, _, _))
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -605,13 +832,13 @@ mod tests {
             AuditedBurn::try_match_burn_with_withdrawal(&burn_tx_out2, &config, &conn),
             Err(Error::NotFound)
         ));
-        assert_audited_burns_table_is_empty(&conn);
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Insert the first withdrawal to the database, we should get a match now.
         insert_gnosis_withdrawal(&mut withdrawal1, &conn);
 
         assert_eq!(
-            withdrawal1,
+            vec![withdrawal1],
             AuditedBurn::try_match_burn_with_withdrawal(&burn_tx_out1, &config, &conn).unwrap()
         );
         assert!(matches!(
@@ -627,7 +854,7 @@ mod tests {
             Err(Error::AlreadyExists(_))
         ));
         assert_eq!(
-            withdrawal2,
+            vec![withdrawal2],
             AuditedBurn::try_match_burn_with_withdrawal(&burn_tx_out2, &config, &conn).unwrap()
         );
 
@@ -646,6 +873,98 @@ mod tests {
         );
     }
 
+    #[test_with_logger]
+    fn test_try_match_withdrawals_batched_into_burn(logger: Logger) {
+        let config = test_gnosis_config();
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let token_id = config.safes[0].tokens[0].token_id;
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        // Create a burn that gets withdrawn in several batched withdrawals.
+        let burn_tx_out = create_and_insert_burn_tx_out(token_id, 300, &conn, &mut rng);
+        let burn_tx_out_id = burn_tx_out.id().unwrap();
+        let mut insert_withdrawal = |amount: u64| {
+            let mut withdrawal = GnosisSafeWithdrawal::new(
+                None,
+                1,
+                EthTxHash::from_random(&mut rng),
+                Utc::now(),
+                EthTxValue::from_str(&format!("{amount}000000000000")).unwrap(),
+                1,
+                EthAddr::from_str(SAFE_ADDR).unwrap(),
+                EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
+                EthAddr::from_str(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR).unwrap(),
+                amount.into(),
+                0,
+                burn_tx_out.public_key_hex().to_string(),
+            );
+            insert_gnosis_withdrawal(&mut withdrawal, &conn);
+            withdrawal
+        };
+
+        // The first two withdrawals get matched when the burn is processed.
+        let withdrawal1 = insert_withdrawal(100);
+        let withdrawal2 = insert_withdrawal(150);
+        assert_eq!(
+            AuditedBurn::try_match_burn_with_withdrawal(&burn_tx_out, &config, &conn).unwrap(),
+            vec![withdrawal1, withdrawal2]
+        );
+        assert_eq!(
+            BurnTxOut::matched_amount(burn_tx_out_id, &conn).unwrap(),
+            Some(250)
+        );
+
+        // The burn is only partially covered so far.
+        assert_eq!(
+            BurnTxOut::find_unaudited_burn_tx_outs(&conn).unwrap(),
+            vec![burn_tx_out.clone()]
+        );
+
+        // A withdrawal exceeding what is left of the burn is a mismatch.
+        let withdrawal3 = insert_withdrawal(60);
+        assert!(matches!(
+            AuditedBurn::try_match_withdrawal_with_burn(&withdrawal3, &config.safes[0], &conn),
+            Err(Error::WithdrawalAndBurnMismatch(_))
+        ));
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_mismatching_burns_and_withdrawals(),
+            1
+        );
+
+        // The last withdrawal covers the rest of the burn.
+        let withdrawal4 = insert_withdrawal(50);
+        assert_eq!(
+            AuditedBurn::try_match_withdrawal_with_burn(&withdrawal4, &config.safes[0], &conn)
+                .unwrap(),
+            burn_tx_out
+        );
+        assert_eq!(
+            BurnTxOut::matched_amount(burn_tx_out_id, &conn).unwrap(),
+            Some(burn_tx_out.amount())
+        );
+        assert_eq!(
+            AuditedBurn::allocated_to_withdrawal(&withdrawal4, &conn).unwrap(),
+            50
+        );
+        assert!(BurnTxOut::find_unaudited_burn_tx_outs(&conn)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            GnosisSafeWithdrawal::find_unaudited_withdrawals(&conn).unwrap(),
+            vec![withdrawal3]
+        );
+
+        // Nothing is left to allocate.
+        assert!(matches!(
+            AuditedBurn::try_match_burn_with_withdrawal(&burn_tx_out, &config, &conn),
+            Err(Error::AlreadyExists(_))
+        ));
+    }
+
     #[test_with_logger]
     fn test_try_match_burn_with_withdrawal_amount_mismatch(logger: Logger) {
         let config = test_gnosis_config();
@@ -661,6 +980,7 @@ mod tests {
         // Create gnosis withdrawal and make the amount msimatch.
         let mut withdrawal = GnosisSafeWithdrawal::new(
             None,
+            1,
             EthTxHash::from_random(&mut rng),
             Utc::now(),
             EthTxValue((burn_tx_out.amount() as u128 + 1) * 1000000000000_u128),
@@ -668,7 +988,8 @@ mod tests {
             EthAddr::from_str(SAFE_ADDR).unwrap(),
             EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
             EthAddr::from_str(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR).unwrap(),
-            burn_tx_out.amount() + 1,
+            u128::from(burn_tx_out.amount()) + 1,
+            0,
             burn_tx_out.public_key_hex().to_string(),
         );
         insert_gnosis_withdrawal(&mut withdrawal, &conn);
@@ -679,8 +1000,8 @@ mod tests {
             Err(Error::WithdrawalAndBurnMismatch(_))
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Mismatch counter was incremented
         assert_eq!(
@@ -707,8 +1028,8 @@ mod tests {
             Err(Error::ObjectNotSaved)
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -738,8 +1059,8 @@ mod tests {
             Err(Error::GnosisSafeNotAudited(_))
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn).unwrap().num_burns_from_unknown_safe(),
@@ -767,8 +1088,8 @@ mod tests {
             Err(Error::WithdrawalAndBurnMismatch(_))
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Mismatch counter was incremented
         assert_eq!(
@@ -800,8 +1121,8 @@ mod tests {
             Err(Error::EthereumTokenNotAudited(_, _, _))
         ));
 
-        // Check that nothing was written to the `audited_burns` table
-        assert_audited_burns_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -841,6 +1162,12 @@ mod tests {
         assert_eq!(burn_tx.public_key_hex(), burns[0].public_key_hex());
         assert_eq!(withdrawal.eth_tx_hash(), withdrawals[0].eth_tx_hash());
 
+        // All the burn tx outs were created at block 0.
+        assert_eq!(AuditedBurn::get_by_block_index(0, &conn).unwrap().len(), 10);
+        assert!(AuditedBurn::get_by_block_index(1, &conn)
+            .unwrap()
+            .is_empty());
+
         let paginated_burns =
             AuditedBurn::list_with_burn_and_withdrawal(Some(4), Some(3), &conn).unwrap();
         assert_eq!(paginated_burns.len(), 3);
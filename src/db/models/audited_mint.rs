@@ -1,21 +1,24 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
 use crate::{
+    anomaly::{AnomalyEvent, AnomalyKind},
     db::{
-        schema::{audited_mints, gnosis_safe_deposits, mint_txs},
-        transaction, Conn, Counters, GnosisSafeDeposit, MintTx,
+        schema::{gnosis_safe_deposits, mint_txs, reserve_matches},
+        transaction, Conn, Counters, GnosisSafeDeposit, MintTx, ReserveMatch,
     },
     gnosis::{AuditedSafeConfig, GnosisSafeConfig},
-    Error,
+    reserve::GnosisReserveBackend,
+    Error, MintTxNonce,
 };
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
-/// Diesel model for the `audited_mints` table.
-/// This stores audit data linking MintTxs with matching GnosisSafeDeposits.
-#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+/// Audit data linking a MintTx with a matching GnosisSafeDeposit. Matches are
+/// stored in the `reserve_matches` table under the Gnosis reserve backend,
+/// with the deposit referenced by its Ethereum transaction hash.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct AuditedMint {
-    /// Id (required to keep Diesel happy).
+    /// Id of the match in the `reserve_matches` table.
     pub id: Option<i32>,
 
     /// Id pointing to the MintTx table.
@@ -23,91 +26,155 @@ pub struct AuditedMint {
 
     /// Id pointing to the GnosisSafeDeposit table.
     pub gnosis_safe_deposit_id: i32,
+
+    /// Amount of the deposit allocated to the MintTx by this match.
+    pub amount: i64,
 }
 
 impl AuditedMint {
-    /// Attempt to find a matching [MintTx] for a given [GnosisSafeDeposit], and
-    /// if successful return the [MintTx] and record the match in the
-    /// database. Note that each [MintTx] can be matched to at most one
-    /// [GnosisSafeDeposit], so calling this repeatedly over the same
+    /// Attempt to find the [MintTx]s matching a given [GnosisSafeDeposit], and
+    /// if successful return them and record the matches in the database. A
+    /// deposit is either minted by a single [MintTx] whose nonce references
+    /// it, or split across several [MintTx]s whose nonces reference one of
+    /// its parts. A [MintTx] that does not match the deposit, e.g. a part
+    /// exceeding what is left of it, is recorded as an anomaly and skipped
+    /// without preventing the other parts from being matched. Once the entire
+    /// deposit amount has been allocated, calling this again over the same
     /// deposit will fail.
     pub fn try_match_deposit_with_mint(
         deposit: &GnosisSafeDeposit,
         config: &AuditedSafeConfig,
         conn: &Conn,
-    ) -> Result<MintTx, Error> {
+    ) -> Result<Vec<MintTx>, Error> {
         // Wrapped in a closure to allow using the ? operator without returning from the
         // function.
-        let result = || -> Result<MintTx, Error> {
+        let result = || -> Result<(Vec<MintTx>, Vec<(MintTx, Error)>), Error> {
             // We only operate on objects that were saved to the database.
-            let deposit_id = deposit.id().ok_or(Error::ObjectNotSaved)?;
+            deposit.id().ok_or(Error::ObjectNotSaved)?;
 
             // The deposit safe needs to match the audited safe configuration.
             // This shouldn't happen and indicates misuse of this function.
-            if deposit.safe_addr() != &config.safe_addr {
+            if deposit.chain_id() != config.chain_id || deposit.safe_addr() != &config.safe_addr {
                 return Err(Error::Other(format!(
-                    "Gnosis safe deposit addr {} does not match audited safe addr {}",
+                    "Gnosis safe deposit addr {} on chain {} does not match audited safe addr {} on chain {}",
                     deposit.safe_addr(),
-                    config.safe_addr
+                    deposit.chain_id(),
+                    config.safe_addr,
+                    config.chain_id,
                 )));
             }
 
             transaction(conn, |conn| {
-                // Currently we only support 1:1 mapping between deposits and mints, so ensure
-                // that there isn't already a match for this deposit.
-                let existing_match: Option<(String, String)> = audited_mints::table
-                    .inner_join(mint_txs::table)
-                    .inner_join(gnosis_safe_deposits::table)
-                    .select((mint_txs::nonce_hex, gnosis_safe_deposits::eth_tx_hash))
-                    .filter(audited_mints::gnosis_safe_deposit_id.eq(deposit_id))
-                    .first(conn)
-                    .optional()?;
-                if let Some((nonce_hex, eth_tx_hash)) = existing_match {
-                    Counters::inc_num_unexpected_errors_matching_deposits_to_mints(conn)?;
-                    return Err(Error::AlreadyExists(format!(
-                        "GnosisSafeDeposit eth_tx_hash={eth_tx_hash} already matched with mint_tx nonce={nonce_hex}",
-                    )));
-                }
-
-                // See if we can find a MintTx that matches the expected nonce and has not been
-                // associated with a deposit.
-                let mint_tx = MintTx::find_unaudited_mint_tx_by_nonce(
-                    deposit.expected_mc_mint_tx_nonce_hex(),
+                // Ensure that some of the deposit amount is still left to be allocated to
+                // mints.
+                let mut unallocated_amount = match Self::unallocated_deposit_amount(deposit, conn)?
+                {
+                    Some(amount) => amount,
+                    None => {
+                        Counters::inc_num_unexpected_errors_matching_deposits_to_mints(conn)?;
+                        return Err(Error::AlreadyExists(format!(
+                            "GnosisSafeDeposit eth_tx_hash={} is already fully matched with mints",
+                            deposit.eth_tx_hash(),
+                        )));
+                    }
+                };
+
+                // See if we can find MintTxs that reference the deposit and have not been
+                // fully associated with deposits.
+                let mint_txs = MintTx::find_unaudited_mint_txs_for_deposit(
+                    deposit.chain_id(),
+                    deposit.eth_tx_hash(),
                     conn,
-                )?
-                .ok_or(Error::NotFound)?;
-
-                // Check that the mint and deposit details match.
-                Self::verify_mint_tx_matches_deposit(&mint_tx, deposit, config)?;
+                )?;
+                if mint_txs.is_empty() {
+                    return Err(Error::NotFound);
+                }
 
-                // Associate the deposit with the mint.
-                Self::associate_deposit_with_mint(
-                    deposit_id,
-                    mint_tx
+                let mut matched_mint_txs = Vec::new();
+                let mut mismatching_mint_txs = Vec::new();
+                for mint_tx in mint_txs {
+                    let mint_tx_id = mint_tx
                         .id()
-                        .expect("got a MintTx without id but database auto-populates that field"),
-                    conn,
-                )?;
+                        .expect("got a MintTx without id but database auto-populates that field");
+                    let amount = mint_tx.amount().saturating_sub(
+                        MintTx::matched_amount(mint_tx_id, conn)?.unwrap_or_default(),
+                    );
+
+                    // Check that the mint and deposit details match. A mismatching mint is
+                    // skipped so that it does not prevent the other parts of the deposit from
+                    // being matched.
+                    match Self::verify_mint_tx_matches_deposit(
+                        &mint_tx,
+                        deposit,
+                        config,
+                        amount,
+                        unallocated_amount,
+                    ) {
+                        Ok(()) => {}
+                        Err(err @ Error::DepositAndMintMismatch(_)) => {
+                            mismatching_mint_txs.push((mint_tx, err));
+                            continue;
+                        }
+                        Err(err) => return Err(err),
+                    }
+
+                    // Associate the deposit with the mint.
+                    Self::associate_deposit_with_mint(deposit, mint_tx_id, amount, conn)?;
+                    unallocated_amount -= u128::from(amount);
+                    matched_mint_txs.push(mint_tx);
+                }
 
-                Ok(mint_tx)
+                Ok((matched_mint_txs, mismatching_mint_txs))
             })
         }();
 
+        // Record the mismatching mints. This needs to happen outside of the transaction
+        // for the same reason as counting errors below. If none of the mints matched,
+        // the mismatch is returned.
+        let result = match result {
+            Ok((mint_txs, mismatching_mint_txs)) => {
+                for (mint_tx, err) in &mismatching_mint_txs {
+                    Counters::record_anomaly(
+                        &AnomalyEvent::new(AnomalyKind::MismatchingMintAndDeposit, err)
+                            .with_mint_tx(mint_tx)
+                            .with_gnosis_deposit(deposit),
+                        conn,
+                    )?;
+                }
+                match mismatching_mint_txs.into_iter().next() {
+                    Some((_, err)) if mint_txs.is_empty() => Err(err),
+                    _ => Ok(mint_txs),
+                }
+            }
+            Err(err) => Err(err),
+        };
+
         // Count certain errors. This needs to happen outside of the transaction because
         // errors result in the transaction getting rolled back.
-        match result {
+        match &result {
             Ok(_) => {}
 
-            Err(Error::DepositAndMintMismatch(_)) => {
-                Counters::inc_num_mismatching_mints_and_deposits(conn)?;
-            }
+            // Already recorded along with the mismatching MintTx above.
+            Err(Error::DepositAndMintMismatch(_)) => {}
 
-            Err(Error::EthereumTokenNotAudited(_, _, _)) => {
-                Counters::inc_num_unknown_ethereum_token_deposits(conn)?;
+            Err(err @ Error::EthereumTokenNotAudited(_, _, _)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnknownEthereumTokenDeposit, err)
+                        .with_gnosis_deposit(deposit),
+                    conn,
+                )?;
             }
 
-            Err(_) => {
-                Counters::inc_num_unexpected_errors_matching_deposits_to_mints(conn)?;
+            // The other side has not been synced yet, or was already matched. Neither is
+            // an anomaly.
+            Err(Error::NotFound) | Err(Error::AlreadyExists(_)) => {}
+
+            Err(err) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnexpectedErrorMatchingDepositToMint, err)
+                        .with_gnosis_deposit(deposit),
+                    conn,
+                )?;
             }
         }
 
@@ -116,9 +183,10 @@ impl AuditedMint {
 
     /// Attempt to find a matching [GnosisSafeDeposit] for a given [MintTx], and
     /// if successful return the [GnosisSafeDeposit] and record the match in the
-    /// database. Note that each [GnosisSafeDeposit] can be matched to at
-    /// most one [MintTx], so calling this repeatedly over the same [MintTx]
-    /// will fail.
+    /// database. The [MintTx] nonce references either an entire deposit or
+    /// one of its parts, in which case the deposit may already be partially
+    /// allocated to other mints. Once the entire [MintTx] amount has been
+    /// allocated, calling this again over the same [MintTx] will fail.
     pub fn try_match_mint_with_deposit(
         mint_tx: &MintTx,
         config: &GnosisSafeConfig,
@@ -131,43 +199,60 @@ impl AuditedMint {
             let mint_tx_id = mint_tx.id().ok_or(Error::ObjectNotSaved)?;
 
             transaction(conn, |conn| -> Result<GnosisSafeDeposit, Error> {
-                // Currently we only support 1:1 mapping between deposits and mints, so ensure
-                // that there isn't already a match for this mint.
-                let existing_match: Option<(String, String)> = audited_mints::table
-                    .inner_join(mint_txs::table)
-                    .inner_join(gnosis_safe_deposits::table)
-                    .select((mint_txs::nonce_hex, gnosis_safe_deposits::eth_tx_hash))
-                    .filter(audited_mints::mint_tx_id.eq(mint_tx_id))
-                    .first(conn)
-                    .optional()?;
-                if let Some((nonce_hex, eth_tx_hash)) = existing_match {
+                // Ensure that some of the mint amount is still left to be allocated to
+                // deposits.
+                let matched_amount = MintTx::matched_amount(mint_tx_id, conn)?;
+                let is_fully_matched =
+                    matched_amount.map_or(false, |amount| amount >= mint_tx.amount());
+                if is_fully_matched {
+                    let existing_matches = ReserveMatch::list_for_mint_tx(mint_tx_id, conn)?;
                     return Err(Error::AlreadyExists(format!(
-                        "MintTx nonce={nonce_hex} already matched with GnosisSafeDeposit eth_tx_hash={eth_tx_hash}",
+                        "MintTx nonce={} already matched with {}",
+                        mint_tx.nonce_hex(),
+                        existing_matches
+                            .iter()
+                            .map(|existing_match| format!(
+                                "{} deposit ref={}",
+                                existing_match.backend(),
+                                existing_match.reference()
+                            ))
+                            .collect::<Vec<_>>()
+                            .join(", "),
                     )));
                 }
-
-                // See if we can find a GnosisSafeDeposit that matches the nonce and has not
-                // been associated with a mint.
+                let amount = mint_tx.amount() - matched_amount.unwrap_or_default();
+
+                // See if we can find a GnosisSafeDeposit that the nonce references and that
+                // has not been fully associated with mints.
+                let nonce = mint_tx.nonce()?;
+                let (chain_id, eth_tx_hash) = nonce
+                    .chain_id()
+                    .zip(nonce.eth_tx_hash().copied())
+                    .ok_or(Error::NotFound)?;
+                let deposit_nonce_hex =
+                    hex::encode(MintTxNonce::for_deposit(chain_id, eth_tx_hash).to_bytes());
                 let deposit =
-                    GnosisSafeDeposit::find_unaudited_deposit_by_nonce(mint_tx.nonce_hex(), conn)?
+                    GnosisSafeDeposit::find_unaudited_deposit_by_nonce(&deposit_nonce_hex, conn)?
                         .ok_or(Error::NotFound)?;
 
                 // See if the deposit we found is for a safe we are auditing.
                 let audited_safe_config = config
-                    .get_audited_safe_config_by_safe_addr(deposit.safe_addr())
+                    .get_audited_safe_config(deposit.chain_id(), deposit.safe_addr())
                     .ok_or_else(|| Error::GnosisSafeNotAudited(deposit.safe_addr().clone()))?;
 
                 // See if they match.
-                Self::verify_mint_tx_matches_deposit(mint_tx, &deposit, &audited_safe_config)?;
+                let unallocated_amount =
+                    Self::unallocated_deposit_amount(&deposit, conn)?.ok_or(Error::NotFound)?;
+                Self::verify_mint_tx_matches_deposit(
+                    mint_tx,
+                    &deposit,
+                    &audited_safe_config,
+                    amount,
+                    unallocated_amount,
+                )?;
 
                 // Associate the mint with the deposit.
-                Self::associate_deposit_with_mint(
-                    deposit.id().expect(
-                        "got a GnosisSafeDeposit without id but database auto-populates that field",
-                    ),
-                    mint_tx_id,
-                    conn,
-                )?;
+                Self::associate_deposit_with_mint(&deposit, mint_tx_id, amount, conn)?;
 
                 Ok(deposit)
             })
@@ -175,23 +260,45 @@ impl AuditedMint {
 
         // Count certain errors. This needs to happen outside of the transaction because
         // errors result in the transaction getting rolled back.
-        match result {
+        match &result {
             Ok(_) => {}
 
-            Err(Error::GnosisSafeNotAudited(_)) => {
-                Counters::inc_num_mints_to_unknown_safe(conn)?;
+            // Already counted when the MintTx was synced.
+            Err(Error::UnknownMintTxNonce(_)) => {}
+
+            Err(err @ Error::GnosisSafeNotAudited(_)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::MintToUnknownSafe, err).with_mint_tx(mint_tx),
+                    conn,
+                )?;
             }
 
-            Err(Error::DepositAndMintMismatch(_)) => {
-                Counters::inc_num_mismatching_mints_and_deposits(conn)?;
+            Err(err @ Error::DepositAndMintMismatch(_)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::MismatchingMintAndDeposit, err)
+                        .with_mint_tx(mint_tx),
+                    conn,
+                )?;
             }
 
-            Err(Error::EthereumTokenNotAudited(_, _, _)) => {
-                Counters::inc_num_unknown_ethereum_token_deposits(conn)?;
+            Err(err @ Error::EthereumTokenNotAudited(_, _, _)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnknownEthereumTokenDeposit, err)
+                        .with_mint_tx(mint_tx),
+                    conn,
+                )?;
             }
 
-            Err(_) => {
-                Counters::inc_num_unexpected_errors_matching_mints_to_deposits(conn)?;
+            // The other side has not been synced yet, or was already matched. Neither is
+            // an anomaly.
+            Err(Error::NotFound) | Err(Error::AlreadyExists(_)) => {}
+
+            Err(err) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnexpectedErrorMatchingMintToDeposit, err)
+                        .with_mint_tx(mint_tx),
+                    conn,
+                )?;
             }
         }
 
@@ -199,19 +306,25 @@ impl AuditedMint {
     }
 
     /// Verify that the details of a MintTx match the details of a
-    /// GnosisSafeDeposit (amount/nonce/token).
+    /// GnosisSafeDeposit (amount/nonce/token). `amount` is the amount the
+    /// match allocates and `unallocated_amount` is what is left of the deposit
+    /// once its other matches are accounted for.
     fn verify_mint_tx_matches_deposit(
         mint_tx: &MintTx,
         deposit: &GnosisSafeDeposit,
         config: &AuditedSafeConfig,
+        amount: u64,
+        unallocated_amount: u128,
     ) -> Result<(), Error> {
         // The deposit safe needs to match the audited safe configuration.
         // This shouldn't happen and indicates misuse of this function.
-        if deposit.safe_addr() != &config.safe_addr {
+        if deposit.chain_id() != config.chain_id || deposit.safe_addr() != &config.safe_addr {
             return Err(Error::Other(format!(
-                "Gnosis safe deposit addr {} does not match audited safe addr {}",
+                "Gnosis safe deposit addr {} on chain {} does not match audited safe addr {} on chain {}",
                 deposit.safe_addr(),
-                config.safe_addr
+                deposit.chain_id(),
+                config.safe_addr,
+                config.chain_id,
             )));
         }
 
@@ -235,42 +348,41 @@ impl AuditedMint {
             )));
         }
 
-        // Nonces should match.
-        if mint_tx.nonce_hex() != deposit.expected_mc_mint_tx_nonce_hex() {
-            return Err(Error::DepositAndMintMismatch(format!(
-                "MintTx nonce {} does not match expected nonce {}",
-                mint_tx.nonce_hex(),
-                deposit.expected_mc_mint_tx_nonce_hex()
-            )));
-        }
-
-        // Check to see if the amount matches the deposit.
-        if mint_tx.amount() != deposit.amount() {
-            return Err(Error::DepositAndMintMismatch(format!(
-                "MintTx amount={} does not match GnosisSafeDeposit amount={} (nonce={})",
-                mint_tx.amount(),
-                deposit.amount(),
-                deposit.expected_mc_mint_tx_nonce_hex(),
-            )));
+        // Nonces should reference the deposit on the chain it happened on, either
+        // entirely or one of its parts.
+        match mint_tx.nonce().ok() {
+            Some(nonce)
+                if nonce.chain_id() == Some(deposit.chain_id())
+                    && nonce.eth_tx_hash() == Some(deposit.eth_tx_hash()) =>
+            {
+                // Check to see if the amount matches the deposit, unless the deposit is
+                // split across several mints.
+                if nonce.part_index().is_none() && u128::from(mint_tx.amount()) != deposit.amount()
+                {
+                    return Err(Error::DepositAndMintMismatch(format!(
+                        "MintTx amount={} does not match GnosisSafeDeposit amount={} (nonce={})",
+                        mint_tx.amount(),
+                        deposit.amount(),
+                        deposit.expected_mc_mint_tx_nonce_hex(),
+                    )));
+                }
+            }
+            _ => {
+                return Err(Error::DepositAndMintMismatch(format!(
+                    "MintTx nonce {} does not match expected nonce {}",
+                    mint_tx.nonce_hex(),
+                    deposit.expected_mc_mint_tx_nonce_hex()
+                )));
+            }
         }
 
-        // Check and see if the tokens match.
-        let audited_token = config
-            .get_token_by_eth_contract_addr(deposit.token_addr())
-            .ok_or_else(|| {
-                Error::EthereumTokenNotAudited(
-                    deposit.token_addr().clone(),
-                    deposit.safe_addr().clone(),
-                    *deposit.eth_tx_hash(),
-                )
-            })?;
-
-        if audited_token.token_id != mint_tx.token_id() {
+        // The match cannot allocate more than what is left of the deposit.
+        if u128::from(amount) > unallocated_amount {
             return Err(Error::DepositAndMintMismatch(format!(
-                "MintTx token_id={} does not match audited token_id={} (nonce={})",
-                mint_tx.token_id(),
-                audited_token.token_id,
-                deposit.expected_mc_mint_tx_nonce_hex(),
+                "MintTx amount={} exceeds unallocated GnosisSafeDeposit amount={} (nonce={})",
+                amount,
+                unallocated_amount,
+                mint_tx.nonce_hex(),
             )));
         }
 
@@ -279,32 +391,99 @@ impl AuditedMint {
 
     // This is pub(crate) since its used in tests.
     pub(crate) fn associate_deposit_with_mint(
-        gnosis_safe_deposit_id: i32,
+        deposit: &GnosisSafeDeposit,
         mint_tx_id: i32,
+        amount: u64,
         conn: &Conn,
     ) -> Result<(), Error> {
-        let audited_mint = Self {
-            id: None,
+        ReserveMatch::insert_for_mint_tx(
+            GnosisReserveBackend::NAME,
+            &deposit.eth_tx_hash().to_string(),
             mint_tx_id,
-            gnosis_safe_deposit_id,
-        };
-        let _ = diesel::insert_into(audited_mints::table)
-            .values(&audited_mint)
-            .execute(conn)?;
+            amount,
+            conn,
+        )?;
 
         Ok(())
     }
 
+    /// Get the total amount allocated to mints by the matches of a given
+    /// [GnosisSafeDeposit]. The deposit is fully covered once this reaches its
+    /// amount.
+    pub fn allocated_to_deposit(deposit: &GnosisSafeDeposit, conn: &Conn) -> Result<u128, Error> {
+        Ok(Self::deposit_matches(deposit, conn)?
+            .iter()
+            .map(|reserve_match| u128::from(reserve_match.amount()))
+            .sum())
+    }
+
+    /// Get the amount of a [GnosisSafeDeposit] that is not allocated to mints
+    /// yet, or None if the deposit is already fully matched.
+    pub(crate) fn unallocated_deposit_amount(
+        deposit: &GnosisSafeDeposit,
+        conn: &Conn,
+    ) -> Result<Option<u128>, Error> {
+        let matches = Self::deposit_matches(deposit, conn)?;
+        let allocated_amount: u128 = matches
+            .iter()
+            .map(|reserve_match| u128::from(reserve_match.amount()))
+            .sum();
+
+        Ok((matches.is_empty() || allocated_amount < deposit.amount())
+            .then(|| deposit.amount() - allocated_amount))
+    }
+
+    fn deposit_matches(
+        deposit: &GnosisSafeDeposit,
+        conn: &Conn,
+    ) -> Result<Vec<ReserveMatch>, Error> {
+        ReserveMatch::list_for_deposit(
+            GnosisReserveBackend::NAME,
+            &deposit.eth_tx_hash().to_string(),
+            conn,
+        )
+    }
+
+    /// Get all audited mints whose [MintTx] appeared in a given block.
+    pub fn get_by_block_index(block_index: u64, conn: &Conn) -> Result<Vec<Self>, Error> {
+        let rows: Vec<(ReserveMatch, MintTx, GnosisSafeDeposit)> = reserve_matches::table
+            .inner_join(mint_txs::table)
+            .inner_join(
+                gnosis_safe_deposits::table
+                    .on(gnosis_safe_deposits::eth_tx_hash.eq(reserve_matches::reference)),
+            )
+            .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+            .filter(mint_txs::block_index.eq(block_index as i64))
+            .order_by(reserve_matches::id)
+            .select((
+                reserve_matches::all_columns,
+                mint_txs::all_columns,
+                gnosis_safe_deposits::all_columns,
+            ))
+            .load(conn)?;
+
+        Ok(rows
+            .iter()
+            .map(|(reserve_match, mint_tx, deposit)| {
+                Self::from_reserve_match(reserve_match, mint_tx, deposit)
+            })
+            .collect())
+    }
+
     /// Get paginated list of audited mints
     pub fn list_with_mint_and_deposit(
         offset: Option<u64>,
         limit: Option<u64>,
         conn: &Conn,
     ) -> Result<Vec<(AuditedMint, MintTx, GnosisSafeDeposit)>, Error> {
-        let mut query = audited_mints::table
-            .into_boxed()
+        let mut query = reserve_matches::table
             .inner_join(mint_txs::table)
-            .inner_join(gnosis_safe_deposits::table);
+            .inner_join(
+                gnosis_safe_deposits::table
+                    .on(gnosis_safe_deposits::eth_tx_hash.eq(reserve_matches::reference)),
+            )
+            .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+            .into_boxed();
 
         if let Some(o) = offset {
             query = query.offset(o as i64);
@@ -314,14 +493,42 @@ impl AuditedMint {
             query = query.limit(l as i64);
         }
 
-        Ok(query
-            .order_by(audited_mints::id)
+        let rows: Vec<(ReserveMatch, MintTx, GnosisSafeDeposit)> = query
+            .order_by(reserve_matches::id)
             .select((
-                audited_mints::all_columns,
+                reserve_matches::all_columns,
                 mint_txs::all_columns,
                 gnosis_safe_deposits::all_columns,
             ))
-            .load(conn)?)
+            .load(conn)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(reserve_match, mint_tx, deposit)| {
+                (
+                    Self::from_reserve_match(&reserve_match, &mint_tx, &deposit),
+                    mint_tx,
+                    deposit,
+                )
+            })
+            .collect())
+    }
+
+    fn from_reserve_match(
+        reserve_match: &ReserveMatch,
+        mint_tx: &MintTx,
+        deposit: &GnosisSafeDeposit,
+    ) -> Self {
+        Self {
+            id: reserve_match.id(),
+            mint_tx_id: mint_tx
+                .id()
+                .expect("got a MintTx without id but database auto-populates that field"),
+            gnosis_safe_deposit_id: deposit.id().expect(
+                "got a GnosisSafeDeposit without id but database auto-populates that field",
+            ),
+            amount: reserve_match.amount() as i64,
+        }
     }
 }
 
@@ -333,19 +540,23 @@ mod tests {
             models::AuditedMint,
             test_utils::{
                 create_gnosis_safe_deposit, insert_gnosis_deposit, insert_mint_tx_from_deposit,
-                test_gnosis_config, TestDbContext,
+                test_gnosis_config, TestDbContext, ETH_TOKEN_CONTRACT_ADDR,
+                GNOSIS_SAFE_DEPOSIT_FROM_ADDR, SAFE_ADDR,
             },
+            AuditEvent,
         },
-        gnosis::EthAddr,
+        gnosis::{EthAddr, EthTxHash, EthTxValue},
     };
+    use chrono::Utc;
     use mc_common::logger::{test_with_logger, Logger};
     use mc_transaction_core::TokenId;
     use mc_transaction_core_test_utils::{create_mint_config_tx_and_signers, create_mint_tx};
+    use mc_util_from_random::FromRandom;
     use std::str::FromStr;
 
-    fn assert_audited_mints_table_is_empty(conn: &Conn) {
-        let num_rows: i64 = audited_mints::table
-            .select(diesel::dsl::count(audited_mints::id))
+    fn assert_reserve_matches_table_is_empty(conn: &Conn) {
+        let num_rows: i64 = reserve_matches::table
+            .select(diesel::dsl::count(reserve_matches::id))
             .first(conn)
             .unwrap();
         assert_eq!(num_rows, 0);
@@ -375,12 +586,12 @@ mod tests {
             AuditedMint::try_match_deposit_with_mint(&deposit2, config, &conn),
             Err(Error::NotFound)
         ));
-        assert_audited_mints_table_is_empty(&conn);
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Insert the first MintTx to the database, we should get a match now.
         let sql_mint_tx1 = insert_mint_tx_from_deposit(&deposit1, &conn, &mut rng);
         assert_eq!(
-            sql_mint_tx1,
+            vec![sql_mint_tx1],
             AuditedMint::try_match_deposit_with_mint(&deposit1, config, &conn).unwrap()
         );
         assert!(matches!(
@@ -395,7 +606,7 @@ mod tests {
             Err(Error::AlreadyExists(_))
         ));
         assert_eq!(
-            sql_mint_tx2,
+            vec![sql_mint_tx2],
             AuditedMint::try_match_deposit_with_mint(&deposit2, config, &conn).unwrap()
         );
 
@@ -429,7 +640,12 @@ mod tests {
 
         // Create MintTxs with a mismatching amount.
         let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id1, &mut rng);
-        let mut mint_tx = create_mint_tx(token_id1, &signers, deposit.amount() + 1, &mut rng);
+        let mut mint_tx = create_mint_tx(
+            token_id1,
+            &signers,
+            deposit.mc_amount().unwrap() + 1,
+            &mut rng,
+        );
 
         mint_tx.prefix.nonce = hex::decode(deposit.expected_mc_mint_tx_nonce_hex()).unwrap();
 
@@ -441,8 +657,8 @@ mod tests {
             Err(Error::DepositAndMintMismatch(_))
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Mismatch counter was incremented
         assert_eq!(
@@ -453,6 +669,215 @@ mod tests {
         );
     }
 
+    #[test_with_logger]
+    fn test_try_match_deposit_split_across_mints(logger: Logger) {
+        let config = test_gnosis_config();
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let token_id1 = config.safes[0].tokens[0].token_id;
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        // Create a gnosis deposit that gets minted in three parts.
+        let mut deposit = create_gnosis_safe_deposit(300, &mut rng);
+        insert_gnosis_deposit(&mut deposit, &conn);
+
+        let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        let mut insert_part = |part_index: u32, amount: u64| {
+            let mut mint_tx = create_mint_tx(token_id1, &signers, amount, &mut rng);
+            mint_tx.prefix.nonce =
+                MintTxNonce::EthereumGnosisDepositPart(*deposit.eth_tx_hash(), part_index)
+                    .to_bytes()
+                    .to_vec();
+            MintTx::insert_from_core_mint_tx(0, None, None, &mint_tx, &conn).unwrap()
+        };
+
+        // The first two parts get matched when the deposit is processed.
+        let sql_mint_tx1 = insert_part(0, 100);
+        let sql_mint_tx2 = insert_part(1, 150);
+        assert_eq!(
+            AuditedMint::try_match_deposit_with_mint(&deposit, &config.safes[0], &conn).unwrap(),
+            vec![sql_mint_tx1, sql_mint_tx2]
+        );
+        assert_eq!(
+            AuditedMint::allocated_to_deposit(&deposit, &conn).unwrap(),
+            250
+        );
+
+        // The deposit is only partially covered so far.
+        assert_eq!(
+            GnosisSafeDeposit::find_unaudited_deposits(&conn).unwrap(),
+            vec![deposit.clone()]
+        );
+        assert!(matches!(
+            AuditedMint::try_match_deposit_with_mint(&deposit, &config.safes[0], &conn),
+            Err(Error::NotFound)
+        ));
+
+        // A part exceeding what is left of the deposit is a mismatch.
+        let sql_mint_tx3 = insert_part(2, 60);
+        assert!(matches!(
+            AuditedMint::try_match_mint_with_deposit(&sql_mint_tx3, &config, &conn),
+            Err(Error::DepositAndMintMismatch(_))
+        ));
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_mismatching_mints_and_deposits(),
+            1
+        );
+
+        // The last part covers the rest of the deposit.
+        let sql_mint_tx4 = insert_part(3, 50);
+        assert_eq!(
+            AuditedMint::try_match_mint_with_deposit(&sql_mint_tx4, &config, &conn).unwrap(),
+            deposit
+        );
+        assert_eq!(
+            AuditedMint::allocated_to_deposit(&deposit, &conn).unwrap(),
+            deposit.amount()
+        );
+        assert_eq!(
+            MintTx::matched_amount(sql_mint_tx4.id().unwrap(), &conn).unwrap(),
+            Some(50)
+        );
+        assert!(GnosisSafeDeposit::find_unaudited_deposits(&conn)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            MintTx::find_unaudited_mint_txs(&conn).unwrap(),
+            vec![sql_mint_tx3]
+        );
+
+        // Nothing is left to allocate.
+        assert!(matches!(
+            AuditedMint::try_match_deposit_with_mint(&deposit, &config.safes[0], &conn),
+            Err(Error::AlreadyExists(_))
+        ));
+    }
+
+    #[test_with_logger]
+    fn test_try_match_deposit_skips_oversized_part(logger: Logger) {
+        let config = &test_gnosis_config().safes[0];
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let token_id1 = config.tokens[0].token_id;
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let mut deposit = create_gnosis_safe_deposit(300, &mut rng);
+        insert_gnosis_deposit(&mut deposit, &conn);
+
+        let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        let mut insert_part = |part_index: u32, amount: u64| {
+            let mut mint_tx = create_mint_tx(token_id1, &signers, amount, &mut rng);
+            mint_tx.prefix.nonce =
+                MintTxNonce::EthereumGnosisDepositPart(*deposit.eth_tx_hash(), part_index)
+                    .to_bytes()
+                    .to_vec();
+            MintTx::insert_from_core_mint_tx(0, None, None, &mint_tx, &conn).unwrap()
+        };
+
+        // The first part exceeds the deposit, the second one fits in it.
+        let sql_mint_tx1 = insert_part(0, 400);
+        let sql_mint_tx2 = insert_part(1, 100);
+
+        // Only the second part gets matched, the first one is flagged.
+        assert_eq!(
+            AuditedMint::try_match_deposit_with_mint(&deposit, config, &conn).unwrap(),
+            vec![sql_mint_tx2.clone()]
+        );
+        assert_eq!(
+            AuditedMint::allocated_to_deposit(&deposit, &conn).unwrap(),
+            100
+        );
+        assert_eq!(
+            MintTx::matched_amount(sql_mint_tx1.id().unwrap(), &conn).unwrap(),
+            None
+        );
+        assert_eq!(
+            MintTx::matched_amount(sql_mint_tx2.id().unwrap(), &conn).unwrap(),
+            Some(100)
+        );
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_mismatching_mints_and_deposits(),
+            1
+        );
+
+        let events = AuditEvent::list(None, None, false, &conn).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].mint_tx_nonce_hex(),
+            Some(sql_mint_tx1.nonce_hex())
+        );
+    }
+
+    #[test_with_logger]
+    fn test_try_match_deposit_on_other_chain(logger: Logger) {
+        let mut config = test_gnosis_config();
+        config.safes[0].chain_id = 10;
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let token_id1 = config.safes[0].tokens[0].token_id;
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        // Create a gnosis deposit on a chain other than Ethereum mainnet.
+        let eth_tx_hash = EthTxHash::from_random(&mut rng);
+        let mut deposit = GnosisSafeDeposit::new(
+            None,
+            10,
+            eth_tx_hash,
+            Utc::now(),
+            EthTxValue::from_str("100000000000000").unwrap(),
+            1,
+            EthAddr::from_str(SAFE_ADDR).unwrap(),
+            EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
+            EthAddr::from_str(GNOSIS_SAFE_DEPOSIT_FROM_ADDR).unwrap(),
+            100,
+            0,
+        );
+        insert_gnosis_deposit(&mut deposit, &conn);
+        assert_eq!(
+            deposit.expected_mc_mint_tx_nonce_hex(),
+            hex::encode(MintTxNonce::EvmDeposit(10, eth_tx_hash).to_bytes())
+        );
+
+        let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        let mut insert_mint_tx = |nonce: MintTxNonce| {
+            let mut mint_tx =
+                create_mint_tx(token_id1, &signers, deposit.mc_amount().unwrap(), &mut rng);
+            mint_tx.prefix.nonce = nonce.to_bytes().to_vec();
+            MintTx::insert_from_core_mint_tx(0, None, None, &mint_tx, &conn).unwrap()
+        };
+
+        // A mint referencing the same transaction hash on Ethereum mainnet does
+        // not match the deposit.
+        let sql_mint_tx1 = insert_mint_tx(MintTxNonce::EthereumGnosisDeposit(eth_tx_hash));
+        assert!(matches!(
+            AuditedMint::try_match_mint_with_deposit(&sql_mint_tx1, &config, &conn),
+            Err(Error::NotFound)
+        ));
+        assert!(matches!(
+            AuditedMint::try_match_deposit_with_mint(&deposit, &config.safes[0], &conn),
+            Err(Error::NotFound)
+        ));
+        assert_reserve_matches_table_is_empty(&conn);
+
+        // A mint referencing the deposit on its own chain matches.
+        let sql_mint_tx2 = insert_mint_tx(MintTxNonce::EvmDeposit(10, eth_tx_hash));
+        assert_eq!(
+            AuditedMint::try_match_mint_with_deposit(&sql_mint_tx2, &config, &conn).unwrap(),
+            deposit
+        );
+        assert_eq!(
+            MintTx::find_unaudited_mint_txs(&conn).unwrap(),
+            vec![sql_mint_tx1]
+        );
+    }
+
     #[test_with_logger]
     fn test_try_match_deposit_with_mint_unsaved_object(logger: Logger) {
         let config = &test_gnosis_config().safes[0];
@@ -468,8 +893,8 @@ mod tests {
             Err(Error::ObjectNotSaved)
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -497,8 +922,8 @@ mod tests {
             Err(Error::Other(_))
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -527,8 +952,8 @@ mod tests {
             Err(Error::DepositAndMintMismatch(_))
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Mismatch counter was incremented
         assert_eq!(
@@ -559,8 +984,8 @@ mod tests {
             Err(Error::EthereumTokenNotAudited(_, _, _))
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -595,7 +1020,7 @@ mod tests {
             AuditedMint::try_match_mint_with_deposit(&sql_mint_tx2, &config, &conn),
             Err(Error::NotFound)
         ));
-        assert_audited_mints_table_is_empty(&conn);
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Insert the first deposit to the database, we should get a match now.
         insert_gnosis_deposit(&mut deposit1, &conn);
@@ -651,7 +1076,12 @@ mod tests {
 
         // Create  MintTxs with a mismatching amount.
         let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id1, &mut rng);
-        let mut mint_tx = create_mint_tx(token_id1, &signers, deposit.amount() + 1, &mut rng);
+        let mut mint_tx = create_mint_tx(
+            token_id1,
+            &signers,
+            deposit.mc_amount().unwrap() + 1,
+            &mut rng,
+        );
 
         mint_tx.prefix.nonce = hex::decode(deposit.expected_mc_mint_tx_nonce_hex()).unwrap();
 
@@ -663,8 +1093,8 @@ mod tests {
             Err(Error::DepositAndMintMismatch(_))
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Mismatch counter was incremented
         assert_eq!(
@@ -693,8 +1123,8 @@ mod tests {
             Err(Error::ObjectNotSaved)
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -724,8 +1154,8 @@ mod tests {
             Err(Error::GnosisSafeNotAudited(_))
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(Counters::get(&conn).unwrap().num_mints_to_unknown_safe(), 1);
     }
@@ -749,8 +1179,8 @@ mod tests {
             Err(Error::DepositAndMintMismatch(_))
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         // Mismatch counter was incremented
         assert_eq!(
@@ -781,8 +1211,8 @@ mod tests {
             Err(Error::EthereumTokenNotAudited(_, _, _))
         ));
 
-        // Check that nothing was written to the `audited_mints` table
-        assert_audited_mints_table_is_empty(&conn);
+        // Check that nothing was written to the `reserve_matches` table
+        assert_reserve_matches_table_is_empty(&conn);
 
         assert_eq!(
             Counters::get(&conn)
@@ -819,6 +1249,12 @@ mod tests {
         assert_eq!(*mint_tx, mints[0]);
         assert_eq!(deposit.eth_tx_hash(), deposits[0].eth_tx_hash());
 
+        // All the mint txs were created at block 0.
+        assert_eq!(AuditedMint::get_by_block_index(0, &conn).unwrap().len(), 10);
+        assert!(AuditedMint::get_by_block_index(1, &conn)
+            .unwrap()
+            .is_empty());
+
         let paginated_mints =
             AuditedMint::list_with_mint_and_deposit(Some(4), Some(3), &conn).unwrap();
         assert_eq!(paginated_mints.len(), 3);
@@ -0,0 +1,240 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    anomaly::{AnomalyEvent, AnomalyKind},
+    db::{
+        schema::{burn_tx_outs, reserve_matches},
+        transaction, BurnTxOut, Conn, Counters, ReserveMatch, ReserveWithdrawal,
+    },
+    Error,
+};
+use diesel::prelude::*;
+
+/// Matching of BurnTxOuts with ReserveWithdrawals. Matches are stored in the
+/// `reserve_matches` table, keyed by the source of the withdrawal and its
+/// external reference.
+pub struct AuditedReserveBurn;
+
+impl AuditedReserveBurn {
+    /// Attempt to find a matching [BurnTxOut] for a given
+    /// [ReserveWithdrawal], and if successful return the [BurnTxOut] and
+    /// record the match in the database. Note that each [BurnTxOut] can be
+    /// matched to at most one withdrawal, so calling this repeatedly over the
+    /// same withdrawal will fail.
+    pub fn try_match_withdrawal_with_burn(
+        withdrawal: &ReserveWithdrawal,
+        conn: &Conn,
+    ) -> Result<BurnTxOut, Error> {
+        // Wrapped in a closure to allow using the ? operator without returning from the
+        // function.
+        let result = || -> Result<BurnTxOut, Error> {
+            // We only operate on objects that were saved to the database.
+            withdrawal.id().ok_or(Error::ObjectNotSaved)?;
+
+            transaction(conn, |conn| {
+                // Only 1:1 mapping between withdrawals and burns is supported, so ensure that
+                // there isn't already a match for this withdrawal.
+                let existing_match: Option<String> = reserve_matches::table
+                    .inner_join(burn_tx_outs::table)
+                    .select(burn_tx_outs::public_key_hex)
+                    .filter(reserve_matches::backend.eq(withdrawal.source()))
+                    .filter(reserve_matches::reference.eq(withdrawal.external_ref()))
+                    .first(conn)
+                    .optional()?;
+                if let Some(public_key_hex) = existing_match {
+                    return Err(Error::AlreadyExists(format!(
+                        "ReserveWithdrawal ref={} already matched with BurnTxOut pub_key={public_key_hex}",
+                        withdrawal.external_ref(),
+                    )));
+                }
+
+                // See if we can find a BurnTxOut that matches the public key and has not been
+                // associated with a withdrawal.
+                let burn_tx_out = BurnTxOut::find_unaudited_burn_tx_out_by_public_key(
+                    withdrawal.mc_tx_out_public_key_hex(),
+                    conn,
+                )?
+                .ok_or(Error::NotFound)?;
+
+                // Check that the burn and withdrawal details match.
+                Self::verify_burn_tx_out_matches_withdrawal(&burn_tx_out, withdrawal)?;
+
+                Self::associate_withdrawal_with_burn(
+                    withdrawal,
+                    burn_tx_out.id().expect(
+                        "got a BurnTxOut without id but database auto-populates that field",
+                    ),
+                    burn_tx_out.amount(),
+                    conn,
+                )?;
+
+                Ok(burn_tx_out)
+            })
+        }();
+
+        // Count certain errors. This needs to happen outside of the transaction because
+        // errors result in the transaction getting rolled back.
+        match &result {
+            Ok(_) => {}
+
+            Err(err @ Error::WithdrawalAndBurnMismatch(_)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::MismatchingBurnAndWithdrawal, err)
+                        .with_reserve_withdrawal(withdrawal),
+                    conn,
+                )?;
+            }
+
+            // The other side has not been synced yet, or was already matched. Neither is
+            // an anomaly.
+            Err(Error::NotFound) | Err(Error::AlreadyExists(_)) => {}
+
+            Err(err) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnexpectedErrorMatchingWithdrawalToBurn, err)
+                        .with_reserve_withdrawal(withdrawal),
+                    conn,
+                )?;
+            }
+        }
+
+        result
+    }
+
+    /// Attempt to find a matching [ReserveWithdrawal] reported by a given
+    /// source for a given [BurnTxOut], and if successful return the
+    /// [ReserveWithdrawal] and record the match in the database.
+    pub fn try_match_burn_with_withdrawal(
+        burn_tx_out: &BurnTxOut,
+        source: &str,
+        conn: &Conn,
+    ) -> Result<ReserveWithdrawal, Error> {
+        // Wrapped in a closure to allow using the ? operator without returning from the
+        // function.
+        let result = || -> Result<ReserveWithdrawal, Error> {
+            // We only operate on objects that were saved to the database.
+            let burn_tx_out_id = burn_tx_out.id().ok_or(Error::ObjectNotSaved)?;
+
+            transaction(conn, |conn| -> Result<ReserveWithdrawal, Error> {
+                // Only 1:1 mapping between withdrawals and burns is supported, so ensure that
+                // there isn't already a match for this burn.
+                if let Some(existing_match) =
+                    ReserveMatch::list_for_burn_tx_out(burn_tx_out_id, conn)?.first()
+                {
+                    return Err(Error::AlreadyExists(format!(
+                        "BurnTxOut pub_key={} already matched with {} withdrawal ref={}",
+                        burn_tx_out.public_key_hex(),
+                        existing_match.backend(),
+                        existing_match.reference(),
+                    )));
+                }
+
+                let withdrawal = ReserveWithdrawal::find_unaudited_withdrawal_by_public_key(
+                    source,
+                    burn_tx_out.public_key_hex(),
+                    conn,
+                )?
+                .ok_or(Error::NotFound)?;
+
+                Self::verify_burn_tx_out_matches_withdrawal(burn_tx_out, &withdrawal)?;
+
+                Self::associate_withdrawal_with_burn(
+                    &withdrawal,
+                    burn_tx_out_id,
+                    burn_tx_out.amount(),
+                    conn,
+                )?;
+
+                Ok(withdrawal)
+            })
+        }();
+
+        // Count certain errors. This needs to happen outside of the transaction because
+        // errors result in the transaction getting rolled back.
+        match &result {
+            Ok(_) => {}
+
+            Err(err @ Error::WithdrawalAndBurnMismatch(_)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::MismatchingBurnAndWithdrawal, err)
+                        .with_burn_tx_out(burn_tx_out),
+                    conn,
+                )?;
+            }
+
+            // The other side has not been synced yet, or was already matched. Neither is
+            // an anomaly.
+            Err(Error::NotFound) | Err(Error::AlreadyExists(_)) => {}
+
+            Err(err) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnexpectedErrorMatchingBurnToWithdrawal, err)
+                        .with_burn_tx_out(burn_tx_out),
+                    conn,
+                )?;
+            }
+        }
+
+        result
+    }
+
+    /// Verify that the details of a BurnTxOut match the details of a
+    /// ReserveWithdrawal (amount/public key/token).
+    fn verify_burn_tx_out_matches_withdrawal(
+        burn_tx_out: &BurnTxOut,
+        withdrawal: &ReserveWithdrawal,
+    ) -> Result<(), Error> {
+        if withdrawal.token_id() != burn_tx_out.token_id() {
+            return Err(Error::WithdrawalAndBurnMismatch(format!(
+                "BurnTxOut token_id={} does not match ReserveWithdrawal token_id={} (pubkey={})",
+                burn_tx_out.token_id(),
+                withdrawal.token_id(),
+                withdrawal.mc_tx_out_public_key_hex(),
+            )));
+        }
+
+        if burn_tx_out.public_key_hex() != withdrawal.mc_tx_out_public_key_hex() {
+            return Err(Error::WithdrawalAndBurnMismatch(format!(
+                "BurnTxOut pubkey {} does not match expected pubkey {}",
+                burn_tx_out.public_key_hex(),
+                withdrawal.mc_tx_out_public_key_hex()
+            )));
+        }
+
+        if u128::from(burn_tx_out.amount()) != withdrawal.amount() {
+            return Err(Error::WithdrawalAndBurnMismatch(format!(
+                "BurnTxOut amount={} does not match ReserveWithdrawal amount={} (pubkey={})",
+                burn_tx_out.amount(),
+                withdrawal.amount(),
+                withdrawal.mc_tx_out_public_key_hex(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn associate_withdrawal_with_burn(
+        withdrawal: &ReserveWithdrawal,
+        burn_tx_out_id: i32,
+        amount: u64,
+        conn: &Conn,
+    ) -> Result<(), Error> {
+        // Matches always cover the whole burn, so it cannot already be matched, even
+        // partially, by another reserve backend.
+        if BurnTxOut::matched_amount(burn_tx_out_id, conn)?.is_some() {
+            return Err(Error::AlreadyExists(format!(
+                "BurnTxOut id={burn_tx_out_id} already matched by another reserve backend",
+            )));
+        }
+
+        ReserveMatch::insert_for_burn_tx_out(
+            withdrawal.source(),
+            withdrawal.external_ref(),
+            burn_tx_out_id,
+            amount,
+            conn,
+        )?;
+
+        Ok(())
+    }
+}
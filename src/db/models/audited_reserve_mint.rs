@@ -0,0 +1,321 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    anomaly::{AnomalyEvent, AnomalyKind},
+    db::{
+        schema::{mint_txs, reserve_matches},
+        transaction, Conn, Counters, MintTx, ReserveDeposit, ReserveMatch,
+    },
+    Error,
+};
+use diesel::prelude::*;
+
+/// Matching of MintTxs with ReserveDeposits. Matches are stored in the
+/// `reserve_matches` table, keyed by the source of the deposit and its
+/// external reference.
+pub struct AuditedReserveMint;
+
+impl AuditedReserveMint {
+    /// Attempt to find a matching [MintTx] for a given [ReserveDeposit], and
+    /// if successful return the [MintTx] and record the match in the
+    /// database. Note that each [MintTx] can be matched to at most one
+    /// deposit, so calling this repeatedly over the same deposit will fail.
+    pub fn try_match_deposit_with_mint(
+        deposit: &ReserveDeposit,
+        conn: &Conn,
+    ) -> Result<MintTx, Error> {
+        // Wrapped in a closure to allow using the ? operator without returning from the
+        // function.
+        let result = || -> Result<MintTx, Error> {
+            // We only operate on objects that were saved to the database.
+            deposit.id().ok_or(Error::ObjectNotSaved)?;
+
+            transaction(conn, |conn| {
+                // Only 1:1 mapping between deposits and mints is supported, so ensure that
+                // there isn't already a match for this deposit.
+                let existing_match: Option<String> = reserve_matches::table
+                    .inner_join(mint_txs::table)
+                    .select(mint_txs::nonce_hex)
+                    .filter(reserve_matches::backend.eq(deposit.source()))
+                    .filter(reserve_matches::reference.eq(deposit.external_ref()))
+                    .first(conn)
+                    .optional()?;
+                if let Some(nonce_hex) = existing_match {
+                    return Err(Error::AlreadyExists(format!(
+                        "ReserveDeposit ref={} already matched with mint_tx nonce={nonce_hex}",
+                        deposit.external_ref(),
+                    )));
+                }
+
+                // See if we can find a MintTx that matches the expected nonce and has not been
+                // associated with a deposit.
+                let mint_tx = MintTx::find_unaudited_mint_tx_by_nonce(
+                    deposit.expected_mc_mint_tx_nonce_hex(),
+                    conn,
+                )?
+                .ok_or(Error::NotFound)?;
+
+                // Check that the mint and deposit details match.
+                Self::verify_mint_tx_matches_deposit(&mint_tx, deposit)?;
+
+                Self::associate_deposit_with_mint(
+                    deposit,
+                    mint_tx
+                        .id()
+                        .expect("got a MintTx without id but database auto-populates that field"),
+                    mint_tx.amount(),
+                    conn,
+                )?;
+
+                Ok(mint_tx)
+            })
+        }();
+
+        // Count certain errors. This needs to happen outside of the transaction because
+        // errors result in the transaction getting rolled back.
+        match &result {
+            Ok(_) => {}
+
+            Err(err @ Error::DepositAndMintMismatch(_)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::MismatchingMintAndDeposit, err)
+                        .with_reserve_deposit(deposit),
+                    conn,
+                )?;
+            }
+
+            // The other side has not been synced yet, or was already matched. Neither is
+            // an anomaly.
+            Err(Error::NotFound) | Err(Error::AlreadyExists(_)) => {}
+
+            Err(err) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnexpectedErrorMatchingDepositToMint, err)
+                        .with_reserve_deposit(deposit),
+                    conn,
+                )?;
+            }
+        }
+
+        result
+    }
+
+    /// Attempt to find a matching [ReserveDeposit] reported by a given source
+    /// for a given [MintTx], and if successful return the [ReserveDeposit]
+    /// and record the match in the database.
+    pub fn try_match_mint_with_deposit(
+        mint_tx: &MintTx,
+        source: &str,
+        conn: &Conn,
+    ) -> Result<ReserveDeposit, Error> {
+        // Wrapped in a closure to allow using the ? operator without returning from the
+        // function.
+        let result = || -> Result<ReserveDeposit, Error> {
+            // We only operate on objects that were saved to the database.
+            let mint_tx_id = mint_tx.id().ok_or(Error::ObjectNotSaved)?;
+
+            transaction(conn, |conn| -> Result<ReserveDeposit, Error> {
+                // Only 1:1 mapping between deposits and mints is supported, so ensure that
+                // there isn't already a match for this mint.
+                if let Some(existing_match) =
+                    ReserveMatch::list_for_mint_tx(mint_tx_id, conn)?.first()
+                {
+                    return Err(Error::AlreadyExists(format!(
+                        "MintTx nonce={} already matched with {} deposit ref={}",
+                        mint_tx.nonce_hex(),
+                        existing_match.backend(),
+                        existing_match.reference(),
+                    )));
+                }
+
+                let deposit = ReserveDeposit::find_unaudited_deposit_by_nonce(
+                    source,
+                    mint_tx.nonce_hex(),
+                    conn,
+                )?
+                .ok_or(Error::NotFound)?;
+
+                Self::verify_mint_tx_matches_deposit(mint_tx, &deposit)?;
+
+                Self::associate_deposit_with_mint(&deposit, mint_tx_id, mint_tx.amount(), conn)?;
+
+                Ok(deposit)
+            })
+        }();
+
+        // Count certain errors. This needs to happen outside of the transaction because
+        // errors result in the transaction getting rolled back.
+        match &result {
+            Ok(_) => {}
+
+            Err(err @ Error::DepositAndMintMismatch(_)) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::MismatchingMintAndDeposit, err)
+                        .with_mint_tx(mint_tx),
+                    conn,
+                )?;
+            }
+
+            // The other side has not been synced yet, or was already matched. Neither is
+            // an anomaly.
+            Err(Error::NotFound) | Err(Error::AlreadyExists(_)) => {}
+
+            Err(err) => {
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::UnexpectedErrorMatchingMintToDeposit, err)
+                        .with_mint_tx(mint_tx),
+                    conn,
+                )?;
+            }
+        }
+
+        result
+    }
+
+    /// Verify that the details of a MintTx match the details of a
+    /// ReserveDeposit (amount/nonce/token).
+    fn verify_mint_tx_matches_deposit(
+        mint_tx: &MintTx,
+        deposit: &ReserveDeposit,
+    ) -> Result<(), Error> {
+        if deposit.token_id() != mint_tx.token_id() {
+            return Err(Error::DepositAndMintMismatch(format!(
+                "MintTx token_id={} does not match ReserveDeposit token_id={} (nonce={})",
+                mint_tx.token_id(),
+                deposit.token_id(),
+                deposit.expected_mc_mint_tx_nonce_hex(),
+            )));
+        }
+
+        if mint_tx.nonce_hex() != deposit.expected_mc_mint_tx_nonce_hex() {
+            return Err(Error::DepositAndMintMismatch(format!(
+                "MintTx nonce {} does not match expected nonce {}",
+                mint_tx.nonce_hex(),
+                deposit.expected_mc_mint_tx_nonce_hex()
+            )));
+        }
+
+        if u128::from(mint_tx.amount()) != deposit.amount() {
+            return Err(Error::DepositAndMintMismatch(format!(
+                "MintTx amount={} does not match ReserveDeposit amount={} (nonce={})",
+                mint_tx.amount(),
+                deposit.amount(),
+                deposit.expected_mc_mint_tx_nonce_hex(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn associate_deposit_with_mint(
+        deposit: &ReserveDeposit,
+        mint_tx_id: i32,
+        amount: u64,
+        conn: &Conn,
+    ) -> Result<(), Error> {
+        // Matches always cover the whole mint, so it cannot already be matched, even
+        // partially, by another reserve backend.
+        if MintTx::matched_amount(mint_tx_id, conn)?.is_some() {
+            return Err(Error::AlreadyExists(format!(
+                "MintTx id={mint_tx_id} already matched by another reserve backend",
+            )));
+        }
+
+        ReserveMatch::insert_for_mint_tx(
+            deposit.source(),
+            deposit.external_ref(),
+            mint_tx_id,
+            amount,
+            conn,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::TestDbContext;
+    use chrono::Utc;
+    use mc_common::logger::{test_with_logger, Logger};
+    use mc_transaction_core::TokenId;
+    use mc_transaction_core_test_utils::{create_mint_config_tx_and_signers, create_mint_tx};
+
+    #[test_with_logger]
+    fn test_try_match_reserve_deposits_and_mints(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id = TokenId::from(1);
+
+        let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let mint_tx1 = create_mint_tx(token_id, &signers, 100, &mut rng);
+        let mint_tx2 = create_mint_tx(token_id, &signers, 200, &mut rng);
+
+        let mut deposit1 = ReserveDeposit::new(
+            "bank",
+            "tx1",
+            token_id,
+            100,
+            &hex::encode(&mint_tx1.prefix.nonce),
+            None,
+        );
+        deposit1.insert(&conn).unwrap();
+
+        // The deposit for the second mint has the wrong amount.
+        let mut deposit2 = ReserveDeposit::new(
+            "bank",
+            "tx2",
+            token_id,
+            201,
+            &hex::encode(&mint_tx2.prefix.nonce),
+            None,
+        );
+        deposit2.insert(&conn).unwrap();
+
+        // Nothing to match against initially.
+        assert!(matches!(
+            AuditedReserveMint::try_match_deposit_with_mint(&deposit1, &conn),
+            Err(Error::NotFound)
+        ));
+
+        // Once the mint shows up it can be matched, but only with deposits of the
+        // same source.
+        let sql_mint_tx1 =
+            MintTx::insert_from_core_mint_tx(0, Some(Utc::now()), None, &mint_tx1, &conn).unwrap();
+        assert!(matches!(
+            AuditedReserveMint::try_match_mint_with_deposit(&sql_mint_tx1, "other", &conn),
+            Err(Error::NotFound)
+        ));
+        assert_eq!(
+            AuditedReserveMint::try_match_mint_with_deposit(&sql_mint_tx1, "bank", &conn).unwrap(),
+            deposit1
+        );
+        assert!(matches!(
+            AuditedReserveMint::try_match_deposit_with_mint(&deposit1, &conn),
+            Err(Error::AlreadyExists(_))
+        ));
+
+        // Matched mints are no longer reported as unaudited.
+        assert!(MintTx::find_unaudited_mint_txs(&conn).unwrap().is_empty());
+        assert!(ReserveDeposit::find_unaudited_deposits(Some("bank"), &conn)
+            .unwrap()
+            .iter()
+            .all(|deposit| deposit.external_ref() != "tx1"));
+
+        // Mismatching amounts are detected and counted.
+        MintTx::insert_from_core_mint_tx(0, Some(Utc::now()), None, &mint_tx2, &conn).unwrap();
+        assert!(matches!(
+            AuditedReserveMint::try_match_deposit_with_mint(&deposit2, &conn),
+            Err(Error::DepositAndMintMismatch(_))
+        ));
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_mismatching_mints_and_deposits(),
+            1
+        );
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     db::{schema::block_audit_data, Conn},
     Error,
 };
+use chrono::{DateTime, NaiveDateTime, Utc};
 use diesel::{dsl::max, prelude::*};
 use mc_blockchain_types::BlockIndex;
 use serde::{Deserialize, Serialize};
@@ -15,13 +16,17 @@ use serde::{Deserialize, Serialize};
 pub struct BlockAuditData {
     /// Block index.
     block_index: i64,
+
+    /// The block timestamp.
+    block_timestamp: Option<NaiveDateTime>,
 }
 
 impl BlockAuditData {
     /// Construct a new [BlockAuditData] object.
-    pub fn new(block_index: BlockIndex) -> Self {
+    pub fn new(block_index: BlockIndex, block_timestamp: Option<DateTime<Utc>>) -> Self {
         Self {
             block_index: block_index as i64,
+            block_timestamp: block_timestamp.map(|ts| ts.naive_utc()),
         }
     }
     /// Get block index.
@@ -29,10 +34,19 @@ impl BlockAuditData {
         self.block_index as u64
     }
 
+    /// Get block timestamp.
+    pub fn block_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.block_timestamp
+            .map(|ts| DateTime::from_naive_utc_and_offset(ts, Utc))
+    }
+
     /// Get block audit data for a given block index.
     pub fn get(conn: &Conn, block_index: BlockIndex) -> Result<Self, Error> {
         Ok(block_audit_data::table
-            .select((block_audit_data::block_index,))
+            .select((
+                block_audit_data::block_index,
+                block_audit_data::block_timestamp,
+            ))
             .filter(block_audit_data::block_index.eq(block_index as i64))
             .get_result(conn)?)
     }
@@ -46,6 +60,14 @@ impl BlockAuditData {
         Ok(())
     }
 
+    /// Delete the audit data of all blocks after a given block index.
+    pub fn delete_after_block(conn: &Conn, block_index: BlockIndex) -> Result<usize, Error> {
+        Ok(diesel::delete(
+            block_audit_data::table.filter(block_audit_data::block_index.gt(block_index as i64)),
+        )
+        .execute(conn)?)
+    }
+
     /// Get the last synced block index.
     pub fn last_synced_block_index(conn: &Conn) -> Result<Option<BlockIndex>, Error> {
         Ok(block_audit_data::table
@@ -60,12 +82,30 @@ impl BlockAuditData {
             .map(|block_index| Self::get(conn, block_index))
             .transpose()
     }
+
+    /// Get the audit data for the last synced block with a timestamp at or
+    /// before a given time. Blocks without a timestamp are ignored.
+    pub fn last_block_at_or_before(
+        conn: &Conn,
+        timestamp: DateTime<Utc>,
+    ) -> Result<Option<BlockAuditData>, Error> {
+        Ok(block_audit_data::table
+            .select((
+                block_audit_data::block_index,
+                block_audit_data::block_timestamp,
+            ))
+            .filter(block_audit_data::block_timestamp.le(timestamp.naive_utc()))
+            .order_by(block_audit_data::block_index.desc())
+            .first(conn)
+            .optional()?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::db::test_utils::TestDbContext;
+    use chrono::Duration;
     use mc_common::logger::{test_with_logger, Logger};
 
     #[test_with_logger]
@@ -79,7 +119,7 @@ mod tests {
             None
         );
 
-        BlockAuditData { block_index: 5 }
+        BlockAuditData::new(5, None)
             .set(&reserve_auditor_db.get_conn().unwrap())
             .unwrap();
         assert_eq!(
@@ -88,7 +128,7 @@ mod tests {
             Some(5)
         );
 
-        BlockAuditData { block_index: 3 }
+        BlockAuditData::new(3, None)
             .set(&reserve_auditor_db.get_conn().unwrap())
             .unwrap();
         assert_eq!(
@@ -97,7 +137,7 @@ mod tests {
             Some(5)
         );
 
-        BlockAuditData { block_index: 6 }
+        BlockAuditData::new(6, None)
             .set(&reserve_auditor_db.get_conn().unwrap())
             .unwrap();
         assert_eq!(
@@ -106,4 +146,57 @@ mod tests {
             Some(6)
         );
     }
+
+    #[test_with_logger]
+    fn get_returns_block_timestamp(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let block_timestamp = Utc::now();
+        let block_audit_data1 = BlockAuditData::new(1, Some(block_timestamp));
+        let block_audit_data2 = BlockAuditData::new(2, None);
+        block_audit_data1.set(&conn).unwrap();
+        block_audit_data2.set(&conn).unwrap();
+
+        let stored1 = BlockAuditData::get(&conn, 1).unwrap();
+        assert_eq!(stored1, block_audit_data1);
+        assert_eq!(stored1.block_timestamp(), Some(block_timestamp));
+
+        let stored2 = BlockAuditData::get(&conn, 2).unwrap();
+        assert_eq!(stored2, block_audit_data2);
+        assert_eq!(stored2.block_timestamp(), None);
+    }
+
+    #[test_with_logger]
+    fn last_block_at_or_before_works(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let now = Utc::now();
+        let block_audit_data1 = BlockAuditData::new(1, Some(now - Duration::hours(2)));
+        let block_audit_data2 = BlockAuditData::new(2, Some(now - Duration::hours(1)));
+        let block_audit_data3 = BlockAuditData::new(3, None);
+        block_audit_data1.set(&conn).unwrap();
+        block_audit_data2.set(&conn).unwrap();
+        block_audit_data3.set(&conn).unwrap();
+
+        assert_eq!(
+            BlockAuditData::last_block_at_or_before(&conn, now - Duration::hours(3)).unwrap(),
+            None
+        );
+        assert_eq!(
+            BlockAuditData::last_block_at_or_before(&conn, now - Duration::minutes(90)).unwrap(),
+            Some(block_audit_data1)
+        );
+        assert_eq!(
+            BlockAuditData::last_block_at_or_before(&conn, now - Duration::hours(1)).unwrap(),
+            Some(block_audit_data2.clone())
+        );
+        assert_eq!(
+            BlockAuditData::last_block_at_or_before(&conn, now).unwrap(),
+            Some(block_audit_data2)
+        );
+    }
 }
@@ -1,6 +1,9 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
-use super::super::{schema::block_balance, transaction, Conn, Error};
+use super::{
+    super::{schema::block_balance, transaction, Conn, Error},
+    SqlU128,
+};
 use diesel::prelude::*;
 use mc_blockchain_types::BlockIndex;
 use mc_common::HashMap;
@@ -19,17 +22,18 @@ pub struct BlockBalance {
     /// Token id.
     token_id: i64,
 
-    /// Balanace.
-    balance: i64,
+    /// Balanace. This is the sum of all mints minus all burns, which is not
+    /// bounded to u64.
+    balance: SqlU128,
 }
 
 impl BlockBalance {
     /// Construct a new [BlockBalance] object.
-    pub fn new(block_index: BlockIndex, token_id: TokenId, balance: u64) -> Self {
+    pub fn new(block_index: BlockIndex, token_id: TokenId, balance: u128) -> Self {
         Self {
             block_index: block_index as i64,
             token_id: *token_id as i64,
-            balance: balance as i64,
+            balance: balance.into(),
         }
     }
 
@@ -44,15 +48,15 @@ impl BlockBalance {
     }
 
     /// Get balance.
-    pub fn balance(&self) -> u64 {
-        self.balance as u64
+    pub fn balance(&self) -> u128 {
+        *self.balance
     }
 
     /// Get a map of TokenId -> balance for a given block id.
     pub fn get_balances_for_block(
         conn: &Conn,
         block_index: BlockIndex,
-    ) -> Result<HashMap<TokenId, u64>, Error> {
+    ) -> Result<HashMap<TokenId, u128>, Error> {
         let query = block_balance::table
             .filter(block_balance::columns::block_index.eq(block_index as i64))
             .select((
@@ -60,11 +64,11 @@ impl BlockBalance {
                 block_balance::columns::balance,
             ));
 
-        let rows = query.load::<(i64, i64)>(conn)?;
+        let rows = query.load::<(i64, SqlU128)>(conn)?;
 
         Ok(rows
             .iter()
-            .map(|(token_id, balance)| (TokenId::from(*token_id as u64), *balance as u64))
+            .map(|(token_id, balance)| (TokenId::from(*token_id as u64), **balance))
             .collect())
     }
 
@@ -72,13 +76,13 @@ impl BlockBalance {
     pub fn set_balances_for_block(
         conn: &Conn,
         block_index: BlockIndex,
-        balances: &HashMap<TokenId, u64>,
+        balances: &HashMap<TokenId, u128>,
     ) -> Result<(), Error> {
         transaction(conn, |conn| {
             for block_balance in balances.iter().map(|(token_id, balance)| Self {
                 block_index: block_index as i64,
                 token_id: *token_id.deref() as i64,
-                balance: *balance as i64,
+                balance: (*balance).into(),
             }) {
                 diesel::insert_into(block_balance::table)
                     .values(&block_balance)
@@ -87,6 +91,14 @@ impl BlockBalance {
             Ok(())
         })
     }
+
+    /// Delete the balances of all blocks after a given block index.
+    pub fn delete_after_block(conn: &Conn, block_index: BlockIndex) -> Result<usize, Error> {
+        Ok(diesel::delete(
+            block_balance::table.filter(block_balance::block_index.gt(block_index as i64)),
+        )
+        .execute(conn)?)
+    }
 }
 
 #[cfg(test)]
@@ -100,10 +112,10 @@ mod tests {
         let test_db_context = TestDbContext::default();
         let reserve_auditor_db = test_db_context.get_db_instance(logger);
 
-        BlockAuditData::new(0)
+        BlockAuditData::new(0, None)
             .set(&reserve_auditor_db.get_conn().unwrap())
             .unwrap();
-        BlockAuditData::new(1)
+        BlockAuditData::new(1, None)
             .set(&reserve_auditor_db.get_conn().unwrap())
             .unwrap();
 
@@ -115,7 +127,8 @@ mod tests {
         let expected_balances = HashMap::from_iter(vec![
             (TokenId::from(1), 10),
             (TokenId::from(2), 20),
-            (TokenId::from(3), <u64>::MAX - 1),
+            (TokenId::from(3), <u64>::MAX as u128 - 1),
+            (TokenId::from(4), <u128>::MAX - 1),
         ]);
         BlockBalance::set_balances_for_block(
             &reserve_auditor_db.get_conn().unwrap(),
@@ -137,7 +150,7 @@ mod tests {
                 let expected_balances2 = HashMap::from_iter(vec![
                     (TokenId::from(1), 10),
                     (TokenId::from(2), 20),
-                    (TokenId::from(<u64>::MAX - 30), <u64>::MAX - 123),
+                    (TokenId::from(<u64>::MAX - 30), <u64>::MAX as u128 - 123),
                 ]);
                 BlockBalance::set_balances_for_block(conn, 1, &expected_balances2).unwrap();
 
@@ -5,16 +5,13 @@
 use crate::{
     db::{
         last_insert_rowid,
-        schema::{audited_burns, burn_tx_outs},
+        schema::{burn_tx_outs, reserve_matches},
         Conn,
     },
     Error,
 };
 use chrono::{DateTime, NaiveDateTime, Utc};
-use diesel::{
-    dsl::{exists, not},
-    prelude::*,
-};
+use diesel::{dsl::sql, expression::SqlLiteral, prelude::*, sql_types::Bool};
 use hex::ToHex;
 use mc_account_keys::burn_address_view_private;
 use mc_blockchain_types::BlockIndex;
@@ -84,6 +81,11 @@ impl BurnTxOut {
         &self.public_key_hex
     }
 
+    /// Get the protobuf-serialized BurnTxOut.
+    pub fn protobuf(&self) -> &[u8] {
+        &self.protobuf
+    }
+
     /// Get the original BurnTxOut
     pub fn decode(&self) -> Result<TxOut, Error> {
         Ok(decode(&self.protobuf)?)
@@ -153,43 +155,48 @@ impl BurnTxOut {
         }
     }
 
-    /// Attempt to find all [BurnTxOut]s that do not have a matching entry in
-    /// the `audited_burn` table.
+    /// Attempt to find all [BurnTxOut]s that are not fully matched by any
+    /// reserve backend.
     pub fn find_unaudited_burn_tx_outs(conn: &Conn) -> Result<Vec<Self>, Error> {
         Ok(burn_tx_outs::table
-            .filter(not(exists(
-                audited_burns::table
-                    .select(audited_burns::burn_tx_out_id)
-                    .filter(
-                        audited_burns::burn_tx_out_id
-                            .nullable()
-                            .eq(burn_tx_outs::id),
-                    ),
-            )))
+            .filter(Self::not_fully_matched())
             .load(conn)?)
     }
 
-    /// Attempt to find a [BurnTxOut] that has a given public key and no
-    /// matching entry in the `audited_burns` table.
+    /// Attempt to find a [BurnTxOut] that has a given public key and that is
+    /// not fully matched by any reserve backend.
     pub fn find_unaudited_burn_tx_out_by_public_key(
         public_key_hex: &str,
         conn: &Conn,
     ) -> Result<Option<Self>, Error> {
         Ok(burn_tx_outs::table
             .filter(burn_tx_outs::public_key_hex.eq(public_key_hex))
-            .filter(not(exists(
-                audited_burns::table
-                    .select(audited_burns::burn_tx_out_id)
-                    .filter(
-                        audited_burns::burn_tx_out_id
-                            .nullable()
-                            .eq(burn_tx_outs::id),
-                    ),
-            )))
+            .filter(Self::not_fully_matched())
             .first(conn)
             .optional()?)
     }
 
+    /// Get the total amount of a [BurnTxOut] that is matched with
+    /// withdrawals, by any reserve backend, or None if it is not matched at
+    /// all. The burn is fully matched once this reaches its amount.
+    pub fn matched_amount(burn_tx_out_id: i32, conn: &Conn) -> Result<Option<u64>, Error> {
+        let amounts: Vec<i64> = reserve_matches::table
+            .filter(reserve_matches::burn_tx_out_id.eq(burn_tx_out_id))
+            .select(reserve_matches::amount)
+            .load(conn)?;
+        Ok((!amounts.is_empty()).then(|| amounts.into_iter().map(|val| val as u64).sum()))
+    }
+
+    /// Filter for burns that are not fully matched by the entries of the
+    /// `reserve_matches` table. Several withdrawals can be batched into a
+    /// single burn, so this sums the allocations of all of its matches.
+    fn not_fully_matched() -> SqlLiteral<Bool> {
+        sql("(NOT EXISTS (SELECT 1 FROM reserve_matches \
+             WHERE reserve_matches.burn_tx_out_id = burn_tx_outs.id) \
+             OR (SELECT SUM(reserve_matches.amount) FROM reserve_matches \
+             WHERE reserve_matches.burn_tx_out_id = burn_tx_outs.id) < burn_tx_outs.amount)")
+    }
+
     /// Get a collection of burn token amounts
     pub fn get_burn_amounts(conn: &Conn, token_id: TokenId) -> Result<Vec<(TokenId, u64)>, Error> {
         let query = burn_tx_outs::table
@@ -215,6 +222,15 @@ impl BurnTxOut {
             .load(conn)?)
     }
 
+    /// Delete all [BurnTxOut]s that were included in blocks after a given
+    /// block index. Returns the number of deleted rows.
+    pub fn delete_after_block(block_index: BlockIndex, conn: &Conn) -> Result<usize, Error> {
+        Ok(diesel::delete(
+            burn_tx_outs::table.filter(burn_tx_outs::block_index.gt(block_index as i64)),
+        )
+        .execute(conn)?)
+    }
+
     /// A helper method to get a copy of this object with the id field set to
     /// None, used in tests.
     #[allow(dead_code)]
@@ -346,12 +362,13 @@ mod tests {
             burn_tx_out2
         );
 
-        // Insert a row to the `audited_burns` table marking the first BurnTxOut as
+        // Insert a row to the `reserve_matches` table marking the first BurnTxOut as
         // audited. We should no longer be able to find it.
         insert_gnosis_withdrawal(&mut withdrawal1, &conn);
         AuditedBurn::associate_withdrawal_with_burn(
-            withdrawal1.id().unwrap(),
+            &withdrawal1,
             burn_tx_out1.id().unwrap(),
+            burn_tx_out1.amount(),
             &conn,
         )
         .unwrap();
@@ -376,8 +393,9 @@ mod tests {
         // Mark the second burn as audited. We should no longer be able to find it.
         insert_gnosis_withdrawal(&mut withdrawal2, &conn);
         AuditedBurn::associate_withdrawal_with_burn(
-            withdrawal2.id().unwrap(),
+            &withdrawal2,
             burn_tx_out2.id().unwrap(),
+            burn_tx_out2.amount(),
             &conn,
         )
         .unwrap();
@@ -1,8 +1,9 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
 use crate::{
+    anomaly::{AnomalyEvent, AnomalyKind},
     counters as prom_counters,
-    db::{schema::counters, transaction, Conn},
+    db::{schema::counters, transaction, AuditEvent, Conn, OutboxNotification},
     Error,
 };
 use diesel::prelude::*;
@@ -67,6 +68,45 @@ pub struct Counters {
 
     // Number of unexpected errors attempting to match burns to withdrawals.
     num_unexpected_errors_matching_burns_to_withdrawals: i64,
+
+    /// Number of mint transactions with a nonce of an unknown type.
+    num_mint_txs_with_unknown_nonce: i64,
+
+    /// Number of deposits and withdrawals the verification source disagreed
+    /// with.
+    num_gnosis_source_disagreements: i64,
+
+    /// Number of mint configuration transactions not signed by the governors
+    /// of their token.
+    num_mint_config_tx_governor_violations: i64,
+
+    /// Number of mint transactions that did not match a mint configuration
+    /// because their token had no active one.
+    num_mint_txs_without_active_mint_config: i64,
+
+    /// Number of mint transactions that did not match a mint configuration
+    /// because no active signer set signed them.
+    num_mint_txs_with_invalid_signature: i64,
+
+    /// Number of mint transactions that did not match a mint configuration
+    /// because they exceeded the total mint limit of the active one.
+    num_mint_txs_exceeding_total_mint_limit: i64,
+
+    /// Number of mint transactions that did not match a mint configuration
+    /// because they exceeded the mint limit of the ones that signed them.
+    num_mint_txs_exceeding_mint_limit: i64,
+
+    /// Number of mint transactions that appeared in a block at or after their
+    /// tombstone block.
+    num_mint_txs_past_tombstone_block: i64,
+
+    /// Number of reserve deposits and withdrawals whose amount does not fit in
+    /// a MobileCoin amount.
+    num_oversized_reserve_amounts: i64,
+
+    /// Number of Gnosis deposits whose value had sub-precision digits dropped
+    /// when truncating it to the MobileCoin token precision.
+    num_deposits_with_truncation_remainder: i64,
 }
 
 // A helper macro for DRYing up get/inc methods for each counter.
@@ -121,9 +161,90 @@ impl_get_and_inc! {
     num_unexpected_errors_matching_mints_to_deposits inc_num_unexpected_errors_matching_mints_to_deposits,
     num_unexpected_errors_matching_withdrawals_to_burns inc_num_unexpected_errors_matching_withdrawals_to_burns
     num_unexpected_errors_matching_burns_to_withdrawals inc_num_unexpected_errors_matching_burns_to_withdrawals
+    num_mint_txs_with_unknown_nonce inc_num_mint_txs_with_unknown_nonce,
+    num_gnosis_source_disagreements inc_num_gnosis_source_disagreements,
+    num_mint_config_tx_governor_violations inc_num_mint_config_tx_governor_violations,
+    num_mint_txs_without_active_mint_config inc_num_mint_txs_without_active_mint_config,
+    num_mint_txs_with_invalid_signature inc_num_mint_txs_with_invalid_signature,
+    num_mint_txs_exceeding_total_mint_limit inc_num_mint_txs_exceeding_total_mint_limit,
+    num_mint_txs_exceeding_mint_limit inc_num_mint_txs_exceeding_mint_limit,
+    num_mint_txs_past_tombstone_block inc_num_mint_txs_past_tombstone_block,
+    num_oversized_reserve_amounts inc_num_oversized_reserve_amounts,
+    num_deposits_with_truncation_remainder inc_num_deposits_with_truncation_remainder,
 }
 
 impl Counters {
+    /// Increase the counter matching an anomaly, record it in the audit event
+    /// log and queue a notification about it. Anomalies that were already
+    /// recorded, which happens when re-auditing or rewinding re-processes the
+    /// objects involved, only get their audit event updated (see
+    /// [AuditEvent::update_duplicate]). Returns whether the anomaly is new,
+    /// so that callers only increase finer-grained counters once.
+    pub fn record_anomaly(event: &AnomalyEvent, conn: &Conn) -> Result<bool, Error> {
+        if AuditEvent::update_duplicate(event, conn)?.is_some() {
+            return Ok(false);
+        }
+
+        match event.kind {
+            AnomalyKind::BurnExceedingBalance => Self::inc_num_burns_exceeding_balance(conn),
+            AnomalyKind::MintTxWithoutMatchingMintConfig => {
+                Self::inc_num_mint_txs_without_matching_mint_config(conn)
+            }
+            AnomalyKind::MismatchingMintAndDeposit => {
+                Self::inc_num_mismatching_mints_and_deposits(conn)
+            }
+            AnomalyKind::MismatchingBurnAndWithdrawal => {
+                Self::inc_num_mismatching_burns_and_withdrawals(conn)
+            }
+            AnomalyKind::UnknownEthereumTokenDeposit => {
+                Self::inc_num_unknown_ethereum_token_deposits(conn)
+            }
+            AnomalyKind::UnknownEthereumTokenWithdrawal => {
+                Self::inc_num_unknown_ethereum_token_withdrawals(conn)
+            }
+            AnomalyKind::MintToUnknownSafe => Self::inc_num_mints_to_unknown_safe(conn),
+            AnomalyKind::BurnFromUnknownSafe => Self::inc_num_burns_from_unknown_safe(conn),
+            AnomalyKind::InvalidEthereumTransaction => {
+                Self::inc_num_invalid_ethereum_transactions(conn)
+            }
+            AnomalyKind::InvalidMultiSigTransaction => {
+                Self::inc_num_invalid_multi_sig_transactions(conn)
+            }
+            AnomalyKind::UnexpectedErrorMatchingDepositToMint => {
+                Self::inc_num_unexpected_errors_matching_deposits_to_mints(conn)
+            }
+            AnomalyKind::UnexpectedErrorMatchingMintToDeposit => {
+                Self::inc_num_unexpected_errors_matching_mints_to_deposits(conn)
+            }
+            AnomalyKind::UnexpectedErrorMatchingWithdrawalToBurn => {
+                Self::inc_num_unexpected_errors_matching_withdrawals_to_burns(conn)
+            }
+            AnomalyKind::UnexpectedErrorMatchingBurnToWithdrawal => {
+                Self::inc_num_unexpected_errors_matching_burns_to_withdrawals(conn)
+            }
+            AnomalyKind::MintTxWithUnknownNonce => Self::inc_num_mint_txs_with_unknown_nonce(conn),
+            AnomalyKind::GnosisSourceDisagreement => {
+                Self::inc_num_gnosis_source_disagreements(conn)
+            }
+            AnomalyKind::MintConfigTxGovernorViolation => {
+                Self::inc_num_mint_config_tx_governor_violations(conn)
+            }
+            AnomalyKind::MintTxPastTombstoneBlock => {
+                Self::inc_num_mint_txs_past_tombstone_block(conn)
+            }
+            AnomalyKind::OversizedReserveAmount => Self::inc_num_oversized_reserve_amounts(conn),
+            AnomalyKind::DepositTruncationRemainder => {
+                Self::inc_num_deposits_with_truncation_remainder(conn)
+            }
+        }?;
+
+        AuditEvent::insert(event, conn)?;
+        AuditEvent::update_prometheus(conn)?;
+        OutboxNotification::enqueue(event, conn)?;
+
+        Ok(true)
+    }
+
     /// Get all counters.
     pub fn get(conn: &Conn) -> Result<Self, Error> {
         match counters::table.get_result(conn) {
@@ -147,6 +268,25 @@ impl Counters {
         })
     }
 
+    /// Set the number of blocks synced. This is only expected to be used when
+    /// rewinding the database to an earlier block.
+    pub fn set_num_blocks_synced(num_blocks_synced: u64, conn: &Conn) -> Result<(), Error> {
+        match diesel::update(counters::table)
+            .set(counters::num_blocks_synced.eq(num_blocks_synced as i64))
+            .execute(conn)?
+        {
+            0 => Err(Error::NotFound),
+            1 => Ok(()),
+            num_rows => Err(Error::Other(format!(
+                "num_blocks_synced: unexpected number of rows ({num_rows})"
+            ))),
+        }?;
+
+        Self::get(conn)?.update_prometheus();
+
+        Ok(())
+    }
+
     /// Update prometheus counters.
     pub fn update_prometheus(&self) {
         prom_counters::NUM_BLOCKS_SYNCED.set(self.num_blocks_synced);
@@ -189,6 +329,32 @@ impl Counters {
 
         prom_counters::NUM_UNEXPECTED_ERRORS_MATCHING_BURNS_TO_WITHDRAWALS
             .set(self.num_unexpected_errors_matching_burns_to_withdrawals);
+
+        prom_counters::NUM_MINT_TXS_WITH_UNKNOWN_NONCE.set(self.num_mint_txs_with_unknown_nonce);
+
+        prom_counters::NUM_GNOSIS_SOURCE_DISAGREEMENTS.set(self.num_gnosis_source_disagreements);
+
+        prom_counters::NUM_MINT_CONFIG_TX_GOVERNOR_VIOLATIONS
+            .set(self.num_mint_config_tx_governor_violations);
+
+        prom_counters::NUM_MINT_TXS_WITHOUT_ACTIVE_MINT_CONFIG
+            .set(self.num_mint_txs_without_active_mint_config);
+
+        prom_counters::NUM_MINT_TXS_WITH_INVALID_SIGNATURE
+            .set(self.num_mint_txs_with_invalid_signature);
+
+        prom_counters::NUM_MINT_TXS_EXCEEDING_TOTAL_MINT_LIMIT
+            .set(self.num_mint_txs_exceeding_total_mint_limit);
+
+        prom_counters::NUM_MINT_TXS_EXCEEDING_MINT_LIMIT
+            .set(self.num_mint_txs_exceeding_mint_limit);
+
+        prom_counters::NUM_MINT_TXS_PAST_TOMBSTONE_BLOCK
+            .set(self.num_mint_txs_past_tombstone_block);
+
+        prom_counters::NUM_OVERSIZED_RESERVE_AMOUNTS.set(self.num_oversized_reserve_amounts);
+        prom_counters::NUM_DEPOSITS_WITH_TRUNCATION_REMAINDER
+            .set(self.num_deposits_with_truncation_remainder);
     }
 }
 
@@ -196,6 +362,7 @@ impl Counters {
 mod tests {
     use super::*;
     use crate::db::test_utils::TestDbContext;
+    use chrono::Utc;
     use mc_common::logger::{test_with_logger, Logger};
 
     #[test_with_logger]
@@ -231,5 +398,22 @@ mod tests {
             Counters::get(&conn).unwrap().num_burns_exceeding_balance(),
             4
         );
+
+        // Recording an anomaly increases its counter, logs it and queues a
+        // notification.
+        let event = AnomalyEvent::new(AnomalyKind::BurnExceedingBalance, "too much");
+        Counters::record_anomaly(&event, &conn).unwrap();
+        assert_eq!(
+            Counters::get(&conn).unwrap().num_burns_exceeding_balance(),
+            5
+        );
+
+        let audit_events = AuditEvent::list(None, None, false, &conn).unwrap();
+        assert_eq!(audit_events.len(), 1);
+        assert_eq!(audit_events[0].kind(), "burn_exceeding_balance");
+
+        let notifications = OutboxNotification::find_due(Utc::now(), 10, &conn).unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].decode().unwrap(), event);
     }
 }
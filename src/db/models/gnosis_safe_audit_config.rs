@@ -0,0 +1,163 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    db::{models::SqlEthAddr, schema::gnosis_safe_audit_configs, Conn},
+    error::Error,
+    gnosis::{AuditedSafeConfig, Error as GnosisError, EthAddr},
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `gnosis_safe_audit_configs` table.
+/// This table stores, for each audited safe, the configuration that was used
+/// when auditing its transactions.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+#[table_name = "gnosis_safe_audit_configs"]
+pub struct GnosisSafeAuditConfig {
+    /// The id of the EVM chain the safe lives on.
+    chain_id: i64,
+
+    /// The safe address this configuration is for.
+    safe_addr: SqlEthAddr,
+
+    /// The JSON representation of the [AuditedSafeConfig].
+    config_json: String,
+
+    /// When the configuration was last stored.
+    updated_at: NaiveDateTime,
+}
+
+impl GnosisSafeAuditConfig {
+    /// Get the id of the EVM chain the safe lives on.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id as u64
+    }
+
+    /// Get safe address.
+    pub fn safe_addr(&self) -> &EthAddr {
+        &self.safe_addr
+    }
+
+    /// Get the time the configuration was last stored.
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.updated_at, Utc)
+    }
+
+    /// Decode the stored [AuditedSafeConfig].
+    pub fn decode(&self) -> Result<AuditedSafeConfig, Error> {
+        serde_json::from_str(&self.config_json).map_err(|err| GnosisError::from(err).into())
+    }
+
+    /// Get the stored configuration of a given safe, if there is one.
+    pub fn get(chain_id: u64, safe_addr: &EthAddr, conn: &Conn) -> Result<Option<Self>, Error> {
+        Ok(gnosis_safe_audit_configs::table
+            .filter(gnosis_safe_audit_configs::chain_id.eq(chain_id as i64))
+            .filter(gnosis_safe_audit_configs::safe_addr.eq(SqlEthAddr::from(safe_addr)))
+            .first(conn)
+            .optional()?)
+    }
+
+    /// Get the stored configurations of all safes.
+    pub fn list(conn: &Conn) -> Result<Vec<Self>, Error> {
+        Ok(gnosis_safe_audit_configs::table
+            .order_by((
+                gnosis_safe_audit_configs::chain_id,
+                gnosis_safe_audit_configs::safe_addr,
+            ))
+            .load(conn)?)
+    }
+
+    /// Store the configuration of a safe, replacing any previously stored
+    /// one.
+    pub fn set(config: &AuditedSafeConfig, conn: &Conn) -> Result<Self, Error> {
+        let obj = Self {
+            chain_id: config.chain_id as i64,
+            safe_addr: (&config.safe_addr).into(),
+            config_json: serde_json::to_string(config).map_err(GnosisError::from)?,
+            updated_at: Utc::now().naive_utc(),
+        };
+
+        diesel::replace_into(gnosis_safe_audit_configs::table)
+            .values(&obj)
+            .execute(conn)?;
+
+        Ok(obj)
+    }
+
+    /// Delete the stored configuration of a given safe.
+    pub fn delete(chain_id: u64, safe_addr: &EthAddr, conn: &Conn) -> Result<(), Error> {
+        diesel::delete(
+            gnosis_safe_audit_configs::table
+                .filter(gnosis_safe_audit_configs::chain_id.eq(chain_id as i64))
+                .filter(gnosis_safe_audit_configs::safe_addr.eq(SqlEthAddr::from(safe_addr))),
+        )
+        .execute(conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::{test_gnosis_config, TestDbContext};
+    use mc_common::logger::{test_with_logger, Logger};
+
+    #[test_with_logger]
+    fn set_get_list_delete_work(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let mut config1 = test_gnosis_config().safes[0].clone();
+        // The same address on another chain is a different safe.
+        let mut config2 = config1.clone();
+        config2.chain_id = 10;
+
+        // Initially nothing is stored.
+        assert_eq!(
+            GnosisSafeAuditConfig::get(1, &config1.safe_addr, &conn).unwrap(),
+            None
+        );
+        assert!(GnosisSafeAuditConfig::list(&conn).unwrap().is_empty());
+
+        let stored1 = GnosisSafeAuditConfig::set(&config1, &conn).unwrap();
+        let stored2 = GnosisSafeAuditConfig::set(&config2, &conn).unwrap();
+        assert_eq!(stored1.decode().unwrap(), config1);
+        assert_eq!(
+            GnosisSafeAuditConfig::get(1, &config1.safe_addr, &conn).unwrap(),
+            Some(stored1)
+        );
+        assert_eq!(GnosisSafeAuditConfig::list(&conn).unwrap().len(), 2);
+
+        // Storing again replaces the previous configuration.
+        config1.tokens.pop();
+        GnosisSafeAuditConfig::set(&config1, &conn).unwrap();
+        assert_eq!(
+            GnosisSafeAuditConfig::get(1, &config1.safe_addr, &conn)
+                .unwrap()
+                .unwrap()
+                .decode()
+                .unwrap(),
+            config1
+        );
+        assert_eq!(GnosisSafeAuditConfig::list(&conn).unwrap().len(), 2);
+
+        // Deleting only affects the given safe.
+        GnosisSafeAuditConfig::delete(1, &config1.safe_addr, &conn).unwrap();
+        assert_eq!(
+            GnosisSafeAuditConfig::get(1, &config1.safe_addr, &conn).unwrap(),
+            None
+        );
+        assert_eq!(GnosisSafeAuditConfig::list(&conn).unwrap(), vec![stored2]);
+        assert_eq!(
+            GnosisSafeAuditConfig::get(10, &config1.safe_addr, &conn)
+                .unwrap()
+                .unwrap()
+                .decode()
+                .unwrap(),
+            config2
+        );
+    }
+}
@@ -3,19 +3,17 @@
 use crate::{
     db::{
         last_insert_rowid,
-        models::{SqlEthAddr, SqlEthTxHash, SqlEthTxValue},
-        schema::{audited_mints, gnosis_safe_deposits},
+        models::{AuditedMint, SqlEthAddr, SqlEthTxHash, SqlEthTxValue, SqlU128},
+        schema::{audit_events, gnosis_safe_deposits, reserve_matches},
         Conn,
     },
     error::Error,
-    gnosis::{EthAddr, EthTxHash, EthTxValue},
+    gnosis::{EthAddr, EthTxHash, EthTxValue, VerificationStatus},
+    reserve::GnosisReserveBackend,
     MintTxNonce,
 };
 use chrono::{DateTime, NaiveDateTime, Utc};
-use diesel::{
-    dsl::{exists, not},
-    prelude::*,
-};
+use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// Diesel model for the `gnosis_safe_deposits` table.
@@ -48,12 +46,24 @@ pub struct GnosisSafeDeposit {
     /// The Ethereum address that sent the transaction to the gnosis safe.
     from_addr: SqlEthAddr,
 
-    /// Amount deposited, truncated to associated mob-chain token's precision
-    amount: i64,
+    /// Amount deposited, truncated to associated mob-chain token's precision.
+    /// This is not guaranteed to fit in a MobileCoin (u64) amount.
+    amount: SqlU128,
 
     /// The hex-encoded MintTx nonce we expect to see on the MobileCoin
     /// blockchain
     expected_mc_mint_tx_nonce_hex: String,
+
+    /// The id of the EVM chain the deposit happened on.
+    chain_id: i64,
+
+    /// The outcome of verifying the deposit against the safe's verification
+    /// source (see [VerificationStatus]).
+    verification_status: String,
+
+    /// The sub-precision part of the Ethereum transaction value that was
+    /// dropped when truncating it into `amount`, in raw token units.
+    truncation_remainder: SqlU128,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -61,6 +71,7 @@ impl GnosisSafeDeposit {
     /// Construct a new [GnosisSafeDeposit] object.
     pub fn new(
         id: Option<i32>,
+        chain_id: u64,
         eth_tx_hash: EthTxHash,
         execution_date: DateTime<Utc>,
         eth_tx_value: EthTxValue,
@@ -68,9 +79,10 @@ impl GnosisSafeDeposit {
         safe_addr: EthAddr,
         token_addr: EthAddr,
         from_addr: EthAddr,
-        amount: u64,
+        amount: u128,
+        truncation_remainder: u128,
     ) -> Self {
-        let expected_mc_mint_tx_nonce = MintTxNonce::EthereumGnosisDeposit(eth_tx_hash);
+        let expected_mc_mint_tx_nonce = MintTxNonce::for_deposit(chain_id, eth_tx_hash);
         let expected_mc_mint_tx_nonce_hex = hex::encode(expected_mc_mint_tx_nonce.to_bytes());
         Self {
             id,
@@ -81,8 +93,11 @@ impl GnosisSafeDeposit {
             safe_addr: safe_addr.into(),
             from_addr: from_addr.into(),
             token_addr: token_addr.into(),
-            amount: amount as i64,
+            amount: amount.into(),
             expected_mc_mint_tx_nonce_hex,
+            chain_id: chain_id as i64,
+            verification_status: VerificationStatus::Unverified.to_string(),
+            truncation_remainder: truncation_remainder.into(),
         }
     }
 
@@ -91,6 +106,11 @@ impl GnosisSafeDeposit {
         self.id
     }
 
+    /// Get the id of the EVM chain the deposit happened on.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id as u64
+    }
+
     /// Get Ethereum transaction hash.
     pub fn eth_tx_hash(&self) -> &EthTxHash {
         &self.eth_tx_hash
@@ -127,8 +147,19 @@ impl GnosisSafeDeposit {
     }
 
     /// Get amount deposited.
-    pub fn amount(&self) -> u64 {
-        self.amount as u64
+    pub fn amount(&self) -> u128 {
+        *self.amount
+    }
+
+    /// Get amount deposited, if it fits in a MobileCoin amount.
+    pub fn mc_amount(&self) -> Option<u64> {
+        u64::try_from(*self.amount).ok()
+    }
+
+    /// Get the sub-precision part of the Ethereum transaction value that was
+    /// dropped when truncating it into the amount, in raw token units.
+    pub fn truncation_remainder(&self) -> u128 {
+        *self.truncation_remainder
     }
 
     /// Get the hex-encoded MintTx nonce we expect to see on the MobileCoin
@@ -137,6 +168,29 @@ impl GnosisSafeDeposit {
         &self.expected_mc_mint_tx_nonce_hex
     }
 
+    /// Get the outcome of verifying the deposit against the safe's
+    /// verification source.
+    pub fn verification_status(&self) -> &str {
+        &self.verification_status
+    }
+
+    /// Update the verification status of a deposit that is already in the
+    /// database.
+    pub fn set_verification_status(
+        &mut self,
+        status: VerificationStatus,
+        conn: &Conn,
+    ) -> Result<(), Error> {
+        let id = self
+            .id
+            .ok_or_else(|| Error::Other("GnosisSafeDeposit has no id".into()))?;
+        self.verification_status = status.to_string();
+        diesel::update(gnosis_safe_deposits::table.filter(gnosis_safe_deposits::id.eq(id)))
+            .set(gnosis_safe_deposits::verification_status.eq(&self.verification_status))
+            .execute(conn)?;
+        Ok(())
+    }
+
     /// Insert a deposit into the database.
     pub fn insert(&mut self, conn: &Conn) -> Result<(), Error> {
         if let Some(id) = self.id {
@@ -154,41 +208,136 @@ impl GnosisSafeDeposit {
         Ok(())
     }
 
-    /// Attempt to find all [GnosisSafeDeposit]s that do not have a matching
-    /// entry in the `audited_mints` table.
-    pub fn find_unaudited_deposits(conn: &Conn) -> Result<Vec<Self>, Error> {
-        Ok(gnosis_safe_deposits::table
-            .filter(not(exists(
-                audited_mints::table
-                    .select(audited_mints::gnosis_safe_deposit_id)
-                    .filter(
-                        audited_mints::gnosis_safe_deposit_id
-                            .nullable()
-                            .eq(gnosis_safe_deposits::id),
+    /// Delete all deposits into a given safe, along with the `reserve_matches`
+    /// entries linking them to MintTxs. Audit events referencing them keep
+    /// their Ethereum transaction hash but lose the id. Returns the number of
+    /// deleted deposits.
+    pub fn delete_by_safe(chain_id: u64, safe_addr: &EthAddr, conn: &Conn) -> Result<usize, Error> {
+        let deposit_ids = gnosis_safe_deposits::table
+            .filter(gnosis_safe_deposits::chain_id.eq(chain_id as i64))
+            .filter(gnosis_safe_deposits::safe_addr.eq(SqlEthAddr::from(safe_addr)))
+            .select(gnosis_safe_deposits::id);
+
+        diesel::update(
+            audit_events::table.filter(audit_events::gnosis_safe_deposit_id.eq_any(deposit_ids)),
+        )
+        .set(audit_events::gnosis_safe_deposit_id.eq(None::<i32>))
+        .execute(conn)?;
+        diesel::delete(
+            reserve_matches::table
+                .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+                .filter(reserve_matches::mint_tx_id.is_not_null())
+                .filter(
+                    reserve_matches::reference.eq_any(
+                        gnosis_safe_deposits::table
+                            .filter(gnosis_safe_deposits::chain_id.eq(chain_id as i64))
+                            .filter(gnosis_safe_deposits::safe_addr.eq(SqlEthAddr::from(safe_addr)))
+                            .select(gnosis_safe_deposits::eth_tx_hash),
                     ),
-            )))
+                ),
+        )
+        .execute(conn)?;
+
+        Ok(diesel::delete(
+            gnosis_safe_deposits::table
+                .filter(gnosis_safe_deposits::chain_id.eq(chain_id as i64))
+                .filter(gnosis_safe_deposits::safe_addr.eq(SqlEthAddr::from(safe_addr))),
+        )
+        .execute(conn)?)
+    }
+
+    /// Delete all deposits, along with the `reserve_matches` entries linking
+    /// them to MintTxs.
+    /// Audit events referencing them keep their Ethereum transaction hash but
+    /// lose the id. Returns the number of deleted deposits.
+    pub fn delete_all(conn: &Conn) -> Result<usize, Error> {
+        diesel::update(
+            audit_events::table.filter(audit_events::gnosis_safe_deposit_id.is_not_null()),
+        )
+        .set(audit_events::gnosis_safe_deposit_id.eq(None::<i32>))
+        .execute(conn)?;
+        diesel::delete(
+            reserve_matches::table
+                .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+                .filter(reserve_matches::mint_tx_id.is_not_null()),
+        )
+        .execute(conn)?;
+        Ok(diesel::delete(gnosis_safe_deposits::table).execute(conn)?)
+    }
+
+    /// Get all deposits into a given safe, ordered by id.
+    pub fn list_by_safe(
+        chain_id: u64,
+        safe_addr: &EthAddr,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        Ok(gnosis_safe_deposits::table
+            .filter(gnosis_safe_deposits::chain_id.eq(chain_id as i64))
+            .filter(gnosis_safe_deposits::safe_addr.eq(SqlEthAddr::from(safe_addr)))
+            .order_by(gnosis_safe_deposits::id)
+            .load(conn)?)
+    }
+
+    /// Get all deposits into a given safe that have not been verified against
+    /// the safe's verification source yet, ordered by id.
+    pub fn list_unverified_by_safe(
+        chain_id: u64,
+        safe_addr: &EthAddr,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        Ok(gnosis_safe_deposits::table
+            .filter(gnosis_safe_deposits::chain_id.eq(chain_id as i64))
+            .filter(gnosis_safe_deposits::safe_addr.eq(SqlEthAddr::from(safe_addr)))
+            .filter(
+                gnosis_safe_deposits::verification_status
+                    .eq(VerificationStatus::Unverified.to_string()),
+            )
+            .order_by(gnosis_safe_deposits::id)
             .load(conn)?)
     }
 
-    /// Attempt to find a [GnosisSafeDeposit] that has a given nonce and no
-    /// matching entry in the `audited_mints` table.
+    /// Attempt to find all [GnosisSafeDeposit]s whose amount is not fully
+    /// allocated by entries in the `reserve_matches` table.
+    pub fn find_unaudited_deposits(conn: &Conn) -> Result<Vec<Self>, Error> {
+        Self::retain_not_fully_allocated(
+            gnosis_safe_deposits::table
+                .order_by(gnosis_safe_deposits::id)
+                .load(conn)?,
+            conn,
+        )
+    }
+
+    /// Attempt to find a [GnosisSafeDeposit] that has a given nonce and whose
+    /// amount is not fully allocated by entries in the `reserve_matches` table.
     pub fn find_unaudited_deposit_by_nonce(
         nonce_hex: &str,
         conn: &Conn,
     ) -> Result<Option<Self>, Error> {
-        Ok(gnosis_safe_deposits::table
-            .filter(gnosis_safe_deposits::expected_mc_mint_tx_nonce_hex.eq(nonce_hex))
-            .filter(not(exists(
-                audited_mints::table
-                    .select(audited_mints::gnosis_safe_deposit_id)
-                    .filter(
-                        audited_mints::gnosis_safe_deposit_id
-                            .nullable()
-                            .eq(gnosis_safe_deposits::id),
-                    ),
-            )))
-            .first(conn)
-            .optional()?)
+        Ok(Self::retain_not_fully_allocated(
+            gnosis_safe_deposits::table
+                .filter(gnosis_safe_deposits::expected_mc_mint_tx_nonce_hex.eq(nonce_hex))
+                .order_by(gnosis_safe_deposits::id)
+                .load(conn)?,
+            conn,
+        )?
+        .into_iter()
+        .next())
+    }
+
+    /// Keep the deposits whose amount is not fully allocated by
+    /// `reserve_matches` entries. A deposit can be split across several mints,
+    /// so this sums the allocations of all of its matches. This happens here
+    /// rather than in SQL since amounts are stored as decimal strings, which
+    /// SQLite cannot compare numerically.
+    fn retain_not_fully_allocated(deposits: Vec<Self>, conn: &Conn) -> Result<Vec<Self>, Error> {
+        let mut result = Vec::with_capacity(deposits.len());
+        for deposit in deposits {
+            deposit.id().ok_or(Error::ObjectNotSaved)?;
+            if AuditedMint::unallocated_deposit_amount(&deposit, conn)?.is_some() {
+                result.push(deposit);
+            }
+        }
+        Ok(result)
     }
 }
 
@@ -276,11 +425,12 @@ mod tests {
             deposit2,
         );
 
-        // Insert a row to the `audited_mints` table marking the first deposit as
+        // Insert a row to the `reserve_matches` table marking the first deposit as
         // audited. We should no longer be able to find it.
         AuditedMint::associate_deposit_with_mint(
-            deposit1.id().unwrap(),
+            &deposit1,
             sql_mint_tx1.id().unwrap(),
+            deposit1.mc_amount().unwrap(),
             &conn,
         )
         .unwrap();
@@ -300,8 +450,9 @@ mod tests {
 
         // Mark the second deposit as audited. We should no longer be able to find it.
         AuditedMint::associate_deposit_with_mint(
-            deposit2.id().unwrap(),
+            &deposit2,
             sql_mint_tx2.id().unwrap(),
+            deposit2.mc_amount().unwrap(),
             &conn,
         )
         .unwrap();
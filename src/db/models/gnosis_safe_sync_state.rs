@@ -0,0 +1,201 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    db::{
+        models::{SqlEthAddr, SqlEthTxHash},
+        schema::gnosis_safe_sync_states,
+        Conn,
+    },
+    error::Error,
+    gnosis::{EthAddr, EthTxHash},
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `gnosis_safe_sync_states` table.
+/// This table stores, for each audited safe, how far we have synced its
+/// transaction history from the Gnosis API.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+#[table_name = "gnosis_safe_sync_states"]
+pub struct GnosisSafeSyncState {
+    /// The id of the EVM chain the safe lives on.
+    chain_id: i64,
+
+    /// The safe address this state is for.
+    safe_addr: SqlEthAddr,
+
+    /// Whether the entire transaction history of the safe has been fetched
+    /// and processed at least once.
+    full_sync_completed: bool,
+
+    /// The hash of the newest transaction seen during the last successful
+    /// sync.
+    last_eth_tx_hash: Option<SqlEthTxHash>,
+
+    /// When the last successful sync completed.
+    last_synced_at: Option<NaiveDateTime>,
+}
+
+impl GnosisSafeSyncState {
+    /// Get the id of the EVM chain the safe lives on.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id as u64
+    }
+
+    /// Get safe address.
+    pub fn safe_addr(&self) -> &EthAddr {
+        &self.safe_addr
+    }
+
+    /// Get whether a full sync has been completed.
+    pub fn full_sync_completed(&self) -> bool {
+        self.full_sync_completed
+    }
+
+    /// Get the hash of the newest transaction seen during the last successful
+    /// sync.
+    pub fn last_eth_tx_hash(&self) -> Option<&EthTxHash> {
+        self.last_eth_tx_hash.as_deref()
+    }
+
+    /// Get the time the last successful sync completed.
+    pub fn last_synced_at(&self) -> Option<DateTime<Utc>> {
+        self.last_synced_at
+            .map(|last_synced_at| DateTime::from_naive_utc_and_offset(last_synced_at, Utc))
+    }
+
+    /// Get the sync state of a given safe, if one has been recorded.
+    pub fn get(chain_id: u64, safe_addr: &EthAddr, conn: &Conn) -> Result<Option<Self>, Error> {
+        Ok(gnosis_safe_sync_states::table
+            .filter(gnosis_safe_sync_states::chain_id.eq(chain_id as i64))
+            .filter(gnosis_safe_sync_states::safe_addr.eq(SqlEthAddr::from(safe_addr)))
+            .first(conn)
+            .optional()?)
+    }
+
+    /// Record a successful sync of a given safe.
+    /// `newest_eth_tx_hash` is the hash of the newest transaction returned by
+    /// the sync, or `None` if the sync returned no transactions (in which
+    /// case the previously recorded hash is kept).
+    pub fn record_successful_sync(
+        chain_id: u64,
+        safe_addr: &EthAddr,
+        full_sync_completed: bool,
+        newest_eth_tx_hash: Option<&EthTxHash>,
+        conn: &Conn,
+    ) -> Result<Self, Error> {
+        let prev_state = Self::get(chain_id, safe_addr, conn)?;
+
+        let last_eth_tx_hash = newest_eth_tx_hash
+            .map(SqlEthTxHash::from)
+            .or_else(|| prev_state.as_ref().and_then(|s| s.last_eth_tx_hash.clone()));
+        let full_sync_completed = full_sync_completed
+            || prev_state
+                .as_ref()
+                .map(|s| s.full_sync_completed)
+                .unwrap_or(false);
+
+        let obj = Self {
+            chain_id: chain_id as i64,
+            safe_addr: safe_addr.into(),
+            full_sync_completed,
+            last_eth_tx_hash,
+            last_synced_at: Some(Utc::now().naive_utc()),
+        };
+
+        diesel::replace_into(gnosis_safe_sync_states::table)
+            .values(&obj)
+            .execute(conn)?;
+
+        Ok(obj)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::{TestDbContext, SAFE_ADDR};
+    use mc_common::logger::{test_with_logger, Logger};
+    use std::str::FromStr;
+
+    #[test_with_logger]
+    fn record_successful_sync_works(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let safe_addr = EthAddr::from_str(SAFE_ADDR).unwrap();
+        let other_safe_addr =
+            EthAddr::from_str("0x0000000000000000000000000000000000000001").unwrap();
+        let tx_hash1 = EthTxHash::from_str(
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let tx_hash2 = EthTxHash::from_str(
+            "0x0000000000000000000000000000000000000000000000000000000000000002",
+        )
+        .unwrap();
+
+        // Initially there is no state.
+        assert_eq!(
+            GnosisSafeSyncState::get(1, &safe_addr, &conn).unwrap(),
+            None
+        );
+
+        // A partial sync without any transactions.
+        let state =
+            GnosisSafeSyncState::record_successful_sync(1, &safe_addr, false, None, &conn).unwrap();
+        assert!(!state.full_sync_completed());
+        assert_eq!(state.last_eth_tx_hash(), None);
+        assert!(state.last_synced_at().is_some());
+        assert_eq!(
+            GnosisSafeSyncState::get(1, &safe_addr, &conn).unwrap(),
+            Some(state)
+        );
+
+        // A full sync.
+        let state = GnosisSafeSyncState::record_successful_sync(
+            1,
+            &safe_addr,
+            true,
+            Some(&tx_hash1),
+            &conn,
+        )
+        .unwrap();
+        assert!(state.full_sync_completed());
+        assert_eq!(state.last_eth_tx_hash(), Some(&tx_hash1));
+
+        // An empty incremental sync keeps the previous hash and full sync flag.
+        let state =
+            GnosisSafeSyncState::record_successful_sync(1, &safe_addr, false, None, &conn).unwrap();
+        assert!(state.full_sync_completed());
+        assert_eq!(state.last_eth_tx_hash(), Some(&tx_hash1));
+
+        // An incremental sync that saw new transactions moves the hash forward.
+        let state = GnosisSafeSyncState::record_successful_sync(
+            1,
+            &safe_addr,
+            false,
+            Some(&tx_hash2),
+            &conn,
+        )
+        .unwrap();
+        assert!(state.full_sync_completed());
+        assert_eq!(state.last_eth_tx_hash(), Some(&tx_hash2));
+        assert_eq!(
+            GnosisSafeSyncState::get(1, &safe_addr, &conn).unwrap(),
+            Some(state)
+        );
+
+        // Other safes, including the same address on another chain, are unaffected.
+        assert_eq!(
+            GnosisSafeSyncState::get(1, &other_safe_addr, &conn).unwrap(),
+            None
+        );
+        assert_eq!(
+            GnosisSafeSyncState::get(10, &safe_addr, &conn).unwrap(),
+            None
+        );
+    }
+}
@@ -3,9 +3,9 @@
 use crate::{
     db::{schema::gnosis_safe_txs, Conn},
     error::Error,
-    gnosis::api_data_types::RawGnosisTransaction,
+    gnosis::{api_data_types::RawGnosisTransaction, EthTxHash},
 };
-use diesel::prelude::*;
+use diesel::{dsl::exists, prelude::*};
 use serde::{Deserialize, Serialize};
 
 /// Diesel model for the `gnosis_safe_txs` table.
@@ -20,14 +20,19 @@ pub struct GnosisSafeTx {
     /// The JSON representation of the transaction, as served from the gnosis
     /// API.
     pub raw_tx_json: String,
+
+    /// The id of the EVM chain the transaction happened on.
+    pub chain_id: i64,
 }
 
 impl GnosisSafeTx {
-    /// Insert a raw Gnosis Safe transaction into the database.
-    pub fn insert(raw_tx: &RawGnosisTransaction, conn: &Conn) -> Result<(), Error> {
+    /// Insert a raw Gnosis Safe transaction that happened on a given chain into
+    /// the database.
+    pub fn insert(raw_tx: &RawGnosisTransaction, chain_id: u64, conn: &Conn) -> Result<(), Error> {
         let obj = Self {
             eth_tx_hash: raw_tx.tx_hash()?.to_string(),
             raw_tx_json: raw_tx.to_json_string(),
+            chain_id: chain_id as i64,
         };
 
         diesel::insert_into(gnosis_safe_txs::table)
@@ -37,6 +42,29 @@ impl GnosisSafeTx {
         Ok(())
     }
 
+    /// Check whether a transaction with the given hash has already been
+    /// stored for a given chain.
+    pub fn exists(chain_id: u64, eth_tx_hash: &EthTxHash, conn: &Conn) -> Result<bool, Error> {
+        Ok(diesel::select(exists(
+            gnosis_safe_txs::table
+                .filter(gnosis_safe_txs::chain_id.eq(chain_id as i64))
+                .filter(gnosis_safe_txs::eth_tx_hash.eq(eth_tx_hash.to_string())),
+        ))
+        .get_result(conn)?)
+    }
+
+    /// Get all stored transactions.
+    pub fn list(conn: &Conn) -> Result<Vec<Self>, Error> {
+        Ok(gnosis_safe_txs::table.load(conn)?)
+    }
+
+    /// Get all stored transactions of a given chain.
+    pub fn list_by_chain_id(chain_id: u64, conn: &Conn) -> Result<Vec<Self>, Error> {
+        Ok(gnosis_safe_txs::table
+            .filter(gnosis_safe_txs::chain_id.eq(chain_id as i64))
+            .load(conn)?)
+    }
+
     /// Decode a Gnosis Safe transaction.
     pub fn decode(&self) -> Result<RawGnosisTransaction, Error> {
         Ok(RawGnosisTransaction::from_json(&self.raw_tx_json)?)
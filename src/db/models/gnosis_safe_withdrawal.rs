@@ -4,18 +4,16 @@
 use crate::{
     db::{
         last_insert_rowid,
-        models::{SqlEthAddr, SqlEthTxHash},
-        schema::{audited_burns, gnosis_safe_withdrawals},
+        models::{AuditedBurn, SqlEthAddr, SqlEthTxHash, SqlU128},
+        schema::{audit_events, gnosis_safe_withdrawals, reserve_matches},
         Conn,
     },
     error::Error,
-    gnosis::{EthAddr, EthTxHash, EthTxValue},
+    gnosis::{EthAddr, EthTxHash, EthTxValue, VerificationStatus},
+    reserve::GnosisReserveBackend,
 };
 use chrono::{DateTime, NaiveDateTime, Utc};
-use diesel::{
-    dsl::{exists, not},
-    prelude::*,
-};
+use diesel::prelude::*;
 use mc_crypto_keys::CompressedRistrettoPublic;
 use serde::{Deserialize, Serialize};
 
@@ -51,11 +49,23 @@ pub struct GnosisSafeWithdrawal {
     /// The ethereum address of the transaction recipient.
     to_addr: SqlEthAddr,
 
-    /// Amount withdrawn, truncated to associated mob-chain token's precision
-    amount: i64,
+    /// Amount withdrawn, truncated to associated mob-chain token's precision.
+    /// This is not guaranteed to fit in a MobileCoin (u64) amount.
+    amount: SqlU128,
 
     /// Associated mobilecoin transaction public key (hex-encoded).
     mc_tx_out_public_key_hex: String,
+
+    /// The id of the EVM chain the withdrawal happened on.
+    chain_id: i64,
+
+    /// The outcome of verifying the withdrawal against the safe's verification
+    /// source (see [VerificationStatus]).
+    verification_status: String,
+
+    /// The sub-precision part of the Ethereum transaction value that was
+    /// dropped when truncating it into `amount`, in raw token units.
+    truncation_remainder: SqlU128,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -63,6 +73,7 @@ impl GnosisSafeWithdrawal {
     /// Construct a new [GnosisSafeWithdrawal] object.
     pub fn new(
         id: Option<i32>,
+        chain_id: u64,
         eth_tx_hash: EthTxHash,
         execution_date: DateTime<Utc>,
         eth_tx_value: EthTxValue,
@@ -70,7 +81,8 @@ impl GnosisSafeWithdrawal {
         safe_addr: EthAddr,
         token_addr: EthAddr,
         to_addr: EthAddr,
-        amount: u64,
+        amount: u128,
+        truncation_remainder: u128,
         mc_tx_out_public_key_hex: String,
     ) -> Self {
         Self {
@@ -82,8 +94,11 @@ impl GnosisSafeWithdrawal {
             safe_addr: safe_addr.into(),
             token_addr: token_addr.into(),
             to_addr: to_addr.into(),
-            amount: amount as i64,
+            amount: amount.into(),
             mc_tx_out_public_key_hex,
+            chain_id: chain_id as i64,
+            verification_status: VerificationStatus::Unverified.to_string(),
+            truncation_remainder: truncation_remainder.into(),
         }
     }
 
@@ -92,6 +107,11 @@ impl GnosisSafeWithdrawal {
         self.id
     }
 
+    /// Get the id of the EVM chain the withdrawal happened on.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id as u64
+    }
+
     /// Get ethereum transaction hash.
     pub fn eth_tx_hash(&self) -> &EthTxHash {
         &self.eth_tx_hash
@@ -128,8 +148,19 @@ impl GnosisSafeWithdrawal {
     }
 
     /// Get amount withdrawan.
-    pub fn amount(&self) -> u64 {
-        self.amount as u64
+    pub fn amount(&self) -> u128 {
+        *self.amount
+    }
+
+    /// Get amount withdrawn, if it fits in a MobileCoin amount.
+    pub fn mc_amount(&self) -> Option<u64> {
+        u64::try_from(*self.amount).ok()
+    }
+
+    /// Get the sub-precision part of the Ethereum transaction value that was
+    /// dropped when truncating it into the amount, in raw token units.
+    pub fn truncation_remainder(&self) -> u128 {
+        *self.truncation_remainder
     }
 
     /// Get associated mobilecoin transaction public key (hex-encoded).
@@ -143,6 +174,29 @@ impl GnosisSafeWithdrawal {
         Ok(CompressedRistrettoPublic::try_from(&key_bytes[..])?)
     }
 
+    /// Get the outcome of verifying the withdrawal against the safe's
+    /// verification source.
+    pub fn verification_status(&self) -> &str {
+        &self.verification_status
+    }
+
+    /// Update the verification status of a withdrawal that is already in the
+    /// database.
+    pub fn set_verification_status(
+        &mut self,
+        status: VerificationStatus,
+        conn: &Conn,
+    ) -> Result<(), Error> {
+        let id = self
+            .id
+            .ok_or_else(|| Error::Other("GnosisSafeWithdrawal has no id".into()))?;
+        self.verification_status = status.to_string();
+        diesel::update(gnosis_safe_withdrawals::table.filter(gnosis_safe_withdrawals::id.eq(id)))
+            .set(gnosis_safe_withdrawals::verification_status.eq(&self.verification_status))
+            .execute(conn)?;
+        Ok(())
+    }
+
     /// Insert a withdrawal into the database.
     pub fn insert(&mut self, conn: &Conn) -> Result<(), Error> {
         if let Some(id) = self.id {
@@ -160,41 +214,151 @@ impl GnosisSafeWithdrawal {
         Ok(())
     }
 
-    /// Attempt to find all [GnosisSafeWithdrawal] that do not have a
-    /// matching entry in the `audited_burns` table.
-    pub fn find_unaudited_withdrawals(conn: &Conn) -> Result<Vec<Self>, Error> {
-        Ok(gnosis_safe_withdrawals::table
-            .filter(not(exists(
-                audited_burns::table
-                    .select(audited_burns::gnosis_safe_withdrawal_id)
-                    .filter(
-                        audited_burns::gnosis_safe_withdrawal_id
-                            .nullable()
-                            .eq(gnosis_safe_withdrawals::id),
+    /// Delete all withdrawals from a given safe, along with the
+    /// `reserve_matches` entries linking them to BurnTxOuts. Audit events
+    /// referencing them keep their Ethereum transaction hash but lose the id.
+    /// Returns the number of deleted withdrawals.
+    pub fn delete_by_safe(chain_id: u64, safe_addr: &EthAddr, conn: &Conn) -> Result<usize, Error> {
+        let withdrawal_ids = gnosis_safe_withdrawals::table
+            .filter(gnosis_safe_withdrawals::chain_id.eq(chain_id as i64))
+            .filter(gnosis_safe_withdrawals::safe_addr.eq(SqlEthAddr::from(safe_addr)))
+            .select(gnosis_safe_withdrawals::id);
+
+        diesel::update(
+            audit_events::table
+                .filter(audit_events::gnosis_safe_withdrawal_id.eq_any(withdrawal_ids)),
+        )
+        .set(audit_events::gnosis_safe_withdrawal_id.eq(None::<i32>))
+        .execute(conn)?;
+        diesel::delete(
+            reserve_matches::table
+                .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+                .filter(reserve_matches::burn_tx_out_id.is_not_null())
+                .filter(
+                    reserve_matches::reference.eq_any(
+                        gnosis_safe_withdrawals::table
+                            .filter(gnosis_safe_withdrawals::chain_id.eq(chain_id as i64))
+                            .filter(
+                                gnosis_safe_withdrawals::safe_addr.eq(SqlEthAddr::from(safe_addr)),
+                            )
+                            .select(gnosis_safe_withdrawals::eth_tx_hash),
                     ),
-            )))
+                ),
+        )
+        .execute(conn)?;
+
+        Ok(diesel::delete(
+            gnosis_safe_withdrawals::table
+                .filter(gnosis_safe_withdrawals::chain_id.eq(chain_id as i64))
+                .filter(gnosis_safe_withdrawals::safe_addr.eq(SqlEthAddr::from(safe_addr))),
+        )
+        .execute(conn)?)
+    }
+
+    /// Delete all withdrawals, along with the `reserve_matches` entries
+    /// linking them to BurnTxOuts. Audit events referencing them keep their
+    /// Ethereum transaction hash but lose the id. Returns the number of deleted withdrawals.
+    pub fn delete_all(conn: &Conn) -> Result<usize, Error> {
+        diesel::update(
+            audit_events::table.filter(audit_events::gnosis_safe_withdrawal_id.is_not_null()),
+        )
+        .set(audit_events::gnosis_safe_withdrawal_id.eq(None::<i32>))
+        .execute(conn)?;
+        diesel::delete(
+            reserve_matches::table
+                .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+                .filter(reserve_matches::burn_tx_out_id.is_not_null()),
+        )
+        .execute(conn)?;
+        Ok(diesel::delete(gnosis_safe_withdrawals::table).execute(conn)?)
+    }
+
+    /// Get all withdrawals from a given safe, ordered by id.
+    pub fn list_by_safe(
+        chain_id: u64,
+        safe_addr: &EthAddr,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        Ok(gnosis_safe_withdrawals::table
+            .filter(gnosis_safe_withdrawals::chain_id.eq(chain_id as i64))
+            .filter(gnosis_safe_withdrawals::safe_addr.eq(SqlEthAddr::from(safe_addr)))
+            .order_by(gnosis_safe_withdrawals::id)
             .load(conn)?)
     }
 
-    /// Attempt to find a [GnosisSafeWithdrawal] that has a given nonce and no
-    /// matching entry in the `audited_burns` table.
+    /// Get all withdrawals from a given safe that have not been verified against
+    /// the safe's verification source yet, ordered by id.
+    pub fn list_unverified_by_safe(
+        chain_id: u64,
+        safe_addr: &EthAddr,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        Ok(gnosis_safe_withdrawals::table
+            .filter(gnosis_safe_withdrawals::chain_id.eq(chain_id as i64))
+            .filter(gnosis_safe_withdrawals::safe_addr.eq(SqlEthAddr::from(safe_addr)))
+            .filter(
+                gnosis_safe_withdrawals::verification_status
+                    .eq(VerificationStatus::Unverified.to_string()),
+            )
+            .order_by(gnosis_safe_withdrawals::id)
+            .load(conn)?)
+    }
+
+    /// Attempt to find all [GnosisSafeWithdrawal] whose amount is not fully
+    /// allocated by entries in the `reserve_matches` table.
+    pub fn find_unaudited_withdrawals(conn: &Conn) -> Result<Vec<Self>, Error> {
+        Self::retain_not_fully_allocated(
+            gnosis_safe_withdrawals::table
+                .order_by(gnosis_safe_withdrawals::id)
+                .load(conn)?,
+            conn,
+        )
+    }
+
+    /// Attempt to find a [GnosisSafeWithdrawal] that has a given public key
+    /// and whose amount is not fully allocated by entries in the
+    /// `reserve_matches` table.
     pub fn find_unaudited_withdrawal_by_public_key(
         public_key_hex: &str,
         conn: &Conn,
     ) -> Result<Option<Self>, Error> {
-        Ok(gnosis_safe_withdrawals::table
-            .filter(gnosis_safe_withdrawals::mc_tx_out_public_key_hex.eq(public_key_hex))
-            .filter(not(exists(
-                audited_burns::table
-                    .select(audited_burns::gnosis_safe_withdrawal_id)
-                    .filter(
-                        audited_burns::gnosis_safe_withdrawal_id
-                            .nullable()
-                            .eq(gnosis_safe_withdrawals::id),
-                    ),
-            )))
-            .first(conn)
-            .optional()?)
+        Ok(
+            Self::find_unaudited_withdrawals_by_public_key(public_key_hex, conn)?
+                .into_iter()
+                .next(),
+        )
+    }
+
+    /// Attempt to find all [GnosisSafeWithdrawal]s that have a given public
+    /// key and whose amount is not fully allocated by entries in the
+    /// `reserve_matches` table. Several withdrawals can be batched into a single
+    /// burn, in which case they all reference its public key.
+    pub fn find_unaudited_withdrawals_by_public_key(
+        public_key_hex: &str,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        Self::retain_not_fully_allocated(
+            gnosis_safe_withdrawals::table
+                .filter(gnosis_safe_withdrawals::mc_tx_out_public_key_hex.eq(public_key_hex))
+                .order_by(gnosis_safe_withdrawals::id)
+                .load(conn)?,
+            conn,
+        )
+    }
+
+    /// Keep the withdrawals whose amount is not fully allocated by
+    /// `reserve_matches` entries. This happens here rather than in SQL since
+    /// amounts are stored as decimal strings, which SQLite cannot compare
+    /// numerically.
+    fn retain_not_fully_allocated(withdrawals: Vec<Self>, conn: &Conn) -> Result<Vec<Self>, Error> {
+        let mut result = Vec::with_capacity(withdrawals.len());
+        for withdrawal in withdrawals {
+            withdrawal.id().ok_or(Error::ObjectNotSaved)?;
+            if AuditedBurn::unallocated_withdrawal_amount(&withdrawal, conn)?.is_some() {
+                result.push(withdrawal);
+            }
+        }
+        Ok(result)
     }
 }
 
@@ -292,11 +456,12 @@ mod tests {
             withdrawal2,
         );
 
-        // Insert a row to the `audited_burns` table marking the first withdrawal as
+        // Insert a row to the `reserve_matches` table marking the first withdrawal as
         // audited. We should no longer be able to find it.
         AuditedBurn::associate_withdrawal_with_burn(
-            withdrawal1.id().unwrap(),
+            &withdrawal1,
             burn_tx_out1.id().unwrap(),
+            withdrawal1.mc_amount().unwrap(),
             &conn,
         )
         .unwrap();
@@ -323,8 +488,9 @@ mod tests {
         // Mark the second withdrawal as audited. We should no longer be able to find
         // it.
         AuditedBurn::associate_withdrawal_with_burn(
-            withdrawal2.id().unwrap(),
+            &withdrawal2,
             burn_tx_out2.id().unwrap(),
+            withdrawal2.mc_amount().unwrap(),
             &conn,
         )
         .unwrap();
@@ -387,11 +553,12 @@ mod tests {
             vec![withdrawal1.clone(), withdrawal2.clone()],
         );
 
-        // Insert a row to the `audited_burns` table marking the first withdrawal as
+        // Insert a row to the `reserve_matches` table marking the first withdrawal as
         // audited. We should no longer be able to find it.
         AuditedBurn::associate_withdrawal_with_burn(
-            withdrawal1.id().unwrap(),
+            &withdrawal1,
             burn_tx_out1.id().unwrap(),
+            withdrawal1.mc_amount().unwrap(),
             &conn,
         )
         .unwrap();
@@ -404,8 +571,9 @@ mod tests {
         // Mark the second withdrawal as audited. We should no longer be able to find
         // it.
         AuditedBurn::associate_withdrawal_with_burn(
-            withdrawal2.id().unwrap(),
+            &withdrawal2,
             burn_tx_out2.id().unwrap(),
+            withdrawal2.mc_amount().unwrap(),
             &conn,
         )
         .unwrap();
@@ -0,0 +1,521 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    db::{
+        last_insert_rowid,
+        models::SqlEthTxHash,
+        schema::{
+            burn_tx_outs, gnosis_safe_deposits, gnosis_safe_withdrawals, manual_matches, mint_txs,
+            reserve_matches,
+        },
+        transaction, AuditedBurn, AuditedMint, BurnTxOut, Conn, GnosisSafeDeposit,
+        GnosisSafeWithdrawal, MintTx,
+    },
+    error::Error,
+    gnosis::EthTxHash,
+    reserve::GnosisReserveBackend,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::{dsl::exists, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `manual_matches` table.
+/// This table records who forced a match between a [MintTx](super::MintTx)
+/// and a [GnosisSafeDeposit](super::GnosisSafeDeposit), or between a
+/// [BurnTxOut](super::BurnTxOut) and a
+/// [GnosisSafeWithdrawal](super::GnosisSafeWithdrawal), and why.
+/// The matched objects are referenced by identifiers that survive re-auditing
+/// and rewinding, which re-create them under new ids, so that the match can
+/// be re-applied afterwards (see [ManualMatch::reapply_all]).
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+pub struct ManualMatch {
+    /// Auto incrementing primary key.
+    id: Option<i32>,
+
+    /// Nonce of the matched MintTx, for mint matches.
+    mint_tx_nonce_hex: Option<String>,
+
+    /// Public key of the matched BurnTxOut, for burn matches.
+    burn_tx_out_public_key_hex: Option<String>,
+
+    /// The id of the EVM chain of the matched GnosisSafeDeposit or
+    /// GnosisSafeWithdrawal.
+    chain_id: i64,
+
+    /// Ethereum transaction hash of the matched GnosisSafeDeposit or
+    /// GnosisSafeWithdrawal.
+    eth_tx_hash: SqlEthTxHash,
+
+    /// The operator that made the match.
+    actor: String,
+
+    /// Why the match was made.
+    justification: String,
+
+    /// When the match was made.
+    created_at: NaiveDateTime,
+}
+
+impl ManualMatch {
+    /// Get id.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// Get the nonce of the matched MintTx, for mint matches.
+    pub fn mint_tx_nonce_hex(&self) -> Option<&str> {
+        self.mint_tx_nonce_hex.as_deref()
+    }
+
+    /// Get the public key of the matched BurnTxOut, for burn matches.
+    pub fn burn_tx_out_public_key_hex(&self) -> Option<&str> {
+        self.burn_tx_out_public_key_hex.as_deref()
+    }
+
+    /// Get the id of the EVM chain of the matched GnosisSafeDeposit or
+    /// GnosisSafeWithdrawal.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id as u64
+    }
+
+    /// Get the Ethereum transaction hash of the matched GnosisSafeDeposit or
+    /// GnosisSafeWithdrawal.
+    pub fn eth_tx_hash(&self) -> &EthTxHash {
+        &self.eth_tx_hash
+    }
+
+    /// Get the operator that made the match.
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+
+    /// Get the reason the match was made.
+    pub fn justification(&self) -> &str {
+        &self.justification
+    }
+
+    /// Get the time the match was made.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.created_at, Utc)
+    }
+
+    /// Force a match between a MintTx and a GnosisSafeDeposit that are not
+    /// fully matched yet, skipping the checks automatic matching performs.
+    /// The match allocates whatever amount is left of both of them, so
+    /// forcing several matches allows splitting a deposit across mints and
+    /// vice versa.
+    pub fn force_match_mint(
+        mint_tx_id: i32,
+        gnosis_safe_deposit_id: i32,
+        actor: &str,
+        justification: &str,
+        conn: &Conn,
+    ) -> Result<Self, Error> {
+        Self::validate_actor_and_justification(actor, justification)?;
+
+        transaction(conn, |conn| {
+            // Ensure both objects exist.
+            let nonce_hex = mint_txs::table
+                .select(mint_txs::nonce_hex)
+                .filter(mint_txs::id.eq(mint_tx_id))
+                .first::<String>(conn)?;
+            let deposit = gnosis_safe_deposits::table
+                .filter(gnosis_safe_deposits::id.eq(gnosis_safe_deposit_id))
+                .first::<GnosisSafeDeposit>(conn)?;
+
+            if !Self::link_mint(mint_tx_id, &deposit, conn)? {
+                return Err(Error::AlreadyExists(format!(
+                    "MintTx id={mint_tx_id} or GnosisSafeDeposit id={gnosis_safe_deposit_id} is already matched"
+                )));
+            }
+
+            let mut obj = Self {
+                id: None,
+                mint_tx_nonce_hex: Some(nonce_hex),
+                burn_tx_out_public_key_hex: None,
+                chain_id: deposit.chain_id() as i64,
+                eth_tx_hash: SqlEthTxHash::from(*deposit.eth_tx_hash()),
+                actor: actor.to_string(),
+                justification: justification.to_string(),
+                created_at: Utc::now().naive_utc(),
+            };
+            obj.insert(conn)?;
+            Ok(obj)
+        })
+    }
+
+    /// Force a match between a BurnTxOut and a GnosisSafeWithdrawal that are
+    /// not fully matched yet, skipping the checks automatic matching performs.
+    /// The match allocates whatever amount is left of both of them.
+    pub fn force_match_burn(
+        burn_tx_out_id: i32,
+        gnosis_safe_withdrawal_id: i32,
+        actor: &str,
+        justification: &str,
+        conn: &Conn,
+    ) -> Result<Self, Error> {
+        Self::validate_actor_and_justification(actor, justification)?;
+
+        transaction(conn, |conn| {
+            // Ensure both objects exist.
+            let public_key_hex = burn_tx_outs::table
+                .select(burn_tx_outs::public_key_hex)
+                .filter(burn_tx_outs::id.eq(burn_tx_out_id))
+                .first::<String>(conn)?;
+            let withdrawal = gnosis_safe_withdrawals::table
+                .filter(gnosis_safe_withdrawals::id.eq(gnosis_safe_withdrawal_id))
+                .first::<GnosisSafeWithdrawal>(conn)?;
+
+            if !Self::link_burn(burn_tx_out_id, &withdrawal, conn)? {
+                return Err(Error::AlreadyExists(format!(
+                    "BurnTxOut id={burn_tx_out_id} or GnosisSafeWithdrawal id={gnosis_safe_withdrawal_id} is already matched"
+                )));
+            }
+
+            let mut obj = Self {
+                id: None,
+                mint_tx_nonce_hex: None,
+                burn_tx_out_public_key_hex: Some(public_key_hex),
+                chain_id: withdrawal.chain_id() as i64,
+                eth_tx_hash: SqlEthTxHash::from(*withdrawal.eth_tx_hash()),
+                actor: actor.to_string(),
+                justification: justification.to_string(),
+                created_at: Utc::now().naive_utc(),
+            };
+            obj.insert(conn)?;
+            Ok(obj)
+        })
+    }
+
+    /// Re-apply all manual matches whose objects exist but are not linked in
+    /// `reserve_matches`. Re-auditing Gnosis transactions and
+    /// rewinding the ledger delete these links along with the objects they
+    /// reference, so this needs to be called once the objects were
+    /// re-created. Returns the number of re-applied matches.
+    pub fn reapply_all(conn: &Conn) -> Result<usize, Error> {
+        Ok(Self::reapply_matching(Self::list(conn)?, conn)?.len())
+    }
+
+    /// Re-apply the manual matches involving a MintTx, which is needed when
+    /// it gets re-synced after rewinding. Returns the re-applied matches.
+    pub fn reapply_for_mint_tx(mint_tx: &MintTx, conn: &Conn) -> Result<Vec<Self>, Error> {
+        Self::reapply_matching(
+            manual_matches::table
+                .filter(manual_matches::mint_tx_nonce_hex.eq(mint_tx.nonce_hex()))
+                .order_by(manual_matches::id)
+                .load(conn)?,
+            conn,
+        )
+    }
+
+    /// Re-apply the manual matches involving a BurnTxOut, which is needed
+    /// when it gets re-synced after rewinding. Returns the re-applied matches.
+    pub fn reapply_for_burn_tx_out(
+        burn_tx_out: &BurnTxOut,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        Self::reapply_matching(
+            manual_matches::table
+                .filter(manual_matches::burn_tx_out_public_key_hex.eq(burn_tx_out.public_key_hex()))
+                .order_by(manual_matches::id)
+                .load(conn)?,
+            conn,
+        )
+    }
+
+    fn reapply_matching(manual_matches: Vec<Self>, conn: &Conn) -> Result<Vec<Self>, Error> {
+        transaction(conn, |conn| {
+            let mut reapplied = Vec::new();
+            for manual_match in manual_matches {
+                if manual_match.reapply(conn)? {
+                    reapplied.push(manual_match);
+                }
+            }
+            Ok(reapplied)
+        })
+    }
+
+    /// Re-apply this match if both of its objects exist and some amount is
+    /// left to allocate between them. Returns whether the match was
+    /// re-applied.
+    fn reapply(&self, conn: &Conn) -> Result<bool, Error> {
+        if let Some(nonce_hex) = self.mint_tx_nonce_hex() {
+            let mint_tx_id = mint_txs::table
+                .select(mint_txs::id)
+                .filter(mint_txs::nonce_hex.eq(nonce_hex))
+                .order_by(mint_txs::id)
+                .first::<Option<i32>>(conn)
+                .optional()?
+                .flatten();
+            let deposit = gnosis_safe_deposits::table
+                .filter(gnosis_safe_deposits::chain_id.eq(self.chain_id))
+                .filter(gnosis_safe_deposits::eth_tx_hash.eq(&self.eth_tx_hash))
+                .first::<GnosisSafeDeposit>(conn)
+                .optional()?;
+            match (mint_tx_id, deposit) {
+                (Some(mint_tx_id), Some(deposit)) => Self::link_mint(mint_tx_id, &deposit, conn),
+                _ => Ok(false),
+            }
+        } else if let Some(public_key_hex) = self.burn_tx_out_public_key_hex() {
+            let burn_tx_out_id = burn_tx_outs::table
+                .select(burn_tx_outs::id)
+                .filter(burn_tx_outs::public_key_hex.eq(public_key_hex))
+                .first::<Option<i32>>(conn)
+                .optional()?
+                .flatten();
+            let withdrawal = gnosis_safe_withdrawals::table
+                .filter(gnosis_safe_withdrawals::chain_id.eq(self.chain_id))
+                .filter(gnosis_safe_withdrawals::eth_tx_hash.eq(&self.eth_tx_hash))
+                .first::<GnosisSafeWithdrawal>(conn)
+                .optional()?;
+            match (burn_tx_out_id, withdrawal) {
+                (Some(burn_tx_out_id), Some(withdrawal)) => {
+                    Self::link_burn(burn_tx_out_id, &withdrawal, conn)
+                }
+                _ => Ok(false),
+            }
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Link a MintTx with a GnosisSafeDeposit in `reserve_matches`,
+    /// allocating whatever amount is left of both of them. Returns false if
+    /// they are already linked or nothing is left to allocate.
+    fn link_mint(mint_tx_id: i32, deposit: &GnosisSafeDeposit, conn: &Conn) -> Result<bool, Error> {
+        let mint_tx_amount = mint_txs::table
+            .select(mint_txs::amount)
+            .filter(mint_txs::id.eq(mint_tx_id))
+            .first::<i64>(conn)? as u64;
+
+        // Both objects need to have some amount left to allocate, and cannot already
+        // be matched with each other. The amount fits in a u64 since it does not
+        // exceed what is left of the mint.
+        let amount = u128::from(
+            mint_tx_amount
+                .saturating_sub(MintTx::matched_amount(mint_tx_id, conn)?.unwrap_or_default()),
+        )
+        .min(
+            deposit
+                .amount()
+                .saturating_sub(AuditedMint::allocated_to_deposit(deposit, conn)?),
+        ) as u64;
+        let already_matched: bool = diesel::select(exists(
+            reserve_matches::table
+                .filter(reserve_matches::mint_tx_id.eq(mint_tx_id))
+                .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+                .filter(reserve_matches::reference.eq(deposit.eth_tx_hash().to_string())),
+        ))
+        .get_result(conn)?;
+        if already_matched || amount == 0 {
+            return Ok(false);
+        }
+
+        AuditedMint::associate_deposit_with_mint(deposit, mint_tx_id, amount, conn)?;
+        Ok(true)
+    }
+
+    /// Link a BurnTxOut with a GnosisSafeWithdrawal in `reserve_matches`,
+    /// allocating whatever amount is left of both of them. Returns false if
+    /// they are already linked or nothing is left to allocate.
+    fn link_burn(
+        burn_tx_out_id: i32,
+        withdrawal: &GnosisSafeWithdrawal,
+        conn: &Conn,
+    ) -> Result<bool, Error> {
+        let burn_tx_out_amount = burn_tx_outs::table
+            .select(burn_tx_outs::amount)
+            .filter(burn_tx_outs::id.eq(burn_tx_out_id))
+            .first::<i64>(conn)? as u64;
+
+        // Both objects need to have some amount left to allocate, and cannot already
+        // be matched with each other. The amount fits in a u64 since it does not
+        // exceed what is left of the burn.
+        let amount =
+            u128::from(burn_tx_out_amount.saturating_sub(
+                BurnTxOut::matched_amount(burn_tx_out_id, conn)?.unwrap_or_default(),
+            ))
+            .min(
+                withdrawal
+                    .amount()
+                    .saturating_sub(AuditedBurn::allocated_to_withdrawal(withdrawal, conn)?),
+            ) as u64;
+        let already_matched: bool = diesel::select(exists(
+            reserve_matches::table
+                .filter(reserve_matches::burn_tx_out_id.eq(burn_tx_out_id))
+                .filter(reserve_matches::backend.eq(GnosisReserveBackend::NAME))
+                .filter(reserve_matches::reference.eq(withdrawal.eth_tx_hash().to_string())),
+        ))
+        .get_result(conn)?;
+        if already_matched || amount == 0 {
+            return Ok(false);
+        }
+
+        AuditedBurn::associate_withdrawal_with_burn(withdrawal, burn_tx_out_id, amount, conn)?;
+        Ok(true)
+    }
+
+    /// Get a [ManualMatch] by id.
+    pub fn get(id: i32, conn: &Conn) -> Result<Self, Error> {
+        Ok(manual_matches::table
+            .filter(manual_matches::id.eq(id))
+            .first(conn)?)
+    }
+
+    /// Get all [ManualMatch]es, ordered by id.
+    pub fn list(conn: &Conn) -> Result<Vec<Self>, Error> {
+        Ok(manual_matches::table
+            .order_by(manual_matches::id)
+            .load(conn)?)
+    }
+
+    fn insert(&mut self, conn: &Conn) -> Result<(), Error> {
+        diesel::insert_into(manual_matches::table)
+            .values(&*self)
+            .execute(conn)?;
+
+        self.id = Some(diesel::select(last_insert_rowid).get_result::<i32>(conn)?);
+
+        Ok(())
+    }
+
+    fn validate_actor_and_justification(actor: &str, justification: &str) -> Result<(), Error> {
+        if actor.trim().is_empty() {
+            return Err(Error::Other("An actor is required".into()));
+        }
+        if justification.trim().is_empty() {
+            return Err(Error::Other("A justification is required".into()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{
+        test_utils::{
+            create_and_insert_burn_tx_out, create_gnosis_safe_deposit,
+            create_gnosis_safe_withdrawal, insert_gnosis_deposit, insert_gnosis_withdrawal,
+            insert_mint_tx_from_deposit, TestDbContext,
+        },
+        GnosisSafeWithdrawal,
+    };
+    use mc_common::logger::{test_with_logger, Logger};
+    use mc_transaction_core::TokenId;
+    use mc_util_test_helper::{RngType, SeedableRng};
+
+    #[test_with_logger]
+    fn force_match_mint_and_burn(logger: Logger) {
+        let mut rng: RngType = SeedableRng::from_seed([1u8; 32]);
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        // A deposit and a mint that were not matched automatically.
+        let mut deposit = create_gnosis_safe_deposit(100, &mut rng);
+        insert_gnosis_deposit(&mut deposit, &conn);
+        let mint_tx = insert_mint_tx_from_deposit(&deposit, &conn, &mut rng);
+        let mut other_deposit = create_gnosis_safe_deposit(200, &mut rng);
+        insert_gnosis_deposit(&mut other_deposit, &conn);
+
+        // Actor and justification are required.
+        assert!(ManualMatch::force_match_mint(
+            mint_tx.id().unwrap(),
+            deposit.id().unwrap(),
+            "alice",
+            " ",
+            &conn
+        )
+        .is_err());
+
+        // Unknown objects cannot be matched.
+        assert!(matches!(
+            ManualMatch::force_match_mint(1234, deposit.id().unwrap(), "alice", "why", &conn),
+            Err(Error::NotFound)
+        ));
+
+        let manual_match = ManualMatch::force_match_mint(
+            mint_tx.id().unwrap(),
+            deposit.id().unwrap(),
+            "alice",
+            "Confirmed with the custodian",
+            &conn,
+        )
+        .unwrap();
+        assert_eq!(manual_match.actor(), "alice");
+        assert_eq!(manual_match.mint_tx_nonce_hex(), Some(mint_tx.nonce_hex()));
+        assert_eq!(manual_match.eth_tx_hash(), deposit.eth_tx_hash());
+        assert_eq!(MintTx::find_unaudited_mint_txs(&conn).unwrap(), vec![]);
+        assert_eq!(
+            ManualMatch::get(manual_match.id().unwrap(), &conn).unwrap(),
+            manual_match
+        );
+
+        // Already matched objects cannot be matched again.
+        assert!(matches!(
+            ManualMatch::force_match_mint(
+                mint_tx.id().unwrap(),
+                other_deposit.id().unwrap(),
+                "alice",
+                "why",
+                &conn
+            ),
+            Err(Error::AlreadyExists(_))
+        ));
+
+        // Burns work the same way.
+        let mut withdrawal = create_gnosis_safe_withdrawal(100, &mut rng);
+        insert_gnosis_withdrawal(&mut withdrawal, &conn);
+        let burn_tx_out = create_and_insert_burn_tx_out(TokenId::from(1), 50, &conn, &mut rng);
+
+        let burn_match = ManualMatch::force_match_burn(
+            burn_tx_out.id().unwrap(),
+            withdrawal.id().unwrap(),
+            "bob",
+            "Partial redemption",
+            &conn,
+        )
+        .unwrap();
+        assert_eq!(
+            burn_match.burn_tx_out_public_key_hex(),
+            Some(burn_tx_out.public_key_hex())
+        );
+        assert_eq!(
+            BurnTxOut::find_unaudited_burn_tx_outs(&conn).unwrap(),
+            vec![]
+        );
+
+        // The burn only covers half of the withdrawal, another burn can cover the rest.
+        assert_eq!(
+            AuditedBurn::allocated_to_withdrawal(&withdrawal, &conn).unwrap(),
+            50
+        );
+        assert_eq!(
+            GnosisSafeWithdrawal::find_unaudited_withdrawals(&conn).unwrap(),
+            vec![withdrawal.clone()]
+        );
+        let other_burn_tx_out =
+            create_and_insert_burn_tx_out(TokenId::from(1), 80, &conn, &mut rng);
+        let other_burn_match = ManualMatch::force_match_burn(
+            other_burn_tx_out.id().unwrap(),
+            withdrawal.id().unwrap(),
+            "bob",
+            "Remainder of the redemption",
+            &conn,
+        )
+        .unwrap();
+        assert_eq!(
+            BurnTxOut::matched_amount(other_burn_tx_out.id().unwrap(), &conn).unwrap(),
+            Some(50)
+        );
+        assert!(GnosisSafeWithdrawal::find_unaudited_withdrawals(&conn)
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            ManualMatch::list(&conn).unwrap(),
+            vec![manual_match, burn_match, other_burn_match]
+        );
+    }
+}
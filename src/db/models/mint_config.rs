@@ -52,6 +52,11 @@ impl MintConfig {
         self.mint_limit as u64
     }
 
+    /// Get the protobuf-serialized MintConfig.
+    pub fn protobuf(&self) -> &[u8] {
+        &self.protobuf
+    }
+
     /// Get the original MintConfig
     pub fn decode(&self) -> Result<CoreMintConfig, Error> {
         Ok(decode(&self.protobuf)?)
@@ -115,9 +120,10 @@ impl MintConfig {
         &self,
         block_index: BlockIndex,
         conn: &Conn,
-    ) -> Result<u64, Error> {
+    ) -> Result<u128, Error> {
         // Note: We sum in Rust and not Sqlite due to Sqlite not properly supporting
-        // unsigned ints.
+        // unsigned ints. The sum is not bounded to u64 since nothing stops mints from
+        // exceeding the limits.
         let mint_amounts: Vec<i64> = mint_txs::table
             .inner_join(mint_configs::table.inner_join(mint_config_txs::table))
             .filter(mint_config_txs::block_index.lt(mint_txs::block_index))
@@ -125,7 +131,10 @@ impl MintConfig {
             .filter(mint_txs::block_index.lt(block_index as i64))
             .select(mint_txs::amount)
             .load::<i64>(conn)?;
-        Ok(mint_amounts.into_iter().map(|val| val as u64).sum())
+        Ok(mint_amounts
+            .into_iter()
+            .map(|val| u128::from(val as u64))
+            .sum())
     }
 }
 
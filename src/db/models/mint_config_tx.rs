@@ -87,6 +87,11 @@ impl MintConfigTx {
         self.tombstone_block as u64
     }
 
+    /// Get the protobuf-serialized MintConfigTx.
+    pub fn protobuf(&self) -> &[u8] {
+        &self.protobuf
+    }
+
     /// Get the original MintConfigTx
     pub fn decode(&self) -> Result<CoreMintConfigTx, Error> {
         Ok(decode(&self.protobuf)?)
@@ -178,9 +183,10 @@ impl MintConfigTx {
         &self,
         block_index: BlockIndex,
         conn: &Conn,
-    ) -> Result<u64, Error> {
+    ) -> Result<u128, Error> {
         // Note: We sum in Rust and not Sqlite due to Sqlite not properly supporting
-        // unsigned ints.
+        // unsigned ints. The sum is not bounded to u64 since nothing stops mints from
+        // exceeding the limits.
         // We default our id to 0 since SQLite auto-inc values start at 1.
         let mint_amounts: Vec<i64> = mint_txs::table
             .inner_join(mint_configs::table.inner_join(mint_config_txs::table))
@@ -189,7 +195,22 @@ impl MintConfigTx {
             .filter(mint_txs::block_index.lt(block_index as i64))
             .select(mint_txs::amount)
             .load::<i64>(conn)?;
-        Ok(mint_amounts.into_iter().map(|val| val as u64).sum())
+        Ok(mint_amounts
+            .into_iter()
+            .map(|val| u128::from(val as u64))
+            .sum())
+    }
+
+    /// Get the ids of all tokens that have a MintConfigTx, in ascending order.
+    pub fn list_token_ids(conn: &Conn) -> Result<Vec<TokenId>, Error> {
+        Ok(mint_config_txs::table
+            .select(mint_config_txs::token_id)
+            .distinct()
+            .order_by(mint_config_txs::token_id)
+            .load::<i64>(conn)?
+            .into_iter()
+            .map(|token_id| TokenId::from(token_id as u64))
+            .collect())
     }
 
     /// Get mint config txs by block index
@@ -202,6 +223,29 @@ impl MintConfigTx {
             .order_by(mint_config_txs::id.asc())
             .load(conn)?)
     }
+
+    /// Delete all [MintConfigTx]s (and their [MintConfig]s) that were included
+    /// in blocks after a given block index. Returns the number of deleted
+    /// mint config txs.
+    pub fn delete_after_block(block_index: BlockIndex, conn: &Conn) -> Result<usize, Error> {
+        let mint_config_tx_ids = mint_config_txs::table
+            .filter(mint_config_txs::block_index.gt(block_index as i64))
+            .select(mint_config_txs::id);
+
+        diesel::delete(
+            mint_configs::table.filter(
+                mint_configs::mint_config_tx_id
+                    .nullable()
+                    .eq_any(mint_config_tx_ids),
+            ),
+        )
+        .execute(conn)?;
+
+        Ok(diesel::delete(
+            mint_config_txs::table.filter(mint_config_txs::block_index.gt(block_index as i64)),
+        )
+        .execute(conn)?)
+    }
 }
 
 #[cfg(test)]
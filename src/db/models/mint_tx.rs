@@ -5,16 +5,14 @@
 use crate::{
     db::{
         last_insert_rowid,
-        schema::{audited_mints, mint_txs},
+        schema::{mint_txs, reserve_matches},
         Conn, MintConfig,
     },
-    Error,
+    gnosis::EthTxHash,
+    Error, MintTxNonce,
 };
 use chrono::{DateTime, NaiveDateTime, Utc};
-use diesel::{
-    dsl::{exists, not},
-    prelude::*,
-};
+use diesel::{dsl::sql, expression::SqlLiteral, prelude::*, sql_types::Bool, sqlite::Sqlite};
 use hex::ToHex;
 use mc_account_keys::PublicAddress;
 use mc_api::printable::PrintableWrapper;
@@ -59,6 +57,9 @@ pub struct MintTx {
 
     /// The mint config id, when we are able to match it with one.
     mint_config_id: Option<i32>,
+
+    /// The decoded nonce, when it is of a known type.
+    decoded_nonce: Option<String>,
 }
 
 impl MintTx {
@@ -93,6 +94,20 @@ impl MintTx {
         &self.nonce_hex
     }
 
+    /// Get the decoded nonce, when it is of a known type.
+    pub fn decoded_nonce(&self) -> Option<&str> {
+        self.decoded_nonce.as_deref()
+    }
+
+    /// Decode the nonce, failing with [Error::UnknownMintTxNonce] if it is not
+    /// of a known type.
+    pub fn nonce(&self) -> Result<MintTxNonce, Error> {
+        hex::decode(&self.nonce_hex)
+            .ok()
+            .and_then(|bytes| MintTxNonce::try_from(&bytes[..]).ok())
+            .ok_or_else(|| Error::UnknownMintTxNonce(self.nonce_hex.clone()))
+    }
+
     /// Get recipient b58 address.
     pub fn recipient_b58_addr(&self) -> &str {
         &self.recipient_b58_addr
@@ -108,6 +123,11 @@ impl MintTx {
         self.mint_config_id
     }
 
+    /// Get the protobuf-serialized MintTx.
+    pub fn protobuf(&self) -> &[u8] {
+        &self.protobuf
+    }
+
     /// Get the original MintTx
     pub fn decode(&self) -> Result<CoreMintTx, Error> {
         Ok(decode(&self.protobuf)?)
@@ -137,6 +157,9 @@ impl MintTx {
             tombstone_block: tx.prefix.tombstone_block as i64,
             protobuf: encode(tx),
             mint_config_id,
+            decoded_nonce: MintTxNonce::try_from(&tx.prefix.nonce[..])
+                .ok()
+                .map(|nonce| nonce.to_string()),
         })
     }
 
@@ -171,35 +194,96 @@ impl MintTx {
         Ok(mint_tx)
     }
 
-    /// Attempt to find all [MintTx]s that do not have a matching entry in the
-    /// `audited_mints` table.
+    /// Populate the decoded nonce of [MintTx]s that were stored before it was
+    /// recorded. Returns the number of updated rows.
+    pub fn backfill_decoded_nonces(conn: &Conn) -> Result<usize, Error> {
+        let rows: Vec<(Option<i32>, String)> = mint_txs::table
+            .select((mint_txs::id, mint_txs::nonce_hex))
+            .filter(mint_txs::decoded_nonce.is_null())
+            .load(conn)?;
+
+        let mut num_updated = 0;
+        for (id, nonce_hex) in rows {
+            let decoded_nonce = match hex::decode(&nonce_hex)
+                .ok()
+                .and_then(|bytes| MintTxNonce::try_from(&bytes[..]).ok())
+            {
+                Some(nonce) => nonce.to_string(),
+                None => continue,
+            };
+            num_updated += diesel::update(mint_txs::table.filter(mint_txs::id.eq(id)))
+                .set(mint_txs::decoded_nonce.eq(decoded_nonce))
+                .execute(conn)?;
+        }
+        Ok(num_updated)
+    }
+
+    /// Attempt to find all [MintTx]s that are not fully matched by any reserve
+    /// backend.
     pub fn find_unaudited_mint_txs(conn: &Conn) -> Result<Vec<Self>, Error> {
         Ok(mint_txs::table
-            .filter(not(exists(
-                audited_mints::table
-                    .select(audited_mints::mint_tx_id)
-                    .filter(audited_mints::mint_tx_id.nullable().eq(mint_txs::id)),
-            )))
+            .filter(Self::not_fully_matched())
             .load(conn)?)
     }
 
-    /// Attempt to find a [MintTx] that has a given nonce and no matching entry
-    /// in the `audited_mints` table.
+    /// Attempt to find a [MintTx] that has a given nonce and that is not fully
+    /// matched by any reserve backend.
     pub fn find_unaudited_mint_tx_by_nonce(
         nonce_hex: &str,
         conn: &Conn,
     ) -> Result<Option<Self>, Error> {
         Ok(mint_txs::table
             .filter(mint_txs::nonce_hex.eq(nonce_hex))
-            .filter(not(exists(
-                audited_mints::table
-                    .select(audited_mints::mint_tx_id)
-                    .filter(audited_mints::mint_tx_id.nullable().eq(mint_txs::id)),
-            )))
+            .filter(Self::not_fully_matched())
             .first(conn)
             .optional()?)
     }
 
+    /// Attempt to find all [MintTx]s whose nonce references a given Gnosis
+    /// safe deposit, either as a whole or as one of its parts, and that are
+    /// not fully audited yet.
+    pub fn find_unaudited_mint_txs_for_deposit(
+        chain_id: u64,
+        eth_tx_hash: &EthTxHash,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        let mut nonce_filter: Box<dyn BoxableExpression<mint_txs::table, Sqlite, SqlType = Bool>> =
+            Box::new(
+                mint_txs::nonce_hex.eq_any(MintTxNonce::deposit_nonce_hexes(chain_id, eth_tx_hash)),
+            );
+        for part_prefix_hex in MintTxNonce::deposit_part_prefix_hexes(chain_id, eth_tx_hash) {
+            nonce_filter =
+                Box::new(nonce_filter.or(mint_txs::nonce_hex.like(format!("{part_prefix_hex}%"))));
+        }
+
+        Ok(mint_txs::table
+            .filter(nonce_filter)
+            .filter(Self::not_fully_matched())
+            .order_by(mint_txs::id)
+            .load(conn)?)
+    }
+
+    /// Get the total amount of a [MintTx] that is matched with deposits, by
+    /// any reserve backend, or None if it is not matched at all. The mint is
+    /// fully matched once this reaches its amount.
+    pub fn matched_amount(mint_tx_id: i32, conn: &Conn) -> Result<Option<u64>, Error> {
+        let amounts: Vec<i64> = reserve_matches::table
+            .filter(reserve_matches::mint_tx_id.eq(mint_tx_id))
+            .select(reserve_matches::amount)
+            .load(conn)?;
+        Ok((!amounts.is_empty()).then(|| amounts.into_iter().map(|val| val as u64).sum()))
+    }
+
+    /// Filter for mints that are not fully matched by the entries of the
+    /// `reserve_matches` table.
+    fn not_fully_matched() -> SqlLiteral<Bool> {
+        sql(
+            "(NOT EXISTS (SELECT 1 FROM reserve_matches WHERE reserve_matches.mint_tx_id = mint_txs.id) \
+             OR (SELECT SUM(reserve_matches.amount) FROM reserve_matches \
+             WHERE reserve_matches.mint_tx_id = mint_txs.id) < mint_txs.amount)",
+        )
+    }
+
     /// Get a collection of mint token amounts
     pub fn get_mint_amounts(conn: &Conn, token_id: TokenId) -> Result<Vec<(TokenId, u64)>, Error> {
         let query = mint_txs::table
@@ -225,6 +309,15 @@ impl MintTx {
             .load(conn)?)
     }
 
+    /// Delete all [MintTx]s that were included in blocks after a given block
+    /// index. Returns the number of deleted rows.
+    pub fn delete_after_block(block_index: BlockIndex, conn: &Conn) -> Result<usize, Error> {
+        Ok(
+            diesel::delete(mint_txs::table.filter(mint_txs::block_index.gt(block_index as i64)))
+                .execute(conn)?,
+        )
+    }
+
     /// Get the list of Ed25519 public keys that signed this MintTx.
     pub fn get_signers(&self, conn: &Conn) -> Result<Vec<Ed25519Public>, Error> {
         let core_mint_tx = self.decode()?;
@@ -245,7 +338,7 @@ impl MintTx {
 mod tests {
     use super::*;
     use crate::db::{
-        models::AuditedMint,
+        models::{AuditedMint, AuditedReserveMint, ReserveDeposit},
         test_utils::{create_gnosis_safe_deposit, insert_gnosis_deposit, TestDbContext},
     };
     use mc_common::logger::{test_with_logger, Logger};
@@ -342,12 +435,13 @@ mod tests {
             sql_mint_tx2
         );
 
-        // Insert a row to the `audited_mints` table marking the first MintTx as
+        // Insert a row to the `reserve_matches` table marking the first MintTx as
         // audited. We should no longer be able to find it.
         insert_gnosis_deposit(&mut deposit1, &conn);
         AuditedMint::associate_deposit_with_mint(
-            deposit1.id().unwrap(),
+            &deposit1,
             sql_mint_tx1.id().unwrap(),
+            sql_mint_tx1.amount(),
             &conn,
         )
         .unwrap();
@@ -369,8 +463,9 @@ mod tests {
         // Mark the second mint as audited. We should no longer be able to find it.
         insert_gnosis_deposit(&mut deposit2, &conn);
         AuditedMint::associate_deposit_with_mint(
-            deposit2.id().unwrap(),
+            &deposit2,
             sql_mint_tx2.id().unwrap(),
+            sql_mint_tx2.amount(),
             &conn,
         )
         .unwrap();
@@ -390,6 +485,83 @@ mod tests {
         .is_none());
     }
 
+    #[test_with_logger]
+    fn test_matched_amount_covers_all_reserve_backends(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let token_id1 = TokenId::from(1);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let (_mint_config_tx1, signers1) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        let mint_tx1 = create_mint_tx(token_id1, &signers1, 100, &mut rng);
+        let mint_tx2 = create_mint_tx(token_id1, &signers1, 100, &mut rng);
+        let sql_mint_tx1 =
+            MintTx::insert_from_core_mint_tx(5, None, None, &mint_tx1, &conn).unwrap();
+        let sql_mint_tx2 =
+            MintTx::insert_from_core_mint_tx(5, None, None, &mint_tx2, &conn).unwrap();
+        assert_eq!(
+            MintTx::matched_amount(sql_mint_tx1.id().unwrap(), &conn).unwrap(),
+            None
+        );
+
+        // Partially match the first mint with a Gnosis safe deposit. It is still
+        // unaudited.
+        let mut gnosis_deposit = create_gnosis_safe_deposit(100, &mut rng);
+        insert_gnosis_deposit(&mut gnosis_deposit, &conn);
+        AuditedMint::associate_deposit_with_mint(
+            &gnosis_deposit,
+            sql_mint_tx1.id().unwrap(),
+            50,
+            &conn,
+        )
+        .unwrap();
+        assert_eq!(
+            MintTx::matched_amount(sql_mint_tx1.id().unwrap(), &conn).unwrap(),
+            Some(50)
+        );
+        assert_eq!(
+            MintTx::find_unaudited_mint_txs(&conn).unwrap(),
+            vec![sql_mint_tx1.clone(), sql_mint_tx2.clone()]
+        );
+
+        // Another reserve backend cannot match the rest of it, since its matches cover
+        // entire mints.
+        let mut deposit1 = ReserveDeposit::new(
+            "bank",
+            "tx1",
+            token_id1,
+            100,
+            sql_mint_tx1.nonce_hex(),
+            None,
+        );
+        deposit1.insert(&conn).unwrap();
+        assert!(matches!(
+            AuditedReserveMint::try_match_deposit_with_mint(&deposit1, &conn),
+            Err(Error::AlreadyExists(_))
+        ));
+
+        // Matching the second mint with a reserve deposit fully matches it.
+        let mut deposit2 = ReserveDeposit::new(
+            "bank",
+            "tx2",
+            token_id1,
+            100,
+            sql_mint_tx2.nonce_hex(),
+            None,
+        );
+        deposit2.insert(&conn).unwrap();
+        AuditedReserveMint::try_match_deposit_with_mint(&deposit2, &conn).unwrap();
+        assert_eq!(
+            MintTx::matched_amount(sql_mint_tx2.id().unwrap(), &conn).unwrap(),
+            Some(100)
+        );
+        assert_eq!(
+            MintTx::find_unaudited_mint_txs(&conn).unwrap(),
+            vec![sql_mint_tx1]
+        );
+    }
+
     #[test_with_logger]
     fn test_get_mint_amounts(logger: Logger) {
         let mut rng = mc_util_test_helper::get_seeded_rng();
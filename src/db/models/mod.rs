@@ -1,31 +1,55 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
+mod audit_event;
+mod audit_event_acknowledgement;
 mod audited_burn;
 mod audited_mint;
+mod audited_reserve_burn;
+mod audited_reserve_mint;
 mod block_audit_data;
 mod block_balance;
 mod burn_tx_out;
 mod counters;
+mod gnosis_safe_audit_config;
 mod gnosis_safe_deposit;
+mod gnosis_safe_sync_state;
 mod gnosis_safe_tx;
 mod gnosis_safe_withdrawal;
+mod manual_match;
 mod mint_config;
 mod mint_config_tx;
 mod mint_tx;
+mod outbox_notification;
+mod reserve_deposit;
+mod reserve_match;
+mod reserve_withdrawal;
+mod signer_set_change;
 mod sql_types;
 
 pub use self::{
+    audit_event::AuditEvent,
+    audit_event_acknowledgement::AuditEventAcknowledgement,
     audited_burn::AuditedBurn,
     audited_mint::AuditedMint,
+    audited_reserve_burn::AuditedReserveBurn,
+    audited_reserve_mint::AuditedReserveMint,
     block_audit_data::BlockAuditData,
     block_balance::BlockBalance,
     burn_tx_out::BurnTxOut,
     counters::Counters,
+    gnosis_safe_audit_config::GnosisSafeAuditConfig,
     gnosis_safe_deposit::GnosisSafeDeposit,
+    gnosis_safe_sync_state::GnosisSafeSyncState,
     gnosis_safe_tx::GnosisSafeTx,
     gnosis_safe_withdrawal::GnosisSafeWithdrawal,
+    manual_match::ManualMatch,
     mint_config::MintConfig,
     mint_config_tx::MintConfigTx,
     mint_tx::MintTx,
-    sql_types::{SqlEthAddr, SqlEthTxHash, SqlEthTxValue},
+    outbox_notification::OutboxNotification,
+    reserve_deposit::ReserveDeposit,
+    reserve_match::ReserveMatch,
+    reserve_withdrawal::ReserveWithdrawal,
+    signer_set_change::SignerSetChange,
+    sql_types::{SqlEthAddr, SqlEthTxHash, SqlEthTxValue, SqlU128},
 };
@@ -0,0 +1,198 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    anomaly::AnomalyEvent,
+    db::{last_insert_rowid, schema::notification_outbox, Conn},
+    error::Error,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `notification_outbox` table.
+/// This table stores anomaly notifications that need to be delivered to the
+/// configured webhooks, so that they are not lost across restarts.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+#[table_name = "notification_outbox"]
+pub struct OutboxNotification {
+    /// Auto incrementing primary key.
+    id: Option<i32>,
+
+    /// The JSON-encoded [AnomalyEvent].
+    payload: String,
+
+    /// When the anomaly was detected.
+    created_at: NaiveDateTime,
+
+    /// Number of failed delivery attempts so far.
+    num_attempts: i32,
+
+    /// When the next delivery attempt should happen.
+    next_attempt_at: NaiveDateTime,
+
+    /// When the notification was delivered, if it was.
+    delivered_at: Option<NaiveDateTime>,
+
+    /// The error of the last failed delivery attempt.
+    last_error: Option<String>,
+}
+
+impl OutboxNotification {
+    /// Get id.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// Get the JSON-encoded [AnomalyEvent].
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+
+    /// Decode the [AnomalyEvent].
+    pub fn decode(&self) -> Result<AnomalyEvent, Error> {
+        serde_json::from_str(&self.payload)
+            .map_err(|err| Error::Other(format!("Failed decoding anomaly event: {err}")))
+    }
+
+    /// Get the time the anomaly was detected.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.created_at, Utc)
+    }
+
+    /// Get the number of failed delivery attempts so far.
+    pub fn num_attempts(&self) -> u32 {
+        self.num_attempts as u32
+    }
+
+    /// Get the time the next delivery attempt should happen.
+    pub fn next_attempt_at(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.next_attempt_at, Utc)
+    }
+
+    /// Get the time the notification was delivered, if it was.
+    pub fn delivered_at(&self) -> Option<DateTime<Utc>> {
+        self.delivered_at
+            .map(|ts| DateTime::from_naive_utc_and_offset(ts, Utc))
+    }
+
+    /// Get the error of the last failed delivery attempt.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Queue a notification about an anomaly for delivery.
+    pub fn enqueue(event: &AnomalyEvent, conn: &Conn) -> Result<Self, Error> {
+        let now = Utc::now().naive_utc();
+        let mut obj = Self {
+            id: None,
+            payload: serde_json::to_string(event)
+                .map_err(|err| Error::Other(format!("Failed encoding anomaly event: {err}")))?,
+            created_at: now,
+            num_attempts: 0,
+            next_attempt_at: now,
+            delivered_at: None,
+            last_error: None,
+        };
+
+        diesel::insert_into(notification_outbox::table)
+            .values(&obj)
+            .execute(conn)?;
+
+        obj.id = Some(diesel::select(last_insert_rowid).get_result::<i32>(conn)?);
+
+        Ok(obj)
+    }
+
+    /// Get undelivered notifications whose next delivery attempt is due at a
+    /// given time, oldest first.
+    pub fn find_due(now: DateTime<Utc>, limit: u64, conn: &Conn) -> Result<Vec<Self>, Error> {
+        Ok(notification_outbox::table
+            .filter(notification_outbox::delivered_at.is_null())
+            .filter(notification_outbox::next_attempt_at.le(now.naive_utc()))
+            .order_by(notification_outbox::id)
+            .limit(limit as i64)
+            .load(conn)?)
+    }
+
+    /// Mark this notification as delivered.
+    pub fn mark_delivered(&mut self, now: DateTime<Utc>, conn: &Conn) -> Result<(), Error> {
+        let id = self.id.ok_or(Error::ObjectNotSaved)?;
+        self.delivered_at = Some(now.naive_utc());
+
+        diesel::update(notification_outbox::table.filter(notification_outbox::id.eq(id)))
+            .set(notification_outbox::delivered_at.eq(self.delivered_at))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// Record a failed delivery attempt, scheduling the next one at a given
+    /// time.
+    pub fn mark_failed(
+        &mut self,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+        conn: &Conn,
+    ) -> Result<(), Error> {
+        let id = self.id.ok_or(Error::ObjectNotSaved)?;
+        self.num_attempts += 1;
+        self.next_attempt_at = next_attempt_at.naive_utc();
+        self.last_error = Some(error.to_string());
+
+        diesel::update(notification_outbox::table.filter(notification_outbox::id.eq(id)))
+            .set((
+                notification_outbox::num_attempts.eq(self.num_attempts),
+                notification_outbox::next_attempt_at.eq(self.next_attempt_at),
+                notification_outbox::last_error.eq(&self.last_error),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anomaly::AnomalyKind, db::test_utils::TestDbContext};
+    use chrono::Duration;
+    use mc_common::logger::{test_with_logger, Logger};
+
+    #[test_with_logger]
+    fn outbox_lifecycle(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let event1 = AnomalyEvent::new(AnomalyKind::BurnExceedingBalance, "too much");
+        let event2 = AnomalyEvent::new(AnomalyKind::InvalidMultiSigTransaction, "bad tx");
+        let mut notification1 = OutboxNotification::enqueue(&event1, &conn).unwrap();
+        let mut notification2 = OutboxNotification::enqueue(&event2, &conn).unwrap();
+        assert_eq!(notification1.decode().unwrap(), event1);
+
+        let now = Utc::now();
+        assert_eq!(
+            OutboxNotification::find_due(now, 10, &conn).unwrap(),
+            vec![notification1.clone(), notification2.clone()]
+        );
+        assert_eq!(
+            OutboxNotification::find_due(now, 1, &conn).unwrap(),
+            vec![notification1.clone()]
+        );
+
+        // A failed notification is retried later, a delivered one never.
+        notification1
+            .mark_failed("connection refused", now + Duration::minutes(1), &conn)
+            .unwrap();
+        notification2.mark_delivered(now, &conn).unwrap();
+        assert_eq!(
+            OutboxNotification::find_due(now, 10, &conn).unwrap(),
+            vec![]
+        );
+
+        let due = OutboxNotification::find_due(now + Duration::minutes(1), 10, &conn).unwrap();
+        assert_eq!(due, vec![notification1]);
+        assert_eq!(due[0].num_attempts(), 1);
+        assert_eq!(due[0].last_error(), Some("connection refused"));
+    }
+}
@@ -0,0 +1,227 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    db::{
+        last_insert_rowid,
+        models::SqlU128,
+        schema::{reserve_deposits, reserve_matches},
+        Conn,
+    },
+    error::Error,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::{
+    dsl::{exists, not},
+    prelude::*,
+};
+use mc_transaction_core::TokenId;
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `reserve_deposits` table.
+/// This table stores deposits reported by reserve backends other than the
+/// Gnosis safe one.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+pub struct ReserveDeposit {
+    /// Auto incrementing primary key.
+    id: Option<i32>,
+
+    /// The name of the reserve backend that reported this deposit.
+    source: String,
+
+    /// The reference the custodian uses for this deposit.
+    external_ref: String,
+
+    /// The MobileCoin token id this deposit backs.
+    token_id: i64,
+
+    /// Amount deposited, in the MobileCoin token's precision.
+    /// This is not guaranteed to fit in a MobileCoin (u64) amount.
+    amount: SqlU128,
+
+    /// The hex-encoded MintTx nonce we expect to see on the MobileCoin
+    /// blockchain
+    expected_mc_mint_tx_nonce_hex: String,
+
+    /// When the custodian recorded the deposit, if known.
+    occurred_at: Option<NaiveDateTime>,
+}
+
+impl ReserveDeposit {
+    /// Construct a new [ReserveDeposit] object.
+    pub fn new(
+        source: &str,
+        external_ref: &str,
+        token_id: TokenId,
+        amount: u128,
+        expected_mc_mint_tx_nonce_hex: &str,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id: None,
+            source: source.to_string(),
+            external_ref: external_ref.to_string(),
+            token_id: *token_id as i64,
+            amount: amount.into(),
+            expected_mc_mint_tx_nonce_hex: expected_mc_mint_tx_nonce_hex.to_string(),
+            occurred_at: occurred_at.map(|ts| ts.naive_utc()),
+        }
+    }
+
+    /// Get id.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// Get the name of the reserve backend that reported this deposit.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Get the reference the custodian uses for this deposit.
+    pub fn external_ref(&self) -> &str {
+        &self.external_ref
+    }
+
+    /// Get token id.
+    pub fn token_id(&self) -> TokenId {
+        TokenId::from(self.token_id as u64)
+    }
+
+    /// Get amount deposited.
+    pub fn amount(&self) -> u128 {
+        *self.amount
+    }
+
+    /// Get amount deposited, if it fits in a MobileCoin amount.
+    pub fn mc_amount(&self) -> Option<u64> {
+        u64::try_from(*self.amount).ok()
+    }
+
+    /// Get the hex-encoded MintTx nonce we expect to see on the MobileCoin
+    /// blockchain.
+    pub fn expected_mc_mint_tx_nonce_hex(&self) -> &str {
+        &self.expected_mc_mint_tx_nonce_hex
+    }
+
+    /// Get the time the custodian recorded the deposit, if known.
+    pub fn occurred_at(&self) -> Option<DateTime<Utc>> {
+        self.occurred_at
+            .map(|ts| DateTime::from_naive_utc_and_offset(ts, Utc))
+    }
+
+    /// Insert a deposit into the database. Returns
+    /// [Error::AlreadyExists] if the source already reported a deposit with
+    /// the same external reference.
+    pub fn insert(&mut self, conn: &Conn) -> Result<(), Error> {
+        if let Some(id) = self.id {
+            return Err(Error::AlreadyExists(format!(
+                "ReserveDeposit already has an id ({id})"
+            )));
+        }
+
+        diesel::insert_into(reserve_deposits::table)
+            .values(self.clone())
+            .execute(conn)?;
+
+        self.id = Some(diesel::select(last_insert_rowid).get_result::<i32>(conn)?);
+
+        Ok(())
+    }
+
+    /// Get all [ReserveDeposit]s, ordered by id.
+    pub fn list(conn: &Conn) -> Result<Vec<Self>, Error> {
+        Ok(reserve_deposits::table
+            .order_by(reserve_deposits::id)
+            .load(conn)?)
+    }
+
+    /// Attempt to find all [ReserveDeposit]s that do not have a matching entry
+    /// in the `reserve_matches` table, optionally only those reported by a
+    /// given source.
+    pub fn find_unaudited_deposits(source: Option<&str>, conn: &Conn) -> Result<Vec<Self>, Error> {
+        let mut query = reserve_deposits::table
+            .filter(not(exists(
+                reserve_matches::table
+                    .select(reserve_matches::id)
+                    .filter(reserve_matches::backend.eq(reserve_deposits::source))
+                    .filter(reserve_matches::reference.eq(reserve_deposits::external_ref))
+                    .filter(reserve_matches::mint_tx_id.is_not_null()),
+            )))
+            .into_boxed();
+
+        if let Some(source) = source {
+            query = query.filter(reserve_deposits::source.eq(source));
+        }
+
+        Ok(query.order_by(reserve_deposits::id).load(conn)?)
+    }
+
+    /// Attempt to find a [ReserveDeposit] of a given source that has a given
+    /// nonce and no matching entry in the `reserve_matches` table.
+    pub fn find_unaudited_deposit_by_nonce(
+        source: &str,
+        nonce_hex: &str,
+        conn: &Conn,
+    ) -> Result<Option<Self>, Error> {
+        Ok(reserve_deposits::table
+            .filter(reserve_deposits::source.eq(source))
+            .filter(reserve_deposits::expected_mc_mint_tx_nonce_hex.eq(nonce_hex))
+            .filter(not(exists(
+                reserve_matches::table
+                    .select(reserve_matches::id)
+                    .filter(reserve_matches::backend.eq(reserve_deposits::source))
+                    .filter(reserve_matches::reference.eq(reserve_deposits::external_ref))
+                    .filter(reserve_matches::mint_tx_id.is_not_null()),
+            )))
+            .first(conn)
+            .optional()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::TestDbContext;
+    use mc_common::logger::{test_with_logger, Logger};
+
+    #[test_with_logger]
+    fn insert_enforces_uniqueness_per_source(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id = TokenId::from(1);
+
+        let mut deposit1 = ReserveDeposit::new("bank", "tx1", token_id, 100, "0102", None);
+        deposit1.insert(&conn).unwrap();
+
+        // Inserting the same object again fails since it already has an id.
+        assert!(matches!(
+            deposit1.insert(&conn),
+            Err(Error::AlreadyExists(_))
+        ));
+
+        // The same reference cannot be reported twice by the same source.
+        let mut deposit2 = ReserveDeposit::new("bank", "tx1", token_id, 200, "0304", None);
+        assert!(matches!(
+            deposit2.insert(&conn),
+            Err(Error::AlreadyExists(_))
+        ));
+
+        // But a different source may use it.
+        let mut deposit3 = ReserveDeposit::new("bank2", "tx1", token_id, 200, "0304", None);
+        deposit3.insert(&conn).unwrap();
+
+        assert_eq!(
+            ReserveDeposit::find_unaudited_deposits(Some("bank"), &conn).unwrap(),
+            vec![deposit1.clone()]
+        );
+        assert_eq!(
+            ReserveDeposit::find_unaudited_deposit_by_nonce("bank", "0102", &conn).unwrap(),
+            Some(deposit1)
+        );
+        assert_eq!(
+            ReserveDeposit::find_unaudited_deposit_by_nonce("bank", "0304", &conn).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    db::{
+        last_insert_rowid,
+        schema::{burn_tx_outs, mint_txs, reserve_matches},
+        Conn,
+    },
+    error::Error,
+};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `reserve_matches` table.
+/// This stores audit data linking MintTxs and BurnTxOuts with the matching
+/// deposits and withdrawals of every reserve backend, Gnosis safes included.
+/// The deposit or withdrawal is identified by the name of the backend holding
+/// it and the reference that backend uses for it.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+pub struct ReserveMatch {
+    /// Auto incrementing primary key.
+    id: Option<i32>,
+
+    /// The name of the reserve backend holding the matched deposit or
+    /// withdrawal.
+    backend: String,
+
+    /// The reference of the matched deposit or withdrawal within its backend.
+    reference: String,
+
+    /// Id pointing to the MintTx table, when matching a deposit.
+    mint_tx_id: Option<i32>,
+
+    /// Id pointing to the BurnTxOut table, when matching a withdrawal.
+    burn_tx_out_id: Option<i32>,
+
+    /// Amount of the MintTx/BurnTxOut allocated by this match.
+    amount: i64,
+}
+
+impl ReserveMatch {
+    /// Get id.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// Get the name of the reserve backend holding the matched deposit or
+    /// withdrawal.
+    pub fn backend(&self) -> &str {
+        &self.backend
+    }
+
+    /// Get the reference of the matched deposit or withdrawal within its
+    /// backend.
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    /// Get the id of the matched MintTx, if this matches a deposit.
+    pub fn mint_tx_id(&self) -> Option<i32> {
+        self.mint_tx_id
+    }
+
+    /// Get the id of the matched BurnTxOut, if this matches a withdrawal.
+    pub fn burn_tx_out_id(&self) -> Option<i32> {
+        self.burn_tx_out_id
+    }
+
+    /// Get the amount allocated by this match.
+    pub fn amount(&self) -> u64 {
+        self.amount as u64
+    }
+
+    /// Record a match allocating `amount` of a MintTx to a deposit of a
+    /// reserve backend.
+    pub fn insert_for_mint_tx(
+        backend: &str,
+        reference: &str,
+        mint_tx_id: i32,
+        amount: u64,
+        conn: &Conn,
+    ) -> Result<Self, Error> {
+        Self::insert(backend, reference, Some(mint_tx_id), None, amount, conn)
+    }
+
+    /// Record a match allocating `amount` of a BurnTxOut to a withdrawal of a
+    /// reserve backend.
+    pub fn insert_for_burn_tx_out(
+        backend: &str,
+        reference: &str,
+        burn_tx_out_id: i32,
+        amount: u64,
+        conn: &Conn,
+    ) -> Result<Self, Error> {
+        Self::insert(backend, reference, None, Some(burn_tx_out_id), amount, conn)
+    }
+
+    fn insert(
+        backend: &str,
+        reference: &str,
+        mint_tx_id: Option<i32>,
+        burn_tx_out_id: Option<i32>,
+        amount: u64,
+        conn: &Conn,
+    ) -> Result<Self, Error> {
+        let mut obj = Self {
+            id: None,
+            backend: backend.to_string(),
+            reference: reference.to_string(),
+            mint_tx_id,
+            burn_tx_out_id,
+            amount: amount as i64,
+        };
+
+        diesel::insert_into(reserve_matches::table)
+            .values(&obj)
+            .execute(conn)?;
+
+        obj.id = Some(diesel::select(last_insert_rowid).get_result::<i32>(conn)?);
+
+        Ok(obj)
+    }
+
+    /// Get the matches of a deposit of a reserve backend, ordered by id.
+    pub fn list_for_deposit(
+        backend: &str,
+        reference: &str,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        Ok(reserve_matches::table
+            .filter(reserve_matches::backend.eq(backend))
+            .filter(reserve_matches::reference.eq(reference))
+            .filter(reserve_matches::mint_tx_id.is_not_null())
+            .order_by(reserve_matches::id)
+            .load(conn)?)
+    }
+
+    /// Get the matches of a withdrawal of a reserve backend, ordered by id.
+    pub fn list_for_withdrawal(
+        backend: &str,
+        reference: &str,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        Ok(reserve_matches::table
+            .filter(reserve_matches::backend.eq(backend))
+            .filter(reserve_matches::reference.eq(reference))
+            .filter(reserve_matches::burn_tx_out_id.is_not_null())
+            .order_by(reserve_matches::id)
+            .load(conn)?)
+    }
+
+    /// Get the matches of a MintTx, by any reserve backend, ordered by id.
+    pub fn list_for_mint_tx(mint_tx_id: i32, conn: &Conn) -> Result<Vec<Self>, Error> {
+        Ok(reserve_matches::table
+            .filter(reserve_matches::mint_tx_id.eq(mint_tx_id))
+            .order_by(reserve_matches::id)
+            .load(conn)?)
+    }
+
+    /// Get the matches of a BurnTxOut, by any reserve backend, ordered by id.
+    pub fn list_for_burn_tx_out(burn_tx_out_id: i32, conn: &Conn) -> Result<Vec<Self>, Error> {
+        Ok(reserve_matches::table
+            .filter(reserve_matches::burn_tx_out_id.eq(burn_tx_out_id))
+            .order_by(reserve_matches::id)
+            .load(conn)?)
+    }
+
+    /// Delete all matches whose MintTx or BurnTxOut was included in a block
+    /// after a given block index. The matched deposits and withdrawals are
+    /// left in place and become unaudited again. Returns the number of deleted
+    /// rows.
+    pub fn delete_after_block(block_index: u64, conn: &Conn) -> Result<usize, Error> {
+        let mint_tx_ids = mint_txs::table
+            .filter(mint_txs::block_index.gt(block_index as i64))
+            .select(mint_txs::id);
+        let burn_tx_out_ids = burn_tx_outs::table
+            .filter(burn_tx_outs::block_index.gt(block_index as i64))
+            .select(burn_tx_outs::id);
+
+        Ok(diesel::delete(
+            reserve_matches::table.filter(
+                reserve_matches::mint_tx_id
+                    .eq_any(mint_tx_ids)
+                    .or(reserve_matches::burn_tx_out_id.eq_any(burn_tx_out_ids)),
+            ),
+        )
+        .execute(conn)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{
+        test_utils::{create_and_insert_burn_tx_out, TestDbContext},
+        MintTx,
+    };
+    use mc_common::logger::{test_with_logger, Logger};
+    use mc_transaction_core::TokenId;
+    use mc_transaction_core_test_utils::{create_mint_config_tx_and_signers, create_mint_tx};
+
+    #[test_with_logger]
+    fn test_matches_are_keyed_by_backend_and_reference(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id = TokenId::from(1);
+
+        let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let mint_tx1 = create_mint_tx(token_id, &signers, 100, &mut rng);
+        let mint_tx2 = create_mint_tx(token_id, &signers, 200, &mut rng);
+        let mint_tx_id1 = MintTx::insert_from_core_mint_tx(0, None, None, &mint_tx1, &conn)
+            .unwrap()
+            .id()
+            .unwrap();
+        let mint_tx_id2 = MintTx::insert_from_core_mint_tx(5, None, None, &mint_tx2, &conn)
+            .unwrap()
+            .id()
+            .unwrap();
+        let burn_tx_out_id = create_and_insert_burn_tx_out(token_id, 300, &conn, &mut rng)
+            .id()
+            .unwrap();
+
+        // The same reference can be used by a deposit and a withdrawal, and by
+        // different backends.
+        let gnosis_mint_match =
+            ReserveMatch::insert_for_mint_tx("gnosis", "0xaa", mint_tx_id1, 100, &conn).unwrap();
+        let bank_mint_match =
+            ReserveMatch::insert_for_mint_tx("bank", "0xaa", mint_tx_id2, 200, &conn).unwrap();
+        let gnosis_burn_match =
+            ReserveMatch::insert_for_burn_tx_out("gnosis", "0xaa", burn_tx_out_id, 300, &conn)
+                .unwrap();
+
+        assert_eq!(
+            ReserveMatch::list_for_deposit("gnosis", "0xaa", &conn).unwrap(),
+            vec![gnosis_mint_match.clone()]
+        );
+        assert_eq!(
+            ReserveMatch::list_for_deposit("bank", "0xaa", &conn).unwrap(),
+            vec![bank_mint_match.clone()]
+        );
+        assert_eq!(
+            ReserveMatch::list_for_withdrawal("gnosis", "0xaa", &conn).unwrap(),
+            vec![gnosis_burn_match.clone()]
+        );
+        assert_eq!(
+            ReserveMatch::list_for_mint_tx(mint_tx_id2, &conn).unwrap(),
+            vec![bank_mint_match]
+        );
+        assert_eq!(
+            ReserveMatch::list_for_burn_tx_out(burn_tx_out_id, &conn).unwrap(),
+            vec![gnosis_burn_match.clone()]
+        );
+
+        // Only the matches of the mint that appeared after the block get deleted.
+        assert_eq!(ReserveMatch::delete_after_block(0, &conn).unwrap(), 1);
+        assert!(ReserveMatch::list_for_deposit("bank", "0xaa", &conn)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            ReserveMatch::list_for_mint_tx(mint_tx_id1, &conn).unwrap(),
+            vec![gnosis_mint_match]
+        );
+        assert_eq!(
+            ReserveMatch::list_for_withdrawal("gnosis", "0xaa", &conn).unwrap(),
+            vec![gnosis_burn_match]
+        );
+    }
+}
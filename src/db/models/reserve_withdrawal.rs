@@ -0,0 +1,182 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    db::{
+        last_insert_rowid,
+        models::SqlU128,
+        schema::{reserve_matches, reserve_withdrawals},
+        Conn,
+    },
+    error::Error,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::{
+    dsl::{exists, not},
+    prelude::*,
+};
+use mc_transaction_core::TokenId;
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `reserve_withdrawals` table.
+/// This table stores withdrawals reported by reserve backends other than the
+/// Gnosis safe one.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+pub struct ReserveWithdrawal {
+    /// Auto incrementing primary key.
+    id: Option<i32>,
+
+    /// The name of the reserve backend that reported this withdrawal.
+    source: String,
+
+    /// The reference the custodian uses for this withdrawal.
+    external_ref: String,
+
+    /// The MobileCoin token id this withdrawal is for.
+    token_id: i64,
+
+    /// Amount withdrawn, in the MobileCoin token's precision.
+    /// This is not guaranteed to fit in a MobileCoin (u64) amount.
+    amount: SqlU128,
+
+    /// The hex-encoded public key of the burn TxOut this withdrawal redeems.
+    mc_tx_out_public_key_hex: String,
+
+    /// When the custodian recorded the withdrawal, if known.
+    occurred_at: Option<NaiveDateTime>,
+}
+
+impl ReserveWithdrawal {
+    /// Construct a new [ReserveWithdrawal] object.
+    pub fn new(
+        source: &str,
+        external_ref: &str,
+        token_id: TokenId,
+        amount: u128,
+        mc_tx_out_public_key_hex: &str,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id: None,
+            source: source.to_string(),
+            external_ref: external_ref.to_string(),
+            token_id: *token_id as i64,
+            amount: amount.into(),
+            mc_tx_out_public_key_hex: mc_tx_out_public_key_hex.to_string(),
+            occurred_at: occurred_at.map(|ts| ts.naive_utc()),
+        }
+    }
+
+    /// Get id.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// Get the name of the reserve backend that reported this withdrawal.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Get the reference the custodian uses for this withdrawal.
+    pub fn external_ref(&self) -> &str {
+        &self.external_ref
+    }
+
+    /// Get token id.
+    pub fn token_id(&self) -> TokenId {
+        TokenId::from(self.token_id as u64)
+    }
+
+    /// Get amount withdrawn.
+    pub fn amount(&self) -> u128 {
+        *self.amount
+    }
+
+    /// Get amount withdrawn, if it fits in a MobileCoin amount.
+    pub fn mc_amount(&self) -> Option<u64> {
+        u64::try_from(*self.amount).ok()
+    }
+
+    /// Get the hex-encoded public key of the burn TxOut this withdrawal
+    /// redeems.
+    pub fn mc_tx_out_public_key_hex(&self) -> &str {
+        &self.mc_tx_out_public_key_hex
+    }
+
+    /// Get the time the custodian recorded the withdrawal, if known.
+    pub fn occurred_at(&self) -> Option<DateTime<Utc>> {
+        self.occurred_at
+            .map(|ts| DateTime::from_naive_utc_and_offset(ts, Utc))
+    }
+
+    /// Insert a withdrawal into the database. Returns
+    /// [Error::AlreadyExists] if the source already reported a withdrawal
+    /// with the same external reference.
+    pub fn insert(&mut self, conn: &Conn) -> Result<(), Error> {
+        if let Some(id) = self.id {
+            return Err(Error::AlreadyExists(format!(
+                "ReserveWithdrawal already has an id ({id})"
+            )));
+        }
+
+        diesel::insert_into(reserve_withdrawals::table)
+            .values(self.clone())
+            .execute(conn)?;
+
+        self.id = Some(diesel::select(last_insert_rowid).get_result::<i32>(conn)?);
+
+        Ok(())
+    }
+
+    /// Get all [ReserveWithdrawal]s, ordered by id.
+    pub fn list(conn: &Conn) -> Result<Vec<Self>, Error> {
+        Ok(reserve_withdrawals::table
+            .order_by(reserve_withdrawals::id)
+            .load(conn)?)
+    }
+
+    /// Attempt to find all [ReserveWithdrawal]s that do not have a matching entry
+    /// in the `reserve_matches` table, optionally only those reported by a
+    /// given source.
+    pub fn find_unaudited_withdrawals(
+        source: Option<&str>,
+        conn: &Conn,
+    ) -> Result<Vec<Self>, Error> {
+        let mut query = reserve_withdrawals::table
+            .filter(not(exists(
+                reserve_matches::table
+                    .select(reserve_matches::id)
+                    .filter(reserve_matches::backend.eq(reserve_withdrawals::source))
+                    .filter(reserve_matches::reference.eq(reserve_withdrawals::external_ref))
+                    .filter(reserve_matches::burn_tx_out_id.is_not_null()),
+            )))
+            .into_boxed();
+
+        if let Some(source) = source {
+            query = query.filter(reserve_withdrawals::source.eq(source));
+        }
+
+        Ok(query.order_by(reserve_withdrawals::id).load(conn)?)
+    }
+
+    /// Attempt to find a [ReserveWithdrawal] of a given source that has a
+    /// given TxOut public key and no matching entry in the
+    /// `reserve_matches` table.
+    pub fn find_unaudited_withdrawal_by_public_key(
+        source: &str,
+        public_key_hex: &str,
+        conn: &Conn,
+    ) -> Result<Option<Self>, Error> {
+        Ok(reserve_withdrawals::table
+            .filter(reserve_withdrawals::source.eq(source))
+            .filter(reserve_withdrawals::mc_tx_out_public_key_hex.eq(public_key_hex))
+            .filter(not(exists(
+                reserve_matches::table
+                    .select(reserve_matches::id)
+                    .filter(reserve_matches::backend.eq(reserve_withdrawals::source))
+                    .filter(reserve_matches::reference.eq(reserve_withdrawals::external_ref))
+                    .filter(reserve_matches::burn_tx_out_id.is_not_null()),
+            )))
+            .first(conn)
+            .optional()?)
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Model file for the signer_set_changes table.
+
+use crate::{
+    db::{
+        last_insert_rowid,
+        models::MintConfigTx,
+        schema::{mint_config_txs, signer_set_changes},
+        Conn,
+    },
+    Error,
+};
+use diesel::prelude::*;
+use mc_blockchain_types::BlockIndex;
+use mc_crypto_keys::Ed25519Public;
+use mc_crypto_multisig::SignerSet;
+use mc_transaction_core::TokenId;
+use serde::{Deserialize, Serialize};
+
+/// Diesel model for the `signer_set_changes` table.
+/// This table keeps the history of the minter signer sets of each token. A
+/// row is added whenever a [MintConfigTx] replaces the signer sets of its
+/// token with different ones.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Insertable, PartialEq, Queryable, Serialize)]
+pub struct SignerSetChange {
+    /// Auto incrementing primary key.
+    id: Option<i32>,
+
+    /// The token id whose signer sets changed.
+    token_id: i64,
+
+    /// The block index the [MintConfigTx] that made the change appeared in.
+    block_index: i64,
+
+    /// Id of the [MintConfigTx] that made the change.
+    mint_config_tx_id: i32,
+
+    /// The JSON-serialized signer sets of the mint configs of the
+    /// [MintConfigTx], in order.
+    signer_sets_json: String,
+}
+
+impl SignerSetChange {
+    /// Get id.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// Get token id.
+    pub fn token_id(&self) -> TokenId {
+        TokenId::from(self.token_id as u64)
+    }
+
+    /// Get block index.
+    pub fn block_index(&self) -> u64 {
+        self.block_index as u64
+    }
+
+    /// Get the id of the [MintConfigTx] that made the change.
+    pub fn mint_config_tx_id(&self) -> i32 {
+        self.mint_config_tx_id
+    }
+
+    /// Get the JSON-serialized signer sets.
+    pub fn signer_sets_json(&self) -> &str {
+        &self.signer_sets_json
+    }
+
+    /// Decode the signer sets.
+    pub fn signer_sets(&self) -> Result<Vec<SignerSet<Ed25519Public>>, Error> {
+        serde_json::from_str(&self.signer_sets_json)
+            .map_err(|err| Error::Other(format!("Failed decoding signer sets: {err}")))
+    }
+
+    /// Record the signer sets of a [MintConfigTx] that was just stored, if
+    /// they differ from the ones of the last recorded change for its token.
+    /// Returns the new change, if one was recorded.
+    pub fn record_if_changed(
+        mint_config_tx: &MintConfigTx,
+        conn: &Conn,
+    ) -> Result<Option<Self>, Error> {
+        let signer_sets = mint_config_tx
+            .decode()?
+            .prefix
+            .configs
+            .into_iter()
+            .map(|config| config.signer_set)
+            .collect::<Vec<_>>();
+        let signer_sets_json = serde_json::to_string(&signer_sets)
+            .map_err(|err| Error::Other(format!("Failed encoding signer sets: {err}")))?;
+
+        let token_id = mint_config_tx.token_id();
+        let last_change = signer_set_changes::table
+            .filter(signer_set_changes::token_id.eq(*token_id as i64))
+            .order_by(signer_set_changes::id.desc())
+            .first::<Self>(conn)
+            .optional()?;
+        if last_change.map(|change| change.signer_sets_json) == Some(signer_sets_json.clone()) {
+            return Ok(None);
+        }
+
+        let mut change = Self {
+            id: None,
+            token_id: *token_id as i64,
+            block_index: mint_config_tx.block_index() as i64,
+            mint_config_tx_id: mint_config_tx.id().ok_or(Error::ObjectNotSaved)?,
+            signer_sets_json,
+        };
+        diesel::insert_into(signer_set_changes::table)
+            .values(&change)
+            .execute(conn)?;
+        change.id = Some(diesel::select(last_insert_rowid).get_result::<i32>(conn)?);
+
+        Ok(Some(change))
+    }
+
+    /// Get the signer set changes of a token (or of all tokens), oldest first.
+    pub fn list(token_id: Option<TokenId>, conn: &Conn) -> Result<Vec<Self>, Error> {
+        let mut query = signer_set_changes::table.into_boxed();
+        if let Some(token_id) = token_id {
+            query = query.filter(signer_set_changes::token_id.eq(*token_id as i64));
+        }
+        Ok(query.order_by(signer_set_changes::id).load(conn)?)
+    }
+
+    /// Delete all changes made by [MintConfigTx]s that were included in
+    /// blocks after a given block index. Returns the number of deleted rows.
+    pub fn delete_after_block(block_index: BlockIndex, conn: &Conn) -> Result<usize, Error> {
+        let mint_config_tx_ids = mint_config_txs::table
+            .filter(mint_config_txs::block_index.gt(block_index as i64))
+            .select(mint_config_txs::id);
+
+        Ok(diesel::delete(
+            signer_set_changes::table.filter(
+                signer_set_changes::mint_config_tx_id
+                    .nullable()
+                    .eq_any(mint_config_tx_ids),
+            ),
+        )
+        .execute(conn)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::TestDbContext;
+    use mc_common::logger::{test_with_logger, Logger};
+    use mc_transaction_core_test_utils::create_mint_config_tx_and_signers;
+
+    #[test_with_logger]
+    fn record_if_changed_only_records_changes(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id1 = TokenId::from(1);
+        let token_id2 = TokenId::from(2);
+
+        // The first MintConfigTx of a token is always a change.
+        let (core_tx1, _signers) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        let tx1 = MintConfigTx::insert_from_core_mint_config_tx(5, None, &core_tx1, &conn).unwrap();
+        let change1 = SignerSetChange::record_if_changed(&tx1, &conn)
+            .unwrap()
+            .unwrap();
+        assert_eq!(change1.token_id(), token_id1);
+        assert_eq!(change1.block_index(), 5);
+        assert_eq!(change1.mint_config_tx_id(), tx1.id().unwrap());
+        assert_eq!(
+            change1.signer_sets().unwrap(),
+            core_tx1
+                .prefix
+                .configs
+                .iter()
+                .map(|config| config.signer_set.clone())
+                .collect::<Vec<_>>()
+        );
+
+        // A MintConfigTx with the same signer sets is not.
+        let mut core_tx2 = core_tx1.clone();
+        core_tx2.prefix.nonce = vec![2; 32];
+        let tx2 = MintConfigTx::insert_from_core_mint_config_tx(6, None, &core_tx2, &conn).unwrap();
+        assert_eq!(
+            SignerSetChange::record_if_changed(&tx2, &conn).unwrap(),
+            None
+        );
+
+        // Other tokens have their own history.
+        let (core_tx3, _signers) = create_mint_config_tx_and_signers(token_id2, &mut rng);
+        let tx3 = MintConfigTx::insert_from_core_mint_config_tx(7, None, &core_tx3, &conn).unwrap();
+        let change3 = SignerSetChange::record_if_changed(&tx3, &conn)
+            .unwrap()
+            .unwrap();
+
+        // New signer sets are a change.
+        let (core_tx4, _signers) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        let tx4 = MintConfigTx::insert_from_core_mint_config_tx(8, None, &core_tx4, &conn).unwrap();
+        let change4 = SignerSetChange::record_if_changed(&tx4, &conn)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            SignerSetChange::list(Some(token_id1), &conn).unwrap(),
+            vec![change1.clone(), change4.clone()]
+        );
+        assert_eq!(
+            SignerSetChange::list(None, &conn).unwrap(),
+            vec![change1.clone(), change3, change4]
+        );
+
+        // Rewinding removes the changes made after the block.
+        assert_eq!(SignerSetChange::delete_after_block(6, &conn).unwrap(), 2);
+        assert_eq!(SignerSetChange::list(None, &conn).unwrap(), vec![change1]);
+    }
+}
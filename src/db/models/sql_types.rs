@@ -200,3 +200,68 @@ where
         ))
     }
 }
+
+/// Diesel wrapper for amounts and balances that do not necessarily fit in a
+/// 64 bit integer. These are stored as decimal strings since SQLite integers
+/// are limited to 64 bits.
+#[derive(
+    AsExpression,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Eq,
+    FromSqlRow,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+)]
+#[sql_type = "diesel::sql_types::Text"]
+#[serde(transparent)]
+pub struct SqlU128(#[serde(with = "serde_with::rust::display_fromstr")] u128);
+
+impl Deref for SqlU128 {
+    type Target = u128;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<u128> for SqlU128 {
+    fn from(src: u128) -> Self {
+        Self(src)
+    }
+}
+
+impl From<u64> for SqlU128 {
+    fn from(src: u64) -> Self {
+        Self(u128::from(src))
+    }
+}
+
+impl fmt::Display for SqlU128 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<DB: Backend> ToSql<diesel::sql_types::Text, DB> for SqlU128 {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, DB>) -> serialize::Result {
+        <String as ToSql<diesel::sql_types::Text, DB>>::to_sql(&self.0.to_string(), out)
+    }
+}
+
+impl<DB> FromSql<diesel::sql_types::Text, DB> for SqlU128
+where
+    DB: Backend,
+    String: FromSql<diesel::sql_types::Text, DB>,
+{
+    fn from_sql(src: Option<&DB::RawValue>) -> deserialize::Result<Self> {
+        let str = String::from_sql(src)?;
+        Ok(SqlU128(u128::from_str(&str).map_err(|e| e.to_string())?))
+    }
+}
@@ -3,18 +3,34 @@
 #![allow(missing_docs)]
 
 diesel::table! {
-    audited_burns (id) {
+    audit_event_acknowledgements (id) {
         id -> Nullable<Integer>,
-        burn_tx_out_id -> Integer,
-        gnosis_safe_withdrawal_id -> Integer,
+        audit_event_id -> Integer,
+        actor -> Text,
+        note -> Text,
+        manual_match_id -> Nullable<Integer>,
+        created_at -> Timestamp,
     }
 }
 
 diesel::table! {
-    audited_mints (id) {
+    audit_events (id) {
         id -> Nullable<Integer>,
-        mint_tx_id -> Integer,
-        gnosis_safe_deposit_id -> Integer,
+        kind -> Text,
+        severity -> Text,
+        message -> Text,
+        block_index -> Nullable<BigInt>,
+        eth_block_number -> Nullable<BigInt>,
+        mint_tx_id -> Nullable<Integer>,
+        burn_tx_out_id -> Nullable<Integer>,
+        gnosis_safe_deposit_id -> Nullable<Integer>,
+        gnosis_safe_withdrawal_id -> Nullable<Integer>,
+        reserve_deposit_id -> Nullable<Integer>,
+        reserve_withdrawal_id -> Nullable<Integer>,
+        created_at -> Timestamp,
+        mint_tx_nonce_hex -> Nullable<Text>,
+        burn_tx_out_public_key_hex -> Nullable<Text>,
+        eth_tx_hash -> Nullable<Text>,
     }
 }
 
@@ -22,6 +38,7 @@ diesel::table! {
     block_audit_data (id) {
         id -> Nullable<Integer>,
         block_index -> BigInt,
+        block_timestamp -> Nullable<Timestamp>,
     }
 }
 
@@ -30,7 +47,7 @@ diesel::table! {
         id -> Nullable<Integer>,
         block_index -> BigInt,
         token_id -> BigInt,
-        balance -> BigInt,
+        balance -> Text,
     }
 }
 
@@ -64,6 +81,25 @@ diesel::table! {
         num_unexpected_errors_matching_mints_to_deposits -> BigInt,
         num_unexpected_errors_matching_withdrawals_to_burns -> BigInt,
         num_unexpected_errors_matching_burns_to_withdrawals -> BigInt,
+        num_mint_txs_with_unknown_nonce -> BigInt,
+        num_gnosis_source_disagreements -> BigInt,
+        num_mint_config_tx_governor_violations -> BigInt,
+        num_mint_txs_without_active_mint_config -> BigInt,
+        num_mint_txs_with_invalid_signature -> BigInt,
+        num_mint_txs_exceeding_total_mint_limit -> BigInt,
+        num_mint_txs_exceeding_mint_limit -> BigInt,
+        num_mint_txs_past_tombstone_block -> BigInt,
+        num_oversized_reserve_amounts -> BigInt,
+        num_deposits_with_truncation_remainder -> BigInt,
+    }
+}
+
+diesel::table! {
+    gnosis_safe_audit_configs (chain_id, safe_addr) {
+        chain_id -> BigInt,
+        safe_addr -> Text,
+        config_json -> Text,
+        updated_at -> Timestamp,
     }
 }
 
@@ -77,8 +113,21 @@ diesel::table! {
         safe_addr -> Text,
         token_addr -> Text,
         from_addr -> Text,
-        amount -> BigInt,
+        amount -> Text,
         expected_mc_mint_tx_nonce_hex -> Text,
+        chain_id -> BigInt,
+        verification_status -> Text,
+        truncation_remainder -> Text,
+    }
+}
+
+diesel::table! {
+    gnosis_safe_sync_states (chain_id, safe_addr) {
+        chain_id -> BigInt,
+        safe_addr -> Text,
+        full_sync_completed -> Bool,
+        last_eth_tx_hash -> Nullable<Text>,
+        last_synced_at -> Nullable<Timestamp>,
     }
 }
 
@@ -86,6 +135,7 @@ diesel::table! {
     gnosis_safe_txs (eth_tx_hash) {
         eth_tx_hash -> Text,
         raw_tx_json -> Text,
+        chain_id -> BigInt,
     }
 }
 
@@ -99,8 +149,24 @@ diesel::table! {
         safe_addr -> Text,
         token_addr -> Text,
         to_addr -> Text,
-        amount -> BigInt,
+        amount -> Text,
         mc_tx_out_public_key_hex -> Text,
+        chain_id -> BigInt,
+        verification_status -> Text,
+        truncation_remainder -> Text,
+    }
+}
+
+diesel::table! {
+    manual_matches (id) {
+        id -> Nullable<Integer>,
+        mint_tx_nonce_hex -> Nullable<Text>,
+        burn_tx_out_public_key_hex -> Nullable<Text>,
+        chain_id -> BigInt,
+        eth_tx_hash -> Text,
+        actor -> Text,
+        justification -> Text,
+        created_at -> Timestamp,
     }
 }
 
@@ -138,29 +204,95 @@ diesel::table! {
         tombstone_block -> BigInt,
         protobuf -> Binary,
         mint_config_id -> Nullable<Integer>,
+        decoded_nonce -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    notification_outbox (id) {
+        id -> Nullable<Integer>,
+        payload -> Text,
+        created_at -> Timestamp,
+        num_attempts -> Integer,
+        next_attempt_at -> Timestamp,
+        delivered_at -> Nullable<Timestamp>,
+        last_error -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    reserve_deposits (id) {
+        id -> Nullable<Integer>,
+        source -> Text,
+        external_ref -> Text,
+        token_id -> BigInt,
+        amount -> Text,
+        expected_mc_mint_tx_nonce_hex -> Text,
+        occurred_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    reserve_matches (id) {
+        id -> Nullable<Integer>,
+        backend -> Text,
+        reference -> Text,
+        mint_tx_id -> Nullable<Integer>,
+        burn_tx_out_id -> Nullable<Integer>,
+        amount -> BigInt,
+    }
+}
+
+diesel::table! {
+    reserve_withdrawals (id) {
+        id -> Nullable<Integer>,
+        source -> Text,
+        external_ref -> Text,
+        token_id -> BigInt,
+        amount -> Text,
+        mc_tx_out_public_key_hex -> Text,
+        occurred_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    signer_set_changes (id) {
+        id -> Nullable<Integer>,
+        token_id -> BigInt,
+        block_index -> BigInt,
+        mint_config_tx_id -> Integer,
+        signer_sets_json -> Text,
     }
 }
 
-diesel::joinable!(audited_burns -> burn_tx_outs (burn_tx_out_id));
-diesel::joinable!(audited_burns -> gnosis_safe_withdrawals (gnosis_safe_withdrawal_id));
-diesel::joinable!(audited_mints -> gnosis_safe_deposits (gnosis_safe_deposit_id));
-diesel::joinable!(audited_mints -> mint_txs (mint_tx_id));
+diesel::joinable!(audit_event_acknowledgements -> audit_events (audit_event_id));
 diesel::joinable!(gnosis_safe_deposits -> gnosis_safe_txs (eth_tx_hash));
 diesel::joinable!(gnosis_safe_withdrawals -> gnosis_safe_txs (eth_tx_hash));
 diesel::joinable!(mint_configs -> mint_config_txs (mint_config_tx_id));
 diesel::joinable!(mint_txs -> mint_configs (mint_config_id));
+diesel::joinable!(reserve_matches -> burn_tx_outs (burn_tx_out_id));
+diesel::joinable!(reserve_matches -> mint_txs (mint_tx_id));
+diesel::joinable!(signer_set_changes -> mint_config_txs (mint_config_tx_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
-    audited_burns,
-    audited_mints,
+    audit_event_acknowledgements,
+    audit_events,
     block_audit_data,
     block_balance,
     burn_tx_outs,
     counters,
+    gnosis_safe_audit_configs,
     gnosis_safe_deposits,
+    gnosis_safe_sync_states,
     gnosis_safe_txs,
     gnosis_safe_withdrawals,
+    manual_matches,
     mint_config_txs,
     mint_configs,
     mint_txs,
+    notification_outbox,
+    reserve_deposits,
+    reserve_matches,
+    reserve_withdrawals,
+    signer_set_changes,
 );
@@ -38,7 +38,10 @@ pub fn test_gnosis_config() -> GnosisSafeConfig {
     GnosisSafeConfig {
         safes: vec![AuditedSafeConfig {
             safe_addr: EthAddr::from_str(SAFE_ADDR).unwrap(),
+            chain_id: 1,
             api_url: Url::parse("https://safe-api.example.com").unwrap(),
+            eth_rpc: None,
+            verify_with: None,
             token_decimals_max: 9,
             tokens: vec![AuditedToken {
                 token_id: TokenId::from(1),
@@ -137,7 +140,7 @@ pub fn insert_gnosis_deposit(deposit: &mut GnosisSafeDeposit, conn: &Conn) {
     let raw_tx = RawGnosisTransaction::from(json!({
         "txHash": deposit.eth_tx_hash(),
     }));
-    GnosisSafeTx::insert(&raw_tx, conn).unwrap();
+    GnosisSafeTx::insert(&raw_tx, deposit.chain_id(), conn).unwrap();
     deposit.insert(conn).unwrap();
 }
 
@@ -146,7 +149,7 @@ pub fn insert_gnosis_withdrawal(withdrawal: &mut GnosisSafeWithdrawal, conn: &Co
     let raw_tx = RawGnosisTransaction::from(json!({
         "txHash": withdrawal.eth_tx_hash(),
     }));
-    GnosisSafeTx::insert(&raw_tx, conn).unwrap();
+    GnosisSafeTx::insert(&raw_tx, withdrawal.chain_id(), conn).unwrap();
     withdrawal.insert(conn).unwrap();
 }
 
@@ -157,6 +160,7 @@ pub fn create_gnosis_safe_deposit(
 ) -> GnosisSafeDeposit {
     GnosisSafeDeposit::new(
         None,
+        1,
         EthTxHash::from_random(rng),
         Utc::now(),
         EthTxValue::from_str(&format!("{}{}", amount, "000000000000")).unwrap(),
@@ -164,7 +168,8 @@ pub fn create_gnosis_safe_deposit(
         EthAddr::from_str(SAFE_ADDR).unwrap(),
         EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
         EthAddr::from_str(GNOSIS_SAFE_DEPOSIT_FROM_ADDR).unwrap(),
-        amount,
+        amount.into(),
+        0,
     )
 }
 
@@ -178,7 +183,7 @@ pub fn insert_mint_tx_from_deposit(
     let token_id = config.tokens[0].token_id;
 
     let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id, rng);
-    let mut mint_tx = create_mint_tx(token_id, &signers, deposit.amount(), rng);
+    let mut mint_tx = create_mint_tx(token_id, &signers, deposit.mc_amount().unwrap(), rng);
     mint_tx.prefix.nonce = hex::decode(deposit.expected_mc_mint_tx_nonce_hex()).unwrap();
     MintTx::insert_from_core_mint_tx(0, Some(Utc::now()), None, &mint_tx, conn).unwrap()
 }
@@ -233,6 +238,7 @@ pub fn create_gnosis_safe_withdrawal(
 
     GnosisSafeWithdrawal::new(
         None,
+        1,
         EthTxHash::from_random(rng),
         Utc::now(),
         EthTxValue::from_str(&format!("{}{}", amount, "000000000000")).unwrap(),
@@ -240,7 +246,8 @@ pub fn create_gnosis_safe_withdrawal(
         EthAddr::from_str(SAFE_ADDR).unwrap(),
         EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
         EthAddr::from_str(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR).unwrap(),
-        amount,
+        amount.into(),
+        0,
         hex::encode(public_key),
     )
 }
@@ -252,6 +259,7 @@ pub fn create_gnosis_safe_withdrawal_from_burn_tx_out(
 ) -> GnosisSafeWithdrawal {
     GnosisSafeWithdrawal::new(
         None,
+        1,
         EthTxHash::from_random(rng),
         Utc::now(),
         EthTxValue::from_str(&format!("{}{}", burn_tx_out.amount(), "000000000000")).unwrap(),
@@ -259,7 +267,8 @@ pub fn create_gnosis_safe_withdrawal_from_burn_tx_out(
         EthAddr::from_str(SAFE_ADDR).unwrap(),
         EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
         EthAddr::from_str(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR).unwrap(),
-        burn_tx_out.amount(),
+        burn_tx_out.amount().into(),
+        0,
         burn_tx_out.public_key_hex().to_string(),
     )
 }
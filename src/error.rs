@@ -44,6 +44,21 @@ pub enum Error {
     /// Gnosis safe {0} not audited
     GnosisSafeNotAudited(EthAddr),
 
+    /// Invalid reserve config: {0}
+    InvalidReserveConfig(String),
+
+    /// Invalid governance config: {0}
+    InvalidGovernanceConfig(String),
+
+    /// Invalid unmatched alert config: {0}
+    InvalidUnmatchedAlertConfig(String),
+
+    /// Invalid reserve statement (line {0}): {1}
+    InvalidReserveStatement(usize, String),
+
+    /// Block {0} has no timestamp
+    MissingBlockTimestamp(BlockIndex),
+
     /// IO: {0}
     Io(IoError),
 
@@ -83,6 +98,9 @@ pub enum Error {
     /// Invalid nonce identifier: {0:?}
     InvalidNonceIdentifier(Vec<u8>),
 
+    /// Unknown MintTx nonce: {0}
+    UnknownMintTxNonce(String),
+
     /// View key match: {0}
     ViewKeyMatch(ViewKeyMatchError),
 
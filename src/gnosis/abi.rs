@@ -0,0 +1,302 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Minimal Ethereum ABI decoding of the calls involved in Gnosis safe
+//! withdrawals, so that they can be audited without relying on data decoded
+//! by a third-party indexer.
+
+use super::{Error, EthAddr, EthTxValue};
+
+/// Selector of the Gnosis safe
+/// `execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)`
+/// method.
+pub const EXEC_TRANSACTION_SELECTOR: [u8; 4] = [0x6a, 0x76, 0x12, 0x02];
+
+/// Selector of the Gnosis `multiSend(bytes)` method.
+pub const MULTI_SEND_SELECTOR: [u8; 4] = [0x8d, 0x80, 0xff, 0x0a];
+
+/// Selector of the ERC20 `transfer(address,uint256)` method.
+pub const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+/// The length of an ABI word.
+const WORD_LEN: usize = 32;
+
+/// A decoded call to a Gnosis safe's `execTransaction` method.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecTransactionCall {
+    /// The contract the safe calls.
+    pub to: EthAddr,
+
+    /// The amount of ETH sent along with the call.
+    pub value: EthTxValue,
+
+    /// The calldata of the call.
+    pub data: Vec<u8>,
+
+    /// 0 for a call, 1 for a delegate call.
+    pub operation: u8,
+}
+
+/// A single transaction within `multiSend` packed data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultiSendTransaction {
+    /// 0 for a call, 1 for a delegate call.
+    pub operation: u8,
+
+    /// The contract being called.
+    pub to: EthAddr,
+
+    /// The amount of ETH sent along with the call.
+    pub value: EthTxValue,
+
+    /// The calldata of the call.
+    pub data: Vec<u8>,
+}
+
+/// A decoded ERC20 `transfer` call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Erc20Transfer {
+    /// The recipient of the tokens.
+    pub to: EthAddr,
+
+    /// The amount of tokens transferred.
+    pub value: EthTxValue,
+}
+
+/// Decode `0x`-prefixed hex data.
+pub fn decode_hex_data(src: &str) -> Result<Vec<u8>, Error> {
+    let hex_str = src
+        .strip_prefix("0x")
+        .ok_or_else(|| Error::AbiDecode(format!("data doesn't start with 0x: {src}")))?;
+    hex::decode(hex_str)
+        .map_err(|err| Error::AbiDecode(format!("data {src} cannot be hex-decoded: {err}")))
+}
+
+/// Decode the calldata of a Gnosis safe `execTransaction` call.
+pub fn decode_exec_transaction(input: &[u8]) -> Result<ExecTransactionCall, Error> {
+    let args = strip_selector(input, &EXEC_TRANSACTION_SELECTOR, "execTransaction")?;
+    Ok(ExecTransactionCall {
+        to: word_to_addr(word(args, 0)?)?,
+        value: word_to_value(word(args, 1)?)?,
+        data: dynamic_bytes(args, 2)?,
+        operation: u8::try_from(word_to_usize(word(args, 3)?)?)
+            .map_err(|_| Error::AbiDecode("operation does not fit in a u8".into()))?,
+    })
+}
+
+/// Get the packed transactions argument of a `multiSend` call.
+pub fn multi_send_packed_data(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let args = strip_selector(data, &MULTI_SEND_SELECTOR, "multiSend")?;
+    dynamic_bytes(args, 0)
+}
+
+/// Decode the calldata of a `multiSend` call into the transactions it
+/// batches. Each transaction is packed as
+/// `operation (1 byte) || to (20 bytes) || value (32 bytes) || data length (32
+/// bytes) || data`.
+pub fn decode_multi_send(data: &[u8]) -> Result<Vec<MultiSendTransaction>, Error> {
+    const HEADER_LEN: usize = 1 + EthAddr::LEN + 2 * WORD_LEN;
+
+    let packed = multi_send_packed_data(data)?;
+
+    let mut transactions = Vec::new();
+    let mut rest = &packed[..];
+    while !rest.is_empty() {
+        if rest.len() < HEADER_LEN {
+            return Err(Error::AbiDecode(format!(
+                "multiSend transaction {} is truncated",
+                transactions.len()
+            )));
+        }
+
+        let data_len = word_to_usize(&rest[1 + EthAddr::LEN + WORD_LEN..HEADER_LEN])?;
+        let end = HEADER_LEN
+            .checked_add(data_len)
+            .filter(|end| *end <= rest.len())
+            .ok_or_else(|| {
+                Error::AbiDecode(format!(
+                    "multiSend transaction {} data is truncated",
+                    transactions.len()
+                ))
+            })?;
+
+        transactions.push(MultiSendTransaction {
+            operation: rest[0],
+            to: bytes_to_addr(&rest[1..1 + EthAddr::LEN]),
+            value: word_to_value(&rest[1 + EthAddr::LEN..1 + EthAddr::LEN + WORD_LEN])?,
+            data: rest[HEADER_LEN..end].to_vec(),
+        });
+        rest = &rest[end..];
+    }
+
+    Ok(transactions)
+}
+
+/// Decode the calldata of an ERC20 `transfer` call.
+pub fn decode_erc20_transfer(data: &[u8]) -> Result<Erc20Transfer, Error> {
+    let args = strip_selector(data, &ERC20_TRANSFER_SELECTOR, "transfer")?;
+    if args.len() != 2 * WORD_LEN {
+        return Err(Error::AbiDecode(format!(
+            "invalid transfer arguments length: got {}, expected {}",
+            args.len(),
+            2 * WORD_LEN
+        )));
+    }
+
+    Ok(Erc20Transfer {
+        to: word_to_addr(word(args, 0)?)?,
+        value: word_to_value(word(args, 1)?)?,
+    })
+}
+
+/// Decode a single ABI word holding an address (e.g. an indexed event topic).
+pub fn decode_addr_word(data: &[u8]) -> Result<EthAddr, Error> {
+    word_to_addr(exact_word(data)?)
+}
+
+/// Decode a single ABI word holding an amount (e.g. the data of an ERC20
+/// `Transfer` event).
+pub fn decode_value_word(data: &[u8]) -> Result<EthTxValue, Error> {
+    word_to_value(exact_word(data)?)
+}
+
+fn strip_selector<'a>(data: &'a [u8], selector: &[u8; 4], method: &str) -> Result<&'a [u8], Error> {
+    data.strip_prefix(&selector[..]).ok_or_else(|| {
+        Error::AbiDecode(format!(
+            "data does not start with the {} selector ({})",
+            method,
+            hex::encode(selector)
+        ))
+    })
+}
+
+fn exact_word(data: &[u8]) -> Result<&[u8], Error> {
+    if data.len() != WORD_LEN {
+        return Err(Error::AbiDecode(format!(
+            "invalid word length: got {}, expected {}",
+            data.len(),
+            WORD_LEN
+        )));
+    }
+    Ok(data)
+}
+
+fn word(args: &[u8], index: usize) -> Result<&[u8], Error> {
+    args.get(index * WORD_LEN..(index + 1) * WORD_LEN)
+        .ok_or_else(|| Error::AbiDecode(format!("missing argument {index}")))
+}
+
+fn word_to_addr(word: &[u8]) -> Result<EthAddr, Error> {
+    let (padding, addr) = word.split_at(WORD_LEN - EthAddr::LEN);
+    if padding.iter().any(|byte| *byte != 0) {
+        return Err(Error::AbiDecode(format!(
+            "invalid address word: {}",
+            hex::encode(word)
+        )));
+    }
+    Ok(bytes_to_addr(addr))
+}
+
+fn bytes_to_addr(bytes: &[u8]) -> EthAddr {
+    EthAddr(format!("0x{}", hex::encode(bytes)))
+}
+
+fn word_to_value(word: &[u8]) -> Result<EthTxValue, Error> {
+    let (high, low) = word.split_at(WORD_LEN - 16);
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(Error::AbiDecode(format!(
+            "value 0x{} exceeds u128",
+            hex::encode(word)
+        )));
+    }
+    Ok(EthTxValue(u128::from_be_bytes(
+        low.try_into().expect("split at 16 bytes"),
+    )))
+}
+
+fn word_to_usize(word: &[u8]) -> Result<usize, Error> {
+    let value = u128::from(word_to_value(word)?);
+    usize::try_from(value).map_err(|_| Error::AbiDecode(format!("{value} exceeds usize")))
+}
+
+fn dynamic_bytes(args: &[u8], index: usize) -> Result<Vec<u8>, Error> {
+    let offset = word_to_usize(word(args, index)?)?;
+    let len_word = args
+        .get(offset..)
+        .and_then(|rest| rest.get(..WORD_LEN))
+        .ok_or_else(|| Error::AbiDecode(format!("invalid offset {offset} of argument {index}")))?;
+    let len = word_to_usize(len_word)?;
+
+    let start = offset + WORD_LEN;
+    start
+        .checked_add(len)
+        .and_then(|end| args.get(start..end))
+        .map(|bytes| bytes.to_vec())
+        .ok_or_else(|| Error::AbiDecode(format!("argument {index} is truncated")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    // The multiSend calldata of a withdrawal in `data/test/all-transactions.json`.
+    const MULTI_SEND_DATA: &str = "0x8d80ff0a0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000015200d92e713d051c37ebb2561803a3b5fbabc496243100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000044a9059cbb000000000000000000000000dc079a637a1417020916ffb8a39ff5a2801a0f0700000000000000000000000000000000000000000000000000000000001e84800076bd419fba96583d968b422d4f3cb2a70bf4cf4000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000064c76f0635000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000200102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f200000000000000000000000000000";
+
+    #[test]
+    fn decode_multi_send_works() {
+        let transactions = decode_multi_send(&decode_hex_data(MULTI_SEND_DATA).unwrap()).unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        assert_eq!(transactions[0].operation, 0);
+        assert_eq!(
+            transactions[0].to,
+            EthAddr::from_str("0xD92E713d051C37EbB2561803a3b5FBAbc4962431").unwrap()
+        );
+        assert_eq!(transactions[0].value, EthTxValue(0));
+        assert_eq!(
+            decode_erc20_transfer(&transactions[0].data).unwrap(),
+            Erc20Transfer {
+                to: EthAddr::from_str("0xdc079a637a1417020916FfB8a39fF5a2801A0F07").unwrap(),
+                value: EthTxValue(2_000_000),
+            }
+        );
+
+        assert_eq!(
+            transactions[1].to,
+            EthAddr::from_str("0x76BD419fBa96583d968b422D4f3CB2A70bf4CF40").unwrap()
+        );
+        assert_eq!(transactions[1].data[..4], [0xc7, 0x6f, 0x06, 0x35]);
+        assert_eq!(
+            transactions[1].data[transactions[1].data.len() - 32..],
+            (1..=32).collect::<Vec<u8>>()[..]
+        );
+        assert!(decode_erc20_transfer(&transactions[1].data).is_err());
+    }
+
+    #[test]
+    fn decode_multi_send_rejects_malformed_data() {
+        let data = decode_hex_data(MULTI_SEND_DATA).unwrap();
+
+        // Wrong selector.
+        assert!(decode_multi_send(&data[1..]).is_err());
+
+        // Truncated calldata.
+        assert!(decode_multi_send(&data[..200]).is_err());
+
+        // A packed transaction claiming more data than there is.
+        let mut data = data;
+        data[4 + 2 * WORD_LEN + 1 + EthAddr::LEN + 2 * WORD_LEN - 1] = 0xff;
+        assert!(decode_multi_send(&data).is_err());
+    }
+
+    #[test]
+    fn decode_value_word_rejects_values_exceeding_u128() {
+        let mut word = [0u8; WORD_LEN];
+        word[WORD_LEN - 1] = 1;
+        assert_eq!(decode_value_word(&word).unwrap(), EthTxValue(1));
+
+        word[15] = 1;
+        assert!(decode_value_word(&word).is_err());
+        assert!(decode_value_word(&word[1..]).is_err());
+    }
+}
@@ -43,15 +43,73 @@ pub struct AuditedToken {
     pub aux_burn_function_sig: [u8; 4],
 }
 
+/// Configuration for reading a safe's transactions from an Ethereum JSON-RPC
+/// node.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EthRpcConfig {
+    /// The JSON-RPC endpoint.
+    pub url: Url,
+
+    /// The block to start scanning from, e.g. the block the safe was created
+    /// in.
+    #[serde(default)]
+    pub start_block: u64,
+
+    /// The maximum number of blocks to request logs for in a single
+    /// `eth_getLogs` call.
+    #[serde(default = "EthRpcConfig::default_max_block_range")]
+    pub max_block_range: u64,
+}
+
+impl EthRpcConfig {
+    /// The default maximum number of blocks per `eth_getLogs` call.
+    pub const DEFAULT_MAX_BLOCK_RANGE: u64 = 10_000;
+
+    fn default_max_block_range() -> u64 {
+        Self::DEFAULT_MAX_BLOCK_RANGE
+    }
+}
+
+/// A source of a safe's transaction history.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SafeDataSource {
+    /// A Gnosis safe transaction service.
+    SafeApi {
+        /// The transaction service API endpoint.
+        api_url: Url,
+    },
+
+    /// An Ethereum JSON-RPC node.
+    EthRpc(EthRpcConfig),
+}
+
 /// Configuration for a single safe we want to audit.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct AuditedSafeConfig {
     /// The safe address.
     pub safe_addr: EthAddr,
 
+    /// The id of the EVM chain the safe lives on. Defaults to Ethereum
+    /// mainnet.
+    #[serde(default = "AuditedSafeConfig::default_chain_id")]
+    pub chain_id: u64,
+
     /// The Gnosis safe transaction service API endpoint to sync from.
     pub api_url: Url,
 
+    /// When set, the safe's transactions are read directly from an Ethereum
+    /// JSON-RPC node instead of the safe transaction service at `api_url`.
+    /// Not serialized, since node URLs commonly embed an API key.
+    #[serde(default, skip_serializing)]
+    pub eth_rpc: Option<EthRpcConfig>,
+
+    /// When set, every deposit and withdrawal is independently verified
+    /// against this second source. Not serialized for the same reason as
+    /// `eth_rpc`.
+    #[serde(default, skip_serializing)]
+    pub verify_with: Option<SafeDataSource>,
+
     /// The tokens we want to audit.
     pub tokens: Vec<AuditedToken>,
 
@@ -60,6 +118,13 @@ pub struct AuditedSafeConfig {
 }
 
 impl AuditedSafeConfig {
+    /// The chain id of safes that do not specify one (Ethereum mainnet).
+    pub const DEFAULT_CHAIN_ID: u64 = 1;
+
+    fn default_chain_id() -> u64 {
+        Self::DEFAULT_CHAIN_ID
+    }
+
     /// Get an audited token by its Ethereum contract address.
     pub fn get_token_by_eth_contract_addr(
         &self,
@@ -97,14 +162,16 @@ impl GnosisSafeConfig {
         Ok(config)
     }
 
-    /// Get an [AuditedSafeConfig] by its safe address.
-    pub fn get_audited_safe_config_by_safe_addr(
+    /// Get an [AuditedSafeConfig] by its chain id and safe address. The same
+    /// address can belong to different safes on different chains.
+    pub fn get_audited_safe_config(
         &self,
+        chain_id: u64,
         safe_addr: &EthAddr,
     ) -> Option<AuditedSafeConfig> {
         self.safes
             .iter()
-            .find(|safe| safe.safe_addr == *safe_addr)
+            .find(|safe| safe.chain_id == chain_id && safe.safe_addr == *safe_addr)
             .cloned()
     }
 }
@@ -118,6 +185,7 @@ mod tests {
     static INPUT_TOML: &str = r#"
         [[safes]]
         safe_addr = "0x90213de428E9Ce4C77dD4943755Aa69cb2F803b7"
+        chain_id = 1
         api_url = "https://safe-api.example.com"
         token_decimals_max = 9
 
@@ -191,7 +259,10 @@ mod tests {
                 safes: vec![AuditedSafeConfig {
                     safe_addr: EthAddr::from_str("0x90213de428E9Ce4C77dD4943755Aa69cb2F803b7")
                         .unwrap(),
+                    chain_id: 1,
                     api_url: Url::parse("https://safe-api.example.com").unwrap(),
+                    eth_rpc: None,
+                    verify_with: None,
                     tokens: vec![
                         AuditedToken {
                             token_id: TokenId::from(1),
@@ -234,6 +305,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn eth_rpc_config() {
+        let cfg: GnosisSafeConfig = toml::from_str(&format!(
+            "{INPUT_TOML}
+        [safes.eth_rpc]
+        url = \"http://localhost:8545\"
+        start_block = 100
+    "
+        ))
+        .expect("failed parsing toml");
+
+        assert_eq!(
+            cfg.safes[0].eth_rpc,
+            Some(EthRpcConfig {
+                url: Url::parse("http://localhost:8545").unwrap(),
+                start_block: 100,
+                max_block_range: EthRpcConfig::DEFAULT_MAX_BLOCK_RANGE,
+            })
+        );
+
+        // The node URL is not exposed when serializing the config.
+        let json = serde_json::to_string(&cfg).unwrap();
+        assert!(!json.contains("localhost:8545"));
+    }
+
+    #[test]
+    fn verify_with_config() {
+        let cfg: GnosisSafeConfig = toml::from_str(&format!(
+            "{INPUT_TOML}
+        [safes.verify_with]
+        type = \"safe_api\"
+        api_url = \"https://safe-transaction.other.example/\"
+    "
+        ))
+        .expect("failed parsing toml");
+
+        assert_eq!(
+            cfg.safes[0].verify_with,
+            Some(SafeDataSource::SafeApi {
+                api_url: Url::parse("https://safe-transaction.other.example/").unwrap(),
+            })
+        );
+
+        let cfg: GnosisSafeConfig = toml::from_str(&format!(
+            "{INPUT_TOML}
+        [safes.verify_with]
+        type = \"eth_rpc\"
+        url = \"http://localhost:8545\"
+    "
+        ))
+        .expect("failed parsing toml");
+
+        assert_eq!(
+            cfg.safes[0].verify_with,
+            Some(SafeDataSource::EthRpc(EthRpcConfig {
+                url: Url::parse("http://localhost:8545").unwrap(),
+                start_block: 0,
+                max_block_range: EthRpcConfig::DEFAULT_MAX_BLOCK_RANGE,
+            }))
+        );
+    }
+
     #[test]
     fn configs_from_path() {
         let dir = tempdir().unwrap();
@@ -44,6 +44,15 @@ pub enum Error {
     /// Unknown Token: {0}
     UnknownToken(String),
 
+    /// ABI decode error: {0}
+    AbiDecode(String),
+
+    /// Ethereum JSON-RPC error: {0}
+    EthRpc(String),
+
+    /// Cannot truncate values with {0} decimals down to {1} decimals
+    UnsupportedDecimals(u8, u8),
+
     /// Other: {0}
     Other(String),
 }
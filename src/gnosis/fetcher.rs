@@ -1,7 +1,7 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
-//! Gnosis Safe transaction fetcher, used to get the transaction data from a
-//! gnosis safe-transaction-service.
+//! Gnosis Safe transaction fetchers, used to get the transaction data of a
+//! safe.
 
 use super::{
     api_data_types::{AllTransactionsResponse, RawGnosisTransaction},
@@ -11,6 +11,31 @@ use mc_common::logger::{log, o, Logger};
 use reqwest::{blocking::Client, StatusCode};
 use url::Url;
 
+/// A source of the executed transactions of a safe, in the format returned
+/// by the Gnosis safe-transaction-service.
+pub trait SafeTransactionFetcher: Send + Sync {
+    /// Fetch transaction data, stopping once a batch containing a transaction
+    /// for which `is_known` returns true has been fetched.
+    /// Transactions are returned from the newest to the oldest, so this
+    /// allows fetching only the transactions that were added since the last
+    /// sync. The entire batch containing the known transaction is returned,
+    /// so the result may include transactions that were already seen.
+    fn get_transaction_data_until(
+        &self,
+        safe_addr: &EthAddr,
+        is_known: &mut dyn FnMut(&RawGnosisTransaction) -> bool,
+    ) -> Result<Vec<RawGnosisTransaction>, Error>;
+
+    /// Fetch all transaction data. This returns only transactions that were
+    /// executed and confirmed.
+    fn get_all_transaction_data(
+        &self,
+        safe_addr: &EthAddr,
+    ) -> Result<Vec<RawGnosisTransaction>, Error> {
+        self.get_transaction_data_until(safe_addr, &mut |_| false)
+    }
+}
+
 /// Gnosis Safe transaction fetcher, used to get the transaction data from a
 /// gnosis safe-transaction-service.
 pub struct GnosisSafeFetcher {
@@ -43,36 +68,6 @@ impl GnosisSafeFetcher {
         })
     }
 
-    /// Fetch transaction data.
-    /// This method will continuously reach out to the API service until all
-    /// transactions have been fetched. Because of pagination, this might result
-    /// in multiple requests. This returns only transactions that were
-    /// executed and confirmed.
-    pub fn get_all_transaction_data(
-        &self,
-        safe_addr: &EthAddr,
-    ) -> Result<Vec<RawGnosisTransaction>, Error> {
-        let mut url = self.base_url.join(&format!(
-            "api/v1/safes/{safe_addr}/all-transactions/?executed=true&queued=false&trusted=true"
-        ))?;
-
-        let mut raw_transactions = Vec::new();
-
-        loop {
-            let response = self.get_all_transaction_data_from_url(&url)?;
-            raw_transactions.extend(response.results.into_iter().map(RawGnosisTransaction::from));
-
-            match response.next {
-                Some(next_url) => {
-                    url = next_url;
-                }
-                None => break,
-            };
-        }
-
-        Ok(raw_transactions)
-    }
-
     /// Fetch transaction data from a specific url endpoint.
     fn get_all_transaction_data_from_url(
         &self,
@@ -98,3 +93,46 @@ impl GnosisSafeFetcher {
         })
     }
 }
+
+impl SafeTransactionFetcher for GnosisSafeFetcher {
+    /// The API is paginated, so this might result in multiple requests.
+    fn get_transaction_data_until(
+        &self,
+        safe_addr: &EthAddr,
+        is_known: &mut dyn FnMut(&RawGnosisTransaction) -> bool,
+    ) -> Result<Vec<RawGnosisTransaction>, Error> {
+        let mut url = self.base_url.join(&format!(
+            "api/v1/safes/{safe_addr}/all-transactions/?executed=true&queued=false&trusted=true"
+        ))?;
+
+        let mut raw_transactions = Vec::new();
+
+        loop {
+            let response = self.get_all_transaction_data_from_url(&url)?;
+            let page = response
+                .results
+                .into_iter()
+                .map(RawGnosisTransaction::from)
+                .collect::<Vec<_>>();
+            let reached_known_tx = page.iter().any(&mut *is_known);
+            raw_transactions.extend(page);
+
+            if reached_known_tx {
+                log::debug!(
+                    self.logger,
+                    "Reached a previously seen transaction, not fetching further pages"
+                );
+                break;
+            }
+
+            match response.next {
+                Some(next_url) => {
+                    url = next_url;
+                }
+                None => break,
+            };
+        }
+
+        Ok(raw_transactions)
+    }
+}
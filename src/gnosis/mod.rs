@@ -2,21 +2,23 @@
 
 //! Gnosis safe auditing support.
 
+mod abi;
 mod config;
 mod error;
 mod eth_data_types;
 mod fetcher;
+mod rpc_fetcher;
 mod sync;
-mod sync_thread;
 mod utils;
+mod verification;
 
 pub mod api_data_types;
 
 pub use self::{
-    config::{AuditedSafeConfig, AuditedToken, GnosisSafeConfig},
+    config::{AuditedSafeConfig, AuditedToken, EthRpcConfig, GnosisSafeConfig, SafeDataSource},
     error::Error,
     eth_data_types::{EthAddr, EthTxHash, EthTxValue},
     sync::GnosisSync,
-    sync_thread::GnosisSyncThread,
-    utils::truncate_value,
+    utils::{truncate_value, TruncatedValue},
+    verification::VerificationStatus,
 };
@@ -0,0 +1,860 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Safe transaction fetcher reading directly from an Ethereum JSON-RPC node.
+//!
+//! Deposits are found through ERC20 `Transfer` logs to the safe, and
+//! multi-sig transactions through the safe's `ExecutionSuccess` logs. The
+//! `execTransaction` calldata of the latter is decoded locally (see
+//! [super::abi]), so nothing is taken from a third-party indexer. The results
+//! are returned in the format of the Gnosis safe-transaction-service, so they
+//! get stored and audited exactly like transactions fetched from it.
+
+use super::{
+    abi, api_data_types::RawGnosisTransaction, fetcher::SafeTransactionFetcher, Error, EthAddr,
+    EthRpcConfig, EthTxHash, EthTxValue,
+};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use mc_common::logger::{log, o, Logger};
+use reqwest::blocking::Client;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Topic of the ERC20 `Transfer(address,address,uint256)` event.
+pub const TRANSFER_EVENT_TOPIC: &str =
+    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Topic of the Gnosis safe `ExecutionSuccess(bytes32,uint256)` event.
+pub const EXECUTION_SUCCESS_EVENT_TOPIC: &str =
+    "0x442e715f626346e8c54381002da614f62bee8d27386535b2521ec8540898556e";
+
+/// A log entry, as returned by `eth_getLogs` and `eth_getTransactionReceipt`.
+#[derive(Clone, Debug, Deserialize)]
+struct RpcLog {
+    address: EthAddr,
+    topics: Vec<String>,
+    data: String,
+    #[serde(rename = "blockNumber")]
+    block_number: String,
+    #[serde(rename = "transactionHash")]
+    transaction_hash: EthTxHash,
+    #[serde(rename = "transactionIndex")]
+    transaction_index: String,
+    #[serde(rename = "logIndex")]
+    log_index: String,
+    #[serde(default)]
+    removed: bool,
+}
+
+/// The parts of an `eth_getTransactionByHash` result we need.
+#[derive(Clone, Debug, Deserialize)]
+struct RpcTransaction {
+    to: Option<EthAddr>,
+    input: String,
+}
+
+/// The parts of an `eth_getTransactionReceipt` result we need.
+#[derive(Clone, Debug, Deserialize)]
+struct RpcReceipt {
+    logs: Vec<RpcLog>,
+}
+
+/// The parts of an `eth_getBlockByNumber` result we need.
+#[derive(Clone, Debug, Deserialize)]
+struct RpcBlock {
+    timestamp: String,
+}
+
+/// Safe transaction fetcher reading directly from an Ethereum JSON-RPC node.
+pub struct EthRpcFetcher {
+    config: EthRpcConfig,
+    chain_id: u64,
+    token_addrs: Vec<EthAddr>,
+    client: Client,
+    logger: Logger,
+}
+
+impl EthRpcFetcher {
+    /// Instantiate an [EthRpcFetcher] for a safe on the given chain, looking
+    /// at transfers of the given token contracts.
+    pub fn new(
+        config: EthRpcConfig,
+        chain_id: u64,
+        token_addrs: Vec<EthAddr>,
+        logger: Logger,
+    ) -> Result<Self, Error> {
+        // Only log the host, since node URLs commonly embed an API key.
+        let logger = logger.new(o!(
+            "eth_rpc_host" => config.url.host_str().unwrap_or_default().to_string(),
+            "chain_id" => chain_id,
+        ));
+
+        let client = Client::builder()
+            .build()
+            .map_err(|e| Error::Other(format!("Failed to create reqwest client: {e}")))?;
+
+        Ok(Self {
+            config,
+            chain_id,
+            token_addrs,
+            client,
+            logger,
+        })
+    }
+
+    /// Perform a JSON-RPC call.
+    fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T, Error> {
+        log::trace!(self.logger, "JSON-RPC call {} {}", method, params);
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response: Value = self
+            .client
+            .post(self.config.url.clone())
+            .json(&request)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| Error::EthRpc(format!("{method} failed: {err}")))?
+            .json()
+            .map_err(|err| Error::EthRpc(format!("Failed parsing {method} response: {err}")))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(Error::EthRpc(format!(
+                "{method} returned an error: {error}"
+            )));
+        }
+        match response.get("result") {
+            None | Some(Value::Null) => Err(Error::EthRpc(format!("{method} returned no result"))),
+            Some(result) => serde_json::from_value(result.clone())
+                .map_err(|err| Error::EthRpc(format!("Failed parsing {method} result: {err}"))),
+        }
+    }
+
+    /// Get the logs matching a filter within a block range, skipping logs
+    /// that were removed by a reorg.
+    fn get_logs(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        address: Value,
+        topics: Value,
+    ) -> Result<Vec<RpcLog>, Error> {
+        let logs: Vec<RpcLog> = self.call(
+            "eth_getLogs",
+            json!([{
+                "fromBlock": format!("{from_block:#x}"),
+                "toBlock": format!("{to_block:#x}"),
+                "address": address,
+                "topics": topics,
+            }]),
+        )?;
+        Ok(logs.into_iter().filter(|log| !log.removed).collect())
+    }
+
+    /// Get the RFC 3339 timestamp of a block, caching it in `cache`.
+    fn get_block_date(
+        &self,
+        block_number: u64,
+        cache: &mut HashMap<u64, String>,
+    ) -> Result<String, Error> {
+        if let Some(date) = cache.get(&block_number) {
+            return Ok(date.clone());
+        }
+
+        let block: RpcBlock = self.call(
+            "eth_getBlockByNumber",
+            json!([format!("{block_number:#x}"), false]),
+        )?;
+        let timestamp = i64::try_from(parse_quantity(&block.timestamp)?)
+            .map_err(|_| Error::EthRpc(format!("Invalid block timestamp {}", block.timestamp)))?;
+        let date = Utc
+            .timestamp_opt(timestamp, 0)
+            .single()
+            .ok_or_else(|| Error::EthRpc(format!("Invalid block timestamp {timestamp}")))?
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        cache.insert(block_number, date.clone());
+        Ok(date)
+    }
+
+    /// Fetch the transactions involving the safe within a block range,
+    /// newest first.
+    fn get_transactions_in_range(
+        &self,
+        safe_addr: &EthAddr,
+        from_block: u64,
+        to_block: u64,
+        block_dates: &mut HashMap<u64, String>,
+    ) -> Result<Vec<RawGnosisTransaction>, Error> {
+        log::debug!(
+            self.logger,
+            "Fetching logs of safe {} in blocks {}-{}",
+            safe_addr,
+            from_block,
+            to_block
+        );
+
+        let safe_topic = addr_topic(safe_addr);
+
+        // Transfers of audited tokens to or from the safe, deduplicated since a
+        // transfer from the safe to itself matches both filters.
+        let mut transfer_logs = Vec::new();
+        if !self.token_addrs.is_empty() {
+            let token_addrs = json!(self.token_addrs);
+            transfer_logs.extend(self.get_logs(
+                from_block,
+                to_block,
+                token_addrs.clone(),
+                json!([TRANSFER_EVENT_TOPIC, null, safe_topic]),
+            )?);
+            transfer_logs.extend(self.get_logs(
+                from_block,
+                to_block,
+                token_addrs,
+                json!([TRANSFER_EVENT_TOPIC, safe_topic]),
+            )?);
+        }
+        let execution_logs = self.get_logs(
+            from_block,
+            to_block,
+            json!(safe_addr),
+            json!([EXECUTION_SUCCESS_EVENT_TOPIC]),
+        )?;
+
+        // Group the logs by transaction, in chain order.
+        let mut transactions = BTreeMap::<(u64, u64), (EthTxHash, Vec<RpcLog>)>::new();
+        let mut seen_logs = HashSet::new();
+        for log in transfer_logs.into_iter().chain(execution_logs) {
+            if !seen_logs.insert((log.transaction_hash, log.log_index.clone())) {
+                continue;
+            }
+            let key = (
+                parse_quantity(&log.block_number)?,
+                parse_quantity(&log.transaction_index)?,
+            );
+            transactions
+                .entry(key)
+                .or_insert_with(|| (log.transaction_hash, Vec::new()))
+                .1
+                .push(log);
+        }
+
+        transactions
+            .into_iter()
+            .rev()
+            .map(|((block_number, _), (tx_hash, logs))| -> Result<_, Error> {
+                let date = self.get_block_date(block_number, block_dates)?;
+                let raw = if logs.iter().any(|log| {
+                    log.topics.first().map(String::as_str) == Some(EXECUTION_SUCCESS_EVENT_TOPIC)
+                }) {
+                    self.multi_sig_transaction(safe_addr, &tx_hash, block_number, &date)?
+                } else {
+                    json!({
+                        "txType": "ETHEREUM_TRANSACTION",
+                        "txHash": tx_hash,
+                        "blockNumber": block_number,
+                        "executionDate": date,
+                        "transfers": logs
+                            .iter()
+                            .map(|log| transfer_json(log, block_number, &date))
+                            .collect::<Result<Vec<_>, _>>()?,
+                    })
+                };
+                Ok(RawGnosisTransaction::from(raw))
+            })
+            .collect()
+    }
+
+    /// Build a multi-sig transaction from an executed safe transaction, by
+    /// decoding its `execTransaction` calldata and collecting the transfers
+    /// of its receipt.
+    fn multi_sig_transaction(
+        &self,
+        safe_addr: &EthAddr,
+        tx_hash: &EthTxHash,
+        block_number: u64,
+        date: &str,
+    ) -> Result<Value, Error> {
+        let tx: RpcTransaction = self.call("eth_getTransactionByHash", json!([tx_hash]))?;
+        let receipt: RpcReceipt = self.call("eth_getTransactionReceipt", json!([tx_hash]))?;
+
+        let transfers = receipt
+            .logs
+            .iter()
+            .filter(|log| {
+                log.topics.len() == 3
+                    && log.topics[0] == TRANSFER_EVENT_TOPIC
+                    && log.topics[1..]
+                        .iter()
+                        .any(|topic| topic.eq_ignore_ascii_case(&addr_topic(safe_addr)))
+            })
+            .map(|log| transfer_json(log, block_number, date))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Transactions relayed through another contract cannot be decoded, and
+        // are stored without data so that they fail auditing.
+        let exec_call = if tx.to.as_ref() == Some(safe_addr) {
+            abi::decode_hex_data(&tx.input).and_then(|input| abi::decode_exec_transaction(&input))
+        } else {
+            Err(Error::AbiDecode(
+                "transaction is not a direct call to the safe".into(),
+            ))
+        };
+        let (to, value, operation, data, data_decoded) = match exec_call {
+            Ok(call) => {
+                let data_decoded = decode_call_data(&call.data).unwrap_or_else(|err| {
+                    log::warn!(
+                        self.logger,
+                        "Failed decoding data of safe transaction {}: {}",
+                        tx_hash,
+                        err
+                    );
+                    None
+                });
+                (
+                    call.to,
+                    call.value,
+                    Some(call.operation),
+                    Some(hex_data(&call.data)),
+                    data_decoded,
+                )
+            }
+            Err(err) => {
+                log::warn!(
+                    self.logger,
+                    "Failed decoding safe transaction {}: {}",
+                    tx_hash,
+                    err
+                );
+                (safe_addr.clone(), EthTxValue(0), None, None, None)
+            }
+        };
+
+        Ok(json!({
+            "txType": "MULTISIG_TRANSACTION",
+            "safe": safe_addr,
+            "to": to,
+            "value": value,
+            "operation": operation,
+            "data": data,
+            "blockNumber": block_number,
+            "transactionHash": tx_hash,
+            "dataDecoded": data_decoded,
+            "transfers": transfers,
+            "executionDate": date,
+        }))
+    }
+}
+
+impl SafeTransactionFetcher for EthRpcFetcher {
+    /// Logs are fetched in windows of at most `max_block_range` blocks,
+    /// from the latest block back to `start_block`.
+    fn get_transaction_data_until(
+        &self,
+        safe_addr: &EthAddr,
+        is_known: &mut dyn FnMut(&RawGnosisTransaction) -> bool,
+    ) -> Result<Vec<RawGnosisTransaction>, Error> {
+        let chain_id = parse_quantity(&self.call::<String>("eth_chainId", json!([]))?)?;
+        if chain_id != self.chain_id {
+            return Err(Error::EthRpc(format!(
+                "Node is on chain {}, expected chain {}",
+                chain_id, self.chain_id
+            )));
+        }
+
+        let latest_block = parse_quantity(&self.call::<String>("eth_blockNumber", json!([]))?)?;
+        let start_block = self.config.start_block;
+        let max_block_range = self.config.max_block_range.max(1);
+
+        let mut raw_transactions = Vec::new();
+        let mut block_dates = HashMap::new();
+        let mut to_block = latest_block;
+        while to_block >= start_block {
+            let from_block = to_block
+                .saturating_sub(max_block_range - 1)
+                .max(start_block);
+            let batch =
+                self.get_transactions_in_range(safe_addr, from_block, to_block, &mut block_dates)?;
+            let reached_known_tx = batch.iter().any(&mut *is_known);
+            raw_transactions.extend(batch);
+
+            if reached_known_tx {
+                log::debug!(
+                    self.logger,
+                    "Reached a previously seen transaction, not fetching older blocks"
+                );
+                break;
+            }
+
+            match from_block.checked_sub(1) {
+                Some(block) => to_block = block,
+                None => break,
+            }
+        }
+
+        Ok(raw_transactions)
+    }
+}
+
+/// Parse a hex-encoded JSON-RPC quantity.
+fn parse_quantity(src: &str) -> Result<u64, Error> {
+    src.strip_prefix("0x")
+        .and_then(|hex_str| u64::from_str_radix(hex_str, 16).ok())
+        .ok_or_else(|| Error::EthRpc(format!("Invalid quantity: {src}")))
+}
+
+/// Encode an address as an indexed event topic.
+fn addr_topic(addr: &EthAddr) -> String {
+    format!("0x{:0>64}", addr.0.trim_start_matches("0x").to_lowercase())
+}
+
+fn hex_data(data: &[u8]) -> String {
+    format!("0x{}", hex::encode(data))
+}
+
+/// Convert an ERC20 `Transfer` log into the transfer format of the Gnosis
+/// safe-transaction-service.
+fn transfer_json(log: &RpcLog, block_number: u64, date: &str) -> Result<Value, Error> {
+    if log.topics.len() != 3 || log.topics[0] != TRANSFER_EVENT_TOPIC {
+        return Err(Error::EthRpc(format!(
+            "Unexpected log in transaction {}: {:?}",
+            log.transaction_hash, log.topics
+        )));
+    }
+
+    Ok(json!({
+        "type": "ERC20_TRANSFER",
+        "executionDate": date,
+        "blockNumber": block_number,
+        "transactionHash": log.transaction_hash,
+        "from": abi::decode_addr_word(&abi::decode_hex_data(&log.topics[1])?)?,
+        "to": abi::decode_addr_word(&abi::decode_hex_data(&log.topics[2])?)?,
+        "tokenAddress": log.address,
+        "tokenInfo": null,
+        "value": abi::decode_value_word(&abi::decode_hex_data(&log.data)?)?,
+    }))
+}
+
+/// Decode the data a safe transaction calls its target with, in the
+/// `dataDecoded` format of the Gnosis safe-transaction-service. Only
+/// `multiSend` and ERC20 `transfer` calls are decoded.
+fn decode_call_data(data: &[u8]) -> Result<Option<Value>, Error> {
+    if data.starts_with(&abi::MULTI_SEND_SELECTOR) {
+        let value_decoded = abi::decode_multi_send(data)?
+            .into_iter()
+            .map(|tx| {
+                json!({
+                    "operation": tx.operation,
+                    "to": tx.to,
+                    "value": tx.value,
+                    "data": hex_data(&tx.data),
+                    // The auxiliary burn contract call is left for the sync to parse.
+                    "dataDecoded": decode_call_data(&tx.data).ok().flatten(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Some(json!({
+            "method": "multiSend",
+            "parameters": [{
+                "name": "transactions",
+                "value": hex_data(&abi::multi_send_packed_data(data)?),
+                "valueDecoded": value_decoded,
+            }],
+        })))
+    } else if data.starts_with(&abi::ERC20_TRANSFER_SELECTOR) {
+        let transfer = abi::decode_erc20_transfer(data)?;
+        Ok(Some(json!({
+            "method": "transfer",
+            "parameters": [
+                { "name": "to", "value": transfer.to },
+                { "name": "value", "value": transfer.value },
+            ],
+        })))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{
+            test_utils::{
+                test_gnosis_config, TestDbContext, AUX_BURN_CONTRACT_ADDR, AUX_BURN_FUNCTION_SIG,
+                ETH_TOKEN_CONTRACT_ADDR, GNOSIS_SAFE_DEPOSIT_FROM_ADDR,
+                GNOSIS_SAFE_WITHDRAWAL_TO_ADDR, SAFE_ADDR,
+            },
+            GnosisSafeDeposit, GnosisSafeWithdrawal,
+        },
+        gnosis::{
+            api_data_types::{MultiSigTransaction, Transaction},
+            GnosisSync,
+        },
+    };
+    use mc_common::logger::{test_with_logger, Logger};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        str::FromStr,
+        thread,
+    };
+    use url::Url;
+
+    const DEPOSIT_TX_HASH: &str =
+        "0x1111111111111111111111111111111111111111111111111111111111111111";
+    const WITHDRAWAL_TX_HASH: &str =
+        "0x2222222222222222222222222222222222222222222222222222222222222222";
+    const MULTI_SEND_CONTRACT_ADDR: &str = "0x40A2aCCbd92BCA938b02010E17A5b8929b49130D";
+    const DEPOSIT_BLOCK: u64 = 30;
+    const WITHDRAWAL_BLOCK: u64 = 105;
+    const LATEST_BLOCK: u64 = 110;
+    const BLOCK_TIMESTAMP: u64 = 1654819523;
+
+    /// Serve JSON-RPC requests on a local port, answering each with the
+    /// result `handler` returns for its method and params.
+    fn start_stub(handler: impl Fn(&str, &Value) -> Value + Send + 'static) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                let result = handler(request["method"].as_str().unwrap(), &request["params"]);
+                let response =
+                    json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+
+        url
+    }
+
+    fn word(bytes: &[u8]) -> Vec<u8> {
+        let mut word = vec![0; 32 - bytes.len()];
+        word.extend(bytes);
+        word
+    }
+
+    fn addr_bytes(addr: &str) -> Vec<u8> {
+        hex::decode(&addr[2..]).unwrap()
+    }
+
+    fn value_word(value: u128) -> Vec<u8> {
+        word(&value.to_be_bytes())
+    }
+
+    fn bytes_arg(data: &[u8]) -> Vec<u8> {
+        let mut arg = value_word(data.len() as u128);
+        arg.extend(data);
+        arg.resize(arg.len() + (32 - data.len() % 32) % 32, 0);
+        arg
+    }
+
+    fn tx_out_public_key() -> Vec<u8> {
+        (1..=32).collect()
+    }
+
+    /// `execTransaction` calldata of a withdrawal of `amount` tokens.
+    fn withdrawal_input(amount: u128) -> String {
+        let mut transfer_data = abi::ERC20_TRANSFER_SELECTOR.to_vec();
+        transfer_data.extend(word(&addr_bytes(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR)));
+        transfer_data.extend(value_word(amount));
+
+        let mut aux_data = AUX_BURN_FUNCTION_SIG.to_vec();
+        aux_data.extend(value_word(32));
+        aux_data.extend(bytes_arg(&tx_out_public_key()));
+
+        let mut packed = Vec::new();
+        for (to, data) in [
+            (ETH_TOKEN_CONTRACT_ADDR, transfer_data),
+            (AUX_BURN_CONTRACT_ADDR, aux_data),
+        ] {
+            packed.push(0);
+            packed.extend(addr_bytes(to));
+            packed.extend(value_word(0));
+            packed.extend(value_word(data.len() as u128));
+            packed.extend(data);
+        }
+
+        let mut multi_send_data = abi::MULTI_SEND_SELECTOR.to_vec();
+        multi_send_data.extend(value_word(32));
+        multi_send_data.extend(bytes_arg(&packed));
+
+        let data_arg = bytes_arg(&multi_send_data);
+        let mut input = abi::EXEC_TRANSACTION_SELECTOR.to_vec();
+        input.extend(word(&addr_bytes(MULTI_SEND_CONTRACT_ADDR)));
+        input.extend(value_word(0));
+        input.extend(value_word(10 * 32));
+        input.extend(value_word(1));
+        for _ in 0..5 {
+            input.extend(value_word(0));
+        }
+        input.extend(value_word((10 * 32 + data_arg.len()) as u128));
+        input.extend(data_arg);
+        input.extend(bytes_arg(&[]));
+        hex_data(&input)
+    }
+
+    fn log_json(
+        address: &str,
+        topics: Vec<String>,
+        data: Vec<u8>,
+        tx_hash: &str,
+        block_number: u64,
+        log_index: u64,
+    ) -> Value {
+        json!({
+            "address": address,
+            "topics": topics,
+            "data": hex_data(&data),
+            "blockNumber": format!("{block_number:#x}"),
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "logIndex": format!("{log_index:#x}"),
+            "removed": false,
+        })
+    }
+
+    fn transfer_log(from: &str, to: &str, amount: u128, tx_hash: &str, block: u64) -> Value {
+        log_json(
+            ETH_TOKEN_CONTRACT_ADDR,
+            vec![
+                TRANSFER_EVENT_TOPIC.to_string(),
+                hex_data(&word(&addr_bytes(from))),
+                hex_data(&word(&addr_bytes(to))),
+            ],
+            value_word(amount),
+            tx_hash,
+            block,
+            0,
+        )
+    }
+
+    /// A node holding a deposit of 1000 tokens and a withdrawal of 400.
+    fn start_node() -> Url {
+        start_stub(|method, params| {
+            let deposit_log = transfer_log(
+                GNOSIS_SAFE_DEPOSIT_FROM_ADDR,
+                SAFE_ADDR,
+                1000,
+                DEPOSIT_TX_HASH,
+                DEPOSIT_BLOCK,
+            );
+            let withdrawal_log = transfer_log(
+                SAFE_ADDR,
+                GNOSIS_SAFE_WITHDRAWAL_TO_ADDR,
+                400,
+                WITHDRAWAL_TX_HASH,
+                WITHDRAWAL_BLOCK,
+            );
+            let execution_log = log_json(
+                SAFE_ADDR,
+                vec![EXECUTION_SUCCESS_EVENT_TOPIC.to_string()],
+                [value_word(0), value_word(0)].concat(),
+                WITHDRAWAL_TX_HASH,
+                WITHDRAWAL_BLOCK,
+                1,
+            );
+
+            match method {
+                "eth_chainId" => json!("0x1"),
+                "eth_blockNumber" => json!(format!("{LATEST_BLOCK:#x}")),
+                "eth_getLogs" => {
+                    let filter = &params[0];
+                    let from_block = parse_quantity(filter["fromBlock"].as_str().unwrap()).unwrap();
+                    let to_block = parse_quantity(filter["toBlock"].as_str().unwrap()).unwrap();
+                    let topics = filter["topics"].as_array().unwrap();
+                    let logs = if topics[0] == EXECUTION_SUCCESS_EVENT_TOPIC {
+                        vec![execution_log]
+                    } else if topics.len() == 3 {
+                        vec![deposit_log]
+                    } else {
+                        vec![withdrawal_log]
+                    };
+                    json!(logs
+                        .into_iter()
+                        .filter(|log| {
+                            let block =
+                                parse_quantity(log["blockNumber"].as_str().unwrap()).unwrap();
+                            (from_block..=to_block).contains(&block)
+                        })
+                        .collect::<Vec<_>>())
+                }
+                "eth_getTransactionByHash" => json!({
+                    "to": SAFE_ADDR,
+                    "input": withdrawal_input(400),
+                }),
+                "eth_getTransactionReceipt" => json!({
+                    "status": "0x1",
+                    "logs": [withdrawal_log, execution_log],
+                }),
+                "eth_getBlockByNumber" => json!({ "timestamp": format!("{BLOCK_TIMESTAMP:#x}") }),
+                _ => Value::Null,
+            }
+        })
+    }
+
+    fn create_fetcher(url: Url, chain_id: u64, logger: Logger) -> EthRpcFetcher {
+        EthRpcFetcher::new(
+            EthRpcConfig {
+                url,
+                start_block: 0,
+                max_block_range: 50,
+            },
+            chain_id,
+            vec![EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap()],
+            logger,
+        )
+        .unwrap()
+    }
+
+    #[test_with_logger]
+    fn get_transaction_data_decodes_deposits_and_withdrawals(logger: Logger) {
+        let fetcher = create_fetcher(start_node(), 1, logger);
+        let safe_addr = EthAddr::from_str(SAFE_ADDR).unwrap();
+
+        let transactions = fetcher.get_all_transaction_data(&safe_addr).unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        // Newest first.
+        match transactions[0].decode().unwrap() {
+            Transaction::MultiSig(MultiSigTransaction {
+                safe,
+                to,
+                eth_block_number,
+                tx_hash,
+                data_decoded,
+                transfer_data,
+                ..
+            }) => {
+                assert_eq!(safe, safe_addr);
+                assert_eq!(to, EthAddr::from_str(MULTI_SEND_CONTRACT_ADDR).unwrap());
+                assert_eq!(eth_block_number, WITHDRAWAL_BLOCK);
+                assert_eq!(tx_hash, EthTxHash::from_str(WITHDRAWAL_TX_HASH).unwrap());
+                assert_eq!(transfer_data.len(), 1);
+                assert_eq!(transfer_data[0].value, Some(EthTxValue(400)));
+
+                let data_decoded = data_decoded.unwrap();
+                assert_eq!(data_decoded.method, "multiSend");
+                let value_decoded = data_decoded.parameters[0].value_decoded.as_ref().unwrap();
+                assert_eq!(value_decoded.len(), 2);
+                assert_eq!(
+                    value_decoded[0].data_decoded.as_ref().unwrap().parameters[1].value,
+                    "400"
+                );
+                assert_eq!(
+                    value_decoded[1].to,
+                    EthAddr::from_str(AUX_BURN_CONTRACT_ADDR).unwrap()
+                );
+                assert!(value_decoded[1].data_decoded.is_none());
+            }
+            tx => panic!("unexpected transaction {tx:?}"),
+        }
+        match transactions[1].decode().unwrap() {
+            Transaction::Ethereum(eth_tx) => {
+                assert_eq!(
+                    eth_tx.tx_hash,
+                    EthTxHash::from_str(DEPOSIT_TX_HASH).unwrap()
+                );
+                assert_eq!(eth_tx.eth_block_number, DEPOSIT_BLOCK);
+                assert_eq!(eth_tx.execution_date.timestamp() as u64, BLOCK_TIMESTAMP);
+                assert_eq!(eth_tx.transfers.len(), 1);
+                assert_eq!(eth_tx.transfers[0].to, safe_addr);
+                assert_eq!(eth_tx.transfers[0].value, Some(EthTxValue(1000)));
+            }
+            tx => panic!("unexpected transaction {tx:?}"),
+        }
+
+        // Fetching stops at the block window containing a known transaction.
+        let withdrawal_tx_hash = EthTxHash::from_str(WITHDRAWAL_TX_HASH).unwrap();
+        let transactions = fetcher
+            .get_transaction_data_until(&safe_addr, &mut |tx| {
+                tx.tx_hash().unwrap() == withdrawal_tx_hash
+            })
+            .unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].tx_hash().unwrap(), withdrawal_tx_hash);
+    }
+
+    #[test_with_logger]
+    fn get_transaction_data_checks_chain_id(logger: Logger) {
+        let fetcher = create_fetcher(start_node(), 10, logger);
+        let safe_addr = EthAddr::from_str(SAFE_ADDR).unwrap();
+
+        assert!(matches!(
+            fetcher.get_all_transaction_data(&safe_addr),
+            Err(Error::EthRpc(_))
+        ));
+    }
+
+    #[test_with_logger]
+    fn sync_from_eth_rpc(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let mut audited_safe = test_gnosis_config().safes[0].clone();
+        audited_safe.eth_rpc = Some(EthRpcConfig {
+            url: start_node(),
+            start_block: 0,
+            max_block_range: 50,
+        });
+
+        GnosisSync::new(audited_safe, reserve_auditor_db, logger)
+            .unwrap()
+            .poll();
+
+        let deposits =
+            GnosisSafeDeposit::list_by_safe(1, &EthAddr::from_str(SAFE_ADDR).unwrap(), &conn)
+                .unwrap();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(
+            deposits[0].eth_tx_hash(),
+            &EthTxHash::from_str(DEPOSIT_TX_HASH).unwrap()
+        );
+        assert_eq!(deposits[0].amount(), 1000);
+
+        let withdrawals =
+            GnosisSafeWithdrawal::list_by_safe(1, &EthAddr::from_str(SAFE_ADDR).unwrap(), &conn)
+                .unwrap();
+        assert_eq!(withdrawals.len(), 1);
+        assert_eq!(
+            withdrawals[0].eth_tx_hash(),
+            &EthTxHash::from_str(WITHDRAWAL_TX_HASH).unwrap()
+        );
+        assert_eq!(withdrawals[0].amount(), 400);
+        assert_eq!(
+            withdrawals[0].mc_tx_out_public_key_hex(),
+            hex::encode(tx_out_public_key())
+        );
+    }
+}
@@ -2,79 +2,333 @@
 
 //! Code for syncing transactions from the Gnosis API into the SQLite database.
 //!
-//! NOTE: Right now, if the audited safes configuration changes, one should
-//! delete the SQLite database and re-audit. The code is not smart enough to
-//! handle adding/removing safes/tokens for Gnosis transactions that were
-//! already processed.
+//! The configuration each safe was audited with is stored in the database.
+//! When it changes (e.g. a token gets added, removed or migrated to a new
+//! contract address), [GnosisSync::apply_config_changes] re-audits the
+//! Gnosis transactions that were already stored, without fetching them again.
 
 use crate::{
+    anomaly::{AnomalyEvent, AnomalyKind},
     counters,
     db::{
-        AuditedBurn, AuditedMint, Conn, Counters, GnosisSafeDeposit, GnosisSafeTx,
-        GnosisSafeWithdrawal, ReserveAuditorDb,
+        AuditedBurn, AuditedMint, Conn, Counters, GnosisSafeAuditConfig, GnosisSafeDeposit,
+        GnosisSafeSyncState, GnosisSafeTx, GnosisSafeWithdrawal, ManualMatch, ReserveAuditorDb,
     },
     error::Error,
     gnosis::{
+        abi::{self, Erc20Transfer},
         api_data_types::{
-            EthereumTransaction, MultiSigTransaction, RawGnosisTransaction, Transaction,
+            EthereumTransaction, EthereumTransfer, MultiSigTransaction, RawGnosisTransaction,
+            Transaction,
         },
-        fetcher::GnosisSafeFetcher,
-        truncate_value, AuditedSafeConfig, Error as GnosisError,
+        fetcher::{GnosisSafeFetcher, SafeTransactionFetcher},
+        rpc_fetcher::EthRpcFetcher,
+        truncate_value,
+        verification::{compare_deposits, compare_withdrawals},
+        AuditedSafeConfig, Error as GnosisError, EthAddr, EthTxHash, GnosisSafeConfig,
+        SafeDataSource, VerificationStatus,
     },
 };
 use mc_common::logger::{log, Logger};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 
 use super::EthTxValue;
 
 /// An object for syncing transaction data from the Gnosis API into the SQLite
 /// database.
 pub struct GnosisSync {
-    fetcher: GnosisSafeFetcher,
+    fetcher: Box<dyn SafeTransactionFetcher>,
+    verifier: Option<Box<dyn SafeTransactionFetcher>>,
     audited_safe: AuditedSafeConfig,
     reserve_auditor_db: ReserveAuditorDb,
     logger: Logger,
 }
 
 impl GnosisSync {
-    /// Instantiate a new [GnosisSync] object.
+    /// Instantiate a new [GnosisSync] object. Transactions are fetched from
+    /// the safe's Ethereum JSON-RPC node if one is configured, and from the
+    /// Gnosis safe transaction service otherwise. If the safe has a
+    /// verification source configured, a fetcher for it is created as well.
     pub fn new(
         audited_safe: AuditedSafeConfig,
         reserve_auditor_db: ReserveAuditorDb,
         logger: Logger,
     ) -> Result<Self, GnosisError> {
+        let source = match &audited_safe.eth_rpc {
+            Some(eth_rpc) => SafeDataSource::EthRpc(eth_rpc.clone()),
+            None => SafeDataSource::SafeApi {
+                api_url: audited_safe.api_url.clone(),
+            },
+        };
+        let fetcher = Self::create_fetcher(&source, &audited_safe, &logger)?;
+        let verifier = audited_safe
+            .verify_with
+            .as_ref()
+            .map(|source| Self::create_fetcher(source, &audited_safe, &logger))
+            .transpose()?;
+
         Ok(Self {
-            fetcher: GnosisSafeFetcher::new(audited_safe.api_url.clone(), logger.clone())?,
+            fetcher,
+            verifier,
             audited_safe,
             reserve_auditor_db,
             logger,
         })
     }
 
-    /// Poll the Gnosis API for transaction data.
+    /// Create a fetcher for reading the safe's transactions from a source.
+    fn create_fetcher(
+        source: &SafeDataSource,
+        audited_safe: &AuditedSafeConfig,
+        logger: &Logger,
+    ) -> Result<Box<dyn SafeTransactionFetcher>, GnosisError> {
+        Ok(match source {
+            SafeDataSource::EthRpc(eth_rpc) => Box::new(EthRpcFetcher::new(
+                eth_rpc.clone(),
+                audited_safe.chain_id,
+                audited_safe
+                    .tokens
+                    .iter()
+                    .flat_map(|token| token.eth_token_contract_addrs.iter().cloned())
+                    .collect(),
+                logger.clone(),
+            )?),
+            SafeDataSource::SafeApi { api_url } => {
+                Box::new(GnosisSafeFetcher::new(api_url.clone(), logger.clone())?)
+            }
+        })
+    }
+
+    /// Poll the Gnosis API (or the Ethereum JSON-RPC node) for transaction
+    /// data.
+    ///
+    /// The Gnosis API returns transactions from the newest to the oldest, and
+    /// right now there is no way to change that (see
+    /// https://github.com/safe-global/safe-transaction-service/issues/847).
+    /// Until we have managed to fetch and process the entire history of the
+    /// safe once, every poll fetches all transactions. After that, fetching
+    /// stops once we reach a page containing a transaction we have already
+    /// stored.
+    ///
+    /// If the safe has a verification source configured, the deposits and
+    /// withdrawals that have not been verified yet are then cross-checked
+    /// against it (see [GnosisSync::verify_against_source]).
     pub fn poll(&self) {
-        // TODO: This is inefficient since it repeatedly fetches all transactions, even
-        // once we reached ones we have seen before. The gnosis safe API returns
-        // transactions from the newest to the oldest, and right now there is no way to
-        // change that (see https://github.com/safe-global/safe-transaction-service/issues/847).
-        // For this to be more efficient we need to first fetch everything once, until
-        // we are certain we have synced all transactions. After that, we could
-        // add an optimization that stops fetching once we've reached a transaction
-        // we've seen before. This requires keeping track of whether we have
-        // managed to complete a full fetch. Since right now we are not
-        // expecting a large amount of transactions, this optimization is postponed to a
-        // future PR.
-        match self
-            .fetcher
-            .get_all_transaction_data(&self.audited_safe.safe_addr)
-        {
-            Ok(transactions) => {
-                self.process_transactions(transactions);
+        let chain_id = self.audited_safe.chain_id;
+        let safe_addr = &self.audited_safe.safe_addr;
+        let conn = match self.reserve_auditor_db.get_conn() {
+            Ok(conn) => conn,
+            Err(err) => {
+                log::error!(self.logger, "Failed getting database connection: {}", err);
+                return;
+            }
+        };
+
+        let full_sync_completed = match GnosisSafeSyncState::get(chain_id, safe_addr, &conn) {
+            Ok(sync_state) => sync_state
+                .map(|sync_state| sync_state.full_sync_completed())
+                .unwrap_or(false),
+            Err(err) => {
+                log::error!(self.logger, "Failed getting Gnosis sync state: {}", err);
+                return;
             }
+        };
+
+        let fetch_result = if full_sync_completed {
+            self.fetcher
+                .get_transaction_data_until(safe_addr, &mut |tx| {
+                    matches!(
+                        tx.tx_hash().map_err(Error::from).and_then(|eth_tx_hash| {
+                            GnosisSafeTx::exists(chain_id, &eth_tx_hash, &conn)
+                        }),
+                        Ok(true)
+                    )
+                })
+        } else {
+            self.fetcher.get_all_transaction_data(safe_addr)
+        };
+
+        let mut transactions = match fetch_result {
+            Ok(transactions) => transactions,
             Err(err) => {
                 counters::NUM_FAILED_GNOSIS_GET_ALL_TRANSACTION_DATA.inc();
                 log::error!(self.logger, "Failed to fetch Gnosis transactions: {}", err);
+                return;
+            }
+        };
+
+        let newest_eth_tx_hash = transactions.first().and_then(|tx| tx.tx_hash().ok());
+
+        // Process the oldest transactions first, so that if we get interrupted we
+        // never end up with a stored transaction that has older, unprocessed ones
+        // behind it.
+        transactions.reverse();
+        self.process_transactions(transactions);
+
+        if let Err(err) = GnosisSafeSyncState::record_successful_sync(
+            chain_id,
+            safe_addr,
+            true,
+            newest_eth_tx_hash.as_ref(),
+            &conn,
+        ) {
+            log::error!(self.logger, "Failed recording Gnosis sync state: {}", err);
+        }
+
+        if let Some(verifier) = &self.verifier {
+            if let Err(err) = self.verify_against_source(verifier.as_ref(), &conn) {
+                log::error!(
+                    self.logger,
+                    "Failed verifying Gnosis data against the verification source: {}",
+                    err
+                );
+            }
+        }
+    }
+
+    /// Cross-check the unverified deposits and withdrawals of this safe
+    /// against the transactions reported by a second source. Each row whose
+    /// transaction the source reports with the same tx hash, block number,
+    /// token, value and recipient is marked as verified. Rows the source
+    /// disagrees with, or that it does not know about even though it has
+    /// already seen later blocks, are marked as a disagreement and recorded as
+    /// an anomaly. Rows in blocks the source has not caught up with yet are
+    /// left unverified and retried on the next poll.
+    fn verify_against_source(
+        &self,
+        verifier: &dyn SafeTransactionFetcher,
+        conn: &Conn,
+    ) -> Result<(), Error> {
+        let chain_id = self.audited_safe.chain_id;
+        let safe_addr = &self.audited_safe.safe_addr;
+        let deposits = GnosisSafeDeposit::list_unverified_by_safe(chain_id, safe_addr, conn)?;
+        let withdrawals = GnosisSafeWithdrawal::list_unverified_by_safe(chain_id, safe_addr, conn)?;
+        if deposits.is_empty() && withdrawals.is_empty() {
+            return Ok(());
+        }
+
+        // The source returns transactions from the newest to the oldest, so stop
+        // once every transaction we are looking for has been seen.
+        let mut remaining = deposits
+            .iter()
+            .map(|deposit| *deposit.eth_tx_hash())
+            .chain(
+                withdrawals
+                    .iter()
+                    .map(|withdrawal| *withdrawal.eth_tx_hash()),
+            )
+            .collect::<HashSet<_>>();
+        let source_txs = verifier.get_transaction_data_until(safe_addr, &mut |tx| {
+            if let Ok(eth_tx_hash) = tx.tx_hash() {
+                remaining.remove(&eth_tx_hash);
+            }
+            remaining.is_empty()
+        })?;
+
+        let mut source_tx_hashes = HashSet::new();
+        let mut source_deposits = HashMap::new();
+        let mut source_withdrawals = HashMap::new();
+        let mut source_last_block = 0;
+        for raw_tx in &source_txs {
+            match raw_tx.decode()? {
+                Transaction::Ethereum(eth_tx) => {
+                    source_tx_hashes.insert(eth_tx.tx_hash);
+                    source_last_block = source_last_block.max(eth_tx.eth_block_number);
+                    for transfer in &eth_tx.transfers {
+                        if transfer.to == *safe_addr {
+                            source_deposits
+                                .insert(transfer.tx_hash, self.parse_deposit(&eth_tx, transfer));
+                        }
+                    }
+                }
+                Transaction::MultiSig(multi_sig_tx) => {
+                    source_tx_hashes.insert(multi_sig_tx.tx_hash);
+                    source_last_block = source_last_block.max(multi_sig_tx.eth_block_number);
+                    source_withdrawals.insert(
+                        multi_sig_tx.tx_hash,
+                        self.parse_withdrawal_with_pub_key_multi_sig_tx(&multi_sig_tx),
+                    );
+                }
+                Transaction::Module(_) => {}
             }
         }
+
+        // Whether the source should know about a transaction we did not find in it.
+        let missing = |eth_tx_hash: &EthTxHash, eth_block_number: u64| {
+            (source_tx_hashes.contains(eth_tx_hash) || source_last_block > eth_block_number)
+                .then(|| "missing from the verification source".to_string())
+        };
+
+        conn.exclusive_transaction(|| -> Result<(), Error> {
+            for mut deposit in deposits {
+                let disagreement = match source_deposits.get(deposit.eth_tx_hash()) {
+                    Some(Ok(source_deposit)) => compare_deposits(&deposit, source_deposit),
+                    Some(Err(err)) => Some(format!("invalid verification source data: {err}")),
+                    None => match missing(deposit.eth_tx_hash(), deposit.eth_block_number()) {
+                        Some(disagreement) => Some(disagreement),
+                        None => continue,
+                    },
+                };
+
+                match disagreement {
+                    None => deposit.set_verification_status(VerificationStatus::Verified, conn)?,
+                    Some(disagreement) => {
+                        deposit.set_verification_status(VerificationStatus::Disagreement, conn)?;
+                        log::crit!(
+                            self.logger,
+                            "Verification source disagrees with Gnosis deposit eth_tx_hash={}: {}",
+                            deposit.eth_tx_hash(),
+                            disagreement
+                        );
+                        Counters::record_anomaly(
+                            &AnomalyEvent::new(AnomalyKind::GnosisSourceDisagreement, disagreement)
+                                .with_gnosis_deposit(&deposit),
+                            conn,
+                        )?;
+                    }
+                }
+            }
+
+            for mut withdrawal in withdrawals {
+                let disagreement = match source_withdrawals.get(withdrawal.eth_tx_hash()) {
+                    Some(Ok(source_withdrawal)) => {
+                        compare_withdrawals(&withdrawal, source_withdrawal)
+                    }
+                    Some(Err(err)) => Some(format!("invalid verification source data: {err}")),
+                    None => match missing(withdrawal.eth_tx_hash(), withdrawal.eth_block_number())
+                    {
+                        Some(disagreement) => Some(disagreement),
+                        None => continue,
+                    },
+                };
+
+                match disagreement {
+                    None => {
+                        withdrawal.set_verification_status(VerificationStatus::Verified, conn)?
+                    }
+                    Some(disagreement) => {
+                        withdrawal
+                            .set_verification_status(VerificationStatus::Disagreement, conn)?;
+                        log::crit!(
+                            self.logger,
+                            "Verification source disagrees with Gnosis withdrawal eth_tx_hash={}: {}",
+                            withdrawal.eth_tx_hash(),
+                            disagreement
+                        );
+                        Counters::record_anomaly(
+                            &AnomalyEvent::new(AnomalyKind::GnosisSourceDisagreement, disagreement)
+                                .with_gnosis_withdrawal(&withdrawal),
+                            conn,
+                        )?;
+                    }
+                }
+            }
+
+            Ok(())
+        })
     }
 
     /// Process transactions and insert them to the database.
@@ -88,7 +342,7 @@ impl GnosisSync {
             // SQLite3 does not like concurrent writes. Since we are going to be writing to
             // the database, ensure we are the only writers.
             conn.exclusive_transaction(|| {
-                match GnosisSafeTx::insert(&tx, &conn) {
+                match GnosisSafeTx::insert(&tx, self.audited_safe.chain_id, &conn) {
                     Ok(_) => {}
                     Err(Error::AlreadyExists(_)) => {
                         log::trace!(
@@ -104,38 +358,257 @@ impl GnosisSync {
                     }
                 };
 
-                match tx.decode()? {
-                    Transaction::Ethereum(eth_tx) => {
-                        match self.process_eth_transaction(&conn, &eth_tx) {
-                            Ok(_) => {}
-                            Err(err) => {
-                                // log but otherwise ignore unknown token transfers
-                                if let Error::Gnosis(GnosisError::UnknownToken(_)) = err {
-                                    log::warn!(self.logger, "Unknown token deposited to Safe");
-                                } else {
-                                    return Err(err);
-                                }
-                            }
-                        }
-                    }
-                    Transaction::MultiSig(multi_sig_tx) => {
-                        self.process_multi_sig_transaction(&conn, &multi_sig_tx)?;
-                    }
-                    Transaction::Module(value) => {
-                        log::warn!(
-                            self.logger,
-                            "Got unexpected \"Module\" transaction: {:?}",
-                            value
-                        );
-                    }
-                };
+                self.process_transaction(&conn, &tx)
+            })
+            .expect("failed processing transaction");
+        }
+    }
+
+    /// Bring the database in line with a (possibly changed) Gnosis safe
+    /// configuration. This is expected to be called on startup, before any
+    /// syncing takes place.
+    /// - Safes that are no longer audited have their deposits, withdrawals
+    ///   and the matching audit records removed.
+    /// - Safes whose audited tokens changed since they were last audited have
+    ///   their stored transactions re-audited (see
+    ///   [GnosisSync::reaudit_stored_transactions]).
+    pub fn apply_config_changes(
+        config: &GnosisSafeConfig,
+        reserve_auditor_db: &ReserveAuditorDb,
+        logger: &Logger,
+    ) -> Result<(), Error> {
+        let conn = reserve_auditor_db.get_conn()?;
+
+        for stored_config in GnosisSafeAuditConfig::list(&conn)? {
+            let chain_id = stored_config.chain_id();
+            let safe_addr = stored_config.safe_addr();
+            if config
+                .get_audited_safe_config(chain_id, safe_addr)
+                .is_some()
+            {
+                continue;
+            }
 
+            conn.exclusive_transaction(|| -> Result<(), Error> {
+                let num_deposits = GnosisSafeDeposit::delete_by_safe(chain_id, safe_addr, &conn)?;
+                let num_withdrawals =
+                    GnosisSafeWithdrawal::delete_by_safe(chain_id, safe_addr, &conn)?;
+                GnosisSafeAuditConfig::delete(chain_id, safe_addr, &conn)?;
+
+                log::info!(
+                    logger,
+                    "Safe {} on chain {} is no longer audited, removed {} deposits and {} withdrawals",
+                    safe_addr,
+                    chain_id,
+                    num_deposits,
+                    num_withdrawals,
+                );
                 Ok(())
+            })?;
+        }
+
+        for audited_safe in &config.safes {
+            let stored_config =
+                GnosisSafeAuditConfig::get(audited_safe.chain_id, &audited_safe.safe_addr, &conn)?
+                    .map(|stored_config| stored_config.decode());
+
+            let needs_reaudit = match stored_config {
+                Some(Ok(stored_config)) => {
+                    stored_config.tokens != audited_safe.tokens
+                        || stored_config.token_decimals_max != audited_safe.token_decimals_max
+                }
+                Some(Err(err)) => {
+                    log::warn!(
+                        logger,
+                        "Failed decoding stored config of safe {}, re-auditing: {}",
+                        audited_safe.safe_addr,
+                        err
+                    );
+                    true
+                }
+                // We have no record of how this safe was audited. If it has deposits or
+                // withdrawals, they were audited by a version that did not record
+                // configurations, with the configuration we have now. Otherwise this is a
+                // new safe, or one that was removed and added back, whose stored
+                // transactions (if any) need to be processed again.
+                None => {
+                    GnosisSafeDeposit::list_by_safe(
+                        audited_safe.chain_id,
+                        &audited_safe.safe_addr,
+                        &conn,
+                    )?
+                    .is_empty()
+                        && GnosisSafeWithdrawal::list_by_safe(
+                            audited_safe.chain_id,
+                            &audited_safe.safe_addr,
+                            &conn,
+                        )?
+                        .is_empty()
+                }
+            };
+
+            if needs_reaudit {
+                GnosisSync::new(
+                    audited_safe.clone(),
+                    reserve_auditor_db.clone(),
+                    logger.clone(),
+                )?
+                .reaudit_stored_transactions()?;
+            } else {
+                GnosisSafeAuditConfig::set(audited_safe, &conn)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-audit the Gnosis transactions of this safe that are already stored
+    /// in the database, using the current configuration. The safe's deposits,
+    /// withdrawals and the matching audit records are deleted and then rebuilt
+    /// from the stored raw transaction data. Nothing is fetched from the
+    /// Gnosis API. Anomalies that were already recorded for re-processed
+    /// transactions are not counted or notified about again.
+    /// Returns the number of transactions that were re-processed.
+    pub fn reaudit_stored_transactions(&self) -> Result<usize, Error> {
+        let conn = self.reserve_auditor_db.get_conn()?;
+
+        // SQLite3 does not like concurrent writes. Since we are going to be writing to
+        // the database, ensure we are the only writers.
+        conn.exclusive_transaction(|| -> Result<usize, Error> {
+            GnosisSafeDeposit::delete_by_safe(
+                self.audited_safe.chain_id,
+                &self.audited_safe.safe_addr,
+                &conn,
+            )?;
+            GnosisSafeWithdrawal::delete_by_safe(
+                self.audited_safe.chain_id,
+                &self.audited_safe.safe_addr,
+                &conn,
+            )?;
+
+            self.replay_stored_transactions(&conn)
+        })
+    }
+
+    /// Re-audit all stored Gnosis transactions from scratch. All deposits,
+    /// withdrawals and audit records are deleted, after which the stored raw
+    /// transaction data of every safe in `config` is processed again and
+    /// matched against the MintTxs and BurnTxOuts already in the database.
+    /// Nothing is fetched from the Gnosis API.
+    /// Returns the number of transactions that were re-processed.
+    pub fn reaudit_all(
+        config: &GnosisSafeConfig,
+        reserve_auditor_db: &ReserveAuditorDb,
+        logger: &Logger,
+    ) -> Result<usize, Error> {
+        let syncs = config
+            .safes
+            .iter()
+            .map(|audited_safe| {
+                GnosisSync::new(
+                    audited_safe.clone(),
+                    reserve_auditor_db.clone(),
+                    logger.clone(),
+                )
             })
-            .expect("failed processing transaction");
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let conn = reserve_auditor_db.get_conn()?;
+        conn.exclusive_transaction(|| -> Result<usize, Error> {
+            let num_deposits = GnosisSafeDeposit::delete_all(&conn)?;
+            let num_withdrawals = GnosisSafeWithdrawal::delete_all(&conn)?;
+            log::info!(
+                logger,
+                "Deleted {} deposits and {} withdrawals, re-auditing",
+                num_deposits,
+                num_withdrawals
+            );
+
+            let mut num_processed = 0;
+            for sync in &syncs {
+                num_processed += sync.replay_stored_transactions(&conn)?;
+            }
+            Ok(num_processed)
+        })
+    }
+
+    /// Process the stored transactions that involve this safe, re-apply the
+    /// manual matches involving them and record the configuration they were
+    /// audited with. Expects the safe's deposits and withdrawals to have been
+    /// deleted beforehand.
+    fn replay_stored_transactions(&self, conn: &Conn) -> Result<usize, Error> {
+        let mut num_processed = 0;
+        for stored_tx in GnosisSafeTx::list_by_chain_id(self.audited_safe.chain_id, conn)? {
+            let tx = stored_tx.decode()?;
+            if self.involves_audited_safe(&tx.decode()?) {
+                self.process_transaction(conn, &tx)?;
+                num_processed += 1;
+            }
+        }
+
+        GnosisSafeAuditConfig::set(&self.audited_safe, conn)?;
+
+        // Deleting the deposits and withdrawals dropped the links of manual matches
+        // involving them.
+        let num_manual_matches = ManualMatch::reapply_all(conn)?;
+
+        log::info!(
+            self.logger,
+            "Re-audited {} stored transactions of safe {}, re-applied {} manual matches",
+            num_processed,
+            self.audited_safe.safe_addr,
+            num_manual_matches,
+        );
+
+        Ok(num_processed)
+    }
+
+    /// Check whether a transaction is one that would have been returned by the
+    /// Gnosis API for the safe we are auditing.
+    fn involves_audited_safe(&self, tx: &Transaction) -> bool {
+        let safe_addr = &self.audited_safe.safe_addr;
+        match tx {
+            Transaction::Ethereum(eth_tx) => eth_tx
+                .transfers
+                .iter()
+                .any(|transfer| transfer.to == *safe_addr || transfer.from == *safe_addr),
+            Transaction::MultiSig(multi_sig_tx) => multi_sig_tx.safe == *safe_addr,
+            Transaction::Module(_) => false,
         }
     }
 
+    /// Process a single transaction that has already been stored in the
+    /// database.
+    fn process_transaction(&self, conn: &Conn, tx: &RawGnosisTransaction) -> Result<(), Error> {
+        match tx.decode()? {
+            Transaction::Ethereum(eth_tx) => {
+                match self.process_eth_transaction(conn, &eth_tx) {
+                    Ok(_) => {}
+                    Err(err) => {
+                        // log but otherwise ignore unknown token transfers
+                        if let Error::Gnosis(GnosisError::UnknownToken(_)) = err {
+                            log::warn!(self.logger, "Unknown token deposited to Safe");
+                        } else {
+                            return Err(err);
+                        }
+                    }
+                }
+            }
+            Transaction::MultiSig(multi_sig_tx) => {
+                self.process_multi_sig_transaction(conn, &multi_sig_tx)?;
+            }
+            Transaction::Module(value) => {
+                log::warn!(
+                    self.logger,
+                    "Got unexpected \"Module\" transaction: {:?}",
+                    value
+                );
+            }
+        };
+
+        Ok(())
+    }
+
     /// Process an Ethereum transaction.
     fn process_eth_transaction(&self, conn: &Conn, tx: &EthereumTransaction) -> Result<(), Error> {
         log::trace!(self.logger, "Processing Ethereum transaction: {:?}", tx);
@@ -149,43 +622,16 @@ impl GnosisSync {
                     transfer
                 );
 
-                let result = || -> Result<GnosisSafeDeposit, GnosisError> {
-                    let token_addr = &transfer.token_addr.clone().ok_or_else(|| {
-                        GnosisError::ApiResultParse("Transfer token address cannot be empty".into())
-                    })?;
-
-                    let token_config = self
-                        .audited_safe
-                        .tokens
-                        .iter()
-                        .find(|token| token.eth_token_contract_addrs.contains(token_addr))
-                        .ok_or_else(|| {
-                            GnosisError::UnknownToken("Unknown token transfer".into())
-                        })?;
-
-                    let truncated_transaction_value = truncate_value(
-                        transfer.value.unwrap(),
-                        token_config.decimals,
-                        self.audited_safe.token_decimals_max,
-                    );
+                let result = self.parse_deposit(tx, transfer);
 
-                    Ok(GnosisSafeDeposit::new(
-                        None,
-                        transfer.tx_hash,
-                        tx.execution_date,
-                        transfer.value.unwrap(),
-                        tx.eth_block_number,
-                        transfer.to.clone(),
-                        token_addr.clone(),
-                        transfer.from.clone(),
-                        truncated_transaction_value,
-                    ))
-                }();
-
-                match result {
+                match &result {
                     Ok(_) => {}
-                    Err(_) => {
-                        Counters::inc_num_invalid_ethereum_transactions(conn)?;
+                    Err(err) => {
+                        Counters::record_anomaly(
+                            &AnomalyEvent::new(AnomalyKind::InvalidEthereumTransaction, err)
+                                .with_eth_tx(&tx.tx_hash, tx.eth_block_number),
+                            conn,
+                        )?;
                     }
                 }
 
@@ -193,15 +639,64 @@ impl GnosisSync {
 
                 deposit.insert(conn)?;
 
+                // The deposit is stored in full so that the reserve is still accounted for,
+                // but it can never be minted.
+                if deposit.mc_amount().is_none() {
+                    log::crit!(
+                        self.logger,
+                        "Gnosis deposit eth_tx_hash={} amount {} does not fit in a MobileCoin amount",
+                        deposit.eth_tx_hash(),
+                        deposit.amount(),
+                    );
+                    Counters::record_anomaly(
+                        &AnomalyEvent::new(
+                            AnomalyKind::OversizedReserveAmount,
+                            format!(
+                                "Deposit amount {} does not fit in a MobileCoin amount",
+                                deposit.amount()
+                            ),
+                        )
+                        .with_gnosis_deposit(&deposit),
+                        conn,
+                    )?;
+                }
+
+                // Sub-precision digits cannot be minted, so two deposits differing only in
+                // them would match the same MintTx amount.
+                if deposit.truncation_remainder() != 0 {
+                    log::warn!(
+                        self.logger,
+                        "Gnosis deposit eth_tx_hash={} value {} had a remainder of {} dropped when truncating it",
+                        deposit.eth_tx_hash(),
+                        u128::from(deposit.eth_tx_value()),
+                        deposit.truncation_remainder(),
+                    );
+                    Counters::record_anomaly(
+                        &AnomalyEvent::new(
+                            AnomalyKind::DepositTruncationRemainder,
+                            format!(
+                                "Deposit value {} had a remainder of {} dropped when truncating it to {}",
+                                u128::from(deposit.eth_tx_value()),
+                                deposit.truncation_remainder(),
+                                deposit.amount()
+                            ),
+                        )
+                        .with_gnosis_deposit(&deposit),
+                        conn,
+                    )?;
+                }
+
                 // Attempt to match the deposit with an existing MintTx.
                 match AuditedMint::try_match_deposit_with_mint(&deposit, &self.audited_safe, conn) {
-                    Ok(mint_tx) => {
-                        log::info!(
-                            self.logger,
-                            "Gnosis deposit eth_tx_hash={} matched MintTx nonce={}",
-                            deposit.eth_tx_hash(),
-                            mint_tx.nonce_hex(),
-                        )
+                    Ok(mint_txs) => {
+                        for mint_tx in mint_txs {
+                            log::info!(
+                                self.logger,
+                                "Gnosis deposit eth_tx_hash={} matched MintTx nonce={}",
+                                deposit.eth_tx_hash(),
+                                mint_tx.nonce_hex(),
+                            )
+                        }
                     }
                     Err(Error::NotFound) => {
                         log::debug!(self.logger, "Gnosis deposit eth_tx_hash={} does not currently have matching MintTx, this could be fine if the ledger is not fully synced.", deposit.eth_tx_hash());
@@ -230,6 +725,44 @@ impl GnosisSync {
         Ok(())
     }
 
+    /// Parse a transfer into the safe into a [GnosisSafeDeposit] object.
+    fn parse_deposit(
+        &self,
+        tx: &EthereumTransaction,
+        transfer: &EthereumTransfer,
+    ) -> Result<GnosisSafeDeposit, GnosisError> {
+        let token_addr = &transfer.token_addr.clone().ok_or_else(|| {
+            GnosisError::ApiResultParse("Transfer token address cannot be empty".into())
+        })?;
+
+        let token_config = self
+            .audited_safe
+            .tokens
+            .iter()
+            .find(|token| token.eth_token_contract_addrs.contains(token_addr))
+            .ok_or_else(|| GnosisError::UnknownToken("Unknown token transfer".into()))?;
+
+        let truncated_value = truncate_value(
+            transfer.value.unwrap(),
+            token_config.decimals,
+            self.audited_safe.token_decimals_max,
+        )?;
+
+        Ok(GnosisSafeDeposit::new(
+            None,
+            self.audited_safe.chain_id,
+            transfer.tx_hash,
+            tx.execution_date,
+            transfer.value.unwrap(),
+            tx.eth_block_number,
+            transfer.to.clone(),
+            token_addr.clone(),
+            transfer.from.clone(),
+            truncated_value.amount,
+            truncated_value.remainder,
+        ))
+    }
+
     /// Process a MultiSig transaction.
     fn process_multi_sig_transaction(
         &self,
@@ -259,6 +792,26 @@ impl GnosisSync {
 
                 withdrawal.insert(conn)?;
 
+                if withdrawal.mc_amount().is_none() {
+                    log::crit!(
+                        self.logger,
+                        "Gnosis withdrawal eth_tx_hash={} amount {} does not fit in a MobileCoin amount",
+                        withdrawal.eth_tx_hash(),
+                        withdrawal.amount(),
+                    );
+                    Counters::record_anomaly(
+                        &AnomalyEvent::new(
+                            AnomalyKind::OversizedReserveAmount,
+                            format!(
+                                "Withdrawal amount {} does not fit in a MobileCoin amount",
+                                withdrawal.amount()
+                            ),
+                        )
+                        .with_gnosis_withdrawal(&withdrawal),
+                        conn,
+                    )?;
+                }
+
                 // Attempt to match the withdrawal with an existing BurnTxOut.
                 match AuditedBurn::try_match_withdrawal_with_burn(
                     &withdrawal,
@@ -288,7 +841,11 @@ impl GnosisSync {
             }
 
             Err(err) => {
-                Counters::inc_num_invalid_multi_sig_transactions(conn)?;
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(AnomalyKind::InvalidMultiSigTransaction, &err)
+                        .with_eth_tx(&multi_sig_tx.tx_hash, multi_sig_tx.eth_block_number),
+                    conn,
+                )?;
                 log::warn!(
                     self.logger,
                     "Failed parsing a withdrawal from multisig tx {}: {}",
@@ -308,127 +865,40 @@ impl GnosisSync {
         &self,
         multi_sig_tx: &MultiSigTransaction,
     ) -> Result<GnosisSafeWithdrawal, GnosisError> {
-        // Get the decoded data - this is the part that contains details about the
-        // individual transfers included in the multi-transfer.
-        let data = multi_sig_tx
-            .data_decoded
-            .as_ref()
-            .ok_or_else(|| GnosisError::ApiResultParse("data_decoded is empty".into()))?;
-
-        if data.method != "multiSend" {
-            return Err(GnosisError::ApiResultParse(format!(
-                "multi-sig tx method mismatch: got {}, expected multiSend",
-                data.method
-            )));
-        }
-
-        // The decoded data is expected to contain a single "transactions" parameter,
-        // which should be an array of the individual transfers.
-        if data.parameters.len() != 1 {
-            return Err(GnosisError::ApiResultParse(format!(
-                "invalid number of parameters: got {}, expected 1",
-                data.parameters.len()
-            )));
-        }
-
-        let parameter = &data.parameters[0];
-        let value_decoded = parameter.value_decoded.as_ref().ok_or_else(|| {
-            GnosisError::ApiResultParse("decoded data parameter is missing value_decoded".into())
-        })?;
-
-        // Each value contains a single transfer. We expect to have two transfers:
-        // 1) A transfer moving the token being withdrawn from the safe
-        // 2) A "dummy" transfer into the auxiliary contract, used to record the
-        // matching MobileCoin tx out public key of the matching burn.
-        if value_decoded.len() != 2 {
-            return Err(GnosisError::ApiResultParse(format!(
-                "Invalid number of values in multiSend transfer: got {}, expected 2",
-                value_decoded.len()
-            )));
-        }
+        let calls = self.decode_withdrawal_calls(multi_sig_tx)?;
 
-        // The first value is the transfer of the actual token held in the safe. It
+        // The first call is the transfer of the actual token held in the safe. It
         // should match a token we are auditing.
-        let transfer_data = &value_decoded[0];
         let audited_token = self
             .audited_safe
-            .get_token_by_eth_contract_addr(&transfer_data.to)
+            .get_token_by_eth_contract_addr(&calls.token_addr)
             .ok_or_else(|| {
                 GnosisError::ApiResultParse(format!(
                     "Encountered multiSend transaction to an unknown token: {}",
-                    transfer_data.to
+                    calls.token_addr
                 ))
             })?;
 
-        // The first value (transfer of token held in safe) should contain two
-        // parameters - the ethereum address receiving the withdrawal and the
-        // amount being moved out of the safe.
-        let transfer_data_decoded = transfer_data.data_decoded.as_ref().ok_or_else(|| {
-            GnosisError::ApiResultParse("multiSend transfer first value has no decoded data".into())
-        })?;
-        if transfer_data_decoded.method != "transfer" {
-            return Err(GnosisError::ApiResultParse(format!(
-                "Invalid first value method: got {}, expected transfer",
-                transfer_data_decoded.method
-            )));
-        }
-
-        // The first index point has the address of the recipient, the second
-        // index has the value of the transaction
-        let transfer_data_parameters = transfer_data_decoded.parameters.clone();
-        if transfer_data_parameters.len() != 2 {
-            return Err(GnosisError::ApiResultParse(format!(
-                "Invalid number of values in multiSend transfer data parameters: got {}, expected 2",
-                value_decoded.len()
-            )));
-        }
-
-        let token_transfers = &multi_sig_tx.transfer_data;
-        if token_transfers.len() != 1 {
-            GnosisError::ApiResultParse(format!(
-                "Invalid number of multisend transactions, got {}, expected 1",
-                token_transfers.len()
-            ));
-        }
-
-        let token_transfer = &token_transfers[0];
-
-        // The number hould be EthTxValue-parseable
-        let eth_tx_value = token_transfer.value.unwrap();
+        // The value and recipient of the transfer, as decoded locally.
+        let eth_tx_value = calls.transfer.value;
+        let to_addr = calls.transfer.to.clone();
 
-        // the recipient address of the transaction
-        let to_addr = token_transfer.to.clone();
-
-        // The second value (dummy transfer to auxiliary contract) should contain the
-        // MobileCoin tx out public key in the data. There is no decoded version
-        // of the data since the Gnosis API does not know how to decode custom
-        // contracts.
-        let aux_contract_value = &value_decoded[1];
-        if aux_contract_value.to != audited_token.aux_burn_contract_addr {
+        // The second call (dummy transfer to auxiliary contract) should contain the
+        // MobileCoin tx out public key in the data.
+        if calls.aux_contract_addr != audited_token.aux_burn_contract_addr {
             return Err(GnosisError::ApiResultParse(format!(
                 "aux contract destination mismatch: got {}, expected {}",
-                aux_contract_value.to, audited_token.aux_burn_contract_addr
+                calls.aux_contract_addr, audited_token.aux_burn_contract_addr
             )));
         }
 
-        if !aux_contract_value.data.starts_with("0x") {
-            return Err(GnosisError::ApiResultParse(format!(
-                "aux contract data doesn't start with 0x: got {}",
-                aux_contract_value.data,
-            )));
-        }
-
-        let aux_data_bytes = hex::decode(&aux_contract_value.data[2..]).map_err(|err| {
-            GnosisError::ApiResultParse(format!(
-                "aux contract data {} cannot be hex-decoded: {}",
-                aux_contract_value.data, err,
-            ))
-        })?;
-
-        if !aux_data_bytes.starts_with(&audited_token.aux_burn_function_sig) {
+        if !calls
+            .aux_data
+            .starts_with(&audited_token.aux_burn_function_sig)
+        {
             return Err(GnosisError::ApiResultParse(format!(
-                "aux contract data {} does not start with the expected function signature ({})",
-                aux_contract_value.data,
+                "aux contract data 0x{} does not start with the expected function signature ({})",
+                hex::encode(&calls.aux_data),
                 hex::encode(audited_token.aux_burn_function_sig),
             )));
         }
@@ -436,49 +906,230 @@ impl GnosisSync {
         // The tx out pub key is the last 32 bytes. Ensure we have enough bytes in the
         // data for that.
         let min_length = audited_token.aux_burn_function_sig.len() + 32;
-        if aux_data_bytes.len() < min_length {
+        if calls.aux_data.len() < min_length {
             return Err(GnosisError::ApiResultParse(format!(
-                "aux contract data {} does not contain enough bytes. got {}, expected at least {}",
-                aux_contract_value.data,
-                aux_contract_value.data.len(),
+                "aux contract data 0x{} does not contain enough bytes. got {}, expected at least {}",
+                hex::encode(&calls.aux_data),
+                calls.aux_data.len(),
                 min_length,
             )));
         }
 
-        let tx_out_pub_key = &aux_data_bytes[aux_data_bytes.len() - 32..];
+        let tx_out_pub_key = &calls.aux_data[calls.aux_data.len() - 32..];
 
         let token_config = self
             .audited_safe
             .tokens
             .iter()
-            .find(|token| token.eth_token_contract_addrs.contains(&transfer_data.to))
+            .find(|token| token.eth_token_contract_addrs.contains(&calls.token_addr))
             .ok_or_else(|| {
                 GnosisError::InvalidAddress(format!(
                     "No matching token in config for address: {}",
-                    transfer_data.to
+                    calls.token_addr
                 ))
             })?;
 
-        let truncated_transaction_value = truncate_value(
-            EthTxValue(transfer_data_parameters[1].value.parse::<u128>().unwrap()),
+        let truncated_value = truncate_value(
+            calls.transfer.value,
             token_config.decimals,
             self.audited_safe.token_decimals_max,
-        );
+        )?;
 
         // Parsed everything we need.
         Ok(GnosisSafeWithdrawal::new(
             None,
+            self.audited_safe.chain_id,
             multi_sig_tx.tx_hash,
             multi_sig_tx.execution_date,
             eth_tx_value,
             multi_sig_tx.eth_block_number,
             multi_sig_tx.safe.clone(),
-            transfer_data.to.clone(),
+            calls.token_addr,
             to_addr,
-            truncated_transaction_value,
+            truncated_value.amount,
+            truncated_value.remainder,
             hex::encode(tx_out_pub_key),
         ))
     }
+
+    /// Get the calls batched in a withdrawal's `multiSend` transaction.
+    ///
+    /// The raw transaction data is decoded locally, and the token transfer it
+    /// contains has to be the only one the Gnosis safe transaction service
+    /// reports the transaction to have made. The view decoded by the service,
+    /// when available, is only cross-checked against the local decoding, and
+    /// disagreements are logged.
+    fn decode_withdrawal_calls(
+        &self,
+        multi_sig_tx: &MultiSigTransaction,
+    ) -> Result<WithdrawalCalls, GnosisError> {
+        let calls = WithdrawalCalls::decode_local(multi_sig_tx)?;
+
+        match WithdrawalCalls::from_data_decoded(multi_sig_tx) {
+            Ok(service_calls) if service_calls != calls => log::warn!(
+                self.logger,
+                "Locally decoded multiSend of tx {} disagrees with the decoded data from the Gnosis API: {:?} != {:?}",
+                multi_sig_tx.tx_hash,
+                calls,
+                service_calls,
+            ),
+            Ok(_) => {}
+            Err(err) => log::debug!(
+                self.logger,
+                "Gnosis API did not decode multiSend of tx {}: {}",
+                multi_sig_tx.tx_hash,
+                err
+            ),
+        }
+
+        match &multi_sig_tx.transfer_data[..] {
+            [transfer]
+                if transfer.token_addr.as_ref() == Some(&calls.token_addr)
+                    && transfer.to == calls.transfer.to
+                    && transfer.value == Some(calls.transfer.value) =>
+            {
+                Ok(calls)
+            }
+            [_] => Err(GnosisError::ApiResultParse(format!(
+                "locally decoded {:?} does not match the reported transfer {:?}",
+                calls, multi_sig_tx.transfer_data[0]
+            ))),
+            transfers => Err(GnosisError::ApiResultParse(format!(
+                "Invalid number of multisend transactions, got {}, expected 1",
+                transfers.len()
+            ))),
+        }
+    }
+}
+
+/// The two calls batched in a withdrawal's `multiSend` transaction: a token
+/// transfer out of the safe, followed by a call to the auxiliary contract that
+/// records the tx out public key of the matching burn.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct WithdrawalCalls {
+    /// The token contract called by the first call.
+    token_addr: EthAddr,
+
+    /// The ERC20 transfer made by the first call.
+    transfer: Erc20Transfer,
+
+    /// The contract called by the second call.
+    aux_contract_addr: EthAddr,
+
+    /// The calldata of the second call.
+    aux_data: Vec<u8>,
+}
+
+impl WithdrawalCalls {
+    /// Get the calls from the decoded data provided by the Gnosis API.
+    fn from_data_decoded(multi_sig_tx: &MultiSigTransaction) -> Result<Self, GnosisError> {
+        // Get the decoded data - this is the part that contains details about the
+        // individual transfers included in the multi-transfer.
+        let data = multi_sig_tx
+            .data_decoded
+            .as_ref()
+            .ok_or_else(|| GnosisError::ApiResultParse("data_decoded is empty".into()))?;
+
+        if data.method != "multiSend" {
+            return Err(GnosisError::ApiResultParse(format!(
+                "multi-sig tx method mismatch: got {}, expected multiSend",
+                data.method
+            )));
+        }
+
+        // The decoded data is expected to contain a single "transactions" parameter,
+        // which should be an array of the individual transfers.
+        if data.parameters.len() != 1 {
+            return Err(GnosisError::ApiResultParse(format!(
+                "invalid number of parameters: got {}, expected 1",
+                data.parameters.len()
+            )));
+        }
+
+        let parameter = &data.parameters[0];
+        let value_decoded = parameter.value_decoded.as_ref().ok_or_else(|| {
+            GnosisError::ApiResultParse("decoded data parameter is missing value_decoded".into())
+        })?;
+
+        // Each value contains a single transfer. We expect to have two transfers:
+        // 1) A transfer moving the token being withdrawn from the safe
+        // 2) A "dummy" transfer into the auxiliary contract, used to record the
+        // matching MobileCoin tx out public key of the matching burn.
+        if value_decoded.len() != 2 {
+            return Err(GnosisError::ApiResultParse(format!(
+                "Invalid number of values in multiSend transfer: got {}, expected 2",
+                value_decoded.len()
+            )));
+        }
+
+        // The first value (transfer of token held in safe) should contain two
+        // parameters - the ethereum address receiving the withdrawal and the
+        // amount being moved out of the safe.
+        let transfer_data = &value_decoded[0];
+        let transfer_data_decoded = transfer_data.data_decoded.as_ref().ok_or_else(|| {
+            GnosisError::ApiResultParse("multiSend transfer first value has no decoded data".into())
+        })?;
+        if transfer_data_decoded.method != "transfer" {
+            return Err(GnosisError::ApiResultParse(format!(
+                "Invalid first value method: got {}, expected transfer",
+                transfer_data_decoded.method
+            )));
+        }
+
+        // The first index point has the address of the recipient, the second
+        // index has the value of the transaction
+        let transfer_data_parameters = &transfer_data_decoded.parameters;
+        if transfer_data_parameters.len() != 2 {
+            return Err(GnosisError::ApiResultParse(format!(
+                "Invalid number of values in multiSend transfer data parameters: got {}, expected 2",
+                transfer_data_parameters.len()
+            )));
+        }
+
+        let transfer = Erc20Transfer {
+            to: EthAddr::from_str(&transfer_data_parameters[0].value)?,
+            value: EthTxValue::from_str(&transfer_data_parameters[1].value)?,
+        };
+
+        // The second value (dummy transfer to auxiliary contract) should contain the
+        // MobileCoin tx out public key in the data. There is no decoded version
+        // of the data since the Gnosis API does not know how to decode custom
+        // contracts.
+        let aux_contract_value = &value_decoded[1];
+
+        Ok(Self {
+            token_addr: transfer_data.to.clone(),
+            transfer,
+            aux_contract_addr: aux_contract_value.to.clone(),
+            aux_data: abi::decode_hex_data(&aux_contract_value.data)?,
+        })
+    }
+
+    /// Decode the calls from the raw `multiSend` calldata of the transaction.
+    fn decode_local(multi_sig_tx: &MultiSigTransaction) -> Result<Self, GnosisError> {
+        let data = multi_sig_tx
+            .data
+            .as_ref()
+            .ok_or_else(|| GnosisError::AbiDecode("multi-sig tx has no data".into()))?;
+
+        let mut transactions = abi::decode_multi_send(&abi::decode_hex_data(data)?)?;
+        if transactions.len() != 2 {
+            return Err(GnosisError::AbiDecode(format!(
+                "Invalid number of transactions in multiSend: got {}, expected 2",
+                transactions.len()
+            )));
+        }
+
+        let aux_call = transactions.pop().expect("checked length");
+        let transfer_call = transactions.pop().expect("checked length");
+
+        Ok(Self {
+            transfer: abi::decode_erc20_transfer(&transfer_call.data)?,
+            token_addr: transfer_call.to,
+            aux_contract_addr: aux_call.to,
+            aux_data: aux_call.data,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -488,11 +1139,11 @@ mod test {
         db::{
             schema::{gnosis_safe_deposits, gnosis_safe_withdrawals},
             test_utils::{
-                insert_mint_tx_from_deposit, TestDbContext, AUX_BURN_CONTRACT_ADDR,
-                AUX_BURN_FUNCTION_SIG, ETH_TOKEN_CONTRACT_ADDR, GNOSIS_SAFE_DEPOSIT_FROM_ADDR,
-                GNOSIS_SAFE_WITHDRAWAL_TO_ADDR, SAFE_ADDR,
+                insert_mint_tx_from_deposit, test_gnosis_config, TestDbContext,
+                AUX_BURN_CONTRACT_ADDR, AUX_BURN_FUNCTION_SIG, ETH_TOKEN_CONTRACT_ADDR,
+                GNOSIS_SAFE_DEPOSIT_FROM_ADDR, GNOSIS_SAFE_WITHDRAWAL_TO_ADDR, SAFE_ADDR,
             },
-            MintTx,
+            AuditEvent, MintTx, OutboxNotification,
         },
         gnosis::{
             api_data_types::AllTransactionsResponse, config::AuditedToken, EthAddr, EthTxHash,
@@ -524,6 +1175,46 @@ mod test {
             .collect()
     }
 
+    // A deposit in ALL_TRANSACTIONS_JSON
+    const DEPOSIT_TX_HASH: &str =
+        "0x4f3124c61c48aa7c7892f8fe426e0c0d8afae100fc0a9aa8e290e530a7632849";
+
+    // Helper to get the transactions of ALL_TRANSACTIONS_JSON, with the value of
+    // the DEPOSIT_TX_HASH deposit replaced.
+    fn get_raw_transactions_with_deposit_value(value: u128) -> Vec<RawGnosisTransaction> {
+        let all_transactions_response: AllTransactionsResponse =
+            serde_json::from_str(ALL_TRANSACTIONS_JSON).unwrap();
+        all_transactions_response
+            .results
+            .into_iter()
+            .map(|mut raw| {
+                if raw["txHash"] == DEPOSIT_TX_HASH {
+                    raw["transfers"][0]["value"] = value.to_string().into();
+                }
+                RawGnosisTransaction::from(raw)
+            })
+            .collect()
+    }
+
+    // Helper to get a withdrawal in ALL_TRANSACTIONS_JSON.
+    fn get_withdrawal_multi_sig_tx() -> MultiSigTransaction {
+        get_raw_transactions()
+            .into_iter()
+            .find_map(|tx| match tx.decode().unwrap() {
+                Transaction::MultiSig(multi_sig_tx)
+                    if multi_sig_tx.tx_hash
+                        == EthTxHash::from_str(
+                            "0x2f55d7b7620876c1dfc25419937a7fd2538489c1dd3adf6b438396a958d88e28",
+                        )
+                        .unwrap() =>
+                {
+                    Some(multi_sig_tx)
+                }
+                _ => None,
+            })
+            .unwrap()
+    }
+
     #[test_with_logger]
     fn process_transactions_works(logger: Logger) {
         let mut rng = mc_util_test_helper::get_seeded_rng();
@@ -535,7 +1226,10 @@ mod test {
         // Must match the contents of the test JSON file.
         let audited_safe_config = AuditedSafeConfig {
             safe_addr: EthAddr::from_str(SAFE_ADDR).unwrap(),
+            chain_id: 1,
             api_url: Url::parse("http://unused:8545").unwrap(),
+            eth_rpc: None,
+            verify_with: None,
             token_decimals_max: 6,
             tokens: vec![AuditedToken {
                 token_id: TokenId::from(1),
@@ -553,6 +1247,7 @@ mod test {
         let expected_deposits = vec![
             GnosisSafeDeposit::new(
                 Some(2),
+                1,
                 EthTxHash::from_str(
                     "0xa202a4c37f0670557ceeb33f796fba0c187f699f5dd4d8add0eba1c3154b2fa7",
                 )
@@ -566,9 +1261,11 @@ mod test {
                 EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
                 EthAddr::from_str(GNOSIS_SAFE_DEPOSIT_FROM_ADDR).unwrap(),
                 1000000,
+                0,
             ),
             GnosisSafeDeposit::new(
                 Some(1),
+                1,
                 EthTxHash::from_str(
                     "0x4f3124c61c48aa7c7892f8fe426e0c0d8afae100fc0a9aa8e290e530a7632849",
                 )
@@ -582,6 +1279,7 @@ mod test {
                 EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
                 EthAddr::from_str(GNOSIS_SAFE_DEPOSIT_FROM_ADDR).unwrap(),
                 10000000,
+                0,
             ),
         ];
 
@@ -627,6 +1325,7 @@ mod test {
         let expected_withdrawals = vec![
             GnosisSafeWithdrawal::new(
                 Some(2),
+                1,
                 EthTxHash::from_str(
                     "0x323b145662d2a64de0a55977089b7a89ed6003e341d5a68266a200dde83639d4",
                 )
@@ -640,10 +1339,12 @@ mod test {
                 EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
                 EthAddr::from_str(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR).unwrap(),
                 500000,
+                0,
                 "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20".to_string(),
             ),
             GnosisSafeWithdrawal::new(
                 Some(1),
+                1,
                 EthTxHash::from_str(
                     "0x2f55d7b7620876c1dfc25419937a7fd2538489c1dd3adf6b438396a958d88e28",
                 )
@@ -657,6 +1358,7 @@ mod test {
                 EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap(),
                 EthAddr::from_str(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR).unwrap(),
                 2000000,
+                0,
                 "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20".to_string(),
             ),
         ];
@@ -669,6 +1371,323 @@ mod test {
         );
     }
 
+    /// A verification source that always returns the same transactions.
+    struct StaticFetcher(Vec<RawGnosisTransaction>);
+
+    impl SafeTransactionFetcher for StaticFetcher {
+        fn get_transaction_data_until(
+            &self,
+            _safe_addr: &EthAddr,
+            _is_known: &mut dyn FnMut(&RawGnosisTransaction) -> bool,
+        ) -> Result<Vec<RawGnosisTransaction>, GnosisError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test_with_logger]
+    fn verify_against_source_works(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let audited_safe_config = test_gnosis_config().safes[0].clone();
+        let sync = GnosisSync::new(audited_safe_config, reserve_auditor_db, logger).unwrap();
+        sync.process_transactions(get_raw_transactions());
+
+        let statuses = || {
+            let deposits = gnosis_safe_deposits::table
+                .order_by(gnosis_safe_deposits::eth_block_number)
+                .load::<GnosisSafeDeposit>(&conn)
+                .unwrap();
+            let withdrawals = gnosis_safe_withdrawals::table
+                .order_by(gnosis_safe_withdrawals::eth_block_number)
+                .load::<GnosisSafeWithdrawal>(&conn)
+                .unwrap();
+            (
+                deposits
+                    .iter()
+                    .map(|deposit| deposit.verification_status().to_string())
+                    .collect::<Vec<_>>(),
+                withdrawals
+                    .iter()
+                    .map(|withdrawal| withdrawal.verification_status().to_string())
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let unverified = VerificationStatus::Unverified.to_string();
+        let verified = VerificationStatus::Verified.to_string();
+        let disagreement = VerificationStatus::Disagreement.to_string();
+
+        // A source that has not indexed anything yet leaves everything unverified.
+        sync.verify_against_source(&StaticFetcher(vec![]), &conn)
+            .unwrap();
+        assert_eq!(
+            statuses(),
+            (
+                vec![unverified.clone(), unverified.clone()],
+                vec![unverified.clone(), unverified]
+            )
+        );
+
+        // A source that reports a different value for the oldest deposit.
+        let source_txs = get_raw_transactions()
+            .into_iter()
+            .map(|tx| {
+                let mut raw: serde_json::Value =
+                    serde_json::from_str(&tx.to_json_string()).unwrap();
+                if raw["txHash"]
+                    == "0xa202a4c37f0670557ceeb33f796fba0c187f699f5dd4d8add0eba1c3154b2fa7"
+                {
+                    raw["transfers"][0]["value"] = "2000000000000000000".into();
+                }
+                RawGnosisTransaction::from(raw)
+            })
+            .collect();
+        sync.verify_against_source(&StaticFetcher(source_txs), &conn)
+            .unwrap();
+        assert_eq!(
+            statuses(),
+            (
+                vec![disagreement, verified.clone()],
+                vec![verified.clone(), verified]
+            )
+        );
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_gnosis_source_disagreements(),
+            1
+        );
+
+        // Rows that were already verified are not checked again.
+        sync.verify_against_source(&StaticFetcher(vec![]), &conn)
+            .unwrap();
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_gnosis_source_disagreements(),
+            1
+        );
+    }
+
+    #[test_with_logger]
+    fn parse_withdrawal_uses_local_decoding(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let audited_safe_config = test_gnosis_config().safes[0].clone();
+        let sync = GnosisSync::new(audited_safe_config, reserve_auditor_db, logger).unwrap();
+
+        let mut multi_sig_tx = get_withdrawal_multi_sig_tx();
+
+        // The raw multiSend data gets decoded locally, so the data decoded by the
+        // API is not needed.
+        multi_sig_tx.data_decoded = None;
+        let withdrawal = sync
+            .parse_withdrawal_with_pub_key_multi_sig_tx(&multi_sig_tx)
+            .unwrap();
+        assert_eq!(
+            withdrawal.eth_tx_value(),
+            &EthTxValue(2_000_000_000_000_000_000)
+        );
+        assert_eq!(
+            withdrawal.token_addr(),
+            &EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap()
+        );
+        assert_eq!(
+            withdrawal.to_addr(),
+            &EthAddr::from_str(GNOSIS_SAFE_WITHDRAWAL_TO_ADDR).unwrap()
+        );
+        assert_eq!(
+            withdrawal.mc_tx_out_public_key_hex(),
+            "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20"
+        );
+
+        // The locally decoded transfer has to match the transfer reported by the API.
+        let mut mismatching_tx = multi_sig_tx.clone();
+        mismatching_tx.transfer_data[0].value = Some(EthTxValue(2000000));
+        assert!(sync
+            .parse_withdrawal_with_pub_key_multi_sig_tx(&mismatching_tx)
+            .is_err());
+
+        // Without any data there is nothing to decode.
+        let mut no_data_tx = multi_sig_tx.clone();
+        no_data_tx.data = None;
+        assert!(sync
+            .parse_withdrawal_with_pub_key_multi_sig_tx(&no_data_tx)
+            .is_err());
+    }
+
+    #[test_with_logger]
+    fn parse_withdrawal_without_transfer_data_fails(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let audited_safe_config = test_gnosis_config().safes[0].clone();
+        let sync = GnosisSync::new(audited_safe_config, reserve_auditor_db, logger).unwrap();
+
+        let mut multi_sig_tx = get_withdrawal_multi_sig_tx();
+
+        // A transaction the API reports no transfers for is rejected rather than
+        // panicking.
+        multi_sig_tx.transfer_data.clear();
+        assert!(matches!(
+            sync.parse_withdrawal_with_pub_key_multi_sig_tx(&multi_sig_tx),
+            Err(GnosisError::ApiResultParse(_))
+        ));
+    }
+
+    #[test_with_logger]
+    fn process_transactions_records_oversized_amounts(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let audited_safe_config = test_gnosis_config().safes[0].clone();
+
+        // Make one of the deposits larger than any MobileCoin amount.
+        let oversized_value = u128::from(u64::MAX) * 1000;
+        let eth_tx_hash = EthTxHash::from_str(DEPOSIT_TX_HASH).unwrap();
+        let raw_transactions = get_raw_transactions_with_deposit_value(oversized_value);
+
+        // Syncing does not fail, and the deposit is stored in full.
+        let sync = GnosisSync::new(audited_safe_config, reserve_auditor_db, logger).unwrap();
+        sync.process_transactions(raw_transactions);
+
+        let deposit = gnosis_safe_deposits::table
+            .filter(gnosis_safe_deposits::eth_tx_hash.eq(eth_tx_hash.to_string()))
+            .first::<GnosisSafeDeposit>(&conn)
+            .unwrap();
+        assert_eq!(deposit.amount(), oversized_value);
+        assert_eq!(deposit.mc_amount(), None);
+
+        let counters = Counters::get(&conn).unwrap();
+        assert_eq!(counters.num_oversized_reserve_amounts(), 1);
+        assert_eq!(
+            GnosisSafeDeposit::find_unaudited_deposits(&conn)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test_with_logger]
+    fn process_transactions_records_truncation_remainders(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        // The Ethereum token has 3 more decimals than the MobileCoin one.
+        let mut audited_safe_config = test_gnosis_config().safes[0].clone();
+        audited_safe_config.tokens[0].decimals = audited_safe_config.token_decimals_max + 3;
+
+        let eth_tx_hash = EthTxHash::from_str(DEPOSIT_TX_HASH).unwrap();
+        let raw_transactions = get_raw_transactions_with_deposit_value(10_000_000_000_000_000_123);
+
+        let sync = GnosisSync::new(audited_safe_config, reserve_auditor_db, logger).unwrap();
+        sync.process_transactions(raw_transactions);
+
+        let deposits = gnosis_safe_deposits::table
+            .order_by(gnosis_safe_deposits::eth_block_number)
+            .load::<GnosisSafeDeposit>(&conn)
+            .unwrap();
+        assert_eq!(deposits.len(), 2);
+        for deposit in deposits {
+            if deposit.eth_tx_hash() == &eth_tx_hash {
+                assert_eq!(deposit.amount(), 10_000_000_000_000_000);
+                assert_eq!(deposit.truncation_remainder(), 123);
+            } else {
+                assert_eq!(deposit.truncation_remainder(), 0);
+            }
+        }
+
+        // Only the deposit with a remainder is flagged.
+        let counters = Counters::get(&conn).unwrap();
+        assert_eq!(counters.num_deposits_with_truncation_remainder(), 1);
+    }
+
+    #[test_with_logger]
+    fn reaudit_does_not_record_anomalies_twice(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        // The Ethereum token has 3 more decimals than the MobileCoin one, so one of the
+        // deposits gets flagged.
+        let mut audited_safe_config = test_gnosis_config().safes[0].clone();
+        audited_safe_config.tokens[0].decimals = audited_safe_config.token_decimals_max + 3;
+        let config = GnosisSafeConfig {
+            safes: vec![audited_safe_config.clone()],
+        };
+        let raw_transactions = get_raw_transactions_with_deposit_value(10_000_000_000_000_000_123);
+
+        // Deposits stored by a version that did not record the configuration safes
+        // were audited with.
+        let sync = GnosisSync::new(
+            audited_safe_config.clone(),
+            reserve_auditor_db.clone(),
+            logger.clone(),
+        )
+        .unwrap();
+        sync.process_transactions(raw_transactions);
+        let deposits = gnosis_safe_deposits::table
+            .order_by(gnosis_safe_deposits::id)
+            .load::<GnosisSafeDeposit>(&conn)
+            .unwrap();
+        assert_eq!(
+            GnosisSafeAuditConfig::get(
+                audited_safe_config.chain_id,
+                &audited_safe_config.safe_addr,
+                &conn
+            )
+            .unwrap(),
+            None
+        );
+
+        let counters = Counters::get(&conn).unwrap();
+        let audit_events = AuditEvent::list(None, None, false, &conn).unwrap();
+        let num_notifications = OutboxNotification::find_due(Utc::now(), 100, &conn)
+            .unwrap()
+            .len();
+        assert_eq!(counters.num_deposits_with_truncation_remainder(), 1);
+        assert_eq!(audit_events.len(), 1);
+
+        // Starting up with a config records it without re-auditing.
+        GnosisSync::apply_config_changes(&config, &reserve_auditor_db, &logger).unwrap();
+        assert!(GnosisSafeAuditConfig::get(
+            audited_safe_config.chain_id,
+            &audited_safe_config.safe_addr,
+            &conn
+        )
+        .unwrap()
+        .is_some());
+        assert_eq!(
+            gnosis_safe_deposits::table
+                .order_by(gnosis_safe_deposits::id)
+                .load::<GnosisSafeDeposit>(&conn)
+                .unwrap(),
+            deposits
+        );
+
+        // Re-auditing detects the same anomaly again, which only updates its audit
+        // event.
+        sync.reaudit_stored_transactions().unwrap();
+        let reaudited_deposit = gnosis_safe_deposits::table
+            .filter(gnosis_safe_deposits::eth_tx_hash.eq(DEPOSIT_TX_HASH))
+            .first::<GnosisSafeDeposit>(&conn)
+            .unwrap();
+        assert_eq!(Counters::get(&conn).unwrap(), counters);
+        assert_eq!(
+            OutboxNotification::find_due(Utc::now(), 100, &conn)
+                .unwrap()
+                .len(),
+            num_notifications
+        );
+        let reaudited_events = AuditEvent::list(None, None, false, &conn).unwrap();
+        assert_eq!(reaudited_events.len(), 1);
+        assert_eq!(reaudited_events[0].id(), audit_events[0].id());
+        assert_eq!(
+            reaudited_events[0].gnosis_safe_deposit_id(),
+            reaudited_deposit.id()
+        );
+    }
+
     #[test_with_logger]
     fn process_transactions_ignores_unknown(logger: Logger) {
         let test_db_context = TestDbContext::default();
@@ -680,7 +1699,10 @@ mod test {
         // (Except the safe address, since that is what we are testing here)
         let unknown_safe_audited_safe_config = AuditedSafeConfig {
             safe_addr: EthAddr::from_str("0x0000000000000000000000000000000000000000").unwrap(),
+            chain_id: 1,
             api_url: Url::parse("http://unused:8545").unwrap(),
+            eth_rpc: None,
+            verify_with: None,
             token_decimals_max: 9,
             tokens: vec![AuditedToken {
                 token_id: TokenId::from(1),
@@ -700,7 +1722,10 @@ mod test {
         // ignored)
         let unknown_token_audited_safe_config = AuditedSafeConfig {
             safe_addr: EthAddr::from_str(SAFE_ADDR).unwrap(),
+            chain_id: 1,
             api_url: Url::parse("http://unused:8545").unwrap(),
+            eth_rpc: None,
+            verify_with: None,
             token_decimals_max: 9,
             tokens: vec![
                 // Unknown token contract address
@@ -778,4 +1803,289 @@ mod test {
             .unwrap();
         assert_eq!(withdrawals, vec![]);
     }
+
+    #[test_with_logger]
+    fn apply_config_changes_reaudits_stored_transactions(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let safe_addr = EthAddr::from_str(SAFE_ADDR).unwrap();
+
+        // Must match the contents of the test JSON file.
+        let audited_safe_config = AuditedSafeConfig {
+            safe_addr: safe_addr.clone(),
+            chain_id: 1,
+            api_url: Url::parse("http://unused:8545").unwrap(),
+            eth_rpc: None,
+            verify_with: None,
+            token_decimals_max: 6,
+            tokens: vec![AuditedToken {
+                token_id: TokenId::from(1),
+                token_type: "ERC20".to_string(),
+                name: "TestToken".to_string(),
+                symbol: "TT".to_string(),
+                decimals: 18,
+                logo_uri: "https://safe-transaction-assets.gnosis-safe.io/tokens/logos/0x50b347Ddbc73e45BD54683a57fcc28AA449Cf229.png".to_string(),
+                eth_token_contract_addrs: vec![EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap()],
+                aux_burn_contract_addr: EthAddr::from_str(AUX_BURN_CONTRACT_ADDR).unwrap(),
+                aux_burn_function_sig: AUX_BURN_FUNCTION_SIG,
+            }],
+        };
+        let config = GnosisSafeConfig {
+            safes: vec![audited_safe_config.clone()],
+        };
+
+        // Initially the safe is audited with a token contract address that does not
+        // appear in any of its transactions.
+        let mut old_audited_safe_config = audited_safe_config.clone();
+        old_audited_safe_config.tokens[0].eth_token_contract_addrs =
+            vec![EthAddr::from_str("0x0000000000000000000000000000000000000000").unwrap()];
+        let old_config = GnosisSafeConfig {
+            safes: vec![old_audited_safe_config.clone()],
+        };
+
+        GnosisSync::apply_config_changes(&old_config, &reserve_auditor_db, &logger).unwrap();
+        let sync = GnosisSync::new(
+            old_audited_safe_config,
+            reserve_auditor_db.clone(),
+            logger.clone(),
+        )
+        .unwrap();
+        sync.process_transactions(get_raw_transactions());
+
+        let load_deposits = || {
+            gnosis_safe_deposits::table
+                .order_by(gnosis_safe_deposits::eth_block_number)
+                .load::<GnosisSafeDeposit>(&conn)
+                .unwrap()
+        };
+        let load_withdrawals = || {
+            gnosis_safe_withdrawals::table
+                .order_by(gnosis_safe_withdrawals::eth_block_number)
+                .load::<GnosisSafeWithdrawal>(&conn)
+                .unwrap()
+        };
+        assert_eq!(load_deposits(), vec![]);
+        assert_eq!(load_withdrawals(), vec![]);
+        let num_stored_txs = GnosisSafeTx::list(&conn).unwrap().len();
+        assert!(num_stored_txs > 0);
+
+        // Switching to the correct token contract address re-audits the stored
+        // transactions.
+        GnosisSync::apply_config_changes(&config, &reserve_auditor_db, &logger).unwrap();
+        let deposits = load_deposits();
+        assert_eq!(deposits.len(), 2);
+        assert_eq!(load_withdrawals().len(), 2);
+        assert_eq!(
+            GnosisSafeAuditConfig::get(1, &safe_addr, &conn)
+                .unwrap()
+                .unwrap()
+                .decode()
+                .unwrap(),
+            audited_safe_config
+        );
+
+        // Applying an unchanged config does not re-audit, so MintTxs added in the
+        // meantime remain unaudited.
+        for deposit in &deposits {
+            insert_mint_tx_from_deposit(deposit, &conn, &mut rng);
+        }
+        GnosisSync::apply_config_changes(&config, &reserve_auditor_db, &logger).unwrap();
+        assert_eq!(MintTx::find_unaudited_mint_txs(&conn).unwrap().len(), 2);
+
+        // Removing the safe removes its deposits and withdrawals, but keeps the raw
+        // transaction data.
+        GnosisSync::apply_config_changes(
+            &GnosisSafeConfig { safes: vec![] },
+            &reserve_auditor_db,
+            &logger,
+        )
+        .unwrap();
+        assert_eq!(load_deposits(), vec![]);
+        assert_eq!(load_withdrawals(), vec![]);
+        assert_eq!(
+            GnosisSafeAuditConfig::get(1, &safe_addr, &conn).unwrap(),
+            None
+        );
+        assert_eq!(GnosisSafeTx::list(&conn).unwrap().len(), num_stored_txs);
+
+        // Adding it back re-audits the stored transactions, which now match the
+        // MintTxs.
+        GnosisSync::apply_config_changes(&config, &reserve_auditor_db, &logger).unwrap();
+        assert_eq!(load_deposits().len(), 2);
+        assert_eq!(load_withdrawals().len(), 2);
+        assert_eq!(MintTx::find_unaudited_mint_txs(&conn).unwrap(), vec![]);
+    }
+
+    #[test_with_logger]
+    fn reaudit_all_rematches_stored_transactions(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        // Must match the contents of the test JSON file.
+        let audited_safe_config = AuditedSafeConfig {
+            safe_addr: EthAddr::from_str(SAFE_ADDR).unwrap(),
+            chain_id: 1,
+            api_url: Url::parse("http://unused:8545").unwrap(),
+            eth_rpc: None,
+            verify_with: None,
+            token_decimals_max: 6,
+            tokens: vec![AuditedToken {
+                token_id: TokenId::from(1),
+                token_type: "ERC20".to_string(),
+                name: "TestToken".to_string(),
+                symbol: "TT".to_string(),
+                decimals: 18,
+                logo_uri: "https://safe-transaction-assets.gnosis-safe.io/tokens/logos/0x50b347Ddbc73e45BD54683a57fcc28AA449Cf229.png".to_string(),
+                eth_token_contract_addrs: vec![EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap()],
+                aux_burn_contract_addr: EthAddr::from_str(AUX_BURN_CONTRACT_ADDR).unwrap(),
+                aux_burn_function_sig: AUX_BURN_FUNCTION_SIG,
+            }],
+        };
+        let config = GnosisSafeConfig {
+            safes: vec![audited_safe_config.clone()],
+        };
+
+        let sync = GnosisSync::new(
+            audited_safe_config,
+            reserve_auditor_db.clone(),
+            logger.clone(),
+        )
+        .unwrap();
+        sync.process_transactions(get_raw_transactions());
+
+        let deposits = gnosis_safe_deposits::table
+            .order_by(gnosis_safe_deposits::eth_block_number)
+            .load::<GnosisSafeDeposit>(&conn)
+            .unwrap();
+        assert_eq!(deposits.len(), 2);
+
+        // MintTxs that show up after the deposits were processed do not get matched
+        // by the Gnosis sync.
+        for deposit in &deposits {
+            insert_mint_tx_from_deposit(deposit, &conn, &mut rng);
+        }
+        assert_eq!(MintTx::find_unaudited_mint_txs(&conn).unwrap().len(), 2);
+
+        // Re-auditing rebuilds the deposits and withdrawals and matches them.
+        let num_processed = GnosisSync::reaudit_all(&config, &reserve_auditor_db, &logger).unwrap();
+        assert_eq!(num_processed, GnosisSafeTx::list(&conn).unwrap().len());
+
+        let reaudited_deposits = gnosis_safe_deposits::table
+            .order_by(gnosis_safe_deposits::eth_block_number)
+            .load::<GnosisSafeDeposit>(&conn)
+            .unwrap();
+        assert_eq!(reaudited_deposits.len(), 2);
+        assert_eq!(
+            reaudited_deposits
+                .iter()
+                .map(|deposit| deposit.eth_tx_hash())
+                .collect::<Vec<_>>(),
+            deposits
+                .iter()
+                .map(|deposit| deposit.eth_tx_hash())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            gnosis_safe_withdrawals::table
+                .load::<GnosisSafeWithdrawal>(&conn)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(MintTx::find_unaudited_mint_txs(&conn).unwrap(), vec![]);
+        assert_eq!(
+            GnosisSafeDeposit::find_unaudited_deposits(&conn).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test_with_logger]
+    fn reaudit_all_reapplies_manual_matches(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        // Must match the contents of the test JSON file.
+        let audited_safe_config = AuditedSafeConfig {
+            safe_addr: EthAddr::from_str(SAFE_ADDR).unwrap(),
+            chain_id: 1,
+            api_url: Url::parse("http://unused:8545").unwrap(),
+            eth_rpc: None,
+            verify_with: None,
+            token_decimals_max: 6,
+            tokens: vec![AuditedToken {
+                token_id: TokenId::from(1),
+                token_type: "ERC20".to_string(),
+                name: "TestToken".to_string(),
+                symbol: "TT".to_string(),
+                decimals: 18,
+                logo_uri: "https://safe-transaction-assets.gnosis-safe.io/tokens/logos/0x50b347Ddbc73e45BD54683a57fcc28AA449Cf229.png".to_string(),
+                eth_token_contract_addrs: vec![EthAddr::from_str(ETH_TOKEN_CONTRACT_ADDR).unwrap()],
+                aux_burn_contract_addr: EthAddr::from_str(AUX_BURN_CONTRACT_ADDR).unwrap(),
+                aux_burn_function_sig: AUX_BURN_FUNCTION_SIG,
+            }],
+        };
+        let config = GnosisSafeConfig {
+            safes: vec![audited_safe_config.clone()],
+        };
+
+        let sync = GnosisSync::new(
+            audited_safe_config,
+            reserve_auditor_db.clone(),
+            logger.clone(),
+        )
+        .unwrap();
+        sync.process_transactions(get_raw_transactions());
+
+        let deposit = gnosis_safe_deposits::table
+            .filter(gnosis_safe_deposits::eth_tx_hash.eq(DEPOSIT_TX_HASH))
+            .first::<GnosisSafeDeposit>(&conn)
+            .unwrap();
+
+        // A MintTx whose nonce does not reference the deposit, forcibly matched with
+        // it.
+        let token_id = TokenId::from(1);
+        let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let core_mint_tx =
+            create_mint_tx(token_id, &signers, deposit.mc_amount().unwrap(), &mut rng);
+        let mint_tx =
+            MintTx::insert_from_core_mint_tx(0, Some(Utc::now()), None, &core_mint_tx, &conn)
+                .unwrap();
+        let manual_match = ManualMatch::force_match_mint(
+            mint_tx.id().unwrap(),
+            deposit.id().unwrap(),
+            "alice",
+            "Nonce was mistyped",
+            &conn,
+        )
+        .unwrap();
+        assert_eq!(MintTx::find_unaudited_mint_txs(&conn).unwrap(), vec![]);
+
+        // Re-auditing re-creates the deposit under a new id, and the match survives.
+        GnosisSync::reaudit_all(&config, &reserve_auditor_db, &logger).unwrap();
+        let reaudited_deposit = gnosis_safe_deposits::table
+            .filter(gnosis_safe_deposits::eth_tx_hash.eq(DEPOSIT_TX_HASH))
+            .first::<GnosisSafeDeposit>(&conn)
+            .unwrap();
+        assert_ne!(reaudited_deposit.id(), deposit.id());
+
+        assert_eq!(ManualMatch::list(&conn).unwrap(), vec![manual_match]);
+        assert_eq!(MintTx::find_unaudited_mint_txs(&conn).unwrap(), vec![]);
+        assert_eq!(
+            AuditedMint::allocated_to_deposit(&reaudited_deposit, &conn).unwrap(),
+            reaudited_deposit.amount()
+        );
+
+        // Re-auditing again does not match them twice.
+        GnosisSync::reaudit_all(&config, &reserve_auditor_db, &logger).unwrap();
+        assert_eq!(
+            MintTx::matched_amount(mint_tx.id().unwrap(), &conn).unwrap(),
+            Some(mint_tx.amount())
+        );
+    }
 }
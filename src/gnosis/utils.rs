@@ -2,21 +2,63 @@
 
 //! Helper functions for gnosis safe related tasks.
 
-use super::EthTxValue;
+use super::{Error, EthTxValue};
+
+/// The result of truncating a raw Ethereum token value down to the mob chain
+/// token precision.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TruncatedValue {
+    /// The value at the mob chain token precision.
+    pub amount: u128,
+
+    /// The sub-precision part of the raw value that was dropped, in the raw
+    /// (Ethereum) token units.
+    pub remainder: u128,
+
+    /// The number of raw token units in one unit of `amount`.
+    pub denominator: u128,
+}
+
+impl TruncatedValue {
+    /// Reconstruct the raw value that was truncated.
+    pub fn raw_value(&self) -> u128 {
+        self.amount * self.denominator + self.remainder
+    }
+}
 
 /// Truncate the raw value of an ethereum token transaction down to the mob chain token precision.
 /// Token transactions can have variable precisions, which causes integer-representations to be off
 /// by orders equal to the difference in their precision.
-pub fn truncate_value(raw_token_value: EthTxValue, token_decimals: u8, max_decimals: u8) -> u64 {
-    let mut token_value = u128::from(raw_token_value);
+/// The result is not bounded to u64, callers are expected to treat values
+/// that do not fit on the MobileCoin chain as anomalies.
+/// The dropped sub-precision digits are returned as the remainder, so that no
+/// part of the raw value is lost.
+/// Fails if the precisions differ by more decimals than a u128 can hold.
+pub fn truncate_value(
+    raw_token_value: EthTxValue,
+    token_decimals: u8,
+    max_decimals: u8,
+) -> Result<TruncatedValue, Error> {
+    let token_value = u128::from(raw_token_value);
     if token_decimals > max_decimals {
         //truncate token
         let orders_reduced_by = token_decimals - max_decimals;
-        let denominator = 10_u128.pow(u32::from(orders_reduced_by));
-
-        token_value /= denominator;
+        let denominator = 10_u128
+            .checked_pow(u32::from(orders_reduced_by))
+            .ok_or(Error::UnsupportedDecimals(token_decimals, max_decimals))?;
+
+        Ok(TruncatedValue {
+            amount: token_value / denominator,
+            remainder: token_value % denominator,
+            denominator,
+        })
+    } else {
+        Ok(TruncatedValue {
+            amount: token_value,
+            remainder: 0,
+            denominator: 1,
+        })
     }
-    u64::try_from(token_value).expect("Failed to truncate EthTxValue token value to u64")
 }
 
 #[cfg(test)]
@@ -30,14 +72,15 @@ mod test {
         // 1_000_000_000 tokens with 21 decimal places
         let token_max_val = EthTxValue(1_000_000_000_000_000_000_000_000_000_000_u128);
         // 1_000_000_000 tokens with 9 decimal places
-        let expected_truncated_value = 1_000_000_000_000_000_000_u64;
+        let expected_truncated_value = 1_000_000_000_000_000_000_u128;
         let max_token_decimals = 9_u8;
         let token_original_precision = 21_u8;
 
         let truncated_token_value =
-            truncate_value(token_max_val, token_original_precision, max_token_decimals);
+            truncate_value(token_max_val, token_original_precision, max_token_decimals).unwrap();
 
-        assert_eq!(expected_truncated_value, truncated_token_value);
+        assert_eq!(expected_truncated_value, truncated_token_value.amount);
+        assert_eq!(truncated_token_value.remainder, 0);
     }
 
     #[test]
@@ -45,14 +88,36 @@ mod test {
         // 1_010_020_030.040_050_060 tokens with 21 decimal places and disappearingly-small values above desired precision
         let token_max_val = EthTxValue(1_010_020_030_040_050_060_070_080_090_100_u128);
         // 1_010_020_030.040_050_060 tokens with 9 decimal places without disappearingly-small values above desired precision
-        let expected_truncated_value = 1_010_020_030_040_050_060_u64;
+        let expected_truncated_value = 1_010_020_030_040_050_060_u128;
         let max_token_decimals = 9_u8;
         let token_original_precision = 21_u8;
 
         let truncated_token_value =
-            truncate_value(token_max_val, token_original_precision, max_token_decimals);
+            truncate_value(token_max_val, token_original_precision, max_token_decimals).unwrap();
 
-        assert_eq!(expected_truncated_value, truncated_token_value);
+        assert_eq!(expected_truncated_value, truncated_token_value.amount);
+        // The disappearingly-small values are kept as the remainder
+        assert_eq!(truncated_token_value.remainder, 70_080_090_100_u128);
+        assert_eq!(truncated_token_value.raw_value(), u128::from(token_max_val));
+    }
+
+    #[test]
+    fn truncate_value_does_not_truncate_lower_precisions() {
+        let token_max_val = EthTxValue(1_000_000_007_u128);
+        let max_token_decimals = 9_u8;
+        let token_original_precision = 6_u8;
+
+        let truncated_token_value =
+            truncate_value(token_max_val, token_original_precision, max_token_decimals).unwrap();
+
+        assert_eq!(
+            truncated_token_value,
+            TruncatedValue {
+                amount: 1_000_000_007_u128,
+                remainder: 0,
+                denominator: 1,
+            }
+        );
     }
 
     #[test]
@@ -62,21 +127,45 @@ mod test {
         let max_token_decimals = 9_u8;
         let token_original_precision = 21_u8;
 
-        let _ = truncate_value(token_max_val, token_original_precision, max_token_decimals);
+        let _ =
+            truncate_value(token_max_val, token_original_precision, max_token_decimals).unwrap();
 
         assert_eq!(token_max_val, expected_token_max_val)
     }
 
     #[test]
-    #[should_panic(expected = "Failed to truncate EthTxValue token value to u64")]
-    fn truncate_value_fails_correctly() {
+    fn truncate_value_does_not_overflow() {
         // 1_000_000_000 tokens with 21 decimal places
-        // token's original value exceeds u64::MAX, and should fail to be converted to u64
+        // token's original value exceeds u64::MAX, and should be returned in full
         let token_max_val = EthTxValue(1_000_000_000_000_000_000_000_000_000_000_u128);
         let token_original_precision = 0_u8;
 
         let max_token_decimals = 9_u8;
 
-        truncate_value(token_max_val, token_original_precision, max_token_decimals);
+        let truncated_token_value =
+            truncate_value(token_max_val, token_original_precision, max_token_decimals).unwrap();
+
+        assert_eq!(truncated_token_value.amount, u128::from(token_max_val));
+        assert!(truncated_token_value.amount > u128::from(u64::MAX));
+    }
+
+    #[test]
+    fn truncate_value_fails_on_unsupported_decimals() {
+        let token_max_val = EthTxValue(1_000_000_000_u128);
+
+        // 10^38 still fits in a u128.
+        let truncated_token_value = truncate_value(token_max_val, 44, 6).unwrap();
+        assert_eq!(truncated_token_value.amount, 0);
+        assert_eq!(truncated_token_value.remainder, u128::from(token_max_val));
+
+        // 10^39 does not.
+        assert!(matches!(
+            truncate_value(token_max_val, 45, 6),
+            Err(Error::UnsupportedDecimals(45, 6))
+        ));
+        assert!(matches!(
+            truncate_value(token_max_val, u8::MAX, 0),
+            Err(Error::UnsupportedDecimals(u8::MAX, 0))
+        ));
     }
 }
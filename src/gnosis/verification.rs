@@ -0,0 +1,158 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Cross-checking Gnosis safe deposits and withdrawals against a second data
+//! source.
+
+use crate::db::{GnosisSafeDeposit, GnosisSafeWithdrawal};
+use displaydoc::Display;
+use serde::{Deserialize, Serialize};
+
+/// The outcome of verifying a deposit or withdrawal against the verification
+/// source of its safe. The display form is what gets stored in the database.
+#[derive(Clone, Copy, Debug, Deserialize, Display, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    /// unverified
+    Unverified,
+
+    /// verified
+    Verified,
+
+    /// disagreement
+    Disagreement,
+}
+
+/// Compare a deposit parsed from the primary source with the same deposit as
+/// parsed from the verification source. Returns a description of every field
+/// the two disagree on, or None if they agree.
+pub fn compare_deposits(deposit: &GnosisSafeDeposit, source: &GnosisSafeDeposit) -> Option<String> {
+    let mut diffs = Vec::new();
+    push_diff(
+        &mut diffs,
+        "tx hash",
+        deposit.eth_tx_hash(),
+        source.eth_tx_hash(),
+    );
+    push_diff(
+        &mut diffs,
+        "block number",
+        &deposit.eth_block_number(),
+        &source.eth_block_number(),
+    );
+    push_diff(
+        &mut diffs,
+        "token",
+        deposit.token_addr(),
+        source.token_addr(),
+    );
+    push_diff(
+        &mut diffs,
+        "value",
+        deposit.eth_tx_value(),
+        source.eth_tx_value(),
+    );
+    push_diff(
+        &mut diffs,
+        "recipient",
+        deposit.safe_addr(),
+        source.safe_addr(),
+    );
+    (!diffs.is_empty()).then(|| diffs.join(", "))
+}
+
+/// Compare a withdrawal parsed from the primary source with the same
+/// withdrawal as parsed from the verification source. Returns a description
+/// of every field the two disagree on, or None if they agree.
+pub fn compare_withdrawals(
+    withdrawal: &GnosisSafeWithdrawal,
+    source: &GnosisSafeWithdrawal,
+) -> Option<String> {
+    let mut diffs = Vec::new();
+    push_diff(
+        &mut diffs,
+        "tx hash",
+        withdrawal.eth_tx_hash(),
+        source.eth_tx_hash(),
+    );
+    push_diff(
+        &mut diffs,
+        "block number",
+        &withdrawal.eth_block_number(),
+        &source.eth_block_number(),
+    );
+    push_diff(
+        &mut diffs,
+        "token",
+        withdrawal.token_addr(),
+        source.token_addr(),
+    );
+    push_diff(
+        &mut diffs,
+        "value",
+        withdrawal.eth_tx_value(),
+        source.eth_tx_value(),
+    );
+    push_diff(
+        &mut diffs,
+        "recipient",
+        withdrawal.to_addr(),
+        source.to_addr(),
+    );
+    push_diff(
+        &mut diffs,
+        "tx out public key",
+        withdrawal.mc_tx_out_public_key_hex(),
+        source.mc_tx_out_public_key_hex(),
+    );
+    (!diffs.is_empty()).then(|| diffs.join(", "))
+}
+
+fn push_diff<T: PartialEq + ToString + ?Sized>(
+    diffs: &mut Vec<String>,
+    field: &str,
+    ours: &T,
+    theirs: &T,
+) {
+    if ours != theirs {
+        diffs.push(format!(
+            "{field} {} != {}",
+            ours.to_string(),
+            theirs.to_string()
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::create_gnosis_safe_deposit;
+
+    #[test]
+    fn compare_deposits_reports_differing_fields() {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let deposit = create_gnosis_safe_deposit(100, &mut rng);
+        assert_eq!(compare_deposits(&deposit, &deposit), None);
+
+        let other = GnosisSafeDeposit::new(
+            None,
+            deposit.chain_id(),
+            *deposit.eth_tx_hash(),
+            deposit.execution_date(),
+            *deposit.eth_tx_value(),
+            deposit.eth_block_number() + 1,
+            deposit.safe_addr().clone(),
+            deposit.token_addr().clone(),
+            deposit.from_addr().clone(),
+            deposit.amount(),
+            0,
+        );
+        assert_eq!(
+            compare_deposits(&deposit, &other),
+            Some(format!(
+                "block number {} != {}",
+                deposit.eth_block_number(),
+                deposit.eth_block_number() + 1
+            ))
+        );
+    }
+}
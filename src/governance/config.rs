@@ -0,0 +1,122 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Configuration for auditing mint configuration governance.
+
+use crate::error::Error;
+use mc_crypto_keys::Ed25519Public;
+use mc_crypto_multisig::SignerSet;
+use mc_transaction_core::TokenId;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// Configuration for auditing mint configuration governance.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct GovernanceConfig {
+    /// The governors expected to sign the mint configurations of each token.
+    #[serde(default)]
+    pub mint_governors: Vec<TokenGovernorsConfig>,
+}
+
+impl GovernanceConfig {
+    /// Load configuration data from a toml/json file.
+    pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        // Read configuration file.
+        let data = fs::read_to_string(path)?;
+
+        // Parse configuration file.
+        let config: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            None => Err(Error::InvalidGovernanceConfig(
+                "cannot figure out file extension".to_string(),
+            )),
+            Some("toml") => {
+                toml::from_str(&data).map_err(|err| Error::InvalidGovernanceConfig(err.to_string()))
+            }
+            Some("json") => serde_json::from_str(&data)
+                .map_err(|err| Error::InvalidGovernanceConfig(err.to_string())),
+            Some(ext) => Err(Error::InvalidGovernanceConfig(format!(
+                "unrecognized file extension {ext}"
+            ))),
+        }?;
+
+        Ok(config)
+    }
+}
+
+/// The governors of a single token, i.e. the keys that are expected to sign
+/// every [mc_transaction_core::mint::MintConfigTx] of the token.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TokenGovernorsConfig {
+    /// The MobileCoin token id.
+    pub token_id: TokenId,
+
+    /// Hex-encoded Ed25519 public keys of the governors.
+    pub signers: Vec<String>,
+
+    /// How many of the governors need to sign a mint configuration.
+    pub threshold: u32,
+}
+
+impl TokenGovernorsConfig {
+    /// Get the governors as a [SignerSet].
+    pub fn signer_set(&self) -> Result<SignerSet<Ed25519Public>, Error> {
+        if self.threshold == 0 || self.threshold as usize > self.signers.len() {
+            return Err(Error::InvalidGovernanceConfig(format!(
+                "token {}: governors threshold must be between 1 and the number of signers",
+                self.token_id
+            )));
+        }
+
+        let signers = self
+            .signers
+            .iter()
+            .map(|signer_hex| {
+                let bytes = hex::decode(signer_hex)?;
+                Ok(Ed25519Public::try_from(&bytes[..])?)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(SignerSet::new(signers, self.threshold))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_config() {
+        let input_toml = r#"
+            [[mint_governors]]
+            token_id = 2
+            signers = ["aabb", "ccdd"]
+            threshold = 1
+        "#;
+        let input_json = r#"{
+            "mint_governors": [
+                { "token_id": 2, "signers": ["aabb", "ccdd"], "threshold": 1 }
+            ]
+        }"#;
+
+        let cfg1: GovernanceConfig = toml::from_str(input_toml).expect("failed parsing toml");
+        let cfg2: GovernanceConfig = serde_json::from_str(input_json).expect("failed parsing json");
+
+        assert_eq!(cfg1, cfg2);
+        assert_eq!(
+            cfg1,
+            GovernanceConfig {
+                mint_governors: vec![TokenGovernorsConfig {
+                    token_id: TokenId::from(2),
+                    signers: vec!["aabb".to_string(), "ccdd".to_string()],
+                    threshold: 1,
+                }],
+            }
+        );
+
+        assert_eq!(
+            serde_json::from_str::<GovernanceConfig>("{}").unwrap(),
+            GovernanceConfig::default()
+        );
+    }
+}
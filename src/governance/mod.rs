@@ -0,0 +1,180 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Auditing of mint configuration governance.
+//!
+//! Every [mc_transaction_core::mint::MintConfigTx] is expected to be signed by
+//! the governors of its token. The consensus network enforces this, but only
+//! against the governors it was configured with, so a compromised or
+//! misconfigured node could accept mint configurations signed by anyone. This
+//! module checks each MintConfigTx against governors configured independently
+//! for the auditor.
+
+mod config;
+
+pub use self::config::{GovernanceConfig, TokenGovernorsConfig};
+
+use crate::{
+    anomaly::{AnomalyEvent, AnomalyKind},
+    db::{Conn, Counters, MintConfigTx},
+    error::Error,
+};
+use mc_blockchain_types::BlockIndex;
+use mc_common::logger::{log, Logger};
+use mc_crypto_keys::Ed25519Public;
+use mc_crypto_multisig::SignerSet;
+use mc_transaction_core::TokenId;
+use std::collections::HashMap;
+
+/// Verifies the signatures of MintConfigTxs against the configured governors
+/// of their token.
+pub struct MintGovernanceAuditor {
+    /// The governors of each token. Tokens that are not listed are not audited.
+    governors: HashMap<TokenId, SignerSet<Ed25519Public>>,
+
+    /// Logger.
+    logger: Logger,
+}
+
+impl MintGovernanceAuditor {
+    /// Create a new [MintGovernanceAuditor].
+    pub fn new(configs: &[TokenGovernorsConfig], logger: Logger) -> Result<Self, Error> {
+        let mut governors = HashMap::new();
+        for config in configs {
+            if governors
+                .insert(config.token_id, config.signer_set()?)
+                .is_some()
+            {
+                return Err(Error::InvalidGovernanceConfig(format!(
+                    "token {} has more than one governors entry",
+                    config.token_id
+                )));
+            }
+        }
+
+        Ok(Self { governors, logger })
+    }
+
+    /// Verify the signatures of the MintConfigTxs included in a given block,
+    /// recording an anomaly for each one that was not signed by the governors
+    /// of its token. Returns the number of violations found.
+    pub fn audit_block(&self, block_index: BlockIndex, conn: &Conn) -> Result<usize, Error> {
+        let mut num_violations = 0;
+
+        for mint_config_tx in MintConfigTx::get_by_block_index(block_index, conn)? {
+            let token_id = mint_config_tx.token_id();
+            let governors = match self.governors.get(&token_id) {
+                Some(governors) => governors,
+                None => continue,
+            };
+
+            let core_tx = mint_config_tx.decode()?;
+            if let Err(err) = governors.verify(&core_tx.prefix.hash(), &core_tx.signature) {
+                log::crit!(
+                    self.logger,
+                    "MintConfigTx {} of token {} in block {} is not signed by the token's governors: {}",
+                    mint_config_tx.nonce_hex(),
+                    token_id,
+                    block_index,
+                    err
+                );
+
+                Counters::record_anomaly(
+                    &AnomalyEvent::new(
+                        AnomalyKind::MintConfigTxGovernorViolation,
+                        format!(
+                            "MintConfigTx nonce={} is not signed by the token's governors",
+                            mint_config_tx.nonce_hex()
+                        ),
+                    )
+                    .with_mint_config_tx(&mint_config_tx),
+                    conn,
+                )?;
+                num_violations += 1;
+            }
+        }
+
+        Ok(num_violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::TestDbContext;
+    use mc_common::logger::{test_with_logger, Logger};
+    use mc_crypto_keys::{Ed25519Pair, Signer};
+    use mc_crypto_multisig::MultiSig;
+    use mc_transaction_core_test_utils::create_mint_config_tx_and_signers;
+    use mc_util_from_random::FromRandom;
+
+    #[test_with_logger]
+    fn invalid_governors_are_rejected(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let governor_hex = hex::encode(Ed25519Pair::from_random(&mut rng).public_key());
+
+        let config = |signers: Vec<String>, threshold| TokenGovernorsConfig {
+            token_id: TokenId::from(1),
+            signers,
+            threshold,
+        };
+
+        assert!(config(vec![governor_hex.clone()], 1).signer_set().is_ok());
+        assert!(config(vec![governor_hex.clone()], 0).signer_set().is_err());
+        assert!(config(vec![governor_hex.clone()], 2).signer_set().is_err());
+        assert!(config(vec!["not hex".to_string()], 1).signer_set().is_err());
+        assert!(config(vec!["abcd".to_string()], 1).signer_set().is_err());
+
+        assert!(MintGovernanceAuditor::new(
+            &[
+                config(vec![governor_hex.clone()], 1),
+                config(vec![governor_hex], 1)
+            ],
+            logger
+        )
+        .is_err());
+    }
+
+    #[test_with_logger]
+    fn audit_block_counts_violations(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id1 = TokenId::from(1);
+        let token_id2 = TokenId::from(2);
+        let token_id3 = TokenId::from(3);
+
+        let governor = Ed25519Pair::from_random(&mut rng);
+        let auditor = MintGovernanceAuditor::new(
+            &[token_id1, token_id2].map(|token_id| TokenGovernorsConfig {
+                token_id,
+                signers: vec![hex::encode(governor.public_key())],
+                threshold: 1,
+            }),
+            logger,
+        )
+        .unwrap();
+
+        // Token 1 is signed by its governor.
+        let (mut tx1, _signers) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        tx1.signature = MultiSig::new(vec![governor.try_sign(tx1.prefix.hash().as_ref()).unwrap()]);
+        MintConfigTx::insert_from_core_mint_config_tx(5, None, &tx1, &conn).unwrap();
+
+        // Token 2 is signed by someone else.
+        let (tx2, _signers) = create_mint_config_tx_and_signers(token_id2, &mut rng);
+        MintConfigTx::insert_from_core_mint_config_tx(5, None, &tx2, &conn).unwrap();
+
+        // Token 3 has no configured governors and is not audited.
+        let (tx3, _signers) = create_mint_config_tx_and_signers(token_id3, &mut rng);
+        MintConfigTx::insert_from_core_mint_config_tx(5, None, &tx3, &conn).unwrap();
+
+        assert_eq!(auditor.audit_block(4, &conn).unwrap(), 0);
+        assert_eq!(auditor.audit_block(5, &conn).unwrap(), 1);
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_mint_config_tx_governor_violations(),
+            1
+        );
+    }
+}
@@ -13,7 +13,7 @@ use mc_common::HashMap;
 use mc_crypto_keys::Ed25519Public;
 use mc_crypto_multisig::SignerSet;
 use mc_transaction_core::TokenId;
-use rocket::serde::Serialize;
+use rocket::serde::{Deserialize, Serialize};
 
 #[derive(Serialize)]
 #[allow(missing_docs)]
@@ -37,17 +37,17 @@ pub struct TokenPrecisionResponse {
 #[allow(missing_docs)]
 pub struct BlockAuditDataResponse {
     pub block_index: u64,
-    pub balances: HashMap<u64, u64>,
+    pub balances: HashMap<u64, String>,
 }
 
 impl BlockAuditDataResponse {
     /// Create a new BlockAuditDataResponse from block index and balance
-    pub fn new(block_audit_data: BlockAuditData, balances: HashMap<TokenId, u64>) -> Self {
+    pub fn new(block_audit_data: BlockAuditData, balances: HashMap<TokenId, u128>) -> Self {
         Self {
             block_index: block_audit_data.block_index(),
             balances: balances
                 .into_iter()
-                .map(|(token_id, balance)| (*token_id, balance))
+                .map(|(token_id, balance)| (*token_id, balance.to_string()))
                 .collect(),
         }
     }
@@ -154,3 +154,42 @@ pub struct BurnInfoResponse {
     pub burn: BurnTxOut,
     pub decoded_burn_memo_bytes: Option<Vec<u8>>,
 }
+
+/// A change of the minter signer sets of a token
+#[derive(Serialize, Debug, Eq, PartialEq)]
+#[allow(missing_docs)]
+pub struct SignerSetChangeResponse {
+    pub id: i32,
+    pub token_id: u64,
+    pub block_index: u64,
+    pub mint_config_tx_id: i32,
+    pub signer_sets: Vec<SignerSet<Ed25519Public>>,
+}
+
+/// Request body for acknowledging an audit event
+#[derive(Deserialize)]
+#[allow(missing_docs)]
+pub struct AcknowledgeAuditEventRequest {
+    pub note: String,
+    pub manual_match_id: Option<i32>,
+}
+
+/// Request body for forcing a match between a mint tx and a gnosis safe
+/// deposit
+#[derive(Deserialize)]
+#[allow(missing_docs)]
+pub struct ForceMatchMintRequest {
+    pub mint_tx_id: i32,
+    pub gnosis_safe_deposit_id: i32,
+    pub justification: String,
+}
+
+/// Request body for forcing a match between a burn tx out and a gnosis safe
+/// withdrawal
+#[derive(Deserialize)]
+#[allow(missing_docs)]
+pub struct ForceMatchBurnRequest {
+    pub burn_tx_out_id: i32,
+    pub gnosis_safe_withdrawal_id: i32,
+    pub justification: String,
+}
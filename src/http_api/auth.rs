@@ -0,0 +1,129 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Authentication of operators for the HTTP write API.
+
+use rocket::{
+    http::Status,
+    request::{FromRequest, Outcome},
+    Request,
+};
+use std::str::FromStr;
+
+/// A bearer token identifying an operator, in `<actor>:<token>` format.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorToken {
+    /// The operator name, recorded as the actor of the actions they take.
+    pub actor: String,
+
+    /// The secret token the operator authenticates with.
+    pub token: String,
+}
+
+impl FromStr for OperatorToken {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        match src.split_once(':') {
+            Some((actor, token)) if !actor.is_empty() && !token.is_empty() => Ok(Self {
+                actor: actor.to_string(),
+                token: token.to_string(),
+            }),
+            _ => Err("Operator token must be in <actor>:<token> format".to_string()),
+        }
+    }
+}
+
+/// The tokens operators may authenticate with. When empty, the write API is
+/// disabled.
+#[derive(Clone, Debug, Default)]
+pub struct OperatorTokens(Vec<OperatorToken>);
+
+impl OperatorTokens {
+    /// Create a new [OperatorTokens] object.
+    pub fn new(tokens: Vec<OperatorToken>) -> Self {
+        Self(tokens)
+    }
+
+    /// Find the actor a token belongs to.
+    fn actor(&self, token: &str) -> Option<&str> {
+        // Compare against every token so that the time taken does not reveal
+        // which one matched.
+        self.0.iter().fold(None, |found, operator_token| {
+            if constant_time_eq(operator_token.token.as_bytes(), token.as_bytes()) {
+                Some(operator_token.actor.as_str())
+            } else {
+                found
+            }
+        })
+    }
+}
+
+/// An authenticated operator. Used as a request guard by routes that modify
+/// the database, expecting an `Authorization: Bearer <token>` header.
+pub struct Operator {
+    /// The operator name.
+    pub actor: String,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Operator {
+    type Error = &'static str;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let operator_tokens = match request.rocket().state::<OperatorTokens>() {
+            Some(operator_tokens) => operator_tokens,
+            None => return Outcome::Failure((Status::Unauthorized, "write API disabled")),
+        };
+
+        let token = match request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+        {
+            Some(token) => token,
+            None => return Outcome::Failure((Status::Unauthorized, "missing bearer token")),
+        };
+
+        match operator_tokens.actor(token) {
+            Some(actor) => Outcome::Success(Operator {
+                actor: actor.to_string(),
+            }),
+            None => Outcome::Failure((Status::Unauthorized, "invalid bearer token")),
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_operator_token() {
+        assert_eq!(
+            OperatorToken::from_str("alice:s3cr:et").unwrap(),
+            OperatorToken {
+                actor: "alice".to_string(),
+                token: "s3cr:et".to_string(),
+            }
+        );
+        assert!(OperatorToken::from_str("alice").is_err());
+        assert!(OperatorToken::from_str(":secret").is_err());
+        assert!(OperatorToken::from_str("alice:").is_err());
+    }
+
+    #[test]
+    fn lookup_actor() {
+        let operator_tokens = OperatorTokens::new(vec![
+            OperatorToken::from_str("alice:secret1").unwrap(),
+            OperatorToken::from_str("bob:secret2").unwrap(),
+        ]);
+        assert_eq!(operator_tokens.actor("secret1"), Some("alice"));
+        assert_eq!(operator_tokens.actor("secret2"), Some("bob"));
+        assert_eq!(operator_tokens.actor("secret"), None);
+        assert_eq!(OperatorTokens::default().actor(""), None);
+    }
+}
@@ -3,10 +3,13 @@
 //! HTTP server for reserve auditor
 
 mod api_types;
+mod auth;
 mod fairings;
 mod routes;
 mod service;
 
+pub use self::auth::{OperatorToken, OperatorTokens};
+
 use crate::{db::ReserveAuditorDb, gnosis::GnosisSafeConfig};
 use rocket::{custom, routes, Config};
 use std::{net::Ipv4Addr, str::FromStr};
@@ -17,6 +20,7 @@ pub async fn start_http_server(
     gnosis_safe_config: GnosisSafeConfig,
     port: u16,
     host: String,
+    operator_tokens: OperatorTokens,
 ) {
     let service = service::ReserveAuditorHttpService::new(db, gnosis_safe_config);
 
@@ -28,6 +32,7 @@ pub async fn start_http_server(
 
     if let Err(e) = custom(&config)
         .manage(service)
+        .manage(operator_tokens)
         .attach(fairings::Cors)
         .mount(
             "/",
@@ -46,7 +51,15 @@ pub async fn start_http_server(
                 routes::get_mint_info_for_block,
                 routes::get_burns_for_block,
                 routes::get_unaudited_withdrawals,
-                routes::get_unaudited_mints
+                routes::get_unaudited_mints,
+                routes::get_reconciliation,
+                routes::get_audit_events,
+                routes::get_mint_limits,
+                routes::get_signer_set_changes,
+                routes::acknowledge_audit_event,
+                routes::get_manual_matches,
+                routes::force_match_mint,
+                routes::force_match_burn
             ],
         )
         .launch()
@@ -3,18 +3,25 @@
 //! Routing for the http server
 
 use crate::{
-    db::{Counters, GnosisSafeWithdrawal, MintTx},
+    db::{
+        AuditEvent, AuditEventAcknowledgement, Counters, GnosisSafeWithdrawal, ManualMatch, MintTx,
+    },
     http_api::{
         api_types::{
-            AuditedBurnResponse, AuditedMintResponse, BlockAuditDataResponse,
-            LedgerBalanceResponse, MintInfoResponse, TokenType, UnauditedBurnTxOutResponse,
-            UnauditedGnosisDepositResponse,
+            AcknowledgeAuditEventRequest, AuditedBurnResponse, AuditedMintResponse,
+            BlockAuditDataResponse, ForceMatchBurnRequest, ForceMatchMintRequest,
+            LedgerBalanceResponse, MintInfoResponse, SignerSetChangeResponse, TokenType,
+            UnauditedBurnTxOutResponse, UnauditedGnosisDepositResponse,
         },
+        auth::Operator,
         service::ReserveAuditorHttpService,
     },
+    mint_limits::MintLimitsReport,
+    reconciliation::{ReconciliationPoint, ReconciliationReport},
 };
+use chrono::{DateTime, Utc};
 use mc_transaction_core::TokenId;
-use rocket::{get, serde::json::Json, State};
+use rocket::{get, post, serde::json::Json, State};
 
 use super::api_types::{BurnInfoResponse, GnosisSafeConfigResponse, TokenPrecisionResponse};
 
@@ -196,3 +203,139 @@ pub fn get_unaudited_mints(
         Err(e) => Err(e.to_string()),
     }
 }
+
+/// Get the reconciliation of token supply against reserve holdings, either
+/// for the latest synced block or as of a given block index or RFC 3339
+/// timestamp.
+#[get("/reconciliation?<block_index>&<timestamp>")]
+pub fn get_reconciliation(
+    block_index: Option<u64>,
+    timestamp: Option<String>,
+    service: &State<ReserveAuditorHttpService>,
+) -> Result<Json<ReconciliationReport>, String> {
+    let point = match (block_index, timestamp) {
+        (None, None) => None,
+        (Some(block_index), None) => Some(ReconciliationPoint::BlockIndex(block_index)),
+        (None, Some(timestamp)) => Some(ReconciliationPoint::Timestamp(
+            DateTime::parse_from_rfc3339(&timestamp)
+                .map_err(|e| format!("Invalid timestamp: {e}"))?
+                .with_timezone(&Utc),
+        )),
+        (Some(_), Some(_)) => {
+            return Err("Only one of block_index and timestamp may be given".to_string())
+        }
+    };
+
+    match service.get_reconciliation(point) {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Get a paginated list of audit events (detected anomalies), optionally only
+/// those that were not acknowledged yet
+#[get("/audit_events?<offset>&<limit>&<open_only>")]
+pub fn get_audit_events(
+    offset: Option<u64>,
+    limit: Option<u64>,
+    open_only: Option<bool>,
+    service: &State<ReserveAuditorHttpService>,
+) -> Result<Json<Vec<AuditEvent>>, String> {
+    match service.get_audit_events(offset, limit, open_only.unwrap_or(false)) {
+        Ok(audit_events) => Ok(Json(audit_events)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Acknowledge an audit event (requires operator authentication)
+#[post("/audit_events/<audit_event_id>/acknowledge", data = "<request>")]
+pub fn acknowledge_audit_event(
+    audit_event_id: i32,
+    request: Json<AcknowledgeAuditEventRequest>,
+    operator: Operator,
+    service: &State<ReserveAuditorHttpService>,
+) -> Result<Json<AuditEventAcknowledgement>, String> {
+    match service.acknowledge_audit_event(
+        audit_event_id,
+        &operator.actor,
+        &request.note,
+        request.manual_match_id,
+    ) {
+        Ok(acknowledgement) => Ok(Json(acknowledgement)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Get how much the active mint configurations of each token have minted and
+/// can still mint
+#[get("/mint_limits")]
+pub fn get_mint_limits(
+    service: &State<ReserveAuditorHttpService>,
+) -> Result<Json<MintLimitsReport>, String> {
+    match service.get_mint_limits() {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Get the history of minter signer set changes, optionally only for a given
+/// token
+#[get("/signer_set_changes?<token_id>")]
+pub fn get_signer_set_changes(
+    token_id: Option<u64>,
+    service: &State<ReserveAuditorHttpService>,
+) -> Result<Json<Vec<SignerSetChangeResponse>>, String> {
+    match service.get_signer_set_changes(token_id.map(TokenId::from)) {
+        Ok(changes) => Ok(Json(changes)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Get all manual matches
+#[get("/manual_matches")]
+pub fn get_manual_matches(
+    service: &State<ReserveAuditorHttpService>,
+) -> Result<Json<Vec<ManualMatch>>, String> {
+    match service.get_manual_matches() {
+        Ok(manual_matches) => Ok(Json(manual_matches)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Force a match between a mint tx and a gnosis safe deposit (requires
+/// operator authentication)
+#[post("/manual_matches/mint", data = "<request>")]
+pub fn force_match_mint(
+    request: Json<ForceMatchMintRequest>,
+    operator: Operator,
+    service: &State<ReserveAuditorHttpService>,
+) -> Result<Json<ManualMatch>, String> {
+    match service.force_match_mint(
+        request.mint_tx_id,
+        request.gnosis_safe_deposit_id,
+        &operator.actor,
+        &request.justification,
+    ) {
+        Ok(manual_match) => Ok(Json(manual_match)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Force a match between a burn tx out and a gnosis safe withdrawal (requires
+/// operator authentication)
+#[post("/manual_matches/burn", data = "<request>")]
+pub fn force_match_burn(
+    request: Json<ForceMatchBurnRequest>,
+    operator: Operator,
+    service: &State<ReserveAuditorHttpService>,
+) -> Result<Json<ManualMatch>, String> {
+    match service.force_match_burn(
+        request.burn_tx_out_id,
+        request.gnosis_safe_withdrawal_id,
+        &operator.actor,
+        &request.justification,
+    ) {
+        Ok(manual_match) => Ok(Json(manual_match)),
+        Err(e) => Err(e.to_string()),
+    }
+}
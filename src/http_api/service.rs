@@ -6,16 +6,18 @@ use mc_transaction_core::TokenId;
 
 use crate::{
     db::{
-        AuditedBurn, AuditedMint, BlockAuditData, BlockBalance, BurnTxOut, Counters,
-        GnosisSafeDeposit, GnosisSafeWithdrawal, MintConfig, MintConfigTx, MintTx,
-        ReserveAuditorDb,
+        AuditEvent, AuditEventAcknowledgement, AuditedBurn, AuditedMint, BlockAuditData,
+        BlockBalance, BurnTxOut, Counters, GnosisSafeDeposit, GnosisSafeWithdrawal, ManualMatch,
+        MintConfig, MintConfigTx, MintTx, ReserveAuditorDb, SignerSetChange,
     },
     gnosis::GnosisSafeConfig,
     http_api::api_types::{
         AuditedBurnResponse, AuditedMintResponse, BlockAuditDataResponse, HybridMintConfig,
-        MintConfigTxWithConfig, MintInfoResponse, MintWithConfig, UnauditedBurnTxOutResponse,
-        UnauditedGnosisDepositResponse,
+        MintConfigTxWithConfig, MintInfoResponse, MintWithConfig, SignerSetChangeResponse,
+        UnauditedBurnTxOutResponse, UnauditedGnosisDepositResponse,
     },
+    mint_limits::MintLimitsReport,
+    reconciliation::{ReconciliationPoint, ReconciliationReport},
     Error,
 };
 
@@ -313,6 +315,112 @@ impl ReserveAuditorHttpService {
 
         Ok(query_result)
     }
+
+    /// Get the reconciliation of token supply against reserve holdings, for
+    /// the latest synced block if no point in time is given.
+    pub fn get_reconciliation(
+        &self,
+        point: Option<ReconciliationPoint>,
+    ) -> Result<ReconciliationReport, Error> {
+        let conn = self.reserve_auditor_db.get_conn()?;
+        ReconciliationReport::compute(point, &conn)
+    }
+
+    /// Get how much the active mint configurations of each token have minted
+    /// and can still mint
+    pub fn get_mint_limits(&self) -> Result<MintLimitsReport, Error> {
+        let conn = self.reserve_auditor_db.get_conn()?;
+        MintLimitsReport::compute(&conn)
+    }
+
+    /// Get the history of minter signer set changes, optionally only for a
+    /// given token
+    pub fn get_signer_set_changes(
+        &self,
+        token_id: Option<TokenId>,
+    ) -> Result<Vec<SignerSetChangeResponse>, Error> {
+        let conn = self.reserve_auditor_db.get_conn()?;
+        SignerSetChange::list(token_id, &conn)?
+            .into_iter()
+            .map(|change| {
+                Ok(SignerSetChangeResponse {
+                    id: change.id().ok_or(Error::ObjectNotSaved)?,
+                    token_id: *change.token_id(),
+                    block_index: change.block_index(),
+                    mint_config_tx_id: change.mint_config_tx_id(),
+                    signer_sets: change.signer_sets()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Get a paginated list of audit events (detected anomalies), optionally
+    /// only those that were not acknowledged yet
+    pub fn get_audit_events(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        open_only: bool,
+    ) -> Result<Vec<AuditEvent>, Error> {
+        let conn = self.reserve_auditor_db.get_conn()?;
+        AuditEvent::list(offset, limit, open_only, &conn)
+    }
+
+    /// Acknowledge an audit event on behalf of an operator
+    pub fn acknowledge_audit_event(
+        &self,
+        audit_event_id: i32,
+        actor: &str,
+        note: &str,
+        manual_match_id: Option<i32>,
+    ) -> Result<AuditEventAcknowledgement, Error> {
+        let conn = self.reserve_auditor_db.get_conn()?;
+        AuditEventAcknowledgement::acknowledge(audit_event_id, actor, note, manual_match_id, &conn)
+    }
+
+    /// Get all manual matches
+    pub fn get_manual_matches(&self) -> Result<Vec<ManualMatch>, Error> {
+        let conn = self.reserve_auditor_db.get_conn()?;
+        ManualMatch::list(&conn)
+    }
+
+    /// Force a match between a mint tx and a gnosis safe deposit on behalf of
+    /// an operator
+    pub fn force_match_mint(
+        &self,
+        mint_tx_id: i32,
+        gnosis_safe_deposit_id: i32,
+        actor: &str,
+        justification: &str,
+    ) -> Result<ManualMatch, Error> {
+        let conn = self.reserve_auditor_db.get_conn()?;
+        ManualMatch::force_match_mint(
+            mint_tx_id,
+            gnosis_safe_deposit_id,
+            actor,
+            justification,
+            &conn,
+        )
+    }
+
+    /// Force a match between a burn tx out and a gnosis safe withdrawal on
+    /// behalf of an operator
+    pub fn force_match_burn(
+        &self,
+        burn_tx_out_id: i32,
+        gnosis_safe_withdrawal_id: i32,
+        actor: &str,
+        justification: &str,
+    ) -> Result<ManualMatch, Error> {
+        let conn = self.reserve_auditor_db.get_conn()?;
+        ManualMatch::force_match_burn(
+            burn_tx_out_id,
+            gnosis_safe_withdrawal_id,
+            actor,
+            justification,
+            &conn,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -427,7 +535,10 @@ mod tests {
         let response = service.get_block_audit_data(2).unwrap();
 
         assert_eq!(response.block_index, 2,);
-        assert_eq!(response.balances, HashMap::from_iter([(1, 101), (22, 2)]));
+        assert_eq!(
+            response.balances,
+            HashMap::from_iter([(1, "101".to_string()), (22, "2".to_string())])
+        );
     }
 
     #[test_with_logger]
@@ -437,7 +548,10 @@ mod tests {
         let service = ReserveAuditorHttpService::new(reserve_auditor_db, config);
         let response = service.get_last_block_audit_data().unwrap();
         assert_eq!(response.block_index, 2,);
-        assert_eq!(response.balances, HashMap::from_iter([(1, 101), (22, 2)]));
+        assert_eq!(
+            response.balances,
+            HashMap::from_iter([(1, "101".to_string()), (22, "2".to_string())])
+        );
     }
 
     #[test_with_logger]
@@ -5,10 +5,17 @@
 #![feature(proc_macro_hygiene, decl_macro)]
 #![deny(missing_docs)]
 
+pub mod anomaly;
 pub mod counters;
 pub mod db;
 pub mod gnosis;
+pub mod governance;
 pub mod http_api;
+pub mod mint_limits;
+pub mod notifier;
+pub mod reconciliation;
+pub mod reserve;
+pub mod unmatched;
 
 mod convert;
 mod error;
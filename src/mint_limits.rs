@@ -0,0 +1,250 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Accounting of how much the active mint configurations of each token can
+//! still mint.
+//!
+//! Once a mint configuration runs out, mints signed by it start failing until
+//! a new MintConfigTx is submitted, so minting operators need to know ahead of
+//! time how much headroom is left.
+
+use crate::{
+    counters,
+    db::{BlockAuditData, Conn, MintConfig, MintConfigTx},
+    error::Error,
+};
+use mc_blockchain_types::BlockIndex;
+use mc_crypto_keys::Ed25519Public;
+use mc_crypto_multisig::SignerSet;
+use mc_transaction_core::TokenId;
+use serde::Serialize;
+
+/// A block index past any block we could have synced, used for looking up the
+/// current state.
+const LATEST_BLOCK_INDEX: BlockIndex = i64::MAX as BlockIndex;
+
+/// Usage of a single mint config.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MintConfigUsage {
+    /// Id of the mint config.
+    pub mint_config_id: i32,
+
+    /// The signer set allowed to mint with this config.
+    pub signer_set: SignerSet<Ed25519Public>,
+
+    /// The maximal amount this config can mint.
+    pub mint_limit: u64,
+
+    /// The amount minted with this config so far. This can exceed the mint
+    /// limit if mints that were not allowed by it made it to the ledger.
+    #[serde(with = "serde_with::rust::display_fromstr")]
+    pub minted: u128,
+
+    /// The amount that can still be minted with this config, ignoring the
+    /// total mint limit of its MintConfigTx.
+    pub remaining: u64,
+}
+
+/// Usage of the active mint configuration of a token.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct TokenMintLimits {
+    /// The token id.
+    pub token_id: TokenId,
+
+    /// Id of the active MintConfigTx.
+    pub mint_config_tx_id: i32,
+
+    /// The hex-encoded nonce of the active MintConfigTx.
+    pub mint_config_tx_nonce_hex: String,
+
+    /// The block the active MintConfigTx appeared in.
+    pub block_index: u64,
+
+    /// The tombstone block of the active MintConfigTx.
+    pub tombstone_block: u64,
+
+    /// The maximal amount all configs of the MintConfigTx can mint together.
+    pub total_mint_limit: u64,
+
+    /// The amount minted with the MintConfigTx so far.
+    #[serde(with = "serde_with::rust::display_fromstr")]
+    pub total_minted: u128,
+
+    /// The amount that can still be minted with the MintConfigTx.
+    pub total_remaining: u64,
+
+    /// Usage of each of the configs of the MintConfigTx.
+    pub mint_configs: Vec<MintConfigUsage>,
+}
+
+/// Usage of the active mint configurations of all tokens.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MintLimitsReport {
+    /// The last synced block, if any.
+    pub block_index: Option<u64>,
+
+    /// Per-token usage, ordered by token id.
+    pub tokens: Vec<TokenMintLimits>,
+}
+
+impl MintLimitsReport {
+    /// Compute the usage of the mint configurations that are active after the
+    /// last synced block.
+    pub fn compute(conn: &Conn) -> Result<Self, Error> {
+        let mut tokens = Vec::new();
+
+        for token_id in MintConfigTx::list_token_ids(conn)? {
+            let mint_config_tx =
+                match MintConfigTx::most_recent_for_token(LATEST_BLOCK_INDEX, token_id, conn)? {
+                    Some(mint_config_tx) => mint_config_tx,
+                    None => continue,
+                };
+            let mint_config_tx_id = mint_config_tx.id().ok_or(Error::ObjectNotSaved)?;
+
+            let mint_configs = MintConfig::get_by_mint_config_tx_id(mint_config_tx_id, conn)?
+                .into_iter()
+                .map(|mint_config| {
+                    let minted =
+                        mint_config.get_total_minted_before_block(LATEST_BLOCK_INDEX, conn)?;
+                    Ok(MintConfigUsage {
+                        mint_config_id: mint_config.id().ok_or(Error::ObjectNotSaved)?,
+                        signer_set: mint_config.decode()?.signer_set,
+                        mint_limit: mint_config.mint_limit(),
+                        minted,
+                        remaining: remaining(mint_config.mint_limit(), minted),
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let total_minted =
+                mint_config_tx.get_total_minted_before_block(LATEST_BLOCK_INDEX, conn)?;
+            tokens.push(TokenMintLimits {
+                token_id,
+                mint_config_tx_id,
+                mint_config_tx_nonce_hex: mint_config_tx.nonce_hex().to_string(),
+                block_index: mint_config_tx.block_index(),
+                tombstone_block: mint_config_tx.tombstone_block(),
+                total_mint_limit: mint_config_tx.total_mint_limit(),
+                total_minted,
+                total_remaining: remaining(mint_config_tx.total_mint_limit(), total_minted),
+                mint_configs,
+            });
+        }
+
+        Ok(Self {
+            block_index: BlockAuditData::last_synced_block_index(conn)?,
+            tokens,
+        })
+    }
+
+    /// Update the per-token prometheus gauges. Configs are identified by their
+    /// position in the MintConfigTx.
+    pub fn update_prometheus(&self) {
+        for token in &self.tokens {
+            let token_id = token.token_id;
+            counters::OP_COUNTERS
+                .gauge(&format!("mint_config_total_minted_token_{token_id}"))
+                .set(gauge_value(token.total_minted));
+            counters::OP_COUNTERS
+                .gauge(&format!("mint_config_total_remaining_token_{token_id}"))
+                .set(token.total_remaining as i64);
+            counters::OP_COUNTERS
+                .gauge(&format!("mint_config_tombstone_block_token_{token_id}"))
+                .set(token.tombstone_block as i64);
+
+            for (index, mint_config) in token.mint_configs.iter().enumerate() {
+                counters::OP_COUNTERS
+                    .gauge(&format!(
+                        "mint_config_minted_token_{token_id}_config_{index}"
+                    ))
+                    .set(gauge_value(mint_config.minted));
+                counters::OP_COUNTERS
+                    .gauge(&format!(
+                        "mint_config_remaining_token_{token_id}_config_{index}"
+                    ))
+                    .set(mint_config.remaining as i64);
+            }
+        }
+    }
+}
+
+/// How much is left of a limit once the minted amount is accounted for.
+fn remaining(limit: u64, minted: u128) -> u64 {
+    // This cannot exceed the limit, so it always fits in a u64.
+    u128::from(limit).saturating_sub(minted) as u64
+}
+
+/// Clamp an amount to what a prometheus gauge can hold.
+fn gauge_value(amount: u128) -> i64 {
+    i64::try_from(amount).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::TestDbContext, MintTx};
+    use mc_common::logger::{test_with_logger, Logger};
+    use mc_transaction_core_test_utils::{create_mint_config_tx_and_signers, create_mint_tx};
+
+    #[test_with_logger]
+    fn compute_reports_active_mint_config_usage(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id1 = TokenId::from(1);
+        let token_id2 = TokenId::from(2);
+
+        // Nothing to report initially.
+        assert_eq!(
+            MintLimitsReport::compute(&conn).unwrap(),
+            MintLimitsReport {
+                block_index: None,
+                tokens: vec![],
+            }
+        );
+
+        // Token 1 gets a MintConfigTx that is later replaced, token 2 gets one.
+        let (mut core_tx1, _signers) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        core_tx1.prefix.total_mint_limit = 100;
+        let (mut core_tx2, signers2) = create_mint_config_tx_and_signers(token_id1, &mut rng);
+        core_tx2.prefix.total_mint_limit = 100;
+        core_tx2.prefix.configs[0].mint_limit = 50;
+        core_tx2.prefix.configs[1].mint_limit = 70;
+        let (core_tx3, _signers) = create_mint_config_tx_and_signers(token_id2, &mut rng);
+
+        MintConfigTx::insert_from_core_mint_config_tx(1, None, &core_tx1, &conn).unwrap();
+        let tx2 = MintConfigTx::insert_from_core_mint_config_tx(2, None, &core_tx2, &conn).unwrap();
+        let tx3 = MintConfigTx::insert_from_core_mint_config_tx(2, None, &core_tx3, &conn).unwrap();
+        let configs2 = MintConfig::get_by_mint_config_tx_id(tx2.id().unwrap(), &conn).unwrap();
+
+        // Mint 30 with the first config of the active MintConfigTx of token 1.
+        let mint_tx = create_mint_tx(token_id1, &signers2, 30, &mut rng);
+        MintTx::insert_from_core_mint_tx(3, None, configs2[0].id(), &mint_tx, &conn).unwrap();
+
+        let report = MintLimitsReport::compute(&conn).unwrap();
+        assert_eq!(report.tokens.len(), 2);
+
+        let token1 = &report.tokens[0];
+        assert_eq!(token1.token_id, token_id1);
+        assert_eq!(token1.mint_config_tx_id, tx2.id().unwrap());
+        assert_eq!(token1.block_index, 2);
+        assert_eq!(token1.tombstone_block, core_tx2.prefix.tombstone_block);
+        assert_eq!(token1.total_mint_limit, 100);
+        assert_eq!(token1.total_minted, 30);
+        assert_eq!(token1.total_remaining, 70);
+        assert_eq!(
+            token1
+                .mint_configs
+                .iter()
+                .map(|config| (config.mint_limit, config.minted, config.remaining))
+                .collect::<Vec<_>>(),
+            vec![(50, 30, 20), (70, 0, 70)]
+        );
+
+        let token2 = &report.tokens[1];
+        assert_eq!(token2.token_id, token_id2);
+        assert_eq!(token2.mint_config_tx_id, tx3.id().unwrap());
+        assert_eq!(token2.total_minted, 0);
+        assert_eq!(token2.total_remaining, core_tx3.prefix.total_mint_limit);
+    }
+}
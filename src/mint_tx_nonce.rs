@@ -2,12 +2,32 @@
 
 use crate::{gnosis::EthTxHash, Error};
 use mc_transaction_core::mint::constants::NONCE_LENGTH;
+use std::fmt;
 
 /// Data structure for representing what is encoded in a MintTx nonce.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum MintTxNonce {
     /// A mint backed by a deposit to a Gnosis Safe on the Ethereum blockchain.
     EthereumGnosisDeposit(EthTxHash),
+
+    /// One of several mints backed by a single deposit to a Gnosis Safe on the
+    /// Ethereum blockchain, identified by the deposit transaction hash and the
+    /// index of the part.
+    EthereumGnosisDepositPart(EthTxHash, u32),
+
+    /// A mint backed by a deposit on an EVM chain, identified by the chain id
+    /// and the deposit transaction hash.
+    EvmDeposit(u64, EthTxHash),
+
+    /// One of several mints backed by a single deposit on an EVM chain,
+    /// identified by the chain id, the deposit transaction hash and the index
+    /// of the part.
+    EvmDepositPart(u64, EthTxHash, u32),
+
+    /// A mint backed by a deposit to an off-chain custodian, identified by the
+    /// custodian's reference for it (at most
+    /// [MintTxNonce::MAX_EXTERNAL_REFERENCE_LEN] bytes).
+    ExternalReference(Vec<u8>),
 }
 
 impl MintTxNonce {
@@ -17,6 +37,36 @@ impl MintTxNonce {
     /// Bytes identifying each variant of [MintTxNonce].
     /// Future revisions might add different types of data stored in the nonce.
     pub const ETHEREUM_GNOSIS_DEPOSIT_IDENTIFIER: [u8; MintTxNonce::IDENTIFIER_LEN] = [0x01];
+    pub const ETHEREUM_GNOSIS_DEPOSIT_PART_IDENTIFIER: [u8; MintTxNonce::IDENTIFIER_LEN] = [0x02];
+    pub const EVM_DEPOSIT_IDENTIFIER: [u8; MintTxNonce::IDENTIFIER_LEN] = [0x03];
+    pub const EVM_DEPOSIT_PART_IDENTIFIER: [u8; MintTxNonce::IDENTIFIER_LEN] = [0x04];
+    pub const EXTERNAL_REFERENCE_IDENTIFIER: [u8; MintTxNonce::IDENTIFIER_LEN] = [0x05];
+
+    /// Part index length (big endian u32, following the deposit tx hash).
+    pub const PART_INDEX_LEN: usize = 4;
+
+    /// Chain id length (big endian u64, following the identifier).
+    pub const CHAIN_ID_LEN: usize = 8;
+
+    /// The chain id of Ethereum mainnet, which the `EthereumGnosisDeposit*`
+    /// variants implicitly refer to.
+    pub const ETHEREUM_MAINNET_CHAIN_ID: u64 = 1;
+
+    /// Maximum length of an external reference. The reference is prefixed by
+    /// the identifier and a single length byte.
+    pub const MAX_EXTERNAL_REFERENCE_LEN: usize = NONCE_LENGTH - Self::IDENTIFIER_LEN - 1;
+
+    /// Create an [MintTxNonce::ExternalReference] nonce, validating the length
+    /// of the reference.
+    pub fn external_reference(reference: &[u8]) -> Result<Self, Error> {
+        if reference.is_empty() || reference.len() > Self::MAX_EXTERNAL_REFERENCE_LEN {
+            return Err(Error::InvalidLength(
+                Self::MAX_EXTERNAL_REFERENCE_LEN,
+                reference.len(),
+            ));
+        }
+        Ok(MintTxNonce::ExternalReference(reference.to_vec()))
+    }
 
     /// Convert to the byte representation.
     pub fn to_bytes(&self) -> [u8; NONCE_LENGTH] {
@@ -28,9 +78,124 @@ impl MintTxNonce {
                 bytes[Self::IDENTIFIER_LEN..Self::IDENTIFIER_LEN + EthTxHash::LEN]
                     .copy_from_slice(eth_tx_hash.as_ref())
             }
+            MintTxNonce::EthereumGnosisDepositPart(eth_tx_hash, part_index) => {
+                bytes[0..Self::IDENTIFIER_LEN]
+                    .copy_from_slice(&MintTxNonce::ETHEREUM_GNOSIS_DEPOSIT_PART_IDENTIFIER);
+                bytes[Self::IDENTIFIER_LEN..Self::IDENTIFIER_LEN + EthTxHash::LEN]
+                    .copy_from_slice(eth_tx_hash.as_ref());
+                bytes[Self::IDENTIFIER_LEN + EthTxHash::LEN
+                    ..Self::IDENTIFIER_LEN + EthTxHash::LEN + Self::PART_INDEX_LEN]
+                    .copy_from_slice(&part_index.to_be_bytes());
+            }
+            MintTxNonce::EvmDeposit(chain_id, eth_tx_hash) => {
+                let hash_start = Self::IDENTIFIER_LEN + Self::CHAIN_ID_LEN;
+                bytes[0..Self::IDENTIFIER_LEN]
+                    .copy_from_slice(&MintTxNonce::EVM_DEPOSIT_IDENTIFIER);
+                bytes[Self::IDENTIFIER_LEN..hash_start].copy_from_slice(&chain_id.to_be_bytes());
+                bytes[hash_start..hash_start + EthTxHash::LEN]
+                    .copy_from_slice(eth_tx_hash.as_ref());
+            }
+            MintTxNonce::EvmDepositPart(chain_id, eth_tx_hash, part_index) => {
+                let hash_start = Self::IDENTIFIER_LEN + Self::CHAIN_ID_LEN;
+                let part_index_start = hash_start + EthTxHash::LEN;
+                bytes[0..Self::IDENTIFIER_LEN]
+                    .copy_from_slice(&MintTxNonce::EVM_DEPOSIT_PART_IDENTIFIER);
+                bytes[Self::IDENTIFIER_LEN..hash_start].copy_from_slice(&chain_id.to_be_bytes());
+                bytes[hash_start..part_index_start].copy_from_slice(eth_tx_hash.as_ref());
+                bytes[part_index_start..part_index_start + Self::PART_INDEX_LEN]
+                    .copy_from_slice(&part_index.to_be_bytes());
+            }
+            MintTxNonce::ExternalReference(reference) => {
+                // Panics if the reference is too long, which
+                // [MintTxNonce::external_reference] guards against.
+                let reference_start = Self::IDENTIFIER_LEN + 1;
+                bytes[0..Self::IDENTIFIER_LEN]
+                    .copy_from_slice(&MintTxNonce::EXTERNAL_REFERENCE_IDENTIFIER);
+                bytes[Self::IDENTIFIER_LEN] = reference.len() as u8;
+                bytes[reference_start..reference_start + reference.len()]
+                    .copy_from_slice(reference);
+            }
         }
         bytes
     }
+
+    /// Get the hash of the deposit transaction backing the mint, if the nonce
+    /// references a deposit on an EVM chain.
+    pub fn eth_tx_hash(&self) -> Option<&EthTxHash> {
+        match self {
+            MintTxNonce::EthereumGnosisDeposit(eth_tx_hash)
+            | MintTxNonce::EthereumGnosisDepositPart(eth_tx_hash, _)
+            | MintTxNonce::EvmDeposit(_, eth_tx_hash)
+            | MintTxNonce::EvmDepositPart(_, eth_tx_hash, _) => Some(eth_tx_hash),
+            MintTxNonce::ExternalReference(_) => None,
+        }
+    }
+
+    /// Get the id of the EVM chain the deposit backing the mint was made on, if
+    /// the nonce references a deposit on an EVM chain.
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            MintTxNonce::EthereumGnosisDeposit(_) | MintTxNonce::EthereumGnosisDepositPart(..) => {
+                Some(Self::ETHEREUM_MAINNET_CHAIN_ID)
+            }
+            MintTxNonce::EvmDeposit(chain_id, _) | MintTxNonce::EvmDepositPart(chain_id, ..) => {
+                Some(*chain_id)
+            }
+            MintTxNonce::ExternalReference(_) => None,
+        }
+    }
+
+    /// Get the index of the part of the deposit backing the mint, if the deposit
+    /// is split across several mints.
+    pub fn part_index(&self) -> Option<u32> {
+        match self {
+            MintTxNonce::EthereumGnosisDepositPart(_, part_index)
+            | MintTxNonce::EvmDepositPart(_, _, part_index) => Some(*part_index),
+            MintTxNonce::EthereumGnosisDeposit(_)
+            | MintTxNonce::EvmDeposit(..)
+            | MintTxNonce::ExternalReference(_) => None,
+        }
+    }
+
+    /// The nonce we expect to see on a mint backed by an entire deposit made on
+    /// a given chain. Ethereum mainnet deposits keep using the
+    /// [MintTxNonce::EthereumGnosisDeposit] nonce.
+    pub fn for_deposit(chain_id: u64, eth_tx_hash: EthTxHash) -> Self {
+        if chain_id == Self::ETHEREUM_MAINNET_CHAIN_ID {
+            MintTxNonce::EthereumGnosisDeposit(eth_tx_hash)
+        } else {
+            MintTxNonce::EvmDeposit(chain_id, eth_tx_hash)
+        }
+    }
+
+    /// Hex-encoded nonces that reference an entire deposit made on a given
+    /// chain.
+    pub fn deposit_nonce_hexes(chain_id: u64, eth_tx_hash: &EthTxHash) -> Vec<String> {
+        let mut nonces = vec![MintTxNonce::EvmDeposit(chain_id, *eth_tx_hash)];
+        if chain_id == Self::ETHEREUM_MAINNET_CHAIN_ID {
+            nonces.push(MintTxNonce::EthereumGnosisDeposit(*eth_tx_hash));
+        }
+        nonces
+            .iter()
+            .map(|nonce| hex::encode(nonce.to_bytes()))
+            .collect()
+    }
+
+    /// Hex-encoded prefixes shared by the nonces of all parts of a deposit made
+    /// on a given chain.
+    pub fn deposit_part_prefix_hexes(chain_id: u64, eth_tx_hash: &EthTxHash) -> Vec<String> {
+        let mut evm_prefix = Self::EVM_DEPOSIT_PART_IDENTIFIER.to_vec();
+        evm_prefix.extend_from_slice(&chain_id.to_be_bytes());
+        evm_prefix.extend_from_slice(eth_tx_hash.as_ref());
+        let mut prefixes = vec![evm_prefix];
+
+        if chain_id == Self::ETHEREUM_MAINNET_CHAIN_ID {
+            let mut ethereum_prefix = Self::ETHEREUM_GNOSIS_DEPOSIT_PART_IDENTIFIER.to_vec();
+            ethereum_prefix.extend_from_slice(eth_tx_hash.as_ref());
+            prefixes.push(ethereum_prefix);
+        }
+        prefixes.iter().map(hex::encode).collect()
+    }
 }
 
 impl TryFrom<&[u8]> for MintTxNonce {
@@ -51,6 +216,65 @@ impl TryFrom<&[u8]> for MintTxNonce {
                 )?;
                 Ok(MintTxNonce::EthereumGnosisDeposit(eth_tx_hash))
             }
+            &Self::ETHEREUM_GNOSIS_DEPOSIT_PART_IDENTIFIER => {
+                let eth_tx_hash = EthTxHash::try_from(
+                    &bytes[Self::IDENTIFIER_LEN..Self::IDENTIFIER_LEN + EthTxHash::LEN],
+                )?;
+                let part_index_start = Self::IDENTIFIER_LEN + EthTxHash::LEN;
+                let part_index = u32::from_be_bytes(
+                    bytes[part_index_start..part_index_start + Self::PART_INDEX_LEN]
+                        .try_into()
+                        .expect("slice has the part index length"),
+                );
+                Ok(MintTxNonce::EthereumGnosisDepositPart(
+                    eth_tx_hash,
+                    part_index,
+                ))
+            }
+            &Self::EVM_DEPOSIT_IDENTIFIER => {
+                let hash_start = Self::IDENTIFIER_LEN + Self::CHAIN_ID_LEN;
+                let chain_id = u64::from_be_bytes(
+                    bytes[Self::IDENTIFIER_LEN..hash_start]
+                        .try_into()
+                        .expect("slice has the chain id length"),
+                );
+                let eth_tx_hash =
+                    EthTxHash::try_from(&bytes[hash_start..hash_start + EthTxHash::LEN])?;
+                Ok(MintTxNonce::EvmDeposit(chain_id, eth_tx_hash))
+            }
+            &Self::EVM_DEPOSIT_PART_IDENTIFIER => {
+                let hash_start = Self::IDENTIFIER_LEN + Self::CHAIN_ID_LEN;
+                let part_index_start = hash_start + EthTxHash::LEN;
+                let chain_id = u64::from_be_bytes(
+                    bytes[Self::IDENTIFIER_LEN..hash_start]
+                        .try_into()
+                        .expect("slice has the chain id length"),
+                );
+                let eth_tx_hash = EthTxHash::try_from(&bytes[hash_start..part_index_start])?;
+                let part_index = u32::from_be_bytes(
+                    bytes[part_index_start..part_index_start + Self::PART_INDEX_LEN]
+                        .try_into()
+                        .expect("slice has the part index length"),
+                );
+                Ok(MintTxNonce::EvmDepositPart(
+                    chain_id,
+                    eth_tx_hash,
+                    part_index,
+                ))
+            }
+            &Self::EXTERNAL_REFERENCE_IDENTIFIER => {
+                let reference_len = bytes[Self::IDENTIFIER_LEN] as usize;
+                let reference_start = Self::IDENTIFIER_LEN + 1;
+                if reference_len == 0 || reference_len > Self::MAX_EXTERNAL_REFERENCE_LEN {
+                    return Err(Error::InvalidLength(
+                        Self::MAX_EXTERNAL_REFERENCE_LEN,
+                        reference_len,
+                    ));
+                }
+                Ok(MintTxNonce::ExternalReference(
+                    bytes[reference_start..reference_start + reference_len].to_vec(),
+                ))
+            }
             _ => Err(Error::InvalidNonceIdentifier(
                 bytes[..Self::IDENTIFIER_LEN].to_vec(),
             )),
@@ -58,6 +282,30 @@ impl TryFrom<&[u8]> for MintTxNonce {
     }
 }
 
+/// Human readable representation of the decoded nonce, stored alongside each
+/// MintTx.
+impl fmt::Display for MintTxNonce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MintTxNonce::EthereumGnosisDeposit(eth_tx_hash) => {
+                write!(f, "ethereum_gnosis_deposit:{eth_tx_hash}")
+            }
+            MintTxNonce::EthereumGnosisDepositPart(eth_tx_hash, part_index) => {
+                write!(f, "ethereum_gnosis_deposit_part:{eth_tx_hash}:{part_index}")
+            }
+            MintTxNonce::EvmDeposit(chain_id, eth_tx_hash) => {
+                write!(f, "evm_deposit:{chain_id}:{eth_tx_hash}")
+            }
+            MintTxNonce::EvmDepositPart(chain_id, eth_tx_hash, part_index) => {
+                write!(f, "evm_deposit_part:{chain_id}:{eth_tx_hash}:{part_index}")
+            }
+            MintTxNonce::ExternalReference(reference) => {
+                write!(f, "external_reference:{}", hex::encode(reference))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,4 +355,123 @@ mod tests {
             matches!(nonce, MintTxNonce::EthereumGnosisDeposit(ref eth_tx_hash_2) if &eth_tx_hash == eth_tx_hash_2)
         );
     }
+
+    #[test]
+    fn mint_tx_nonce_deposit_part_round_trips() {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let eth_tx_hash = EthTxHash::from_random(&mut rng);
+
+        let nonce = MintTxNonce::EthereumGnosisDepositPart(eth_tx_hash, 0x01020304);
+        let bytes = nonce.to_bytes();
+
+        // Hardcoded indexes to ensure data ends up exactly where we think it should be.
+        assert_eq!(
+            bytes[0..1],
+            MintTxNonce::ETHEREUM_GNOSIS_DEPOSIT_PART_IDENTIFIER
+        );
+        assert_eq!(&bytes[1..33], eth_tx_hash.as_ref());
+        assert_eq!(bytes[33..37], [1, 2, 3, 4]);
+        assert_eq!(bytes[37..], [0u8; 64 - 37]);
+
+        assert_eq!(MintTxNonce::try_from(&bytes[..]).unwrap(), nonce);
+        assert_eq!(nonce.eth_tx_hash(), Some(&eth_tx_hash));
+        assert_eq!(nonce.part_index(), Some(0x01020304));
+        assert!(MintTxNonce::deposit_part_prefix_hexes(1, &eth_tx_hash)
+            .iter()
+            .any(|prefix| hex::encode(bytes).starts_with(prefix)));
+    }
+
+    #[test]
+    fn mint_tx_nonce_evm_deposits_round_trip() {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let eth_tx_hash = EthTxHash::from_random(&mut rng);
+
+        let nonce = MintTxNonce::EvmDeposit(10, eth_tx_hash);
+        let bytes = nonce.to_bytes();
+
+        // Hardcoded indexes to ensure data ends up exactly where we think it should be.
+        assert_eq!(bytes[0..1], MintTxNonce::EVM_DEPOSIT_IDENTIFIER);
+        assert_eq!(bytes[1..9], [0, 0, 0, 0, 0, 0, 0, 10]);
+        assert_eq!(&bytes[9..41], eth_tx_hash.as_ref());
+        assert_eq!(bytes[41..], [0u8; 64 - 41]);
+
+        assert_eq!(MintTxNonce::try_from(&bytes[..]).unwrap(), nonce);
+        assert_eq!(nonce.eth_tx_hash(), Some(&eth_tx_hash));
+        assert_eq!(nonce.chain_id(), Some(10));
+
+        let nonce = MintTxNonce::EvmDepositPart(42161, eth_tx_hash, 7);
+        let bytes = nonce.to_bytes();
+
+        assert_eq!(bytes[0..1], MintTxNonce::EVM_DEPOSIT_PART_IDENTIFIER);
+        assert_eq!(bytes[1..9], 42161u64.to_be_bytes());
+        assert_eq!(&bytes[9..41], eth_tx_hash.as_ref());
+        assert_eq!(bytes[41..45], [0, 0, 0, 7]);
+        assert_eq!(bytes[45..], [0u8; 64 - 45]);
+
+        assert_eq!(MintTxNonce::try_from(&bytes[..]).unwrap(), nonce);
+        assert_eq!(nonce.chain_id(), Some(42161));
+        assert!(MintTxNonce::deposit_part_prefix_hexes(42161, &eth_tx_hash)
+            .iter()
+            .any(|prefix| hex::encode(bytes).starts_with(prefix)));
+        assert!(!MintTxNonce::deposit_part_prefix_hexes(10, &eth_tx_hash)
+            .iter()
+            .any(|prefix| hex::encode(bytes).starts_with(prefix)));
+        assert_eq!(
+            nonce.to_string(),
+            format!("evm_deposit_part:42161:{eth_tx_hash}:7")
+        );
+
+        // The Ethereum variants implicitly refer to mainnet, so mainnet deposits keep
+        // their nonce while other chains use the EVM ones.
+        assert_eq!(
+            MintTxNonce::EthereumGnosisDeposit(eth_tx_hash).chain_id(),
+            Some(MintTxNonce::ETHEREUM_MAINNET_CHAIN_ID)
+        );
+        assert_eq!(
+            MintTxNonce::for_deposit(1, eth_tx_hash),
+            MintTxNonce::EthereumGnosisDeposit(eth_tx_hash)
+        );
+        assert_eq!(
+            MintTxNonce::for_deposit(10, eth_tx_hash),
+            MintTxNonce::EvmDeposit(10, eth_tx_hash)
+        );
+        assert_eq!(MintTxNonce::deposit_nonce_hexes(1, &eth_tx_hash).len(), 2);
+        assert_eq!(
+            MintTxNonce::deposit_nonce_hexes(10, &eth_tx_hash),
+            vec![hex::encode(
+                MintTxNonce::EvmDeposit(10, eth_tx_hash).to_bytes()
+            )]
+        );
+    }
+
+    #[test]
+    fn mint_tx_nonce_external_reference_round_trips() {
+        let nonce = MintTxNonce::external_reference(b"bank-ref-1234").unwrap();
+        let bytes = nonce.to_bytes();
+
+        assert_eq!(bytes[0..1], MintTxNonce::EXTERNAL_REFERENCE_IDENTIFIER);
+        assert_eq!(bytes[1], 13);
+        assert_eq!(&bytes[2..15], b"bank-ref-1234");
+        assert_eq!(bytes[15..], [0u8; 64 - 15]);
+
+        assert_eq!(MintTxNonce::try_from(&bytes[..]).unwrap(), nonce);
+        assert_eq!(nonce.eth_tx_hash(), None);
+        assert_eq!(nonce.chain_id(), None);
+        assert_eq!(
+            nonce.to_string(),
+            format!("external_reference:{}", hex::encode(b"bank-ref-1234"))
+        );
+
+        // Empty and oversized references are rejected.
+        assert!(MintTxNonce::external_reference(b"").is_err());
+        assert!(MintTxNonce::external_reference(&[1u8; 62]).is_ok());
+        assert!(MintTxNonce::external_reference(&[1u8; 63]).is_err());
+
+        let mut bytes = [0u8; NONCE_LENGTH];
+        bytes[..MintTxNonce::IDENTIFIER_LEN]
+            .copy_from_slice(&MintTxNonce::EXTERNAL_REFERENCE_IDENTIFIER);
+        assert!(MintTxNonce::try_from(&bytes[..]).is_err());
+        bytes[MintTxNonce::IDENTIFIER_LEN] = 63;
+        assert!(MintTxNonce::try_from(&bytes[..]).is_err());
+    }
 }
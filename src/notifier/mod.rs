@@ -0,0 +1,238 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Delivery of anomaly notifications to webhooks.
+//!
+//! Anomalies are queued in the `notification_outbox` table by
+//! [Counters::record_anomaly](crate::db::Counters::record_anomaly) in the same
+//! database transaction that increments the matching counter. The
+//! [WebhookNotifier] then POSTs them to the configured webhook URLs, retrying
+//! failed deliveries with an exponential backoff. A notification is only
+//! considered delivered once all URLs accepted it, so a webhook may receive
+//! the same notification more than once and should use its `id` to
+//! deduplicate.
+
+mod notifier_thread;
+
+pub use notifier_thread::NotifierThread;
+
+use crate::{
+    db::{Conn, OutboxNotification},
+    error::Error,
+};
+use chrono::{DateTime, Duration, Utc};
+use mc_common::logger::{log, Logger};
+use reqwest::blocking::Client;
+use serde_json::json;
+use url::Url;
+
+/// Maximal number of notifications delivered in one go.
+pub const DELIVERY_BATCH_SIZE: u64 = 100;
+
+/// Delay before retrying a failed delivery for the first time. The delay
+/// doubles with every failed attempt.
+pub const INITIAL_RETRY_DELAY_SECS: i64 = 10;
+
+/// Maximal delay between two delivery attempts.
+pub const MAX_RETRY_DELAY_SECS: i64 = 3600;
+
+/// Timeout for a single webhook request.
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Delivers queued anomaly notifications to webhooks.
+pub struct WebhookNotifier {
+    urls: Vec<Url>,
+    client: Client,
+    logger: Logger,
+}
+
+impl WebhookNotifier {
+    /// Instantiate a [WebhookNotifier] posting to the given URLs.
+    pub fn new(urls: Vec<Url>, logger: Logger) -> Result<Self, Error> {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| Error::Other(format!("Failed to create reqwest client: {e}")))?;
+
+        Ok(Self {
+            urls,
+            client,
+            logger,
+        })
+    }
+
+    /// Attempt to deliver all notifications that are due at a given time.
+    /// Returns the number of notifications delivered.
+    pub fn deliver_pending(&self, conn: &Conn, now: DateTime<Utc>) -> Result<usize, Error> {
+        let mut num_delivered = 0;
+
+        for mut notification in OutboxNotification::find_due(now, DELIVERY_BATCH_SIZE, conn)? {
+            match self.deliver(&notification) {
+                Ok(()) => {
+                    notification.mark_delivered(now, conn)?;
+                    num_delivered += 1;
+                }
+                Err(err) => {
+                    let next_attempt_at = now + retry_delay(notification.num_attempts());
+                    log::error!(
+                        self.logger,
+                        "Failed delivering notification {:?} (attempt {}), retrying at {}: {}",
+                        notification.id(),
+                        notification.num_attempts() + 1,
+                        next_attempt_at,
+                        err
+                    );
+                    notification.mark_failed(&err, next_attempt_at, conn)?;
+                }
+            }
+        }
+
+        Ok(num_delivered)
+    }
+
+    /// POST a notification to all webhook URLs.
+    fn deliver(&self, notification: &OutboxNotification) -> Result<(), String> {
+        let event = notification.decode().map_err(|err| err.to_string())?;
+        let body = json!({
+            "id": notification.id(),
+            "created_at": notification.created_at(),
+            "event": event,
+        });
+
+        let mut errors = Vec::new();
+        for url in &self.urls {
+            let result = self
+                .client
+                .post(url.clone())
+                .json(&body)
+                .send()
+                .and_then(|response| response.error_for_status());
+            if let Err(err) = result {
+                errors.push(format!("{url}: {err}"));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join(", "))
+        }
+    }
+}
+
+/// The delay before the next delivery attempt, given the number of failed
+/// attempts so far.
+fn retry_delay(num_attempts: u32) -> Duration {
+    let delay_secs = INITIAL_RETRY_DELAY_SECS
+        .checked_shl(num_attempts)
+        .filter(|delay_secs| *delay_secs > 0)
+        .map_or(MAX_RETRY_DELAY_SECS, |delay_secs| {
+            delay_secs.min(MAX_RETRY_DELAY_SECS)
+        });
+    Duration::seconds(delay_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        anomaly::{AnomalyEvent, AnomalyKind},
+        db::test_utils::TestDbContext,
+    };
+    use mc_common::logger::{test_with_logger, Logger};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Start a webhook stub that accepts a single request, replies with a 200
+    /// and returns the request body.
+    fn start_webhook_stub() -> (Url, thread::JoinHandle<serde_json::Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/hook", listener.local_addr().unwrap())).unwrap();
+
+        let join_handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .unwrap();
+
+            serde_json::from_slice(&body).unwrap()
+        });
+
+        (url, join_handle)
+    }
+
+    /// A URL nothing listens on.
+    fn unreachable_url() -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        Url::parse(&format!("http://{}/hook", listener.local_addr().unwrap())).unwrap()
+    }
+
+    #[test]
+    fn retry_delay_backs_off_exponentially() {
+        assert_eq!(retry_delay(0), Duration::seconds(10));
+        assert_eq!(retry_delay(1), Duration::seconds(20));
+        assert_eq!(retry_delay(3), Duration::seconds(80));
+        assert_eq!(retry_delay(9), Duration::seconds(MAX_RETRY_DELAY_SECS));
+        assert_eq!(retry_delay(200), Duration::seconds(MAX_RETRY_DELAY_SECS));
+    }
+
+    #[test_with_logger]
+    fn deliver_pending_posts_and_retries(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        let event = AnomalyEvent::new(AnomalyKind::BurnExceedingBalance, "too much");
+        let notification = OutboxNotification::enqueue(&event, &conn).unwrap();
+        let now = Utc::now();
+
+        // Delivery fails if one of the webhooks is unreachable.
+        let notifier = WebhookNotifier::new(vec![unreachable_url()], logger.clone()).unwrap();
+        assert_eq!(notifier.deliver_pending(&conn, now).unwrap(), 0);
+        assert_eq!(
+            OutboxNotification::find_due(now, 10, &conn).unwrap(),
+            vec![]
+        );
+
+        let retry_at = now + retry_delay(0);
+        let due = OutboxNotification::find_due(retry_at, 10, &conn).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].num_attempts(), 1);
+        assert!(due[0].last_error().is_some());
+
+        // Once the webhook is reachable, the notification gets delivered.
+        let (url, join_handle) = start_webhook_stub();
+        let notifier = WebhookNotifier::new(vec![url], logger).unwrap();
+        assert_eq!(notifier.deliver_pending(&conn, retry_at).unwrap(), 1);
+        assert_eq!(
+            OutboxNotification::find_due(retry_at, 10, &conn).unwrap(),
+            vec![]
+        );
+
+        let body = join_handle.join().unwrap();
+        assert_eq!(body["id"], json!(notification.id()));
+        assert_eq!(body["event"]["kind"], json!("burn_exceeding_balance"));
+        assert_eq!(body["event"]["message"], json!("too much"));
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Background thread for periodically delivering anomaly notifications to webhooks.
+
+use super::WebhookNotifier;
+use crate::{db::ReserveAuditorDb, error::Error};
+use chrono::Utc;
+use mc_common::logger::{log, Logger};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{sleep, Builder as ThreadBuilder, JoinHandle},
+    time::Duration,
+};
+
+/// Background thread for periodically delivering anomaly notifications to webhooks.
+pub struct NotifierThread {
+    stop_requested: Arc<AtomicBool>,
+    join_handle: Option<JoinHandle<()>>,
+    logger: Logger,
+}
+
+impl NotifierThread {
+    /// Start the notifier thread.
+    pub fn start(
+        notifier: WebhookNotifier,
+        reserve_auditor_db: ReserveAuditorDb,
+        poll_interval: Duration,
+        logger: Logger,
+    ) -> Result<Self, Error> {
+        let stop_requested = Arc::new(AtomicBool::new(false));
+
+        let thread_stop_requested = stop_requested.clone();
+        let thread_logger = logger.clone();
+
+        let join_handle = Some(ThreadBuilder::new().name("WebhookNotifier".into()).spawn(
+            move || {
+                thread_entry_point(
+                    thread_stop_requested,
+                    notifier,
+                    reserve_auditor_db,
+                    poll_interval,
+                    thread_logger,
+                )
+            },
+        )?);
+
+        Ok(Self {
+            stop_requested,
+            join_handle,
+            logger,
+        })
+    }
+
+    /// Stop the notifier thread.
+    pub fn stop(&mut self) {
+        log::info!(self.logger, "Stopping notifier thread...");
+        self.stop_requested.store(true, Ordering::Relaxed);
+        if let Some(join_handle) = self.join_handle.take() {
+            join_handle.join().expect("failed joining notifier thread");
+        }
+    }
+}
+
+impl Drop for NotifierThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn thread_entry_point(
+    stop_requested: Arc<AtomicBool>,
+    mut notifier: WebhookNotifier,
+    reserve_auditor_db: ReserveAuditorDb,
+    poll_interval: Duration,
+    logger: Logger,
+) {
+    log::info!(logger, "Notifier thread started");
+
+    loop {
+        if stop_requested.load(Ordering::Relaxed) {
+            log::info!(logger, "Notifier thread stop trigger received");
+            break;
+        }
+
+        let result = reserve_auditor_db
+            .get_conn()
+            .and_then(|conn| notifier.deliver_pending(&conn, Utc::now()));
+        match result {
+            Ok(0) => {}
+            Ok(num_delivered) => {
+                log::info!(logger, "Delivered {} anomaly notifications", num_delivered);
+            }
+            Err(err) => {
+                log::error!(logger, "Failed delivering anomaly notifications: {}", err);
+            }
+        }
+
+        sleep(poll_interval);
+    }
+}
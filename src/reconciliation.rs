@@ -0,0 +1,663 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Reconciliation of the MobileCoin supply of audited tokens against the
+//! reserves backing them.
+
+use crate::{
+    db::{
+        BlockAuditData, BlockBalance, BurnTxOut, Conn, GnosisSafeAuditConfig, GnosisSafeDeposit,
+        GnosisSafeWithdrawal, MintTx, ReserveDeposit, ReserveWithdrawal,
+    },
+    error::Error,
+    gnosis::{truncate_value, AuditedSafeConfig, AuditedToken, EthAddr, EthTxValue},
+};
+use chrono::{DateTime, Utc};
+use mc_blockchain_types::BlockIndex;
+use mc_transaction_core::TokenId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Reconciliation of a single audited token.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TokenReconciliation {
+    /// The token id.
+    pub token_id: TokenId,
+
+    /// Circulating supply on the MobileCoin blockchain (minted minus burned).
+    #[serde(with = "serde_with::rust::display_fromstr")]
+    pub supply: u128,
+
+    /// Net holdings of the Gnosis safes (deposits minus withdrawals),
+    /// truncated to the MobileCoin token precision.
+    #[serde(with = "serde_with::rust::display_fromstr")]
+    pub gnosis_safe_holdings: i128,
+
+    /// Net holdings of the Gnosis safes in raw Ethereum token units, before
+    /// any truncation.
+    #[serde(with = "serde_with::rust::display_fromstr")]
+    pub gnosis_safe_raw_holdings: i128,
+
+    /// Net sub-precision remainders dropped when truncating the Gnosis safe
+    /// deposits and withdrawals (deposits minus withdrawals), in raw Ethereum
+    /// token units. Together with the truncated amounts of the individual
+    /// deposits and withdrawals, this accounts for all of
+    /// `gnosis_safe_raw_holdings`.
+    #[serde(with = "serde_with::rust::display_fromstr")]
+    pub gnosis_safe_dust: i128,
+
+    /// Net holdings reported by the other reserve backends (deposits minus
+    /// withdrawals).
+    #[serde(with = "serde_with::rust::display_fromstr")]
+    pub other_reserve_holdings: i128,
+
+    /// Total reserve holdings minus supply. Positive when the token is
+    /// over-collateralized, negative when it is under-collateralized.
+    #[serde(with = "serde_with::rust::display_fromstr")]
+    pub difference: i128,
+
+    /// Number of mints not matched with a deposit.
+    pub num_unmatched_mints: u64,
+
+    /// Number of burns not matched with a withdrawal.
+    pub num_unmatched_burns: u64,
+
+    /// Number of deposits not matched with a mint.
+    pub num_unmatched_deposits: u64,
+
+    /// Number of withdrawals not matched with a burn.
+    pub num_unmatched_withdrawals: u64,
+}
+
+impl TokenReconciliation {
+    fn new(token_id: TokenId) -> Self {
+        Self {
+            token_id,
+            supply: 0,
+            gnosis_safe_holdings: 0,
+            gnosis_safe_raw_holdings: 0,
+            gnosis_safe_dust: 0,
+            other_reserve_holdings: 0,
+            difference: 0,
+            num_unmatched_mints: 0,
+            num_unmatched_burns: 0,
+            num_unmatched_deposits: 0,
+            num_unmatched_withdrawals: 0,
+        }
+    }
+}
+
+/// The point in time a [ReconciliationReport] is computed for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReconciliationPoint {
+    /// The end of a given MobileCoin block. The block timestamp is used to
+    /// select the reserve movements that happened by then, so the block must
+    /// have been synced with a watcher.
+    BlockIndex(BlockIndex),
+
+    /// A given time. The supply is taken from the last synced block with a
+    /// timestamp at or before it.
+    Timestamp(DateTime<Utc>),
+}
+
+/// Reconciliation of all audited tokens at a given point in time.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ReconciliationReport {
+    /// The block the supply was taken from, if any block was synced by then.
+    pub block_index: Option<u64>,
+
+    /// The timestamp of that block, if known.
+    pub block_timestamp: Option<DateTime<Utc>>,
+
+    /// Reserve movements up to (and including) this time are counted. Not set
+    /// for reports of the latest state, which count every known movement.
+    pub as_of: Option<DateTime<Utc>>,
+
+    /// Per-token reconciliation, ordered by token id.
+    pub tokens: Vec<TokenReconciliation>,
+}
+
+impl ReconciliationReport {
+    /// Compute the reconciliation report from the database, either for the
+    /// latest synced block or for a given point in time.
+    ///
+    /// A token is audited if it is listed by one of the stored Gnosis safe
+    /// configurations or if any other reserve backend reported a deposit or
+    /// withdrawal for it. For point-in-time reports, reserve movements reported
+    /// without a timestamp are not counted, and the unmatched counts only
+    /// include items that happened by then and are still unmatched.
+    pub fn compute(point: Option<ReconciliationPoint>, conn: &Conn) -> Result<Self, Error> {
+        let (block_audit_data, as_of) = match point {
+            None => (BlockAuditData::last_block_audit_data(conn)?, None),
+            Some(ReconciliationPoint::BlockIndex(block_index)) => {
+                let block_audit_data = BlockAuditData::get(conn, block_index)?;
+                let block_timestamp = block_audit_data
+                    .block_timestamp()
+                    .ok_or(Error::MissingBlockTimestamp(block_index))?;
+                (Some(block_audit_data), Some(block_timestamp))
+            }
+            Some(ReconciliationPoint::Timestamp(timestamp)) => (
+                BlockAuditData::last_block_at_or_before(conn, timestamp)?,
+                Some(timestamp),
+            ),
+        };
+        let last_block_index = block_audit_data
+            .as_ref()
+            .map(|block_audit_data| block_audit_data.block_index());
+        let balances = match last_block_index {
+            Some(block_index) => BlockBalance::get_balances_for_block(conn, block_index)?,
+            None => Default::default(),
+        };
+
+        // Whether a MobileCoin block is covered by the report.
+        let is_block_included =
+            |block_index: u64| last_block_index.map_or(false, |last| block_index <= last);
+        // Whether a reserve movement is covered by the report.
+        let is_movement_included = |timestamp: Option<DateTime<Utc>>| match as_of {
+            None => true,
+            Some(as_of) => timestamp.map_or(false, |timestamp| timestamp <= as_of),
+        };
+
+        let mut tokens = BTreeMap::<TokenId, TokenReconciliation>::new();
+
+        // Gnosis safe holdings.
+        let unaudited_gnosis_deposit_ids = GnosisSafeDeposit::find_unaudited_deposits(conn)?
+            .iter()
+            .filter_map(|deposit| deposit.id())
+            .collect::<HashSet<_>>();
+        let unaudited_gnosis_withdrawal_ids =
+            GnosisSafeWithdrawal::find_unaudited_withdrawals(conn)?
+                .iter()
+                .filter_map(|withdrawal| withdrawal.id())
+                .collect::<HashSet<_>>();
+
+        for stored_config in GnosisSafeAuditConfig::list(conn)? {
+            let safe_config = stored_config.decode()?;
+            let deposits = GnosisSafeDeposit::list_by_safe(
+                safe_config.chain_id,
+                &safe_config.safe_addr,
+                conn,
+            )?;
+            let withdrawals = GnosisSafeWithdrawal::list_by_safe(
+                safe_config.chain_id,
+                &safe_config.safe_addr,
+                conn,
+            )?;
+
+            for token in &safe_config.tokens {
+                let is_token_addr =
+                    |addr: &EthAddr| token.eth_token_contract_addrs.iter().any(|a| a == addr);
+                let token_deposits = deposits
+                    .iter()
+                    .filter(|deposit| {
+                        is_token_addr(deposit.token_addr())
+                            && is_movement_included(Some(deposit.execution_date()))
+                    })
+                    .collect::<Vec<_>>();
+                let token_withdrawals = withdrawals
+                    .iter()
+                    .filter(|withdrawal| {
+                        is_token_addr(withdrawal.token_addr())
+                            && is_movement_included(Some(withdrawal.execution_date()))
+                    })
+                    .collect::<Vec<_>>();
+
+                let deposited =
+                    sum_eth_tx_values(token_deposits.iter().map(|deposit| deposit.eth_tx_value()))?;
+                let withdrawn = sum_eth_tx_values(
+                    token_withdrawals
+                        .iter()
+                        .map(|withdrawal| withdrawal.eth_tx_value()),
+                )?;
+
+                let entry = tokens
+                    .entry(token.token_id)
+                    .or_insert_with(|| TokenReconciliation::new(token.token_id));
+                entry.gnosis_safe_holdings +=
+                    net_holdings(deposited, withdrawn, token, &safe_config)?;
+                entry.gnosis_safe_raw_holdings +=
+                    saturating_i128(deposited).saturating_sub(saturating_i128(withdrawn));
+                let deposited_dust = token_deposits.iter().fold(0i128, |total, deposit| {
+                    total.saturating_add(saturating_i128(deposit.truncation_remainder()))
+                });
+                let withdrawn_dust = token_withdrawals.iter().fold(0i128, |total, withdrawal| {
+                    total.saturating_add(saturating_i128(withdrawal.truncation_remainder()))
+                });
+                entry.gnosis_safe_dust = entry
+                    .gnosis_safe_dust
+                    .saturating_add(deposited_dust.saturating_sub(withdrawn_dust));
+                entry.num_unmatched_deposits += token_deposits
+                    .iter()
+                    .filter(|deposit| {
+                        deposit
+                            .id()
+                            .map_or(false, |id| unaudited_gnosis_deposit_ids.contains(&id))
+                    })
+                    .count() as u64;
+                entry.num_unmatched_withdrawals += token_withdrawals
+                    .iter()
+                    .filter(|withdrawal| {
+                        withdrawal
+                            .id()
+                            .map_or(false, |id| unaudited_gnosis_withdrawal_ids.contains(&id))
+                    })
+                    .count() as u64;
+            }
+        }
+
+        // Holdings reported by other reserve backends. Every token they report
+        // is audited, even if nothing was reported by the report point yet.
+        for deposit in ReserveDeposit::list(conn)? {
+            let entry = tokens
+                .entry(deposit.token_id())
+                .or_insert_with(|| TokenReconciliation::new(deposit.token_id()));
+            if is_movement_included(deposit.occurred_at()) {
+                entry.other_reserve_holdings = entry
+                    .other_reserve_holdings
+                    .saturating_add(saturating_i128(deposit.amount()));
+            }
+        }
+        for withdrawal in ReserveWithdrawal::list(conn)? {
+            let entry = tokens
+                .entry(withdrawal.token_id())
+                .or_insert_with(|| TokenReconciliation::new(withdrawal.token_id()));
+            if is_movement_included(withdrawal.occurred_at()) {
+                entry.other_reserve_holdings = entry
+                    .other_reserve_holdings
+                    .saturating_sub(saturating_i128(withdrawal.amount()));
+            }
+        }
+        for deposit in ReserveDeposit::find_unaudited_deposits(None, conn)? {
+            if !is_movement_included(deposit.occurred_at()) {
+                continue;
+            }
+            if let Some(entry) = tokens.get_mut(&deposit.token_id()) {
+                entry.num_unmatched_deposits += 1;
+            }
+        }
+        for withdrawal in ReserveWithdrawal::find_unaudited_withdrawals(None, conn)? {
+            if !is_movement_included(withdrawal.occurred_at()) {
+                continue;
+            }
+            if let Some(entry) = tokens.get_mut(&withdrawal.token_id()) {
+                entry.num_unmatched_withdrawals += 1;
+            }
+        }
+
+        // MobileCoin side.
+        for mint_tx in MintTx::find_unaudited_mint_txs(conn)? {
+            if !is_block_included(mint_tx.block_index()) {
+                continue;
+            }
+            if let Some(entry) = tokens.get_mut(&mint_tx.token_id()) {
+                entry.num_unmatched_mints += 1;
+            }
+        }
+        for burn_tx_out in BurnTxOut::find_unaudited_burn_tx_outs(conn)? {
+            if !is_block_included(burn_tx_out.block_index()) {
+                continue;
+            }
+            if let Some(entry) = tokens.get_mut(&burn_tx_out.token_id()) {
+                entry.num_unmatched_burns += 1;
+            }
+        }
+
+        for entry in tokens.values_mut() {
+            entry.supply = balances.get(&entry.token_id).copied().unwrap_or(0);
+            entry.difference = entry
+                .gnosis_safe_holdings
+                .saturating_add(entry.other_reserve_holdings)
+                .saturating_sub(saturating_i128(entry.supply));
+        }
+
+        Ok(Self {
+            block_index: last_block_index,
+            block_timestamp: block_audit_data
+                .as_ref()
+                .and_then(|block_audit_data| block_audit_data.block_timestamp()),
+            as_of,
+            tokens: tokens.into_values().collect(),
+        })
+    }
+}
+
+/// Sum raw Ethereum transaction values, failing on overflow.
+fn sum_eth_tx_values<'a>(values: impl Iterator<Item = &'a EthTxValue>) -> Result<u128, Error> {
+    values.try_fold(0u128, |total, value| {
+        total
+            .checked_add(u128::from(*value))
+            .ok_or_else(|| Error::Other("Overflow while summing Ethereum values".to_string()))
+    })
+}
+
+/// Net raw holdings of a safe for a token, truncated to the MobileCoin token
+/// precision.
+fn net_holdings(
+    deposited: u128,
+    withdrawn: u128,
+    token: &AuditedToken,
+    safe_config: &AuditedSafeConfig,
+) -> Result<i128, Error> {
+    Ok(if deposited >= withdrawn {
+        saturating_i128(
+            truncate_value(
+                EthTxValue(deposited - withdrawn),
+                token.decimals,
+                safe_config.token_decimals_max,
+            )?
+            .amount,
+        )
+    } else {
+        -saturating_i128(
+            truncate_value(
+                EthTxValue(withdrawn - deposited),
+                token.decimals,
+                safe_config.token_decimals_max,
+            )?
+            .amount,
+        )
+    })
+}
+
+/// Convert an amount to a signed value, saturating at [i128::MAX].
+fn saturating_i128(amount: u128) -> i128 {
+    i128::try_from(amount).unwrap_or(i128::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::{
+        create_and_insert_burn_tx_out, create_burn_tx_out, create_gnosis_safe_deposit,
+        create_gnosis_safe_withdrawal, insert_gnosis_deposit, insert_gnosis_withdrawal,
+        insert_mint_tx_from_deposit, test_gnosis_config, TestDbContext,
+    };
+    use chrono::Duration;
+    use mc_common::{
+        logger::{test_with_logger, Logger},
+        HashMap,
+    };
+    use mc_util_test_helper::{RngType, SeedableRng};
+
+    #[test_with_logger]
+    fn compute_reconciles_supply_with_reserves(logger: Logger) {
+        let mut rng = RngType::from_seed([1u8; 32]);
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let safe_config = &test_gnosis_config().safes[0];
+        let token = &safe_config.tokens[0];
+        let token_id = token.token_id;
+
+        // Nothing synced yet.
+        let report = ReconciliationReport::compute(None, &conn).unwrap();
+        assert_eq!(report.block_index, None);
+        assert!(report.tokens.is_empty());
+
+        GnosisSafeAuditConfig::set(safe_config, &conn).unwrap();
+
+        // Two deposits, only one of which is minted, and one unmatched
+        // withdrawal and burn.
+        let mut deposit1 = create_gnosis_safe_deposit(100, &mut rng);
+        insert_gnosis_deposit(&mut deposit1, &conn);
+        let mut deposit2 = create_gnosis_safe_deposit(200, &mut rng);
+        insert_gnosis_deposit(&mut deposit2, &conn);
+        let mut withdrawal = create_gnosis_safe_withdrawal(50, &mut rng);
+        insert_gnosis_withdrawal(&mut withdrawal, &conn);
+        create_and_insert_burn_tx_out(token_id, 10, &conn, &mut rng);
+        let _ = insert_mint_tx_from_deposit(&deposit1, &conn, &mut rng);
+
+        // Another backend reported a deposit for the same token.
+        let mut reserve_deposit = ReserveDeposit::new("bank", "tx1", token_id, 7, "0102", None);
+        reserve_deposit.insert(&conn).unwrap();
+
+        BlockAuditData::new(5, None).set(&conn).unwrap();
+        BlockBalance::set_balances_for_block(&conn, 5, &HashMap::from_iter([(token_id, 1000)]))
+            .unwrap();
+
+        let report = ReconciliationReport::compute(None, &conn).unwrap();
+        assert_eq!(report.block_index, Some(5));
+        assert_eq!(report.tokens.len(), safe_config.tokens.len());
+
+        let expected_raw_holdings =
+            deposit1.eth_tx_value().0 + deposit2.eth_tx_value().0 - withdrawal.eth_tx_value().0;
+        let expected_holdings = truncate_value(
+            EthTxValue(expected_raw_holdings),
+            token.decimals,
+            safe_config.token_decimals_max,
+        )
+        .unwrap()
+        .amount as i128;
+
+        let reconciliation = report
+            .tokens
+            .iter()
+            .find(|reconciliation| reconciliation.token_id == token_id)
+            .unwrap();
+        assert_eq!(
+            reconciliation,
+            &TokenReconciliation {
+                token_id,
+                supply: 1000,
+                gnosis_safe_holdings: expected_holdings,
+                gnosis_safe_raw_holdings: expected_raw_holdings as i128,
+                gnosis_safe_dust: 0,
+                other_reserve_holdings: 7,
+                difference: expected_holdings + 7 - 1000,
+                num_unmatched_mints: 1,
+                num_unmatched_burns: 1,
+                num_unmatched_deposits: 3,
+                num_unmatched_withdrawals: 1,
+            }
+        );
+
+        // Unused tokens have nothing to reconcile.
+        let other = report
+            .tokens
+            .iter()
+            .find(|reconciliation| reconciliation.token_id == safe_config.tokens[1].token_id)
+            .unwrap();
+        assert_eq!(other, &TokenReconciliation::new(other.token_id));
+    }
+
+    #[test_with_logger]
+    fn compute_accounts_for_truncation_dust(logger: Logger) {
+        let mut rng = RngType::from_seed([1u8; 32]);
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+
+        // The Ethereum token has 3 more decimals than the MobileCoin one.
+        let mut safe_config = test_gnosis_config().safes[0].clone();
+        safe_config.tokens[0].decimals = safe_config.token_decimals_max + 3;
+        let token = safe_config.tokens[0].clone();
+        GnosisSafeAuditConfig::set(&safe_config, &conn).unwrap();
+
+        let truncated = |raw_value: u128| {
+            truncate_value(
+                EthTxValue(raw_value),
+                token.decimals,
+                safe_config.token_decimals_max,
+            )
+            .unwrap()
+        };
+        let mut deposits = Vec::new();
+        for raw_value in [100_123, 200_999, 5_000] {
+            let template = create_gnosis_safe_deposit(0, &mut rng);
+            let truncated_value = truncated(raw_value);
+            let mut deposit = GnosisSafeDeposit::new(
+                None,
+                template.chain_id(),
+                *template.eth_tx_hash(),
+                template.execution_date(),
+                EthTxValue(raw_value),
+                template.eth_block_number(),
+                template.safe_addr().clone(),
+                template.token_addr().clone(),
+                template.from_addr().clone(),
+                truncated_value.amount,
+                truncated_value.remainder,
+            );
+            insert_gnosis_deposit(&mut deposit, &conn);
+            deposits.push(deposit);
+        }
+        let template = create_gnosis_safe_withdrawal(0, &mut rng);
+        let truncated_value = truncated(50_500);
+        let mut withdrawal = GnosisSafeWithdrawal::new(
+            None,
+            template.chain_id(),
+            *template.eth_tx_hash(),
+            template.execution_date(),
+            EthTxValue(50_500),
+            template.eth_block_number(),
+            template.safe_addr().clone(),
+            template.token_addr().clone(),
+            template.to_addr().clone(),
+            truncated_value.amount,
+            truncated_value.remainder,
+            template.mc_tx_out_public_key_hex().to_string(),
+        );
+        insert_gnosis_withdrawal(&mut withdrawal, &conn);
+
+        let report = ReconciliationReport::compute(None, &conn).unwrap();
+        let reconciliation = report
+            .tokens
+            .iter()
+            .find(|reconciliation| reconciliation.token_id == token.token_id)
+            .unwrap();
+
+        // The dropped remainders are reported separately, and every raw unit
+        // is accounted for by either a truncated amount or the dust.
+        assert_eq!(reconciliation.gnosis_safe_raw_holdings, 255_622);
+        assert_eq!(reconciliation.gnosis_safe_dust, 123 + 999 - 500);
+        let truncated_amounts = deposits
+            .iter()
+            .map(|deposit| deposit.amount() as i128)
+            .sum::<i128>()
+            - withdrawal.amount() as i128;
+        assert_eq!(
+            truncated_amounts * 1000 + reconciliation.gnosis_safe_dust,
+            reconciliation.gnosis_safe_raw_holdings
+        );
+
+        // The truncated holdings are still those of the net raw holdings.
+        assert_eq!(reconciliation.gnosis_safe_holdings, 255);
+    }
+
+    #[test_with_logger]
+    fn compute_at_point_in_time(logger: Logger) {
+        let mut rng = RngType::from_seed([1u8; 32]);
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger);
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id = TokenId::from(1);
+        let now = Utc::now();
+
+        // Three blocks, the last of which was synced without a watcher.
+        for (block_index, block_timestamp, balance) in [
+            (1, Some(now - Duration::hours(2)), 100),
+            (2, Some(now - Duration::hours(1)), 300),
+            (3, None, 550),
+        ] {
+            BlockAuditData::new(block_index, block_timestamp)
+                .set(&conn)
+                .unwrap();
+            BlockBalance::set_balances_for_block(
+                &conn,
+                block_index,
+                &HashMap::from_iter([(token_id, balance)]),
+            )
+            .unwrap();
+        }
+
+        for (external_ref, amount, occurred_at) in [
+            ("tx1", 100, Some(now - Duration::hours(3))),
+            ("tx2", 200, Some(now - Duration::minutes(90))),
+            ("tx3", 250, Some(now - Duration::minutes(30))),
+            ("tx4", 1000, None),
+        ] {
+            ReserveDeposit::new(
+                "bank",
+                external_ref,
+                token_id,
+                amount,
+                external_ref,
+                occurred_at,
+            )
+            .insert(&conn)
+            .unwrap();
+        }
+
+        // A burn in the last block.
+        let tx_out = create_burn_tx_out(token_id, 10, &mut rng).decode().unwrap();
+        BurnTxOut::from_core_tx_out(3, None, &tx_out)
+            .unwrap()
+            .insert(&conn)
+            .unwrap();
+
+        let get_token = |report: &ReconciliationReport| {
+            report
+                .tokens
+                .iter()
+                .find(|reconciliation| reconciliation.token_id == token_id)
+                .cloned()
+                .unwrap()
+        };
+
+        // At the end of block 1.
+        let report =
+            ReconciliationReport::compute(Some(ReconciliationPoint::BlockIndex(1)), &conn).unwrap();
+        assert_eq!(report.block_index, Some(1));
+        assert_eq!(report.as_of, Some(now - Duration::hours(2)));
+        let reconciliation = get_token(&report);
+        assert_eq!(reconciliation.supply, 100);
+        assert_eq!(reconciliation.other_reserve_holdings, 100);
+        assert_eq!(reconciliation.difference, 0);
+        assert_eq!(reconciliation.num_unmatched_deposits, 1);
+        assert_eq!(reconciliation.num_unmatched_burns, 0);
+
+        // Between blocks 2 and 3.
+        let as_of = now - Duration::minutes(45);
+        let report =
+            ReconciliationReport::compute(Some(ReconciliationPoint::Timestamp(as_of)), &conn)
+                .unwrap();
+        assert_eq!(report.block_index, Some(2));
+        assert_eq!(report.block_timestamp, Some(now - Duration::hours(1)));
+        assert_eq!(report.as_of, Some(as_of));
+        let reconciliation = get_token(&report);
+        assert_eq!(reconciliation.supply, 300);
+        assert_eq!(reconciliation.other_reserve_holdings, 300);
+        assert_eq!(reconciliation.num_unmatched_deposits, 2);
+
+        // Before anything was synced.
+        let report = ReconciliationReport::compute(
+            Some(ReconciliationPoint::Timestamp(now - Duration::hours(4))),
+            &conn,
+        )
+        .unwrap();
+        assert_eq!(report.block_index, None);
+        assert_eq!(get_token(&report), TokenReconciliation::new(token_id));
+
+        // Block 3 has no timestamp to align the reserves with.
+        assert!(matches!(
+            ReconciliationReport::compute(Some(ReconciliationPoint::BlockIndex(3)), &conn),
+            Err(Error::MissingBlockTimestamp(3))
+        ));
+
+        // Unsynced blocks are not found.
+        assert!(matches!(
+            ReconciliationReport::compute(Some(ReconciliationPoint::BlockIndex(4)), &conn),
+            Err(Error::NotFound)
+        ));
+
+        // The latest report counts everything.
+        let report = ReconciliationReport::compute(None, &conn).unwrap();
+        assert_eq!(report.block_index, Some(3));
+        assert_eq!(report.as_of, None);
+        let reconciliation = get_token(&report);
+        assert_eq!(reconciliation.supply, 550);
+        assert_eq!(reconciliation.other_reserve_holdings, 1550);
+        assert_eq!(reconciliation.difference, 1000);
+        assert_eq!(reconciliation.num_unmatched_deposits, 4);
+        assert_eq!(reconciliation.num_unmatched_burns, 1);
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Configuration for reserve backends other than Gnosis safes.
+
+use crate::error::Error;
+use mc_transaction_core::TokenId;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Configuration for a reserve backed by a custodian that provides statements
+/// in CSV format (e.g. a bank).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CsvStatementConfig {
+    /// A name uniquely identifying this reserve. It is recorded alongside
+    /// every deposit and withdrawal imported from the statement.
+    pub name: String,
+
+    /// Path to the statement file. The file is expected to be appended to
+    /// as new entries become available.
+    pub path: PathBuf,
+
+    /// The MobileCoin tokens whose reserve is held by this custodian.
+    pub token_ids: Vec<TokenId>,
+}
+
+/// Configuration for reserve backends other than Gnosis safes.
+/// Unknown fields are rejected, so that sections which moved to the governance
+/// and unmatched alert configs are not silently ignored.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReserveConfig {
+    /// Reserves whose activity is imported from CSV statements.
+    #[serde(default)]
+    pub csv_statements: Vec<CsvStatementConfig>,
+}
+
+impl ReserveConfig {
+    /// Load configuration data from a toml/json file.
+    pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        // Read configuration file.
+        let data = fs::read_to_string(path)?;
+
+        // Parse configuration file.
+        let config: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            None => Err(Error::InvalidReserveConfig(
+                "cannot figure out file extension".to_string(),
+            )),
+            Some("toml") => {
+                toml::from_str(&data).map_err(|err| Error::InvalidReserveConfig(err.to_string()))
+            }
+            Some("json") => serde_json::from_str(&data)
+                .map_err(|err| Error::InvalidReserveConfig(err.to_string())),
+            Some(ext) => Err(Error::InvalidReserveConfig(format!(
+                "unrecognized file extension {ext}"
+            ))),
+        }?;
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_config() {
+        let input_toml = r#"
+            [[csv_statements]]
+            name = "bank"
+            path = "/var/lib/reserve/bank.csv"
+            token_ids = [2, 3]
+        "#;
+        let input_json = r#"{
+            "csv_statements": [
+                {
+                    "name": "bank",
+                    "path": "/var/lib/reserve/bank.csv",
+                    "token_ids": [2, 3]
+                }
+            ]
+        }"#;
+
+        let cfg1: ReserveConfig = toml::from_str(input_toml).expect("failed parsing toml");
+        let cfg2: ReserveConfig = serde_json::from_str(input_json).expect("failed parsing json");
+
+        assert_eq!(cfg1, cfg2);
+        assert_eq!(
+            cfg1,
+            ReserveConfig {
+                csv_statements: vec![CsvStatementConfig {
+                    name: "bank".to_string(),
+                    path: PathBuf::from("/var/lib/reserve/bank.csv"),
+                    token_ids: vec![TokenId::from(2), TokenId::from(3)],
+                }],
+            }
+        );
+
+        assert_eq!(
+            serde_json::from_str::<ReserveConfig>("{}").unwrap(),
+            ReserveConfig::default()
+        );
+        assert!(serde_json::from_str::<ReserveConfig>(r#"{"mint_governors": []}"#).is_err());
+    }
+}
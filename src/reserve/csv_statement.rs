@@ -0,0 +1,595 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Reserve backend importing deposits and withdrawals from CSV statements.
+//!
+//! The statement starts with the header
+//! `type,reference,token_id,amount,mc_reference,timestamp`, followed by a
+//! line for each entry:
+//! - `type` is either `deposit` or `withdrawal`.
+//! - `reference` is the custodian's reference for the entry, and must be
+//!   unique within the statement.
+//! - `amount` is in the MobileCoin token's precision. Amounts that do not
+//!   fit in a MobileCoin amount are imported, but flagged as anomalies.
+//! - `mc_reference` is the hex-encoded nonce of the expected MintTx for
+//!   deposits, and the hex-encoded public key of the burn TxOut for
+//!   withdrawals.
+//! - `timestamp` is an optional RFC 3339 timestamp.
+//!
+//! Fields are not quoted, so they cannot contain commas.
+
+use super::{CsvStatementConfig, ReserveBackend};
+use crate::{
+    anomaly::{AnomalyEvent, AnomalyKind},
+    db::{
+        AuditedReserveBurn, AuditedReserveMint, BurnTxOut, Conn, Counters, MintTx,
+        ReserveAuditorDb, ReserveDeposit, ReserveWithdrawal,
+    },
+    error::Error,
+};
+use chrono::{DateTime, Utc};
+use mc_common::logger::{log, Logger};
+use mc_transaction_core::TokenId;
+use std::fs;
+
+/// The expected statement header.
+const STATEMENT_HEADER: &[&str] = &[
+    "type",
+    "reference",
+    "token_id",
+    "amount",
+    "mc_reference",
+    "timestamp",
+];
+
+/// The type of a statement entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StatementEntryKind {
+    /// A deposit into the reserve.
+    Deposit,
+
+    /// A withdrawal from the reserve.
+    Withdrawal,
+}
+
+/// A single entry of a CSV statement.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatementEntry {
+    /// The type of the entry.
+    pub kind: StatementEntryKind,
+
+    /// The custodian's reference for the entry.
+    pub reference: String,
+
+    /// The MobileCoin token id.
+    pub token_id: TokenId,
+
+    /// The amount, in the MobileCoin token's precision. This is not
+    /// guaranteed to fit in a MobileCoin (u64) amount.
+    pub amount: u128,
+
+    /// The hex-encoded MintTx nonce (for deposits) or burn TxOut public key
+    /// (for withdrawals).
+    pub mc_reference: String,
+
+    /// When the custodian recorded the entry, if known.
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl StatementEntry {
+    /// Parse a CSV statement. A malformed line fails the entire statement,
+    /// so that a partially broken statement is never partially imported.
+    pub fn parse_statement(data: &str) -> Result<Vec<Self>, Error> {
+        let mut lines = data
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        match lines.next() {
+            Some((_, header))
+                if header
+                    .split(',')
+                    .map(str::trim)
+                    .eq(STATEMENT_HEADER.iter().copied()) => {}
+            Some((line_number, _)) => {
+                return Err(Error::InvalidReserveStatement(
+                    line_number,
+                    format!("expected header {}", STATEMENT_HEADER.join(",")),
+                ))
+            }
+            None => return Ok(vec![]),
+        }
+
+        lines
+            .map(|(line_number, line)| {
+                Self::parse_line(line)
+                    .map_err(|err| Error::InvalidReserveStatement(line_number, err))
+            })
+            .collect()
+    }
+
+    fn parse_line(line: &str) -> Result<Self, String> {
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+        if fields.len() != STATEMENT_HEADER.len() {
+            return Err(format!(
+                "expected {} fields, got {}",
+                STATEMENT_HEADER.len(),
+                fields.len()
+            ));
+        }
+
+        let kind = match fields[0] {
+            "deposit" => StatementEntryKind::Deposit,
+            "withdrawal" => StatementEntryKind::Withdrawal,
+            other => return Err(format!("unknown entry type {other}")),
+        };
+
+        let reference = fields[1].to_string();
+        if reference.is_empty() {
+            return Err("empty reference".to_string());
+        }
+
+        let token_id = fields[2]
+            .parse::<u64>()
+            .map(TokenId::from)
+            .map_err(|err| format!("invalid token id: {err}"))?;
+
+        let amount = fields[3]
+            .parse::<u128>()
+            .map_err(|err| format!("invalid amount: {err}"))?;
+
+        let mc_reference = fields[4].to_lowercase();
+        let mc_reference_bytes =
+            hex::decode(&mc_reference).map_err(|err| format!("invalid mc_reference: {err}"))?;
+        if mc_reference_bytes.is_empty() {
+            return Err("empty mc_reference".to_string());
+        }
+        if kind == StatementEntryKind::Withdrawal && mc_reference_bytes.len() != 32 {
+            return Err(format!(
+                "invalid mc_reference: expected a 32 byte public key, got {} bytes",
+                mc_reference_bytes.len()
+            ));
+        }
+
+        let timestamp = if fields[5].is_empty() {
+            None
+        } else {
+            Some(
+                DateTime::parse_from_rfc3339(fields[5])
+                    .map_err(|err| format!("invalid timestamp: {err}"))?
+                    .with_timezone(&Utc),
+            )
+        };
+
+        Ok(Self {
+            kind,
+            reference,
+            token_id,
+            amount,
+            mc_reference,
+            timestamp,
+        })
+    }
+}
+
+/// Reserve backend importing deposits and withdrawals from a CSV statement.
+pub struct CsvStatementBackend {
+    config: CsvStatementConfig,
+    reserve_auditor_db: ReserveAuditorDb,
+    logger: Logger,
+}
+
+impl CsvStatementBackend {
+    /// Create a new [CsvStatementBackend].
+    pub fn new(
+        config: CsvStatementConfig,
+        reserve_auditor_db: ReserveAuditorDb,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            config,
+            reserve_auditor_db,
+            logger,
+        }
+    }
+
+    /// Read the statement and import any entries that were not imported yet.
+    /// Returns the number of newly imported entries.
+    pub fn import_statement(&self) -> Result<usize, Error> {
+        let data = fs::read_to_string(&self.config.path)?;
+        let entries = StatementEntry::parse_statement(&data)?;
+
+        let conn = self.reserve_auditor_db.get_conn()?;
+        let mut num_imported = 0;
+        for entry in entries {
+            if !self.config.token_ids.contains(&entry.token_id) {
+                log::error!(
+                    self.logger,
+                    "Statement {} entry {} is for token {} which is not held by this reserve",
+                    self.config.name,
+                    entry.reference,
+                    entry.token_id,
+                );
+                continue;
+            }
+
+            // SQLite3 does not like concurrent writes. Since we are going to be writing to
+            // the database, ensure we are the only writers.
+            let imported = conn.exclusive_transaction(|| self.import_entry(&entry, &conn))?;
+            if imported {
+                num_imported += 1;
+            }
+        }
+
+        Ok(num_imported)
+    }
+
+    /// Store a single statement entry and try to match it. Returns false if
+    /// the entry was already imported.
+    fn import_entry(&self, entry: &StatementEntry, conn: &Conn) -> Result<bool, Error> {
+        match entry.kind {
+            StatementEntryKind::Deposit => {
+                let mut deposit = ReserveDeposit::new(
+                    &self.config.name,
+                    &entry.reference,
+                    entry.token_id,
+                    entry.amount,
+                    &entry.mc_reference,
+                    entry.timestamp,
+                );
+                match deposit.insert(conn) {
+                    Ok(()) => {}
+                    Err(Error::AlreadyExists(_)) => return Ok(false),
+                    Err(err) => return Err(err),
+                }
+
+                // The deposit is stored in full so that the reserve is still accounted for,
+                // but it can never be minted.
+                if deposit.mc_amount().is_none() {
+                    log::crit!(
+                        self.logger,
+                        "{} deposit {} amount {} does not fit in a MobileCoin amount",
+                        self.config.name,
+                        entry.reference,
+                        deposit.amount(),
+                    );
+                    Counters::record_anomaly(
+                        &AnomalyEvent::new(
+                            AnomalyKind::OversizedReserveAmount,
+                            format!(
+                                "Deposit amount {} does not fit in a MobileCoin amount",
+                                deposit.amount()
+                            ),
+                        )
+                        .with_reserve_deposit(&deposit),
+                        conn,
+                    )?;
+                }
+
+                match AuditedReserveMint::try_match_deposit_with_mint(&deposit, conn) {
+                    Ok(mint_tx) => {
+                        log::info!(
+                            self.logger,
+                            "{} deposit {} matched MintTx nonce={}",
+                            self.config.name,
+                            entry.reference,
+                            mint_tx.nonce_hex(),
+                        );
+                    }
+                    Err(Error::NotFound) => {}
+                    Err(err) => {
+                        log::error!(
+                            self.logger,
+                            "{} deposit {} failed matching MintTx: {}",
+                            self.config.name,
+                            entry.reference,
+                            err
+                        );
+                    }
+                }
+            }
+
+            StatementEntryKind::Withdrawal => {
+                let mut withdrawal = ReserveWithdrawal::new(
+                    &self.config.name,
+                    &entry.reference,
+                    entry.token_id,
+                    entry.amount,
+                    &entry.mc_reference,
+                    entry.timestamp,
+                );
+                match withdrawal.insert(conn) {
+                    Ok(()) => {}
+                    Err(Error::AlreadyExists(_)) => return Ok(false),
+                    Err(err) => return Err(err),
+                }
+
+                if withdrawal.mc_amount().is_none() {
+                    log::crit!(
+                        self.logger,
+                        "{} withdrawal {} amount {} does not fit in a MobileCoin amount",
+                        self.config.name,
+                        entry.reference,
+                        withdrawal.amount(),
+                    );
+                    Counters::record_anomaly(
+                        &AnomalyEvent::new(
+                            AnomalyKind::OversizedReserveAmount,
+                            format!(
+                                "Withdrawal amount {} does not fit in a MobileCoin amount",
+                                withdrawal.amount()
+                            ),
+                        )
+                        .with_reserve_withdrawal(&withdrawal),
+                        conn,
+                    )?;
+                }
+
+                match AuditedReserveBurn::try_match_withdrawal_with_burn(&withdrawal, conn) {
+                    Ok(burn_tx_out) => {
+                        log::info!(
+                            self.logger,
+                            "{} withdrawal {} matched BurnTxOut pub_key={}",
+                            self.config.name,
+                            entry.reference,
+                            burn_tx_out.public_key_hex(),
+                        );
+                    }
+                    Err(Error::NotFound) => {}
+                    Err(err) => {
+                        log::error!(
+                            self.logger,
+                            "{} withdrawal {} failed matching BurnTxOut: {}",
+                            self.config.name,
+                            entry.reference,
+                            err
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl ReserveBackend for CsvStatementBackend {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn token_ids(&self) -> Vec<TokenId> {
+        self.config.token_ids.clone()
+    }
+
+    fn poll(&self) {
+        match self.import_statement() {
+            Ok(0) => {}
+            Ok(num_imported) => {
+                log::info!(
+                    self.logger,
+                    "Imported {} entries from statement {}",
+                    num_imported,
+                    self.config.name
+                );
+            }
+            Err(err) => {
+                log::error!(
+                    self.logger,
+                    "Failed importing statement {}: {}",
+                    self.config.name,
+                    err
+                );
+            }
+        }
+    }
+
+    fn audit_mint(&self, mint_tx: &MintTx, conn: &Conn) -> Result<String, Error> {
+        let deposit =
+            AuditedReserveMint::try_match_mint_with_deposit(mint_tx, &self.config.name, conn)?;
+        Ok(format!("ref={}", deposit.external_ref()))
+    }
+
+    fn audit_burn(&self, burn_tx_out: &BurnTxOut, conn: &Conn) -> Result<String, Error> {
+        let withdrawal = AuditedReserveBurn::try_match_burn_with_withdrawal(
+            burn_tx_out,
+            &self.config.name,
+            conn,
+        )?;
+        Ok(format!("ref={}", withdrawal.external_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::{create_and_insert_burn_tx_out, TestDbContext};
+    use chrono::Utc;
+    use mc_common::logger::{test_with_logger, Logger};
+    use mc_transaction_core_test_utils::{create_mint_config_tx_and_signers, create_mint_tx};
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn parse_statement_works() {
+        let public_key_hex = "11".repeat(32);
+        let data = format!(
+            "type,reference,token_id,amount,mc_reference,timestamp\n\
+             deposit,tx1,2,100,0102AB,2022-06-01T12:00:00Z\n\
+             \n\
+             withdrawal,tx2,2,50,{public_key_hex},\n"
+        );
+
+        assert_eq!(
+            StatementEntry::parse_statement(&data).unwrap(),
+            vec![
+                StatementEntry {
+                    kind: StatementEntryKind::Deposit,
+                    reference: "tx1".to_string(),
+                    token_id: TokenId::from(2),
+                    amount: 100,
+                    mc_reference: "0102ab".to_string(),
+                    timestamp: Some(
+                        DateTime::parse_from_rfc3339("2022-06-01T12:00:00Z")
+                            .unwrap()
+                            .with_timezone(&Utc)
+                    ),
+                },
+                StatementEntry {
+                    kind: StatementEntryKind::Withdrawal,
+                    reference: "tx2".to_string(),
+                    token_id: TokenId::from(2),
+                    amount: 50,
+                    mc_reference: public_key_hex,
+                    timestamp: None,
+                },
+            ]
+        );
+
+        // Empty statements are fine.
+        assert_eq!(StatementEntry::parse_statement("").unwrap(), vec![]);
+
+        // Bad header.
+        assert!(matches!(
+            StatementEntry::parse_statement("type,reference\ndeposit,tx1"),
+            Err(Error::InvalidReserveStatement(1, _))
+        ));
+
+        // Bad lines fail the entire statement.
+        for line in [
+            "transfer,tx1,2,100,0102,",
+            "deposit,,2,100,0102,",
+            "deposit,tx1,two,100,0102,",
+            "deposit,tx1,2,-5,0102,",
+            "deposit,tx1,2,100,xyz,",
+            "deposit,tx1,2,100,,",
+            "withdrawal,tx1,2,100,0102,",
+            "deposit,tx1,2,100,0102,yesterday",
+            "deposit,tx1,2,100,0102",
+        ] {
+            let data = format!(
+                "type,reference,token_id,amount,mc_reference,timestamp\n\
+                 deposit,tx0,2,100,0101,\n\
+                 {line}\n"
+            );
+            assert!(
+                matches!(
+                    StatementEntry::parse_statement(&data),
+                    Err(Error::InvalidReserveStatement(3, _))
+                ),
+                "{line}"
+            );
+        }
+    }
+
+    #[test_with_logger]
+    fn import_statement_matches_mints_and_burns(logger: Logger) {
+        let mut rng = mc_util_test_helper::get_seeded_rng();
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id = TokenId::from(2);
+
+        // A mint and a burn that are already on the ledger.
+        let (_mint_config_tx, signers) = create_mint_config_tx_and_signers(token_id, &mut rng);
+        let mint_tx = create_mint_tx(token_id, &signers, 100, &mut rng);
+        let sql_mint_tx =
+            MintTx::insert_from_core_mint_tx(0, Some(Utc::now()), None, &mint_tx, &conn).unwrap();
+        let burn_tx_out = create_and_insert_burn_tx_out(token_id, 50, &conn, &mut rng);
+
+        let mut statement = NamedTempFile::new().unwrap();
+        writeln!(
+            statement,
+            "type,reference,token_id,amount,mc_reference,timestamp\n\
+             deposit,tx1,2,100,{},",
+            sql_mint_tx.nonce_hex()
+        )
+        .unwrap();
+
+        let backend = CsvStatementBackend::new(
+            CsvStatementConfig {
+                name: "bank".to_string(),
+                path: statement.path().to_path_buf(),
+                token_ids: vec![token_id],
+            },
+            reserve_auditor_db.clone(),
+            logger,
+        );
+
+        // The deposit gets imported and matched with the mint.
+        assert_eq!(backend.import_statement().unwrap(), 1);
+        assert!(MintTx::find_unaudited_mint_txs(&conn).unwrap().is_empty());
+        assert!(ReserveDeposit::find_unaudited_deposits(Some("bank"), &conn)
+            .unwrap()
+            .is_empty());
+
+        // Re-reading the statement does not import anything new.
+        assert_eq!(backend.import_statement().unwrap(), 0);
+
+        // Entries appended to the statement are imported on the next read. Entries
+        // for tokens not held by the reserve are skipped.
+        writeln!(
+            statement,
+            "withdrawal,tx2,2,50,{},2022-06-01T12:00:00Z\n\
+             deposit,tx3,3,100,0102,",
+            burn_tx_out.public_key_hex()
+        )
+        .unwrap();
+        assert_eq!(backend.import_statement().unwrap(), 1);
+        assert!(BurnTxOut::find_unaudited_burn_tx_outs(&conn)
+            .unwrap()
+            .is_empty());
+
+        // The ledger side of the match is idempotent as well.
+        assert!(matches!(
+            backend.audit_mint(&sql_mint_tx, &conn),
+            Err(Error::AlreadyExists(_))
+        ));
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_mismatching_mints_and_deposits(),
+            0
+        );
+    }
+
+    #[test_with_logger]
+    fn import_statement_flags_oversized_amounts(logger: Logger) {
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let token_id = TokenId::from(2);
+        let oversized_amount = u64::MAX as u128 + 1;
+
+        let mut statement = NamedTempFile::new().unwrap();
+        writeln!(
+            statement,
+            "type,reference,token_id,amount,mc_reference,timestamp\n\
+             deposit,tx1,2,{oversized_amount},0102,",
+        )
+        .unwrap();
+
+        let backend = CsvStatementBackend::new(
+            CsvStatementConfig {
+                name: "bank".to_string(),
+                path: statement.path().to_path_buf(),
+                token_ids: vec![token_id],
+            },
+            reserve_auditor_db.clone(),
+            logger,
+        );
+
+        // The deposit is imported in full, but flagged since it can never be minted.
+        assert_eq!(backend.import_statement().unwrap(), 1);
+        let deposits = ReserveDeposit::find_unaudited_deposits(Some("bank"), &conn).unwrap();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].amount(), oversized_amount);
+        assert_eq!(deposits[0].mc_amount(), None);
+        assert_eq!(
+            Counters::get(&conn)
+                .unwrap()
+                .num_oversized_reserve_amounts(),
+            1
+        );
+    }
+}
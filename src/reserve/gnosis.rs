@@ -0,0 +1,110 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Reserve backend for reserves held in Gnosis safes.
+
+use super::ReserveBackend;
+use crate::{
+    db::{AuditedBurn, AuditedMint, BurnTxOut, Conn, ManualMatch, MintTx, ReserveAuditorDb},
+    error::Error,
+    gnosis::{GnosisSafeConfig, GnosisSync},
+};
+use mc_common::logger::Logger;
+use mc_transaction_core::TokenId;
+
+/// Reserve backend for reserves held in Gnosis safes.
+pub struct GnosisReserveBackend {
+    config: GnosisSafeConfig,
+    syncs: Vec<GnosisSync>,
+}
+
+impl GnosisReserveBackend {
+    /// The name of this backend.
+    pub const NAME: &'static str = "gnosis";
+
+    /// Create a new [GnosisReserveBackend] for the safes in a given
+    /// configuration.
+    pub fn new(
+        config: GnosisSafeConfig,
+        reserve_auditor_db: ReserveAuditorDb,
+        logger: Logger,
+    ) -> Result<Self, Error> {
+        let syncs = config
+            .safes
+            .iter()
+            .map(|safe_config| {
+                GnosisSync::new(
+                    safe_config.clone(),
+                    reserve_auditor_db.clone(),
+                    logger.clone(),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { config, syncs })
+    }
+}
+
+impl ReserveBackend for GnosisReserveBackend {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn token_ids(&self) -> Vec<TokenId> {
+        let mut token_ids = self
+            .config
+            .safes
+            .iter()
+            .flat_map(|safe| safe.tokens.iter().map(|token| token.token_id))
+            .collect::<Vec<_>>();
+        token_ids.sort();
+        token_ids.dedup();
+        token_ids
+    }
+
+    fn poll(&self) {
+        for sync in &self.syncs {
+            sync.poll();
+        }
+    }
+
+    fn audit_mint(&self, mint_tx: &MintTx, conn: &Conn) -> Result<String, Error> {
+        // A MintTx that was manually matched before rewinding gets the same match
+        // once it is re-synced.
+        let manual_matches = ManualMatch::reapply_for_mint_tx(mint_tx, conn)?;
+        if !manual_matches.is_empty() {
+            return Ok(describe_manual_matches(&manual_matches));
+        }
+
+        let deposit = AuditedMint::try_match_mint_with_deposit(mint_tx, &self.config, conn)?;
+        Ok(format!("eth_tx_hash={}", deposit.eth_tx_hash()))
+    }
+
+    fn audit_burn(&self, burn_tx_out: &BurnTxOut, conn: &Conn) -> Result<String, Error> {
+        let manual_matches = ManualMatch::reapply_for_burn_tx_out(burn_tx_out, conn)?;
+        if !manual_matches.is_empty() {
+            return Ok(describe_manual_matches(&manual_matches));
+        }
+
+        let withdrawals =
+            AuditedBurn::try_match_burn_with_withdrawal(burn_tx_out, &self.config, conn)?;
+        Ok(withdrawals
+            .iter()
+            .map(|withdrawal| format!("eth_tx_hash={}", withdrawal.eth_tx_hash()))
+            .collect::<Vec<_>>()
+            .join(", "))
+    }
+}
+
+fn describe_manual_matches(manual_matches: &[ManualMatch]) -> String {
+    manual_matches
+        .iter()
+        .map(|manual_match| {
+            format!(
+                "eth_tx_hash={} (manual match id={})",
+                manual_match.eth_tx_hash(),
+                manual_match.id().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
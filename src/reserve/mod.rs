@@ -0,0 +1,271 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Reserve backends.
+//!
+//! A reserve backend is a source of deposits into and withdrawals from the
+//! reserve that backs one or more MobileCoin tokens. Each token is backed by
+//! at most one backend, which is responsible for matching the token's mints
+//! and burns against the deposits and withdrawals it knows about.
+
+mod config;
+mod csv_statement;
+mod gnosis;
+mod sync_thread;
+
+pub use self::{
+    config::{CsvStatementConfig, ReserveConfig},
+    csv_statement::{CsvStatementBackend, StatementEntry, StatementEntryKind},
+    gnosis::GnosisReserveBackend,
+    sync_thread::ReserveSyncThread,
+};
+
+use crate::{
+    db::{BurnTxOut, Conn, MintTx, SyncBlockData},
+    error::Error,
+};
+use mc_common::{
+    logger::{log, Logger},
+    HashMap,
+};
+use mc_transaction_core::TokenId;
+use std::sync::Arc;
+
+/// A source of reserve deposits and withdrawals.
+pub trait ReserveBackend: Send + Sync {
+    /// A name uniquely identifying this backend.
+    fn name(&self) -> &str;
+
+    /// The MobileCoin tokens whose reserve is held by this backend.
+    fn token_ids(&self) -> Vec<TokenId>;
+
+    /// Fetch new deposits and withdrawals from the custodian, store them in
+    /// the database and try to match them with mints and burns that were
+    /// already synced.
+    fn poll(&self);
+
+    /// Try to match a [MintTx] with a deposit known to this backend, and
+    /// record the match in the database. Returns a description of the
+    /// matched deposit, or [Error::NotFound] if no matching deposit is known
+    /// yet.
+    fn audit_mint(&self, mint_tx: &MintTx, conn: &Conn) -> Result<String, Error>;
+
+    /// Try to match a [BurnTxOut] with a withdrawal known to this backend,
+    /// and record the match in the database. Returns a description of the
+    /// matched withdrawal, or [Error::NotFound] if no matching withdrawal is
+    /// known yet.
+    fn audit_burn(&self, burn_tx_out: &BurnTxOut, conn: &Conn) -> Result<String, Error>;
+}
+
+/// The set of configured reserve backends.
+#[derive(Clone, Default)]
+pub struct ReserveBackends {
+    backends: Vec<Arc<dyn ReserveBackend>>,
+    backend_by_token_id: HashMap<TokenId, usize>,
+}
+
+impl ReserveBackends {
+    /// Create a new [ReserveBackends] object. Fails if two backends share a
+    /// name, or if a token is backed by more than one backend.
+    pub fn new(backends: Vec<Arc<dyn ReserveBackend>>) -> Result<Self, Error> {
+        let mut backend_by_token_id = HashMap::default();
+
+        for (index, backend) in backends.iter().enumerate() {
+            if backends[..index]
+                .iter()
+                .any(|other| other.name() == backend.name())
+            {
+                return Err(Error::InvalidReserveConfig(format!(
+                    "duplicate reserve backend name {}",
+                    backend.name()
+                )));
+            }
+
+            for token_id in backend.token_ids() {
+                if let Some(other_index) = backend_by_token_id.insert(token_id, index) {
+                    if other_index != index {
+                        return Err(Error::InvalidReserveConfig(format!(
+                            "token {} is backed by both {} and {}",
+                            token_id,
+                            backends[other_index].name(),
+                            backend.name()
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            backends,
+            backend_by_token_id,
+        })
+    }
+
+    /// Get all backends.
+    pub fn backends(&self) -> &[Arc<dyn ReserveBackend>] {
+        &self.backends
+    }
+
+    /// Get the backend holding the reserve of a given token, if there is one.
+    pub fn backend_for_token(&self, token_id: TokenId) -> Option<&Arc<dyn ReserveBackend>> {
+        self.backend_by_token_id
+            .get(&token_id)
+            .map(|index| &self.backends[*index])
+    }
+
+    /// Audit the mints and burns found in a block, using the backend of each
+    /// token.
+    pub fn audit_block_data(
+        &self,
+        sync_block_data: &SyncBlockData,
+        conn: &Conn,
+        logger: &Logger,
+    ) -> Result<(), Error> {
+        // Audit mints.
+        for mint_tx in &sync_block_data.mint_txs {
+            let backend = match self.backend_for_token(mint_tx.token_id()) {
+                Some(backend) => backend,
+                None => {
+                    log::warn!(
+                        logger,
+                        "MintTx nonce={} is for token {} which has no reserve backend",
+                        mint_tx.nonce_hex(),
+                        mint_tx.token_id(),
+                    );
+                    continue;
+                }
+            };
+
+            match backend.audit_mint(mint_tx, conn) {
+                Ok(deposit) => {
+                    log::info!(
+                        logger,
+                        "MintTx nonce={} matched {} deposit {}",
+                        mint_tx.nonce_hex(),
+                        backend.name(),
+                        deposit,
+                    )
+                }
+                Err(Error::NotFound) => {
+                    log::debug!(logger, "MintTx with nonce={} does not currently have matching {} deposit, this could be fine if the reserve data is not fully synced.", mint_tx.nonce_hex(), backend.name());
+                }
+                Err(err) => {
+                    log::error!(
+                        logger,
+                        "MintTx nonce={} failed matching {} deposit: {}",
+                        mint_tx.nonce_hex(),
+                        backend.name(),
+                        err
+                    );
+                }
+            };
+        }
+
+        // Audit burns.
+        for burn_tx_out in &sync_block_data.burn_tx_outs {
+            let backend = match self.backend_for_token(burn_tx_out.token_id()) {
+                Some(backend) => backend,
+                None => {
+                    log::warn!(
+                        logger,
+                        "BurnTxOut pub_key={} is for token {} which has no reserve backend",
+                        burn_tx_out.public_key_hex(),
+                        burn_tx_out.token_id(),
+                    );
+                    continue;
+                }
+            };
+
+            match backend.audit_burn(burn_tx_out, conn) {
+                Ok(withdrawal) => {
+                    log::info!(
+                        logger,
+                        "BurnTxOut pub_key={} matched {} withdrawal {}",
+                        burn_tx_out.public_key_hex(),
+                        backend.name(),
+                        withdrawal,
+                    )
+                }
+                Err(Error::NotFound) => {
+                    log::debug!(logger, "BurnTxOut with pub_key={} does not currently have matching {} withdrawal, this could be fine if the reserve data is not fully synced.", burn_tx_out.public_key_hex(), backend.name());
+                }
+                Err(err) => {
+                    log::error!(
+                        logger,
+                        "BurnTxOut pub_key={} failed matching {} withdrawal: {}",
+                        burn_tx_out.public_key_hex(),
+                        backend.name(),
+                        err
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestBackend {
+        name: String,
+        token_ids: Vec<TokenId>,
+    }
+
+    impl ReserveBackend for TestBackend {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn token_ids(&self) -> Vec<TokenId> {
+            self.token_ids.clone()
+        }
+
+        fn poll(&self) {}
+
+        fn audit_mint(&self, _mint_tx: &MintTx, _conn: &Conn) -> Result<String, Error> {
+            Err(Error::NotFound)
+        }
+
+        fn audit_burn(&self, _burn_tx_out: &BurnTxOut, _conn: &Conn) -> Result<String, Error> {
+            Err(Error::NotFound)
+        }
+    }
+
+    fn test_backend(name: &str, token_ids: &[u64]) -> Arc<dyn ReserveBackend> {
+        Arc::new(TestBackend {
+            name: name.to_string(),
+            token_ids: token_ids.iter().map(|id| TokenId::from(*id)).collect(),
+        })
+    }
+
+    #[test]
+    fn backends_are_routed_by_token_id() {
+        let backends =
+            ReserveBackends::new(vec![test_backend("a", &[1, 2]), test_backend("b", &[3])])
+                .unwrap();
+
+        assert_eq!(
+            backends.backend_for_token(TokenId::from(2)).unwrap().name(),
+            "a"
+        );
+        assert_eq!(
+            backends.backend_for_token(TokenId::from(3)).unwrap().name(),
+            "b"
+        );
+        assert!(backends.backend_for_token(TokenId::from(4)).is_none());
+
+        // A token can only be backed by a single backend.
+        assert!(matches!(
+            ReserveBackends::new(vec![test_backend("a", &[1, 2]), test_backend("b", &[2])]),
+            Err(Error::InvalidReserveConfig(_))
+        ));
+
+        // Backend names need to be unique.
+        assert!(matches!(
+            ReserveBackends::new(vec![test_backend("a", &[1]), test_backend("a", &[2])]),
+            Err(Error::InvalidReserveConfig(_))
+        ));
+    }
+}
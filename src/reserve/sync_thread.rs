@@ -0,0 +1,90 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Background thread for periodically polling a reserve backend.
+
+use super::ReserveBackend;
+use crate::error::Error;
+use mc_common::logger::{log, Logger};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{sleep, Builder as ThreadBuilder, JoinHandle},
+    time::Duration,
+};
+
+/// Background thread for periodically polling a reserve backend.
+pub struct ReserveSyncThread {
+    stop_requested: Arc<AtomicBool>,
+    join_handle: Option<JoinHandle<()>>,
+    logger: Logger,
+}
+
+impl ReserveSyncThread {
+    /// Start the sync thread.
+    pub fn start(
+        backend: Arc<dyn ReserveBackend>,
+        poll_interval: Duration,
+        logger: Logger,
+    ) -> Result<Self, Error> {
+        let stop_requested = Arc::new(AtomicBool::new(false));
+
+        let thread_stop_requested = stop_requested.clone();
+        let thread_logger = logger.clone();
+
+        let join_handle = Some(
+            ThreadBuilder::new()
+                .name(format!("ReserveSync-{}", backend.name()))
+                .spawn(move || {
+                    thread_entry_point(thread_stop_requested, backend, poll_interval, thread_logger)
+                })?,
+        );
+
+        Ok(Self {
+            stop_requested,
+            join_handle,
+            logger,
+        })
+    }
+
+    /// Stop the sync thread.
+    pub fn stop(&mut self) {
+        log::info!(self.logger, "Stopping reserve sync thread...");
+        self.stop_requested.store(true, Ordering::Relaxed);
+        if let Some(join_handle) = self.join_handle.take() {
+            join_handle
+                .join()
+                .expect("failed joining reserve sync thread");
+        }
+    }
+}
+
+impl Drop for ReserveSyncThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn thread_entry_point(
+    stop_requested: Arc<AtomicBool>,
+    backend: Arc<dyn ReserveBackend>,
+    poll_interval: Duration,
+    logger: Logger,
+) {
+    log::info!(logger, "Reserve sync thread for {} started", backend.name());
+
+    loop {
+        if stop_requested.load(Ordering::Relaxed) {
+            log::info!(
+                logger,
+                "Reserve sync thread for {} stop trigger received",
+                backend.name()
+            );
+            break;
+        }
+
+        backend.poll();
+        sleep(poll_interval);
+    }
+}
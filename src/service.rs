@@ -3,20 +3,67 @@
 //! Reserve auditor GRPC service implementation.
 
 use crate::{
-    db::{BlockAuditData, Counters, ReserveAuditorDb},
+    db::{
+        AuditEvent, AuditedBurn, AuditedMint, BlockAuditData, BlockBalance, BurnTxOut, Conn,
+        Counters, GnosisSafeDeposit, GnosisSafeWithdrawal, MintConfig, MintConfigTx, MintTx,
+        ReserveAuditorDb, SyncBlockData,
+    },
+    reconciliation::{ReconciliationPoint, ReconciliationReport},
     Error,
 };
-use grpcio::{RpcContext, RpcStatus, RpcStatusCode, Service, UnarySink};
-use mc_common::logger::Logger;
+use chrono::{TimeZone, Utc};
+use futures::{channel::mpsc, executor::block_on, FutureExt, SinkExt, StreamExt};
+use grpcio::{
+    RpcContext, RpcStatus, RpcStatusCode, ServerStreamingSink, Service, UnarySink, WriteFlags,
+};
+use mc_common::{
+    logger::{log, Logger},
+    HashMap,
+};
 use mc_reserve_auditor_api::{
     empty::Empty,
     reserve_auditor::{
-        BlockAuditData as GrpcBlockAuditData, Counters as GrpcCounters, GetBlockAuditDataRequest,
-        GetBlockAuditDataResponse, GetLastBlockAuditDataResponse,
+        AuditedBurnInfo, AuditedMintInfo, BlockAuditData as GrpcBlockAuditData, BurnInfo,
+        Counters as GrpcCounters, GetAuditEventsRequest, GetAuditEventsResponse,
+        GetAuditedBurnsRequest, GetAuditedBurnsResponse, GetAuditedMintsRequest,
+        GetAuditedMintsResponse, GetBlockAuditDataRequest, GetBlockAuditDataResponse,
+        GetBurnsRequest, GetBurnsResponse, GetLastBlockAuditDataResponse, GetLedgerBalanceRequest,
+        GetLedgerBalanceResponse, GetMintInfoRequest, GetMintInfoResponse,
+        GetReconciliationReportRequest, GetReconciliationReportResponse,
+        GetUnauditedBurnTxOutsResponse, GetUnauditedGnosisDepositsResponse,
+        GetUnauditedMintsResponse, GetUnauditedWithdrawalsResponse, MintConfigTxWithConfigs,
+        MintWithConfig, SubscribeSyncedBlocksRequest, SyncedBlock,
     },
     reserve_auditor_grpc::{create_reserve_auditor_api, ReserveAuditorApi},
 };
+use mc_transaction_core::TokenId;
 use mc_util_grpc::{rpc_logger, send_result};
+use std::{fmt::Display, thread, time::Duration};
+
+/// How long a synced blocks subscription waits for a new block before checking
+/// whether the client is still connected.
+const SUBSCRIPTION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum number of synced blocks buffered for a subscriber that is not
+/// keeping up.
+const SUBSCRIPTION_CHANNEL_SIZE: usize = 16;
+
+/// Map an unexpected error into an INTERNAL [RpcStatus].
+fn internal_error(err: impl Display) -> RpcStatus {
+    RpcStatus::with_message(RpcStatusCode::INTERNAL, err.to_string())
+}
+
+/// Convert a [BurnTxOut] into a [BurnInfo], including the decoded burn
+/// redemption memo when one is available.
+fn burn_info(burn: &BurnTxOut) -> BurnInfo {
+    let mut info = BurnInfo::new();
+    info.set_burn(burn.into());
+    if let Ok(memo) = burn.burn_redemption_memo() {
+        info.set_decoded_burn_memo_bytes(memo.memo_data().to_vec());
+    }
+    info
+}
+
 /// Reserve auditor GRPC service implementation.
 #[derive(Clone)]
 pub struct ReserveAuditorService {
@@ -61,14 +108,39 @@ impl ReserveAuditorService {
                 _ => RpcStatus::with_message(RpcStatusCode::INTERNAL, err.to_string()),
             })?;
 
-        let mut grpc_block_audit_data = GrpcBlockAuditData::new();
-        grpc_block_audit_data.set_block_index(block_audit_data.block_index());
+        let balances = BlockBalance::get_balances_for_block(&conn, block_audit_data.block_index())
+            .map_err(internal_error)?;
+        let grpc_block_audit_data = Self::grpc_block_audit_data(&block_audit_data, &balances);
 
         let mut resp = GetBlockAuditDataResponse::new();
         resp.set_block_audit_data(grpc_block_audit_data);
         Ok(resp)
     }
 
+    /// Build the gRPC representation of a [BlockAuditData], including the
+    /// per-token balances at that block.
+    fn grpc_block_audit_data(
+        block_audit_data: &BlockAuditData,
+        balances: &HashMap<TokenId, u128>,
+    ) -> GrpcBlockAuditData {
+        let mut grpc_block_audit_data = GrpcBlockAuditData::new();
+        grpc_block_audit_data.set_block_index(block_audit_data.block_index());
+        grpc_block_audit_data.set_balances(
+            balances
+                .iter()
+                .map(|(token_id, balance)| (**token_id, balance.to_string()))
+                .collect(),
+        );
+        if let Some(block_timestamp) = block_audit_data.block_timestamp() {
+            grpc_block_audit_data.set_block_timestamp(
+                block_timestamp
+                    .timestamp_nanos_opt()
+                    .expect("Invalid block_timestamp stored in database") as u64,
+            );
+        }
+        grpc_block_audit_data
+    }
+
     fn get_last_block_audit_data_impl(&self) -> Result<GetLastBlockAuditDataResponse, RpcStatus> {
         let conn = self
             .reserve_auditor_db
@@ -84,8 +156,9 @@ impl ReserveAuditorService {
                 )
             })?;
 
-        let mut grpc_block_audit_data = GrpcBlockAuditData::new();
-        grpc_block_audit_data.set_block_index(block_audit_data.block_index());
+        let balances = BlockBalance::get_balances_for_block(&conn, block_audit_data.block_index())
+            .map_err(internal_error)?;
+        let grpc_block_audit_data = Self::grpc_block_audit_data(&block_audit_data, &balances);
 
         let mut resp = GetLastBlockAuditDataResponse::new();
         resp.set_block_audit_data(grpc_block_audit_data);
@@ -99,6 +172,316 @@ impl ReserveAuditorService {
             .map_err(|err| RpcStatus::with_message(RpcStatusCode::INTERNAL, err.to_string()))
             .map(|counters| GrpcCounters::from(&counters))
     }
+
+    fn get_conn(&self) -> Result<Conn, RpcStatus> {
+        self.reserve_auditor_db.get_conn().map_err(internal_error)
+    }
+
+    fn get_audited_mints_impl(
+        &self,
+        req: &GetAuditedMintsRequest,
+    ) -> Result<GetAuditedMintsResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        let audited_mints = AuditedMint::list_with_mint_and_deposit(
+            (req.offset != 0).then_some(req.offset),
+            (req.limit != 0).then_some(req.limit),
+            &conn,
+        )
+        .map_err(internal_error)?;
+
+        let mut resp = GetAuditedMintsResponse::new();
+        resp.set_audited_mints(
+            audited_mints
+                .iter()
+                .map(|(audited, mint, deposit)| {
+                    let mut info = AuditedMintInfo::new();
+                    info.set_audited(audited.into());
+                    info.set_mint(mint.into());
+                    info.set_deposit(deposit.into());
+                    info
+                })
+                .collect(),
+        );
+        Ok(resp)
+    }
+
+    fn get_audited_burns_impl(
+        &self,
+        req: &GetAuditedBurnsRequest,
+    ) -> Result<GetAuditedBurnsResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        let audited_burns = AuditedBurn::list_with_burn_and_withdrawal(
+            (req.offset != 0).then_some(req.offset),
+            (req.limit != 0).then_some(req.limit),
+            &conn,
+        )
+        .map_err(internal_error)?;
+
+        let mut resp = GetAuditedBurnsResponse::new();
+        resp.set_audited_burns(
+            audited_burns
+                .iter()
+                .map(|(audited, burn, withdrawal)| {
+                    let mut info = AuditedBurnInfo::new();
+                    info.set_audited(audited.into());
+                    info.set_burn(burn.into());
+                    info.set_withdrawal(withdrawal.try_into()?);
+                    if let Ok(memo) = burn.burn_redemption_memo() {
+                        info.set_decoded_burn_memo_bytes(memo.memo_data().to_vec());
+                    }
+                    Ok(info)
+                })
+                .collect::<Result<_, Error>>()
+                .map_err(internal_error)?,
+        );
+        Ok(resp)
+    }
+
+    fn get_unaudited_gnosis_deposits_impl(
+        &self,
+    ) -> Result<GetUnauditedGnosisDepositsResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        let deposits = GnosisSafeDeposit::find_unaudited_deposits(&conn).map_err(internal_error)?;
+
+        let mut resp = GetUnauditedGnosisDepositsResponse::new();
+        resp.set_deposits(deposits.iter().map(Into::into).collect());
+        Ok(resp)
+    }
+
+    fn get_unaudited_withdrawals_impl(&self) -> Result<GetUnauditedWithdrawalsResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        let withdrawals =
+            GnosisSafeWithdrawal::find_unaudited_withdrawals(&conn).map_err(internal_error)?;
+
+        let mut resp = GetUnauditedWithdrawalsResponse::new();
+        resp.set_withdrawals(
+            withdrawals
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, Error>>()
+                .map_err(internal_error)?,
+        );
+        Ok(resp)
+    }
+
+    fn get_unaudited_mints_impl(&self) -> Result<GetUnauditedMintsResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        let mint_txs = MintTx::find_unaudited_mint_txs(&conn).map_err(internal_error)?;
+
+        let mut resp = GetUnauditedMintsResponse::new();
+        resp.set_mint_txs(mint_txs.iter().map(Into::into).collect());
+        Ok(resp)
+    }
+
+    fn get_unaudited_burn_tx_outs_impl(&self) -> Result<GetUnauditedBurnTxOutsResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        let burns = BurnTxOut::find_unaudited_burn_tx_outs(&conn).map_err(internal_error)?;
+
+        let mut resp = GetUnauditedBurnTxOutsResponse::new();
+        resp.set_burns(burns.iter().map(burn_info).collect());
+        Ok(resp)
+    }
+
+    fn get_ledger_balance_impl(
+        &self,
+        req: &GetLedgerBalanceRequest,
+    ) -> Result<GetLedgerBalanceResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+        let token_id = TokenId::from(req.token_id);
+
+        let mint_balance: u128 = MintTx::get_mint_amounts(&conn, token_id)
+            .map_err(internal_error)?
+            .into_iter()
+            .map(|(_token_id, amount)| amount as u128)
+            .sum();
+        let burn_balance: u128 = BurnTxOut::get_burn_amounts(&conn, token_id)
+            .map_err(internal_error)?
+            .into_iter()
+            .map(|(_token_id, amount)| amount as u128)
+            .sum();
+
+        let mut resp = GetLedgerBalanceResponse::new();
+        resp.set_token_id(req.token_id);
+        resp.set_mint_balance(mint_balance.to_string());
+        resp.set_burn_balance(burn_balance.to_string());
+        Ok(resp)
+    }
+
+    fn get_mint_info_impl(
+        &self,
+        req: &GetMintInfoRequest,
+    ) -> Result<GetMintInfoResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        // Wrapped in a closure to allow using the ? operator.
+        let result = || -> Result<GetMintInfoResponse, Error> {
+            let mut resp = GetMintInfoResponse::new();
+
+            for mint_config_tx in MintConfigTx::get_by_block_index(req.block_index, &conn)? {
+                let mint_configs = match mint_config_tx.id() {
+                    Some(id) => MintConfig::get_by_mint_config_tx_id(id, &conn)?,
+                    None => vec![],
+                };
+
+                let mut info = MintConfigTxWithConfigs::new();
+                info.set_mint_config_tx((&mint_config_tx).into());
+                info.set_mint_configs(mint_configs.iter().map(Into::into).collect());
+                resp.mut_mint_config_txs().push(info);
+            }
+
+            for mint_tx in MintTx::get_mint_txs_by_block_index(req.block_index, &conn)? {
+                let mint_config = match mint_tx.mint_config_id() {
+                    Some(config_id) => MintConfig::get_by_id(config_id, &conn)?,
+                    None => None,
+                };
+                let mint_config_tx = match &mint_config {
+                    Some(mint_config) => {
+                        MintConfigTx::get_by_id(mint_config.mint_config_tx_id(), &conn)?
+                    }
+                    None => None,
+                };
+
+                // Same as the HTTP API, only MintTxs we were able to match with a
+                // configuration are included.
+                if let (Some(mint_config), Some(mint_config_tx)) = (mint_config, mint_config_tx) {
+                    let mut info = MintWithConfig::new();
+                    info.set_mint_tx_signers(
+                        mint_tx.get_signers(&conn)?.iter().map(Into::into).collect(),
+                    );
+                    info.set_mint_tx((&mint_tx).into());
+                    info.set_mint_config_tx((&mint_config_tx).into());
+                    info.set_mint_config((&mint_config).into());
+                    resp.mut_mint_txs().push(info);
+                }
+            }
+
+            Ok(resp)
+        }();
+
+        result.map_err(internal_error)
+    }
+
+    fn get_burns_impl(&self, req: &GetBurnsRequest) -> Result<GetBurnsResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        let burns =
+            BurnTxOut::get_burn_txs_by_block(req.block_index, &conn).map_err(internal_error)?;
+
+        let mut resp = GetBurnsResponse::new();
+        resp.set_burns(burns.iter().map(burn_info).collect());
+        Ok(resp)
+    }
+
+    fn get_audit_events_impl(
+        &self,
+        req: &GetAuditEventsRequest,
+    ) -> Result<GetAuditEventsResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        let audit_events = AuditEvent::list(
+            (req.offset != 0).then_some(req.offset),
+            (req.limit != 0).then_some(req.limit),
+            req.open_only,
+            &conn,
+        )
+        .map_err(internal_error)?;
+
+        let mut resp = GetAuditEventsResponse::new();
+        resp.set_audit_events(audit_events.iter().map(Into::into).collect());
+        Ok(resp)
+    }
+
+    fn get_reconciliation_report_impl(
+        &self,
+        req: &GetReconciliationReportRequest,
+    ) -> Result<GetReconciliationReportResponse, RpcStatus> {
+        let conn = self.get_conn()?;
+
+        let point = if req.has_block_index() {
+            Some(ReconciliationPoint::BlockIndex(req.get_block_index()))
+        } else if req.has_timestamp() {
+            Some(ReconciliationPoint::Timestamp(
+                Utc.timestamp_nanos(req.get_timestamp() as i64),
+            ))
+        } else {
+            None
+        };
+
+        let report = ReconciliationReport::compute(point, &conn).map_err(|err| match err {
+            Error::NotFound => RpcStatus::with_message(
+                RpcStatusCode::NOT_FOUND,
+                format!(
+                    "Block audit data not found for block index {}",
+                    req.get_block_index()
+                ),
+            ),
+            err => internal_error(err),
+        })?;
+
+        Ok((&report).into())
+    }
+
+    /// Build the [SyncedBlock] message for a block that has been synced.
+    fn synced_block(&self, sync_block_data: &SyncBlockData) -> Result<SyncedBlock, RpcStatus> {
+        let conn = self.get_conn()?;
+        let block_index = sync_block_data.block_audit.block_index();
+
+        let audited_mints =
+            AuditedMint::get_by_block_index(block_index, &conn).map_err(internal_error)?;
+        let audited_burns =
+            AuditedBurn::get_by_block_index(block_index, &conn).map_err(internal_error)?;
+
+        let mut synced_block = SyncedBlock::new();
+        synced_block.set_block_audit_data(Self::grpc_block_audit_data(
+            &sync_block_data.block_audit,
+            &sync_block_data.balance_map,
+        ));
+        synced_block.set_mint_txs(sync_block_data.mint_txs.iter().map(Into::into).collect());
+        synced_block.set_burn_tx_outs(
+            sync_block_data
+                .burn_tx_outs
+                .iter()
+                .map(Into::into)
+                .collect(),
+        );
+        synced_block.set_audited_mints(audited_mints.iter().map(Into::into).collect());
+        synced_block.set_audited_burns(audited_burns.iter().map(Into::into).collect());
+        Ok(synced_block)
+    }
+
+    /// Feed synced blocks, starting at `start_block_index`, into `sender`.
+    /// Blocks that were already synced are sent right away, after which this
+    /// waits for new blocks to get synced. Returns once the receiving end is
+    /// dropped or an error has been sent.
+    fn stream_synced_blocks(
+        &self,
+        start_block_index: u64,
+        mut sender: mpsc::Sender<Result<SyncedBlock, RpcStatus>>,
+    ) {
+        let mut block_index = start_block_index;
+        while !sender.is_closed() {
+            let result = match self
+                .reserve_auditor_db
+                .wait_for_sync_block_data(block_index, SUBSCRIPTION_POLL_INTERVAL)
+            {
+                Ok(None) => continue,
+                Ok(Some(sync_block_data)) => self.synced_block(&sync_block_data),
+                Err(err) => Err(internal_error(err)),
+            };
+
+            let is_err = result.is_err();
+            if block_on(sender.send(result)).is_err() || is_err {
+                break;
+            }
+            block_index += 1;
+        }
+    }
 }
 
 impl ReserveAuditorApi for ReserveAuditorService {
@@ -126,12 +509,173 @@ impl ReserveAuditorApi for ReserveAuditorService {
         let logger = rpc_logger(&ctx, &self.logger);
         send_result(ctx, sink, self.get_counters_impl(), &logger)
     }
+
+    fn get_audited_mints(
+        &mut self,
+        ctx: RpcContext,
+        req: GetAuditedMintsRequest,
+        sink: UnarySink<GetAuditedMintsResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(ctx, sink, self.get_audited_mints_impl(&req), &logger)
+    }
+
+    fn get_audited_burns(
+        &mut self,
+        ctx: RpcContext,
+        req: GetAuditedBurnsRequest,
+        sink: UnarySink<GetAuditedBurnsResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(ctx, sink, self.get_audited_burns_impl(&req), &logger)
+    }
+
+    fn get_unaudited_gnosis_deposits(
+        &mut self,
+        ctx: RpcContext,
+        _req: Empty,
+        sink: UnarySink<GetUnauditedGnosisDepositsResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(
+            ctx,
+            sink,
+            self.get_unaudited_gnosis_deposits_impl(),
+            &logger,
+        )
+    }
+
+    fn get_unaudited_withdrawals(
+        &mut self,
+        ctx: RpcContext,
+        _req: Empty,
+        sink: UnarySink<GetUnauditedWithdrawalsResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(ctx, sink, self.get_unaudited_withdrawals_impl(), &logger)
+    }
+
+    fn get_unaudited_mints(
+        &mut self,
+        ctx: RpcContext,
+        _req: Empty,
+        sink: UnarySink<GetUnauditedMintsResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(ctx, sink, self.get_unaudited_mints_impl(), &logger)
+    }
+
+    fn get_unaudited_burn_tx_outs(
+        &mut self,
+        ctx: RpcContext,
+        _req: Empty,
+        sink: UnarySink<GetUnauditedBurnTxOutsResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(ctx, sink, self.get_unaudited_burn_tx_outs_impl(), &logger)
+    }
+
+    fn get_ledger_balance(
+        &mut self,
+        ctx: RpcContext,
+        req: GetLedgerBalanceRequest,
+        sink: UnarySink<GetLedgerBalanceResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(ctx, sink, self.get_ledger_balance_impl(&req), &logger)
+    }
+
+    fn get_mint_info(
+        &mut self,
+        ctx: RpcContext,
+        req: GetMintInfoRequest,
+        sink: UnarySink<GetMintInfoResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(ctx, sink, self.get_mint_info_impl(&req), &logger)
+    }
+
+    fn get_burns(
+        &mut self,
+        ctx: RpcContext,
+        req: GetBurnsRequest,
+        sink: UnarySink<GetBurnsResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(ctx, sink, self.get_burns_impl(&req), &logger)
+    }
+
+    fn get_reconciliation_report(
+        &mut self,
+        ctx: RpcContext,
+        req: GetReconciliationReportRequest,
+        sink: UnarySink<GetReconciliationReportResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(
+            ctx,
+            sink,
+            self.get_reconciliation_report_impl(&req),
+            &logger,
+        )
+    }
+
+    fn get_audit_events(
+        &mut self,
+        ctx: RpcContext,
+        req: GetAuditEventsRequest,
+        sink: UnarySink<GetAuditEventsResponse>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        send_result(ctx, sink, self.get_audit_events_impl(&req), &logger)
+    }
+
+    fn subscribe_synced_blocks(
+        &mut self,
+        ctx: RpcContext,
+        req: SubscribeSyncedBlocksRequest,
+        mut sink: ServerStreamingSink<SyncedBlock>,
+    ) {
+        let logger = rpc_logger(&ctx, &self.logger);
+        let (sender, mut receiver) = mpsc::channel(SUBSCRIPTION_CHANNEL_SIZE);
+
+        // Waiting for blocks to get synced is blocking, so it happens on a
+        // dedicated thread that feeds the stream.
+        let service = self.clone();
+        let start_block_index = req.start_block_index;
+        let spawn_result = thread::Builder::new()
+            .name("SubscribeSyncedBlocks".into())
+            .spawn(move || service.stream_synced_blocks(start_block_index, sender));
+        if let Err(err) = spawn_result {
+            ctx.spawn(sink.fail(internal_error(err)).map(|_| ()));
+            return;
+        }
+
+        let future = async move {
+            while let Some(result) = receiver.next().await {
+                match result {
+                    Ok(synced_block) => sink.send((synced_block, WriteFlags::default())).await?,
+                    Err(status) => return sink.fail(status).await,
+                }
+            }
+            sink.close().await
+        };
+
+        ctx.spawn(future.map(move |result: Result<(), grpcio::Error>| {
+            if let Err(err) = result {
+                log::debug!(logger, "SubscribeSyncedBlocks stream ended: {}", err);
+            }
+        }));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::test_utils::{append_and_sync, TestDbContext};
+    use crate::{
+        anomaly::{AnomalyEvent, AnomalyKind},
+        db::test_utils::{append_and_sync, TestDbContext},
+    };
     use chrono::Utc;
     use grpcio::{ChannelBuilder, Environment, Server, ServerBuilder};
     use mc_account_keys::AccountKey;
@@ -139,7 +683,7 @@ mod tests {
     use mc_common::logger::{test_with_logger, Logger};
     use mc_ledger_db::{
         test_utils::{create_ledger, initialize_ledger},
-        Ledger,
+        Ledger, LedgerDB,
     };
     use mc_reserve_auditor_api::reserve_auditor_grpc::ReserveAuditorApiClient;
     use mc_transaction_core::TokenId;
@@ -147,6 +691,7 @@ mod tests {
         create_mint_config_tx_and_signers, create_mint_tx, create_test_tx_out,
         mint_config_tx_to_validated as to_validated,
     };
+    use mc_util_test_helper::{RngType, SeedableRng};
     use std::sync::Arc;
 
     /// Starts the service on localhost and connects a client to it.
@@ -172,6 +717,13 @@ mod tests {
 
     /// Create a test database with some data in it.
     fn get_test_db(logger: &Logger) -> (ReserveAuditorDb, TestDbContext) {
+        let (reserve_auditor_db, _ledger_db, test_db_context) = get_test_db_and_ledger(logger);
+        (reserve_auditor_db, test_db_context)
+    }
+
+    /// Create a test database with some data in it, along with the ledger it
+    /// was synced from.
+    fn get_test_db_and_ledger(logger: &Logger) -> (ReserveAuditorDb, LedgerDB, TestDbContext) {
         let mut rng = mc_util_test_helper::get_seeded_rng();
         let token_id1 = TokenId::from(1);
         let token_id2 = TokenId::from(22);
@@ -239,7 +791,7 @@ mod tests {
         )
         .unwrap();
 
-        (reserve_auditor_db, test_db_context)
+        (reserve_auditor_db, ledger_db, test_db_context)
     }
 
     #[test_with_logger]
@@ -255,6 +807,22 @@ mod tests {
         let response = client.get_block_audit_data(&request).unwrap();
 
         assert_eq!(response.get_block_audit_data().block_index, 2,);
+        assert_eq!(
+            response.get_block_audit_data().get_balances(),
+            &std::collections::HashMap::from_iter([(1, "101".to_string()), (22, "2".to_string())])
+        );
+
+        // The initial block was synced with a timestamp, the rest were not.
+        let request = GetBlockAuditDataRequest {
+            block_index: 0,
+            ..Default::default()
+        };
+
+        let response = client.get_block_audit_data(&request).unwrap();
+
+        assert_eq!(response.get_block_audit_data().block_index, 0,);
+        assert!(response.get_block_audit_data().get_block_timestamp() > 0);
+        assert!(response.get_block_audit_data().get_balances().is_empty());
     }
 
     #[test_with_logger]
@@ -264,6 +832,11 @@ mod tests {
 
         let response = client.get_last_block_audit_data(&Empty::default()).unwrap();
         assert_eq!(response.get_block_audit_data().block_index, 2,);
+        assert_eq!(response.get_block_audit_data().get_block_timestamp(), 0);
+        assert_eq!(
+            response.get_block_audit_data().get_balances(),
+            &std::collections::HashMap::from_iter([(1, "101".to_string()), (22, "2".to_string())])
+        );
     }
 
     #[test_with_logger]
@@ -283,4 +856,173 @@ mod tests {
             }
         );
     }
+
+    #[test_with_logger]
+    fn test_get_audit_events(logger: Logger) {
+        let (reserve_auditor_db, _test_db_context) = get_test_db(&logger);
+        let (client, _server) = get_client_server(&reserve_auditor_db, &logger);
+
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let mut event = AnomalyEvent::new(AnomalyKind::MismatchingMintAndDeposit, "mismatch");
+        event.block_index = Some(2);
+        Counters::record_anomaly(&event, &conn).unwrap();
+        Counters::record_anomaly(
+            &AnomalyEvent::new(AnomalyKind::InvalidMultiSigTransaction, "bad tx"),
+            &conn,
+        )
+        .unwrap();
+
+        let response = client.get_audit_events(&Default::default()).unwrap();
+        let audit_events = response.get_audit_events();
+        assert_eq!(audit_events.len(), 2);
+        assert_eq!(audit_events[0].get_kind(), "mismatching_mint_and_deposit");
+        assert_eq!(audit_events[0].get_severity(), "critical");
+        assert_eq!(audit_events[0].get_message(), "mismatch");
+        assert_eq!(audit_events[0].get_block_index(), 2);
+        assert_eq!(audit_events[1].get_kind(), "invalid_multi_sig_transaction");
+
+        let request = GetAuditEventsRequest {
+            offset: 1,
+            limit: 1,
+            ..Default::default()
+        };
+        let response = client.get_audit_events(&request).unwrap();
+        assert_eq!(response.get_audit_events().len(), 1);
+        assert_eq!(response.get_audit_events()[0].get_message(), "bad tx");
+    }
+
+    #[test_with_logger]
+    fn test_get_unaudited_mints(logger: Logger) {
+        let (reserve_auditor_db, _test_db_context) = get_test_db(&logger);
+        let (client, _server) = get_client_server(&reserve_auditor_db, &logger);
+
+        // None of the mints in the test database have a matching deposit.
+        let response = client.get_unaudited_mints(&Empty::default()).unwrap();
+        let mut amounts = response
+            .get_mint_txs()
+            .iter()
+            .map(|mint_tx| mint_tx.get_amount())
+            .collect::<Vec<_>>();
+        amounts.sort();
+        assert_eq!(amounts, vec![1, 2, 100]);
+
+        let response = client.get_audited_mints(&Default::default()).unwrap();
+        assert!(response.get_audited_mints().is_empty());
+    }
+
+    #[test_with_logger]
+    fn test_get_ledger_balance(logger: Logger) {
+        let (reserve_auditor_db, _test_db_context) = get_test_db(&logger);
+        let (client, _server) = get_client_server(&reserve_auditor_db, &logger);
+
+        let request = GetLedgerBalanceRequest {
+            token_id: 1,
+            ..Default::default()
+        };
+        let response = client.get_ledger_balance(&request).unwrap();
+        assert_eq!(response.get_token_id(), 1);
+        assert_eq!(response.get_mint_balance(), "101");
+        assert_eq!(response.get_burn_balance(), "0");
+
+        let request = GetLedgerBalanceRequest {
+            token_id: 22,
+            ..Default::default()
+        };
+        let response = client.get_ledger_balance(&request).unwrap();
+        assert_eq!(response.get_mint_balance(), "2");
+    }
+
+    #[test_with_logger]
+    fn test_get_mint_info(logger: Logger) {
+        let (reserve_auditor_db, _test_db_context) = get_test_db(&logger);
+        let (client, _server) = get_client_server(&reserve_auditor_db, &logger);
+
+        // Block 1 contains the mint config txs.
+        let request = GetMintInfoRequest {
+            block_index: 1,
+            ..Default::default()
+        };
+        let response = client.get_mint_info(&request).unwrap();
+        assert_eq!(response.get_mint_config_txs().len(), 2);
+        assert!(response
+            .get_mint_config_txs()
+            .iter()
+            .all(|info| !info.get_mint_configs().is_empty()));
+        assert!(response.get_mint_txs().is_empty());
+
+        // Block 2 contains the mint txs.
+        let request = GetMintInfoRequest {
+            block_index: 2,
+            ..Default::default()
+        };
+        let response = client.get_mint_info(&request).unwrap();
+        assert!(response.get_mint_config_txs().is_empty());
+        assert_eq!(response.get_mint_txs().len(), 3);
+        for info in response.get_mint_txs() {
+            assert_eq!(
+                info.get_mint_tx().get_mint_config_id(),
+                info.get_mint_config().get_id()
+            );
+            assert_eq!(
+                info.get_mint_config().get_mint_config_tx_id(),
+                info.get_mint_config_tx().get_id()
+            );
+            assert!(!info.get_mint_tx_signers().is_empty());
+        }
+
+        // There are no burns in the test database.
+        let request = GetBurnsRequest {
+            block_index: 2,
+            ..Default::default()
+        };
+        let response = client.get_burns(&request).unwrap();
+        assert!(response.get_burns().is_empty());
+    }
+
+    #[test_with_logger]
+    fn test_subscribe_synced_blocks(logger: Logger) {
+        // Use a different seed than the one used to populate the ledger so that
+        // the new TxOut does not collide with existing ones.
+        let mut rng = RngType::from_seed([1u8; 32]);
+        let (reserve_auditor_db, mut ledger_db, _test_db_context) = get_test_db_and_ledger(&logger);
+        let (client, _server) = get_client_server(&reserve_auditor_db, &logger);
+
+        let request = SubscribeSyncedBlocksRequest {
+            start_block_index: 1,
+            ..Default::default()
+        };
+        let mut stream = client.subscribe_synced_blocks(&request).unwrap();
+
+        // Blocks that were already synced get streamed right away.
+        let synced_block = block_on(stream.next()).unwrap().unwrap();
+        assert_eq!(synced_block.get_block_audit_data().get_block_index(), 1);
+        assert!(synced_block.get_mint_txs().is_empty());
+
+        let synced_block = block_on(stream.next()).unwrap().unwrap();
+        assert_eq!(synced_block.get_block_audit_data().get_block_index(), 2);
+        assert_eq!(synced_block.get_mint_txs().len(), 3);
+        assert!(synced_block.get_burn_tx_outs().is_empty());
+        assert!(synced_block.get_audited_mints().is_empty());
+        assert_eq!(
+            synced_block.get_block_audit_data().get_balances(),
+            &std::collections::HashMap::from_iter([(1, "101".to_string()), (22, "2".to_string())])
+        );
+
+        // Blocks get streamed as they are synced.
+        let block_contents = BlockContents {
+            outputs: vec![create_test_tx_out(BlockVersion::MAX, &mut rng)],
+            ..Default::default()
+        };
+        append_and_sync(
+            block_contents,
+            &mut ledger_db,
+            &reserve_auditor_db,
+            &mut rng,
+        )
+        .unwrap();
+
+        let synced_block = block_on(stream.next()).unwrap().unwrap();
+        assert_eq!(synced_block.get_block_audit_data().get_block_index(), 3);
+        assert!(synced_block.get_mint_txs().is_empty());
+    }
 }
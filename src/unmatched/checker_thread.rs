@@ -0,0 +1,99 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Background thread for periodically checking for overdue unmatched items.
+
+use super::UnmatchedChecker;
+use crate::{db::ReserveAuditorDb, error::Error};
+use chrono::Utc;
+use mc_common::logger::{log, Logger};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{sleep, Builder as ThreadBuilder, JoinHandle},
+    time::Duration,
+};
+
+/// Background thread for periodically checking for overdue unmatched items.
+pub struct UnmatchedCheckerThread {
+    stop_requested: Arc<AtomicBool>,
+    join_handle: Option<JoinHandle<()>>,
+    logger: Logger,
+}
+
+impl UnmatchedCheckerThread {
+    /// Start the checker thread.
+    pub fn start(
+        checker: UnmatchedChecker,
+        reserve_auditor_db: ReserveAuditorDb,
+        check_interval: Duration,
+        logger: Logger,
+    ) -> Result<Self, Error> {
+        let stop_requested = Arc::new(AtomicBool::new(false));
+
+        let thread_stop_requested = stop_requested.clone();
+        let thread_logger = logger.clone();
+
+        let join_handle = Some(ThreadBuilder::new().name("UnmatchedChecker".into()).spawn(
+            move || {
+                thread_entry_point(
+                    thread_stop_requested,
+                    checker,
+                    reserve_auditor_db,
+                    check_interval,
+                    thread_logger,
+                )
+            },
+        )?);
+
+        Ok(Self {
+            stop_requested,
+            join_handle,
+            logger,
+        })
+    }
+
+    /// Stop the checker thread.
+    pub fn stop(&mut self) {
+        log::info!(self.logger, "Stopping unmatched checker thread...");
+        self.stop_requested.store(true, Ordering::Relaxed);
+        if let Some(join_handle) = self.join_handle.take() {
+            join_handle
+                .join()
+                .expect("failed joining unmatched checker thread");
+        }
+    }
+}
+
+impl Drop for UnmatchedCheckerThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn thread_entry_point(
+    stop_requested: Arc<AtomicBool>,
+    mut checker: UnmatchedChecker,
+    reserve_auditor_db: ReserveAuditorDb,
+    check_interval: Duration,
+    logger: Logger,
+) {
+    log::info!(logger, "Unmatched checker thread started");
+
+    loop {
+        if stop_requested.load(Ordering::Relaxed) {
+            log::info!(logger, "Unmatched checker thread stop trigger received");
+            break;
+        }
+
+        let result = reserve_auditor_db
+            .get_conn()
+            .and_then(|conn| checker.check(&conn, Utc::now()));
+        if let Err(err) = result {
+            log::error!(logger, "Failed checking for unmatched items: {}", err);
+        }
+
+        sleep(check_interval);
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Configuration for alerting on items that stay unmatched.
+
+use crate::error::Error;
+use mc_transaction_core::TokenId;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, time::Duration};
+
+/// The grace period used for tokens that do not have one configured.
+pub const DEFAULT_GRACE_PERIOD_SECS: u64 = 3600;
+
+fn default_grace_period_secs() -> u64 {
+    DEFAULT_GRACE_PERIOD_SECS
+}
+
+/// Grace period for a single token.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TokenGracePeriodConfig {
+    /// The MobileCoin token id.
+    pub token_id: TokenId,
+
+    /// How long (in seconds) an item of this token may stay unmatched before
+    /// an alert is raised.
+    pub grace_period_secs: u64,
+}
+
+/// Configuration for alerting on mints, burns, deposits and withdrawals that
+/// stay unmatched for longer than expected.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct UnmatchedAlertConfig {
+    /// How long (in seconds) an item may stay unmatched before an alert is
+    /// raised, for tokens that are not listed in `tokens`.
+    #[serde(default = "default_grace_period_secs")]
+    pub default_grace_period_secs: u64,
+
+    /// Per-token grace periods.
+    #[serde(default)]
+    pub tokens: Vec<TokenGracePeriodConfig>,
+}
+
+impl Default for UnmatchedAlertConfig {
+    fn default() -> Self {
+        Self {
+            default_grace_period_secs: DEFAULT_GRACE_PERIOD_SECS,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+impl UnmatchedAlertConfig {
+    /// Load configuration data from a toml/json file.
+    pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        // Read configuration file.
+        let data = fs::read_to_string(path)?;
+
+        // Parse configuration file.
+        let config: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            None => Err(Error::InvalidUnmatchedAlertConfig(
+                "cannot figure out file extension".to_string(),
+            )),
+            Some("toml") => toml::from_str(&data)
+                .map_err(|err| Error::InvalidUnmatchedAlertConfig(err.to_string())),
+            Some("json") => serde_json::from_str(&data)
+                .map_err(|err| Error::InvalidUnmatchedAlertConfig(err.to_string())),
+            Some(ext) => Err(Error::InvalidUnmatchedAlertConfig(format!(
+                "unrecognized file extension {ext}"
+            ))),
+        }?;
+
+        Ok(config)
+    }
+
+    /// Get the grace period of a given token. Items whose token is not known
+    /// use the default grace period.
+    pub fn grace_period(&self, token_id: Option<TokenId>) -> Duration {
+        let secs = token_id
+            .and_then(|token_id| {
+                self.tokens
+                    .iter()
+                    .find(|token| token.token_id == token_id)
+                    .map(|token| token.grace_period_secs)
+            })
+            .unwrap_or(self.default_grace_period_secs);
+        Duration::from_secs(secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_config() {
+        let input_toml = r#"
+            default_grace_period_secs = 7200
+
+            [[tokens]]
+            token_id = 2
+            grace_period_secs = 600
+        "#;
+        let input_json = r#"{
+            "default_grace_period_secs": 7200,
+            "tokens": [
+                { "token_id": 2, "grace_period_secs": 600 }
+            ]
+        }"#;
+
+        let cfg1: UnmatchedAlertConfig = toml::from_str(input_toml).expect("failed parsing toml");
+        let cfg2: UnmatchedAlertConfig =
+            serde_json::from_str(input_json).expect("failed parsing json");
+
+        assert_eq!(cfg1, cfg2);
+        assert_eq!(
+            cfg1,
+            UnmatchedAlertConfig {
+                default_grace_period_secs: 7200,
+                tokens: vec![TokenGracePeriodConfig {
+                    token_id: TokenId::from(2),
+                    grace_period_secs: 600,
+                }],
+            }
+        );
+        assert_eq!(
+            cfg1.grace_period(Some(TokenId::from(3))),
+            Duration::from_secs(7200)
+        );
+
+        assert_eq!(
+            serde_json::from_str::<UnmatchedAlertConfig>("{}").unwrap(),
+            UnmatchedAlertConfig::default()
+        );
+    }
+}
@@ -0,0 +1,331 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Alerting on mints, burns, deposits and withdrawals that stay unmatched.
+//!
+//! Items are expected to be unmatched for a little while, since the ledger and
+//! the reserves are synced independently. Once an item stays unmatched for
+//! longer than the grace period of its token it is considered overdue, which
+//! most likely indicates a missing reserve movement or an unbacked mint.
+
+mod checker_thread;
+mod config;
+
+pub use self::{
+    checker_thread::UnmatchedCheckerThread,
+    config::{TokenGracePeriodConfig, UnmatchedAlertConfig, DEFAULT_GRACE_PERIOD_SECS},
+};
+
+use crate::{
+    counters,
+    db::{
+        BurnTxOut, Conn, GnosisSafeAuditConfig, GnosisSafeDeposit, GnosisSafeWithdrawal, MintTx,
+        ReserveDeposit, ReserveWithdrawal,
+    },
+    error::Error,
+    gnosis::{AuditedSafeConfig, EthAddr},
+};
+use chrono::{DateTime, Utc};
+use displaydoc::Display;
+use mc_common::logger::{log, Logger};
+use mc_transaction_core::TokenId;
+use std::collections::{HashMap, HashSet};
+
+/// The kind of an unmatched item.
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum UnmatchedItemKind {
+    /// Mint
+    Mint,
+
+    /// Burn
+    Burn,
+
+    /// Deposit
+    Deposit,
+
+    /// Withdrawal
+    Withdrawal,
+}
+
+/// A mint, burn, deposit or withdrawal that does not have a matching
+/// counterpart.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnmatchedItem {
+    /// The kind of the item.
+    pub kind: UnmatchedItemKind,
+
+    /// Human readable identifier of the item, unique per kind.
+    pub identifier: String,
+
+    /// The MobileCoin token the item is for, if known.
+    pub token_id: Option<TokenId>,
+
+    /// When the item happened, if known.
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// Number of overdue unmatched items of each kind.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OverdueCounts {
+    /// Number of overdue mints.
+    pub mints: u64,
+
+    /// Number of overdue burns.
+    pub burns: u64,
+
+    /// Number of overdue deposits.
+    pub deposits: u64,
+
+    /// Number of overdue withdrawals.
+    pub withdrawals: u64,
+}
+
+/// Periodically checks for items that stay unmatched for longer than their
+/// grace period.
+pub struct UnmatchedChecker {
+    /// Grace period configuration.
+    config: UnmatchedAlertConfig,
+
+    /// When each currently unmatched item was first seen by the checker. Used
+    /// for items whose own timestamp is not known.
+    first_seen: HashMap<(UnmatchedItemKind, String), DateTime<Utc>>,
+
+    /// Overdue items we already raised an alert for.
+    alerted: HashSet<(UnmatchedItemKind, String)>,
+
+    /// Logger.
+    logger: Logger,
+}
+
+impl UnmatchedChecker {
+    /// Create a new [UnmatchedChecker].
+    pub fn new(config: UnmatchedAlertConfig, logger: Logger) -> Self {
+        Self {
+            config,
+            first_seen: HashMap::new(),
+            alerted: HashSet::new(),
+            logger,
+        }
+    }
+
+    /// Find all mints, burns, deposits and withdrawals that currently do not
+    /// have a matching counterpart.
+    pub fn find_unmatched_items(conn: &Conn) -> Result<Vec<UnmatchedItem>, Error> {
+        let safe_configs = GnosisSafeAuditConfig::list(conn)?
+            .iter()
+            .map(|stored_config| stored_config.decode())
+            .collect::<Result<Vec<_>, _>>()?;
+        let gnosis_token_id = |chain_id: u64, safe_addr: &EthAddr, token_addr: &EthAddr| {
+            safe_configs
+                .iter()
+                .find(|safe_config| {
+                    safe_config.chain_id == chain_id && &safe_config.safe_addr == safe_addr
+                })
+                .and_then(|safe_config: &AuditedSafeConfig| {
+                    safe_config.get_token_by_eth_contract_addr(token_addr)
+                })
+                .map(|token| token.token_id)
+        };
+
+        let mut items = Vec::new();
+
+        for mint_tx in MintTx::find_unaudited_mint_txs(conn)? {
+            items.push(UnmatchedItem {
+                kind: UnmatchedItemKind::Mint,
+                identifier: format!("MintTx nonce={}", mint_tx.nonce_hex()),
+                token_id: Some(mint_tx.token_id()),
+                timestamp: mint_tx.block_timestamp(),
+            });
+        }
+
+        for burn_tx_out in BurnTxOut::find_unaudited_burn_tx_outs(conn)? {
+            items.push(UnmatchedItem {
+                kind: UnmatchedItemKind::Burn,
+                identifier: format!("BurnTxOut pub_key={}", burn_tx_out.public_key_hex()),
+                token_id: Some(burn_tx_out.token_id()),
+                timestamp: burn_tx_out.block_timestamp(),
+            });
+        }
+
+        for deposit in GnosisSafeDeposit::find_unaudited_deposits(conn)? {
+            items.push(UnmatchedItem {
+                kind: UnmatchedItemKind::Deposit,
+                identifier: format!("Gnosis deposit eth_tx_hash={}", deposit.eth_tx_hash()),
+                token_id: gnosis_token_id(
+                    deposit.chain_id(),
+                    deposit.safe_addr(),
+                    deposit.token_addr(),
+                ),
+                timestamp: Some(deposit.execution_date()),
+            });
+        }
+
+        for deposit in ReserveDeposit::find_unaudited_deposits(None, conn)? {
+            items.push(UnmatchedItem {
+                kind: UnmatchedItemKind::Deposit,
+                identifier: format!(
+                    "{} deposit ref={}",
+                    deposit.source(),
+                    deposit.external_ref()
+                ),
+                token_id: Some(deposit.token_id()),
+                timestamp: deposit.occurred_at(),
+            });
+        }
+
+        for withdrawal in GnosisSafeWithdrawal::find_unaudited_withdrawals(conn)? {
+            items.push(UnmatchedItem {
+                kind: UnmatchedItemKind::Withdrawal,
+                identifier: format!("Gnosis withdrawal eth_tx_hash={}", withdrawal.eth_tx_hash()),
+                token_id: gnosis_token_id(
+                    withdrawal.chain_id(),
+                    withdrawal.safe_addr(),
+                    withdrawal.token_addr(),
+                ),
+                timestamp: Some(withdrawal.execution_date()),
+            });
+        }
+
+        for withdrawal in ReserveWithdrawal::find_unaudited_withdrawals(None, conn)? {
+            items.push(UnmatchedItem {
+                kind: UnmatchedItemKind::Withdrawal,
+                identifier: format!(
+                    "{} withdrawal ref={}",
+                    withdrawal.source(),
+                    withdrawal.external_ref()
+                ),
+                token_id: Some(withdrawal.token_id()),
+                timestamp: withdrawal.occurred_at(),
+            });
+        }
+
+        Ok(items)
+    }
+
+    /// Check for overdue unmatched items as of a given time, update the
+    /// overdue gauges and raise an alert for every item that became overdue
+    /// since the previous check.
+    pub fn check(&mut self, conn: &Conn, now: DateTime<Utc>) -> Result<OverdueCounts, Error> {
+        let items = Self::find_unmatched_items(conn)?;
+
+        let mut counts = OverdueCounts::default();
+        let mut first_seen = HashMap::new();
+        let mut alerted = HashSet::new();
+
+        for item in items {
+            let key = (item.kind, item.identifier.clone());
+            let seen_at = self.first_seen.get(&key).copied().unwrap_or(now);
+            first_seen.insert(key.clone(), seen_at);
+
+            let unmatched_since = item.timestamp.unwrap_or(seen_at);
+            let grace_period = self.config.grace_period(item.token_id);
+            let is_overdue = (now - unmatched_since)
+                .to_std()
+                .map_or(false, |unmatched_for| unmatched_for > grace_period);
+            if !is_overdue {
+                continue;
+            }
+
+            match item.kind {
+                UnmatchedItemKind::Mint => counts.mints += 1,
+                UnmatchedItemKind::Burn => counts.burns += 1,
+                UnmatchedItemKind::Deposit => counts.deposits += 1,
+                UnmatchedItemKind::Withdrawal => counts.withdrawals += 1,
+            }
+
+            if !self.alerted.contains(&key) {
+                log::crit!(
+                    self.logger,
+                    "{} {} (token_id={:?}) has been unmatched since {}, exceeding the grace period of {}s",
+                    item.kind,
+                    item.identifier,
+                    item.token_id.map(|token_id| *token_id),
+                    unmatched_since,
+                    grace_period.as_secs(),
+                );
+            }
+            alerted.insert(key);
+        }
+
+        // Forget about items that got matched in the meantime.
+        self.first_seen = first_seen;
+        self.alerted = alerted;
+
+        counters::NUM_OVERDUE_UNMATCHED_MINTS.set(counts.mints as i64);
+        counters::NUM_OVERDUE_UNMATCHED_BURNS.set(counts.burns as i64);
+        counters::NUM_OVERDUE_UNMATCHED_DEPOSITS.set(counts.deposits as i64);
+        counters::NUM_OVERDUE_UNMATCHED_WITHDRAWALS.set(counts.withdrawals as i64);
+
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::{
+        create_gnosis_safe_deposit, insert_gnosis_deposit, insert_mint_tx_from_deposit,
+        test_gnosis_config, TestDbContext,
+    };
+    use chrono::Duration;
+    use mc_common::logger::{test_with_logger, Logger};
+    use mc_util_test_helper::{RngType, SeedableRng};
+
+    #[test_with_logger]
+    fn check_respects_grace_periods(logger: Logger) {
+        let mut rng = RngType::from_seed([1u8; 32]);
+        let test_db_context = TestDbContext::default();
+        let reserve_auditor_db = test_db_context.get_db_instance(logger.clone());
+        let conn = reserve_auditor_db.get_conn().unwrap();
+        let safe_config = &test_gnosis_config().safes[0];
+        let token_id = safe_config.tokens[0].token_id;
+        GnosisSafeAuditConfig::set(safe_config, &conn).unwrap();
+
+        let mut checker = UnmatchedChecker::new(
+            UnmatchedAlertConfig {
+                default_grace_period_secs: 3600,
+                tokens: vec![TokenGracePeriodConfig {
+                    token_id,
+                    grace_period_secs: 60,
+                }],
+            },
+            logger,
+        );
+        let now = Utc::now();
+
+        // An unmatched deposit and mint of a token with a short grace period, and
+        // a deposit without a timestamp of a token using the default one.
+        let mut deposit = create_gnosis_safe_deposit(100, &mut rng);
+        insert_gnosis_deposit(&mut deposit, &conn);
+        let unrelated_deposit = create_gnosis_safe_deposit(200, &mut rng);
+        insert_mint_tx_from_deposit(&unrelated_deposit, &conn, &mut rng);
+        ReserveDeposit::new("bank", "tx1", TokenId::from(5), 10, "0102", None)
+            .insert(&conn)
+            .unwrap();
+
+        assert_eq!(
+            UnmatchedChecker::find_unmatched_items(&conn).unwrap().len(),
+            3
+        );
+        assert_eq!(checker.check(&conn, now).unwrap(), OverdueCounts::default());
+
+        assert_eq!(
+            checker.check(&conn, now + Duration::minutes(2)).unwrap(),
+            OverdueCounts {
+                mints: 1,
+                deposits: 1,
+                ..Default::default()
+            }
+        );
+
+        // The reserve deposit is timed from when the checker first saw it.
+        assert_eq!(
+            checker.check(&conn, now + Duration::hours(2)).unwrap(),
+            OverdueCounts {
+                mints: 1,
+                deposits: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(counters::NUM_OVERDUE_UNMATCHED_DEPOSITS.get(), 2);
+    }
+}